@@ -1,4 +1,4 @@
-import React, { useState } from 'react'
+import React, { useEffect, useState } from 'react'
 import { AuthProvider } from './contexts/AuthContext'
 import AdvancedDashboard from './components/AdvancedDashboard'
 import AnalyticsDashboard from './components/AnalyticsDashboard'
@@ -9,9 +9,25 @@ import UniversalSearch from './components/UniversalSearch'
 import SyncStatusDashboard from './components/SyncStatusDashboard'
 import MyChart from './components/MyChart'
 import PeopleIntelligence from './components/PeopleIntelligence'
+import SignIn from './components/SignIn'
+import { apiFetch } from './api'
 
 export default function App() {
   const [activeTab, setActiveTab] = useState<'universal' | 'search' | 'sync' | 'advanced' | 'analytics' | 'graph' | 'knowledge' | 'people' | 'chart'>('universal')
+  const [signedIn, setSignedIn] = useState<boolean | null>(null)
+
+  useEffect(() => {
+    apiFetch('/api/auth/me')
+      .then((response) => setSignedIn(response.ok))
+      .catch(() => setSignedIn(false))
+  }, [])
+
+  if (signedIn === null) {
+    return <div className="min-h-screen bg-gray-900" />
+  }
+  if (!signedIn) {
+    return <SignIn onSignedIn={() => setSignedIn(true)} />
+  }
 
   return (
     <AuthProvider>
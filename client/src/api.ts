@@ -0,0 +1,7 @@
+// The API is addressed as localhost, the same site as the UI, so the SameSite=Lax session
+// cookie goes with every request
+export const API_BASE = 'http://localhost:3001'
+
+export function apiFetch(path: string, init: RequestInit = {}): Promise<Response> {
+  return fetch(`${API_BASE}${path}`, { ...init, credentials: 'include' })
+}
@@ -3,6 +3,7 @@ import SmartKnowledgeGraph from './SmartKnowledgeGraph'
 import AIInsightsPanel from './AIInsightsPanel'
 import PredictiveAnalytics from './PredictiveAnalytics'
 import PerformanceScoreCard from './PerformanceScoreCard'
+import { apiFetch } from '../api'
 
 interface AdvancedMetrics {
   velocity_insights: {
@@ -84,7 +85,7 @@ export default function AdvancedDashboard() {
     const fetchAdvancedData = async () => {
       try {
         setLoading(true)
-        const response = await fetch('http://127.0.0.1:3001/api/analytics/advanced')
+        const response = await apiFetch('/api/analytics/advanced')
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
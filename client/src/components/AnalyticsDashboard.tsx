@@ -1,6 +1,7 @@
 import React, { useEffect, useRef, useState } from 'react'
 import * as d3 from 'd3'
 import TrendChart from './TrendChart'
+import { apiFetch } from '../api'
 
 interface TeamVelocity {
   period: string
@@ -55,7 +56,7 @@ export default function AnalyticsDashboard() {
     const fetchAnalytics = async () => {
       try {
         setLoading(true)
-        const response = await fetch('http://127.0.0.1:3001/api/analytics')
+        const response = await apiFetch('/api/analytics')
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
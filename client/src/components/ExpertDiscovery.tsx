@@ -1,5 +1,6 @@
 import React, { useState, useEffect } from 'react';
 import { MagnifyingGlassIcon, UserGroupIcon, LightBulbIcon, ChatBubbleLeftRightIcon } from '@heroicons/react/24/outline';
+import { apiFetch } from '../api';
 
 // ================================
 // TYPE DEFINITIONS
@@ -132,7 +133,7 @@ const ExpertDiscovery: React.FC<ExpertDiscoveryProps> = ({
   const searchExpertsByTopic = async (topic: string) => {
     try {
       // Call backend API to search experts by topic
-      const response = await fetch(`/api/people/search?topic=${encodeURIComponent(topic)}`);
+      const response = await apiFetch(`/api/people/search?topic=${encodeURIComponent(topic)}`);
       const data = await response.json();
       setSearchResults(data.experts || []);
     } catch (error) {
@@ -143,7 +144,7 @@ const ExpertDiscovery: React.FC<ExpertDiscoveryProps> = ({
   const getRecommendations = async (personId: string, topic?: string) => {
     try {
       const url = `/api/people/recommendations/${personId}${topic ? `?topic=${encodeURIComponent(topic)}` : ''}`;
-      const response = await fetch(url);
+      const response = await apiFetch(url);
       const data = await response.json();
       setRecommendations(data.recommendations || []);
       setActiveTab('recommendations');
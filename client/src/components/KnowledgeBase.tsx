@@ -1,4 +1,5 @@
 import React, { useEffect, useState } from 'react'
+import { apiFetch } from '../api'
 
 interface KnowledgeConcept {
   id: string
@@ -127,7 +128,7 @@ export default function KnowledgeBase() {
     const fetchKnowledgeBase = async () => {
       try {
         setLoading(true)
-        const response = await fetch('http://127.0.0.1:3001/api/knowledge')
+        const response = await apiFetch('/api/knowledge')
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
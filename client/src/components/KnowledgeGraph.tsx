@@ -1,5 +1,6 @@
 import React, { useEffect, useRef, useState } from 'react'
 import * as d3 from 'd3'
+import { apiFetch } from '../api'
 
 interface GraphNode {
   id: string
@@ -61,9 +62,7 @@ export default function KnowledgeGraph({
     const fetchGraphData = async () => {
       try {
         setLoading(true)
-        const response = await fetch(
-          `http://127.0.0.1:3001/api/graph?limit=${maxNodes}`
-        )
+        const response = await apiFetch(`/api/graph?limit=${maxNodes}`)
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
@@ -12,6 +12,7 @@ import PeopleNetworkGraph from './PeopleNetworkGraph';
 import ExpertDiscovery from './ExpertDiscovery';
 import CollaborationTimeline from './CollaborationTimeline';
 import TeamInsightsDashboard from './TeamInsightsDashboard';
+import { apiFetch } from '../api';
 
 // ================================
 // TYPE DEFINITIONS
@@ -91,7 +92,7 @@ const PeopleIntelligence: React.FC<PeopleIntelligenceProps> = ({ className = ''
   const loadPeopleIntelligence = async () => {
     try {
       // Load network overview data
-      const response = await fetch('/api/people/overview');
+      const response = await apiFetch('/api/people/overview');
       const data = await response.json();
       
       // Update state with real data
@@ -132,7 +133,7 @@ const PeopleIntelligence: React.FC<PeopleIntelligenceProps> = ({ className = ''
   const handleProcessContent = async (platform: string, contentId: string) => {
     setIsProcessing(true);
     try {
-      const response = await fetch('/api/people/analyze', {
+      const response = await apiFetch('/api/people/analyze', {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({
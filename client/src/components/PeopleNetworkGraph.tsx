@@ -1,5 +1,6 @@
 import React, { useEffect, useRef, useState } from 'react';
 import * as d3 from 'd3';
+import { apiFetch } from '../api';
 
 // ================================
 // TYPE DEFINITIONS
@@ -80,7 +81,7 @@ const PeopleNetworkGraph: React.FC<PeopleNetworkGraphProps> = ({
   const loadNetworkData = async () => {
     try {
       setIsLoading(true);
-      const response = await fetch('/api/people/overview');
+      const response = await apiFetch('/api/people/overview');
       const networkData = await response.json();
       // Transform backend data to our format
       // This is a placeholder - would need actual data transformation
@@ -0,0 +1,67 @@
+import React, { useState } from 'react'
+import { apiFetch } from '../api'
+
+interface SignInProps {
+  onSignedIn: () => void
+}
+
+// Exchanges a username and password for the session cookie every other API call relies on
+export default function SignIn({ onSignedIn }: SignInProps) {
+  const [username, setUsername] = useState('')
+  const [password, setPassword] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const [submitting, setSubmitting] = useState(false)
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault()
+    setSubmitting(true)
+    setError(null)
+    try {
+      const response = await apiFetch('/api/auth/login', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ username, password }),
+      })
+      if (!response.ok) {
+        const body = await response.json().catch(() => ({}))
+        throw new Error(body.error || `Sign in failed (${response.status})`)
+      }
+      onSignedIn()
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Sign in failed')
+    } finally {
+      setSubmitting(false)
+    }
+  }
+
+  return (
+    <div className="min-h-screen bg-gray-900 text-white flex items-center justify-center">
+      <form onSubmit={handleSubmit} className="bg-gray-800 p-8 rounded-lg w-80 space-y-4">
+        <h1 className="text-2xl font-bold text-blue-400">🧠 Triage</h1>
+        <input
+          className="w-full px-3 py-2 rounded bg-gray-700 text-white"
+          placeholder="Username"
+          autoComplete="username"
+          value={username}
+          onChange={(e) => setUsername(e.target.value)}
+        />
+        <input
+          className="w-full px-3 py-2 rounded bg-gray-700 text-white"
+          type="password"
+          placeholder="Password"
+          autoComplete="current-password"
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+        />
+        {error && <p className="text-sm text-red-400">{error}</p>}
+        <button
+          type="submit"
+          disabled={submitting || !username || !password}
+          className="w-full py-2 rounded bg-blue-600 hover:bg-blue-500 disabled:opacity-50"
+        >
+          {submitting ? 'Signing in…' : 'Sign in'}
+        </button>
+      </form>
+    </div>
+  )
+}
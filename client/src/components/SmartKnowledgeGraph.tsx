@@ -1,5 +1,6 @@
 import React, { useEffect, useRef, useState } from 'react'
 import * as d3 from 'd3'
+import { apiFetch } from '../api'
 
 interface SmartGraphData {
   nodes: Array<{
@@ -85,7 +86,7 @@ export default function SmartKnowledgeGraph() {
     const fetchSmartGraph = async () => {
       try {
         setLoading(true)
-        const response = await fetch('http://127.0.0.1:3001/api/graph/smart')
+        const response = await apiFetch('/api/graph/smart')
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
@@ -1,4 +1,5 @@
 import React, { useState, useEffect, useRef } from 'react'
+import { apiFetch } from '../api'
 
 interface SearchResult {
   id: string
@@ -126,7 +127,7 @@ export default function SmartSearch() {
 
   const loadUserNotes = async () => {
     try {
-      const response = await fetch('http://127.0.0.1:3001/api/notes')
+      const response = await apiFetch('/api/notes')
       if (response.ok) {
         const data = await response.json()
         setUserNotes(data.notes)
@@ -138,7 +139,7 @@ export default function SmartSearch() {
 
   const loadSavedViews = async () => {
     try {
-      const response = await fetch('http://127.0.0.1:3001/api/views')
+      const response = await apiFetch('/api/views')
       if (response.ok) {
         const data = await response.json()
         setSavedViews(data.views)
@@ -160,7 +161,7 @@ export default function SmartSearch() {
         include_related: 'true'
       })
 
-      const response = await fetch(`http://127.0.0.1:3001/api/search?${searchParams}`)
+      const response = await apiFetch(`/api/search?${searchParams}`)
       if (response.ok) {
         const data: SearchResponse = await response.json()
         setSearchResults(data)
@@ -201,7 +202,7 @@ export default function SmartSearch() {
     }))
 
     try {
-      const response = await fetch('http://127.0.0.1:3001/api/notes', {
+      const response = await apiFetch('/api/notes', {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -238,7 +239,7 @@ export default function SmartSearch() {
     if (!searchResults || !viewName.trim()) return
 
     try {
-      const response = await fetch('http://127.0.0.1:3001/api/views', {
+      const response = await apiFetch('/api/views', {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -281,7 +282,7 @@ export default function SmartSearch() {
 
   const loadSavedView = async (view: SavedView) => {
     // Update view usage
-    await fetch(`http://127.0.0.1:3001/api/views/${view.id}/use`, {
+    await apiFetch(`/api/views/${view.id}/use`, {
       method: 'PUT'
     })
 
@@ -299,7 +300,7 @@ export default function SmartSearch() {
 
   const toggleViewFavorite = async (viewId: string) => {
     try {
-      const response = await fetch(`http://127.0.0.1:3001/api/views/${viewId}/favorite`, {
+      const response = await apiFetch(`/api/views/${viewId}/favorite`, {
         method: 'PUT'
       })
       if (response.ok) {
@@ -1,4 +1,5 @@
 import React, { useEffect, useState } from 'react'
+import { apiFetch } from '../api'
 
 interface SyncStatus {
   last_sync_time: string
@@ -84,7 +85,7 @@ export default function SyncStatusDashboard() {
     const fetchSyncStatus = async () => {
       try {
         setLoading(true)
-        const response = await fetch('http://127.0.0.1:3001/api/sync/status')
+        const response = await apiFetch('/api/sync/status')
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
@@ -11,6 +11,7 @@ import {
   DocumentTextIcon,
   ExclamationTriangleIcon
 } from '@heroicons/react/24/outline';
+import { apiFetch } from '../api';
 
 // ================================
 // TYPE DEFINITIONS
@@ -124,7 +125,7 @@ const TeamInsightsDashboard: React.FC<TeamInsightsDashboardProps> = ({
     setIsLoading(true);
     try {
       // This would call the backend API to get team insights
-      const response = await fetch(`/api/people/overview?timeRange=${timeRange}`);
+      const response = await apiFetch(`/api/people/overview?timeRange=${timeRange}`);
       const data = await response.json();
       
       // For now, using mock data
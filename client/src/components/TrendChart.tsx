@@ -1,5 +1,6 @@
 import React, { useEffect, useRef, useState } from 'react'
 import * as d3 from 'd3'
+import { apiFetch } from '../api'
 
 interface TrendData {
   period: string
@@ -35,7 +36,7 @@ export default function TrendChart({
     const fetchTrendData = async () => {
       try {
         setLoading(true)
-        const response = await fetch(`http://127.0.0.1:3001/api/analytics/timeseries?metric=${metric}&period=${period}`)
+        const response = await apiFetch(`/api/analytics/timeseries?metric=${metric}&period=${period}`)
         if (!response.ok) {
           throw new Error(`HTTP error! status: ${response.status}`)
         }
@@ -1,6 +1,7 @@
 import React, { useState, useEffect, useMemo } from 'react';
 import { Search, Filter, Globe, FileText, MessageSquare, GitBranch, Users, Clock, TrendingUp, ExternalLink, Lock, Unlock, Star } from 'lucide-react';
 import { useAuth } from '../contexts/AuthContext';
+import { apiFetch } from '../api';
 
 // Types for the unified search system
 interface UnifiedSearchRequest {
@@ -132,7 +133,7 @@ const UniversalSearch: React.FC = () => {
         user_id: 'current_user', // Would come from auth context
       };
 
-      const response = await fetch('http://127.0.0.1:3001/api/search/unified', {
+      const response = await apiFetch('/api/search/unified', {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -1,4 +1,5 @@
 import React, { createContext, useContext, useState, useEffect, ReactNode } from 'react';
+import { apiFetch } from '../api';
 
 interface PlatformAuthStatus {
   isAuthenticated: boolean;
@@ -104,7 +105,7 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
     setError(null);
     
     try {
-      const response = await fetch(`http://127.0.0.1:3001/api/auth/${platform}?user_id=current_user`);
+      const response = await apiFetch(`/api/auth/${platform}?user_id=current_user`);
       
       if (!response.ok) {
         throw new Error(`Failed to initiate ${platform} authentication`);
@@ -148,7 +149,7 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
 
   const checkAuthStatus = async (platform: string): Promise<void> => {
     try {
-      const response = await fetch(`http://127.0.0.1:3001/api/auth/${platform}/status?user_id=current_user`);
+      const response = await apiFetch(`/api/auth/${platform}/status?user_id=current_user`);
       
       if (response.ok) {
         const statusData = await response.json();
@@ -191,7 +192,7 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
     setError(null);
     
     try {
-      const response = await fetch(`http://127.0.0.1:3001/api/auth/${platform}/refresh`, {
+      const response = await apiFetch(`/api/auth/${platform}/refresh`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
@@ -217,7 +218,7 @@ export const AuthProvider: React.FC<AuthProviderProps> = ({ children }) => {
     setIsLoading(true);
     
     try {
-      const response = await fetch(`http://127.0.0.1:3001/api/auth/${platform}/logout`, {
+      const response = await apiFetch(`/api/auth/${platform}/logout`, {
         method: 'POST',
         headers: {
           'Content-Type': 'application/json',
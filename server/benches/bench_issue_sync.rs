@@ -0,0 +1,121 @@
+use axum::extract::{Path, Query, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+use triage::constants::PAGE_SIZE;
+use triage::jira::{fetch_issues_for_project, SyncContext, SyncMessage};
+
+const ISSUE_COUNT: usize = 500;
+/// Every tenth issue has more history than a search embeds and needs its own request.
+const TRUNCATED_EVERY: usize = 10;
+
+/// Serves search pages, counts and single issues, counting every request it answers.
+#[derive(Default)]
+struct MockJira {
+    calls: AtomicUsize,
+}
+
+fn mock_issue(n: usize, from_search: bool) -> Value {
+    let truncated = from_search && n.is_multiple_of(TRUNCATED_EVERY);
+    json!({
+        "id": (10_000 + n).to_string(),
+        "key": format!("BENCH-{}", n),
+        "self": format!("http://mock/rest/api/3/issue/{}", 10_000 + n),
+        "fields": {
+            "summary": format!("Issue {}", n),
+            "status": { "name": "Open" },
+            "issuetype": { "name": "Bug", "id": "1" },
+            "project": { "key": "BENCH", "name": "Bench" },
+            "created": "2025-01-01T00:00:00.000+0000",
+            "updated": "2025-01-02T00:00:00.000+0000",
+            "comment": { "comments": [], "total": 0 }
+        },
+        "changelog": {
+            "histories": vec![json!({ "items": [] }); if truncated { 100 } else { 3 }],
+            "total": if truncated { 150 } else { 3 }
+        }
+    })
+}
+
+async fn search(State(mock): State<Arc<MockJira>>, Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+    mock.calls.fetch_add(1, Ordering::Relaxed);
+    let start: usize = params.get("nextPageToken").and_then(|t| t.parse().ok()).unwrap_or(0);
+    let end = (start + PAGE_SIZE).min(ISSUE_COUNT);
+    let is_last = end >= ISSUE_COUNT;
+    Json(json!({
+        "issues": (start..end).map(|n| mock_issue(n, true)).collect::<Vec<_>>(),
+        "names": { "summary": "Summary" },
+        "nextPageToken": if is_last { Value::Null } else { json!(end.to_string()) },
+        "isLast": is_last
+    }))
+}
+
+async fn count(State(mock): State<Arc<MockJira>>) -> Json<Value> {
+    mock.calls.fetch_add(1, Ordering::Relaxed);
+    Json(json!({ "count": ISSUE_COUNT }))
+}
+
+async fn issue(State(mock): State<Arc<MockJira>>, Path(id): Path<usize>) -> Json<Value> {
+    mock.calls.fetch_add(1, Ordering::Relaxed);
+    Json(mock_issue(id - 10_000, false))
+}
+
+/// Syncs the mock project once; returns issues delivered and API calls made.
+async fn sync_once(mock: &MockJira, base_url: &str) -> (usize, usize) {
+    mock.calls.store(0, Ordering::Relaxed);
+    let ctx = Arc::new(SyncContext::new(base_url.to_string(), "bench".to_string()));
+    let (tx, mut rx) = mpsc::channel(PAGE_SIZE * 10);
+    let drain = tokio::spawn(async move {
+        let mut issues = 0;
+        while let Some(message) = rx.recv().await {
+            if let SyncMessage::Issue(_) = message {
+                issues += 1;
+            }
+        }
+        issues
+    });
+
+    fetch_issues_for_project(ctx, "BENCH", None, None, tx)
+        .await
+        .expect("mock sync failed");
+    let issues = drain.await.unwrap();
+    (issues, mock.calls.load(Ordering::Relaxed))
+}
+
+fn bench_issue_sync(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mock = Arc::new(MockJira::default());
+    let app = Router::new()
+        .route("/rest/api/3/search/jql", get(search))
+        .route("/rest/api/3/search/approximate-count", post(count))
+        .route("/rest/api/3/issue/:id", get(issue))
+        .with_state(mock.clone());
+    let listener = rt.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    rt.spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (issues, calls) = rt.block_on(sync_once(&mock, &base_url));
+    assert_eq!(issues, ISSUE_COUNT);
+    println!(
+        "issues synced per API call: {:.1} ({} issues, {} calls)",
+        issues as f64 / calls as f64,
+        issues,
+        calls
+    );
+
+    let mut group = c.benchmark_group("issue_sync");
+    group.throughput(Throughput::Elements(ISSUE_COUNT as u64));
+    group.bench_function("fetch_issues_for_project", |b| {
+        b.iter(|| rt.block_on(sync_once(&mock, &base_url)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_issue_sync);
+criterion_main!(benches);
@@ -0,0 +1,367 @@
+use crate::accounts::AuthUser;
+use crate::db_utils::with_connection;
+use crate::google_client::GoogleSharingInfo;
+use crate::link_detector::LinkDetector;
+use crate::people_graph::PlatformIdentity;
+use crate::slack_client::{SlackChannelType, SlackConversationContent};
+use crate::types::PlatformType;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+static LINK_DETECTOR: Lazy<LinkDetector> = Lazy::new(LinkDetector::new);
+
+// ================================
+// CONTENT ACCESS CONTROL
+// ================================
+
+/// Shown in place of titles and previews the caller is not allowed to open.
+pub const REDACTED_TITLE: &str = "Restricted content";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Anyone with the link, including people outside the organization
+    Public,
+    /// Every signed-in user, e.g. Jira data or public Slack channels
+    Organization,
+    /// Only the listed principals, domains and people
+    Restricted,
+}
+
+/// Who may see a piece of extracted content; stored as `extracted_content.access_permissions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentAcl {
+    pub visibility: Visibility,
+    /// Lowercased emails and `slack:<user id>` handles granted access at the source
+    #[serde(default)]
+    pub principals: Vec<String>,
+    /// Email domains granted access, e.g. a Google Doc shared with the whole company
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// People graph ids the principals resolved to when the ACL was recorded
+    #[serde(default)]
+    pub person_ids: Vec<String>,
+}
+
+impl ContentAcl {
+    pub fn organization() -> Self {
+        Self::with_visibility(Visibility::Organization)
+    }
+
+    /// Used when the source's sharing settings could not be read; nobody but listed people may look.
+    pub fn unknown() -> Self {
+        Self::with_visibility(Visibility::Restricted)
+    }
+
+    fn with_visibility(visibility: Visibility) -> Self {
+        Self {
+            visibility,
+            principals: Vec::new(),
+            domains: Vec::new(),
+            person_ids: Vec::new(),
+        }
+    }
+
+    /// Group grants are kept as the group's address; membership is not expanded, so they only
+    /// match a caller whose own address is the group's.
+    pub fn from_google_sharing(sharing: &GoogleSharingInfo) -> Self {
+        let mut acl = Self::unknown();
+        for permission in &sharing.permissions {
+            match permission.user_type.as_str() {
+                "anyone" => acl.visibility = Visibility::Public,
+                "domain" => {
+                    if let Some(domain) = &permission.domain {
+                        push_unique(&mut acl.domains, domain.to_lowercase());
+                    }
+                }
+                _ => {
+                    if let Some(email) = &permission.email {
+                        push_unique(&mut acl.principals, email.to_lowercase());
+                    }
+                }
+            }
+        }
+        acl
+    }
+
+    /// Public channels are open to the workspace; everything else is limited to its members.
+    pub fn from_slack_conversation(conversation: &SlackConversationContent) -> Self {
+        if conversation.channel_type == SlackChannelType::Public {
+            return Self::organization();
+        }
+        let mut acl = Self::unknown();
+        let participant_ids = conversation.participants.iter().map(|user| &user.id);
+        for id in conversation.member_ids.iter().chain(participant_ids) {
+            push_unique(&mut acl.principals, format!("slack:{}", id));
+        }
+        acl
+    }
+
+    /// Rows stored before ACLs were recorded: Jira data stays visible, anything else is hidden.
+    pub fn from_stored(raw: Option<&str>, platform: &str) -> Self {
+        raw.and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_else(|| {
+                if platform == "jira" {
+                    Self::organization()
+                } else {
+                    Self::unknown()
+                }
+            })
+    }
+
+    /// Fills `person_ids` from the people graph so access survives identity merges.
+    pub fn with_resolved_people(mut self) -> Self {
+        if self.principals.is_empty() {
+            return self;
+        }
+        for (person_id, handles) in load_people_handles() {
+            if handles.iter().any(|handle| self.principals.contains(handle)) {
+                push_unique(&mut self.person_ids, person_id);
+            }
+        }
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    pub fn allows(&self, viewer: &Viewer) -> bool {
+        match self.visibility {
+            Visibility::Public | Visibility::Organization => true,
+            Visibility::Restricted => {
+                self.principals.iter().any(|principal| viewer.handles.contains(principal))
+                    || viewer.person_id.as_ref().is_some_and(|id| self.person_ids.contains(id))
+                    || viewer.domain.as_ref().is_some_and(|domain| self.domains.contains(domain))
+            }
+        }
+    }
+
+    /// The `sharing_level` reported with search results.
+    pub fn sharing_level(&self) -> &'static str {
+        match self.visibility {
+            Visibility::Public => "public",
+            Visibility::Organization => "team",
+            Visibility::Restricted => "private",
+        }
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// The caller's identities across platforms, used to evaluate `ContentAcl`s.
+#[derive(Debug, Clone, Default)]
+pub struct Viewer {
+    pub user_id: Option<String>,
+    pub person_id: Option<String>,
+    /// Lowercased emails and `slack:<user id>` handles, matching `ContentAcl::principals`
+    pub handles: Vec<String>,
+    pub domain: Option<String>,
+}
+
+impl Viewer {
+    /// Resolves the account's email to a person and collects that person's platform identities.
+    pub fn for_user(user: &AuthUser) -> Self {
+        let mut viewer = Viewer {
+            user_id: Some(user.id.clone()),
+            ..Default::default()
+        };
+        let Some(email) = user.email.as_ref().map(|email| email.trim().to_lowercase()) else {
+            return viewer;
+        };
+        viewer.domain = email.split_once('@').map(|(_, domain)| domain.to_string());
+        push_unique(&mut viewer.handles, email.clone());
+
+        if let Some((person_id, handles)) = load_people_handles()
+            .into_iter()
+            .find(|(_, handles)| handles.contains(&email))
+        {
+            viewer.person_id = Some(person_id);
+            for handle in handles {
+                push_unique(&mut viewer.handles, handle);
+            }
+        }
+        viewer
+    }
+}
+
+/// Whether the viewer may see content identified by its source id (issue key, document id,
+/// thread ts). Jira is open to every signed-in user; other sources need a recorded ACL.
+pub fn can_view_source(viewer: &Viewer, platform: &str, source_id: &str) -> bool {
+    if platform == "jira" {
+        return true;
+    }
+    // Narrows the scan only; a URL may contain the id without being the same source
+    let undotted = source_id.replace('.', "");
+    let mut rows: Vec<(String, Option<String>)> = Vec::new();
+    with_connection("can_view_source", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT source_url, access_permissions FROM extracted_content \
+                 WHERE source_platform = ? AND (contains(source_url, ?) OR contains(source_url, ?))",
+            )
+            .expect("Failed to prepare source ACL query");
+        rows = stmt
+            .query_map([platform, source_id, undotted.as_str()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+            })
+            .expect("Failed to query source ACLs")
+            .flatten()
+            .collect();
+    });
+    let acls: Vec<ContentAcl> = rows
+        .into_iter()
+        .filter(|(url, _)| is_same_source(url, source_id))
+        .map(|(_, raw)| ContentAcl::from_stored(raw.as_deref(), platform))
+        .collect();
+    !acls.is_empty() && acls.iter().all(|acl| acl.allows(viewer))
+}
+
+/// Whether `url` is the source itself or links to the document or Slack message with that id.
+fn is_same_source(url: &str, source_id: &str) -> bool {
+    url == source_id || source_id_of(url).as_deref() == Some(source_id)
+}
+
+/// The id the people graph records for a linked source: a Google file id, or a Slack thread or
+/// message ts such as `1712345678.123456`.
+fn source_id_of(url: &str) -> Option<String> {
+    match LINK_DETECTOR.classify_url(url) {
+        PlatformType::GoogleDocs { document_id } => Some(document_id),
+        PlatformType::GoogleSheets { spreadsheet_id } => Some(spreadsheet_id),
+        PlatformType::GoogleSlides { presentation_id } => Some(presentation_id),
+        PlatformType::SlackThread { thread_ts, .. } => Some(thread_ts),
+        // Permalinks drop the dot: p1712345678123456
+        PlatformType::SlackMessage { message_ts, .. } if message_ts.len() > 6 => {
+            let (seconds, micros) = message_ts.split_at(message_ts.len() - 6);
+            Some(format!("{}.{}", seconds, micros))
+        }
+        _ => None,
+    }
+}
+
+/// Every person's handles: their email, identity emails and Slack user ids.
+fn load_people_handles() -> Vec<(String, Vec<String>)> {
+    let mut people = Vec::new();
+    with_connection("load_people_handles", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, email, platform_identities FROM people")
+            .expect("Failed to prepare people handle query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .expect("Failed to query people handles");
+
+        for (id, email, identities_json) in rows.flatten() {
+            let identities: HashMap<String, PlatformIdentity> =
+                serde_json::from_str(&identities_json).unwrap_or_default();
+            people.push((id, person_handles(&email, &identities)));
+        }
+    });
+    people
+}
+
+fn person_handles(email: &str, identities: &HashMap<String, PlatformIdentity>) -> Vec<String> {
+    let mut handles = vec![email.trim().to_lowercase()];
+    for identity in identities.values() {
+        if let Some(email) = &identity.email {
+            push_unique(&mut handles, email.trim().to_lowercase());
+        }
+        if identity.platform == "slack" {
+            push_unique(&mut handles, format!("slack:{}", identity.platform_user_id));
+        }
+    }
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::google_client::GooglePermission;
+
+    fn viewer(email: &str, extra: &[&str]) -> Viewer {
+        let mut handles = vec![email.to_string()];
+        handles.extend(extra.iter().map(|h| h.to_string()));
+        Viewer {
+            user_id: Some("user_1".to_string()),
+            person_id: Some("person_1".to_string()),
+            handles,
+            domain: email.split_once('@').map(|(_, d)| d.to_string()),
+        }
+    }
+
+    fn permission(user_type: &str, email: Option<&str>, domain: Option<&str>) -> GooglePermission {
+        GooglePermission {
+            role: "reader".to_string(),
+            user_type: user_type.to_string(),
+            email: email.map(|s| s.to_string()),
+            domain: domain.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_google_sharing_maps_to_principals_and_domains() {
+        let sharing = GoogleSharingInfo {
+            shared_with_me: false,
+            owned_by_me: false,
+            permissions: vec![
+                permission("user", Some("Ana@Example.com"), None),
+                permission("domain", None, Some("partner.io")),
+            ],
+            sharing_link: None,
+        };
+        let acl = ContentAcl::from_google_sharing(&sharing);
+
+        assert_eq!(acl.visibility, Visibility::Restricted);
+        assert_eq!(acl.principals, vec!["ana@example.com"]);
+        assert!(acl.allows(&viewer("ana@example.com", &[])));
+        assert!(acl.allows(&viewer("bo@partner.io", &[])));
+        assert!(!acl.allows(&viewer("bo@example.com", &[])));
+        assert_eq!(acl.sharing_level(), "private");
+    }
+
+    #[test]
+    fn test_restricted_content_matches_slack_handles_and_people() {
+        let mut acl = ContentAcl::unknown();
+        acl.principals.push("slack:U123".to_string());
+
+        assert!(acl.allows(&viewer("ana@example.com", &["slack:U123"])));
+        assert!(!acl.allows(&viewer("ana@example.com", &["slack:U999"])));
+
+        acl.person_ids.push("person_1".to_string());
+        assert!(acl.allows(&viewer("ana@example.com", &[])));
+        assert!(!acl.allows(&Viewer::default()));
+    }
+
+    #[test]
+    fn test_source_ids_match_exactly() {
+        let doc = "https://docs.google.com/document/d/abc123/edit";
+        assert!(is_same_source(doc, "abc123"));
+        assert!(!is_same_source(doc, "abc"));
+        assert!(!is_same_source("https://docs.google.com/document/d/abc1234/edit", "abc123"));
+
+        let thread = "https://acme.slack.com/archives/C01/p1712345678123456?thread_ts=1712345600.000100";
+        assert!(is_same_source(thread, "1712345600.000100"));
+        assert!(!is_same_source(thread, "1712345678.123456"));
+        assert!(is_same_source("https://acme.slack.com/archives/C01/p1712345678123456", "1712345678.123456"));
+        assert!(is_same_source("https://example.com/spec", "https://example.com/spec"));
+    }
+
+    #[test]
+    fn test_stored_acls_fall_back_by_platform() {
+        assert!(ContentAcl::from_stored(None, "jira").allows(&Viewer::default()));
+        assert!(!ContentAcl::from_stored(Some("{}"), "slack").allows(&Viewer::default()));
+
+        let public = ContentAcl::with_visibility(Visibility::Public).to_json();
+        assert!(ContentAcl::from_stored(Some(&public), "google").allows(&Viewer::default()));
+    }
+}
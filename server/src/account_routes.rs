@@ -0,0 +1,126 @@
+use axum::{
+    extract::Path,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
+    Router,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::accounts::{
+    create_api_key, create_session, create_user, end_session, is_secure_request, list_api_keys,
+    revoke_api_key, verify_credentials, AuthUser,
+};
+
+// ================================
+// API REQUEST TYPES
+// ================================
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct RegisterRequest {
+    pub username: String,
+    pub password: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+}
+
+// ================================
+// ROUTE HANDLERS
+// ================================
+
+/// Log in with a username and password and receive a session cookie
+async fn login(headers: HeaderMap, Json(request): Json<LoginRequest>) -> Response {
+    match verify_credentials(&request.username, &request.password) {
+        Ok(user) => {
+            let cookie = create_session(&user, is_secure_request(&headers));
+            tracing::info!("{} logged in", user.username);
+            ([(header::SET_COOKIE, cookie)], Json(serde_json::json!({ "user": user }))).into_response()
+        }
+        Err(e) => (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": e.to_string() }))).into_response(),
+    }
+}
+
+async fn logout(headers: HeaderMap) -> Response {
+    let cookie = end_session(&headers);
+    ([(header::SET_COOKIE, cookie)], Json(serde_json::json!({ "success": true }))).into_response()
+}
+
+/// Admins add users; the first admin is created with `triage users add --admin`
+async fn register(caller: AuthUser, Json(request): Json<RegisterRequest>) -> Response {
+    if !caller.is_admin {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match create_user(
+        &request.username,
+        &request.password,
+        request.display_name.as_deref(),
+        request.email.as_deref(),
+        request.is_admin,
+    ) {
+        Ok(user) => (StatusCode::CREATED, Json(serde_json::json!({ "user": user }))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response(),
+    }
+}
+
+async fn current_user(user: AuthUser) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "user": user }))
+}
+
+async fn get_api_keys(user: AuthUser) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "keys": list_api_keys(&user) }))
+}
+
+/// The full key is only returned here; store it somewhere safe
+async fn post_api_key(
+    user: AuthUser,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+    if request.name.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let (summary, key) = create_api_key(&user, &request.name);
+    Ok((StatusCode::CREATED, Json(serde_json::json!({ "key": key, "details": summary }))))
+}
+
+async fn delete_api_key(user: AuthUser, Path(id): Path<String>) -> StatusCode {
+    if revoke_api_key(&user, &id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+// ================================
+// ROUTER SETUP
+// ================================
+
+/// Routes reachable without a session
+pub fn create_public_account_routes() -> Router {
+    Router::new()
+        .route("/api/auth/login", post(login))
+        .route("/api/auth/logout", post(logout))
+}
+
+pub fn create_account_routes() -> Router {
+    Router::new()
+        .route("/api/auth/me", get(current_user))
+        .route("/api/auth/register", post(register))
+        .route("/api/auth/api-keys", get(get_api_keys))
+        .route("/api/auth/api-keys", post(post_api_key))
+        .route("/api/auth/api-keys/:id", delete(delete_api_key))
+}
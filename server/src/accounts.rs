@@ -0,0 +1,608 @@
+use crate::db_utils::with_connection;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{Duration, Utc};
+use dirs::home_dir;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+// ================================
+// USER ACCOUNTS, SESSIONS AND API KEYS
+// ================================
+
+pub const SESSION_COOKIE: &str = "triage_session";
+pub const SESSION_TTL_DAYS: i64 = 14;
+/// Personal API keys start with this so they are recognisable in scripts and logs.
+const API_KEY_PREFIX: &str = "trg_";
+const MIN_PASSWORD_LENGTH: usize = 10;
+/// How long an OAuth connect flow may take between its start and the provider's callback.
+const OAUTH_STATE_TTL_MINUTES: i64 = 10;
+
+/// Signs session cookies; set `TRIAGE_SESSION_SECRET` when several replicas share sessions.
+static SESSION_SECRET: Lazy<Vec<u8>> = Lazy::new(load_session_secret);
+
+pub const CREATE_ACCOUNT_TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE,
+    display_name TEXT,
+    email TEXT,
+    password_hash TEXT NOT NULL, -- argon2 PHC string
+    is_admin BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL,
+    last_login_at TEXT
+);
+CREATE TABLE IF NOT EXISTS sessions (
+    token_hash TEXT PRIMARY KEY, -- sha256 of the cookie token
+    user_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS api_keys (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE, -- sha256 of the full key
+    key_prefix TEXT NOT NULL, -- first characters, shown in listings
+    created_at TEXT NOT NULL,
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+CREATE TABLE IF NOT EXISTS oauth_states (
+    state_hash TEXT PRIMARY KEY, -- sha256 of the state sent to the provider
+    owner_hash TEXT NOT NULL, -- sha256 of the session token, or of the user id for API keys
+    provider TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_sessions_user ON sessions(user_id);
+CREATE INDEX IF NOT EXISTS idx_api_keys_user ON api_keys(user_id);
+"#;
+
+const SELECT_USER_COLUMNS: &str = "SELECT u.id, u.username, u.display_name, u.email, u.is_admin FROM users u";
+
+/// The caller behind a request, resolved from a session cookie or an API key.
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+pub struct AuthUser {
+    pub id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiKeySummary {
+    pub id: String,
+    pub name: String,
+    pub key_prefix: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AccountError {
+    UsernameTaken,
+    WeakPassword,
+    InvalidUsername,
+    InvalidCredentials,
+}
+
+impl std::fmt::Display for AccountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountError::UsernameTaken => write!(f, "username is already taken"),
+            AccountError::WeakPassword => {
+                write!(f, "password must be at least {} characters", MIN_PASSWORD_LENGTH)
+            }
+            AccountError::InvalidUsername => {
+                write!(f, "username may only contain letters, digits, '.', '-' and '_'")
+            }
+            AccountError::InvalidCredentials => write!(f, "invalid username or password"),
+        }
+    }
+}
+
+pub async fn initialize_account_tables() {
+    with_connection("initialize_account_tables", |conn| {
+        conn.execute_batch(CREATE_ACCOUNT_TABLES)
+            .expect("Failed to create account tables");
+    });
+}
+
+fn load_session_secret() -> Vec<u8> {
+    if let Ok(secret) = std::env::var("TRIAGE_SESSION_SECRET") {
+        if !secret.is_empty() {
+            return secret.into_bytes();
+        }
+    }
+    let mut path = home_dir().expect("Could not find home directory");
+    path.push(".triage");
+    path.push("session.key");
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.trim().is_empty() {
+            return existing.trim().as_bytes().to_vec();
+        }
+    }
+    let generated = random_hex(32);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("Failed to create .triage directory");
+    }
+    write_private_file(&path, &generated).expect("Failed to write session key");
+    generated.into_bytes()
+}
+
+/// Anyone who can read the key can forge sessions, so only the owner may.
+#[cfg(unix)]
+fn write_private_file(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private_file(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
+fn random_hex(bytes: usize) -> String {
+    (0..bytes).map(|_| format!("{:02x}", rand::random::<u8>())).collect()
+}
+
+fn sha256_hex(value: &str) -> String {
+    Sha256::digest(value.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ================================
+// PASSWORDS AND USERS
+// ================================
+
+pub fn hash_password(password: &str) -> String {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>()).expect("16 bytes is a valid salt");
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("Failed to hash password")
+        .to_string()
+}
+
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+fn validate_new_account(username: &str, password: &str) -> Result<(), AccountError> {
+    let valid_username = !username.is_empty()
+        && username.len() <= 64
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if !valid_username {
+        return Err(AccountError::InvalidUsername);
+    }
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AccountError::WeakPassword);
+    }
+    Ok(())
+}
+
+pub fn user_count() -> usize {
+    let mut count = 0;
+    with_connection("user_count", |conn| {
+        count = conn
+            .query_row("SELECT COUNT(*) FROM users", [], |row| row.get::<_, i64>(0))
+            .unwrap_or(0) as usize;
+    });
+    count
+}
+
+pub fn create_user(
+    username: &str,
+    password: &str,
+    display_name: Option<&str>,
+    email: Option<&str>,
+    is_admin: bool,
+) -> Result<AuthUser, AccountError> {
+    let username = username.trim().to_lowercase();
+    validate_new_account(&username, password)?;
+    if find_user("WHERE u.username = ?", &username).is_some() {
+        return Err(AccountError::UsernameTaken);
+    }
+
+    let user = AuthUser {
+        id: format!("user_{}", uuid::Uuid::new_v4()),
+        username,
+        display_name: display_name.map(|s| s.to_string()),
+        email: email.map(|s| s.to_string()),
+        is_admin,
+    };
+    let password_hash = hash_password(password);
+    with_connection("create_user", |conn| {
+        conn.execute(
+            "INSERT INTO users (id, username, display_name, email, password_hash, is_admin, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            duckdb::params![
+                user.id,
+                user.username,
+                user.display_name,
+                user.email,
+                password_hash,
+                user.is_admin,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .expect("Failed to insert user");
+    });
+    Ok(user)
+}
+
+fn find_user(filter: &str, param: &str) -> Option<AuthUser> {
+    let mut user = None;
+    with_connection("find_user", |conn| {
+        user = conn
+            .query_row(&format!("{} {}", SELECT_USER_COLUMNS, filter), [param], |row| {
+                Ok(AuthUser {
+                    id: row.get(0)?,
+                    username: row.get(1)?,
+                    display_name: row.get(2)?,
+                    email: row.get(3)?,
+                    is_admin: row.get(4)?,
+                })
+            })
+            .ok();
+    });
+    user
+}
+
+pub fn verify_credentials(username: &str, password: &str) -> Result<AuthUser, AccountError> {
+    let username = username.trim().to_lowercase();
+    let mut stored_hash: Option<String> = None;
+    with_connection("verify_credentials", |conn| {
+        stored_hash = conn
+            .query_row("SELECT password_hash FROM users WHERE username = ?", [&username], |row| row.get(0))
+            .ok();
+    });
+
+    match stored_hash {
+        Some(hash) if verify_password(password, &hash) => {
+            find_user("WHERE u.username = ?", &username).ok_or(AccountError::InvalidCredentials)
+        }
+        Some(_) => Err(AccountError::InvalidCredentials),
+        None => {
+            // Spend the same time as a real check so usernames can't be probed by timing
+            verify_password(password, &hash_password("not-a-real-password"));
+            Err(AccountError::InvalidCredentials)
+        }
+    }
+}
+
+// ================================
+// SESSIONS
+// ================================
+
+fn sign(token: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&SESSION_SECRET).expect("HMAC accepts keys of any length");
+    mac.update(token.as_bytes());
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `<token>.<hmac>`; only the hash of the token is stored.
+fn signed_cookie_value(token: &str) -> String {
+    format!("{}.{}", token, sign(token))
+}
+
+fn verify_cookie_value(value: &str) -> Option<&str> {
+    let (token, signature) = value.split_once('.')?;
+    constant_time_eq(sign(token).as_bytes(), signature.as_bytes()).then_some(token)
+}
+
+/// Starts a session and returns the `Set-Cookie` header value for it.
+pub fn create_session(user: &AuthUser, secure: bool) -> String {
+    let token = random_hex(32);
+    let now = Utc::now();
+    with_connection("create_session", |conn| {
+        conn.execute(
+            "INSERT INTO sessions (token_hash, user_id, created_at, expires_at) VALUES (?, ?, ?, ?)",
+            [
+                sha256_hex(&token),
+                user.id.clone(),
+                now.to_rfc3339(),
+                (now + Duration::days(SESSION_TTL_DAYS)).to_rfc3339(),
+            ],
+        )
+        .expect("Failed to create session");
+        conn.execute("UPDATE users SET last_login_at = ? WHERE id = ?", [now.to_rfc3339(), user.id.clone()])
+            .expect("Failed to record login");
+    });
+
+    session_cookie(&signed_cookie_value(&token), SESSION_TTL_DAYS * 24 * 3600, secure)
+}
+
+fn session_cookie(value: &str, max_age_secs: i64, secure: bool) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}",
+        SESSION_COOKIE,
+        value,
+        max_age_secs,
+        if secure { "; Secure" } else { "" }
+    )
+}
+
+/// Starts an OAuth connect flow: a random `state` tied to the caller's session and `provider`.
+pub fn issue_oauth_state(headers: &HeaderMap, user: &AuthUser, provider: &str) -> String {
+    let state = random_hex(32);
+    let now = Utc::now();
+    with_connection("issue_oauth_state", |conn| {
+        conn.execute("DELETE FROM oauth_states WHERE expires_at <= ?", [now.to_rfc3339()])
+            .expect("Failed to prune OAuth states");
+        conn.execute(
+            "INSERT INTO oauth_states (state_hash, owner_hash, provider, expires_at) VALUES (?, ?, ?, ?)",
+            [
+                sha256_hex(&state),
+                oauth_state_owner(headers, user),
+                provider.to_string(),
+                (now + Duration::minutes(OAUTH_STATE_TTL_MINUTES)).to_rfc3339(),
+            ],
+        )
+        .expect("Failed to store OAuth state");
+    });
+    state
+}
+
+/// Whether `state` was issued to this caller for `provider` and has not expired; each state
+/// is accepted once.
+pub fn consume_oauth_state(headers: &HeaderMap, user: &AuthUser, provider: &str, state: Option<&str>) -> bool {
+    let Some(state) = state.filter(|state| !state.is_empty()) else {
+        return false;
+    };
+    let mut consumed = 0;
+    with_connection("consume_oauth_state", |conn| {
+        consumed = conn
+            .execute(
+                "DELETE FROM oauth_states WHERE state_hash = ? AND owner_hash = ? AND provider = ? AND expires_at > ?",
+                [
+                    sha256_hex(state),
+                    oauth_state_owner(headers, user),
+                    provider.to_string(),
+                    Utc::now().to_rfc3339(),
+                ],
+            )
+            .expect("Failed to check OAuth state");
+    });
+    consumed == 1
+}
+
+fn oauth_state_owner(headers: &HeaderMap, user: &AuthUser) -> String {
+    session_token(headers)
+        .map(|token| sha256_hex(&token))
+        .unwrap_or_else(|| sha256_hex(&user.id))
+}
+
+/// Ends the session carried by `headers`, if any, and returns a cookie that clears it.
+pub fn end_session(headers: &HeaderMap) -> String {
+    if let Some(token) = session_token(headers) {
+        with_connection("end_session", |conn| {
+            conn.execute("DELETE FROM sessions WHERE token_hash = ?", [sha256_hex(&token)])
+                .expect("Failed to delete session");
+        });
+    }
+    session_cookie("", 0, is_secure_request(headers))
+}
+
+fn session_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .and_then(|(_, value)| verify_cookie_value(value).map(|token| token.to_string()))
+}
+
+/// Behind an ingress the original scheme arrives in `X-Forwarded-Proto`.
+pub fn is_secure_request(headers: &HeaderMap) -> bool {
+    headers
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|proto| proto.eq_ignore_ascii_case("https"))
+}
+
+fn user_for_session(token: &str) -> Option<AuthUser> {
+    let mut user = None;
+    with_connection("user_for_session", |conn| {
+        user = conn
+            .query_row(
+                &format!(
+                    "{} JOIN sessions s ON s.user_id = u.id WHERE s.token_hash = ? AND s.expires_at > ?",
+                    SELECT_USER_COLUMNS
+                ),
+                [sha256_hex(token), Utc::now().to_rfc3339()],
+                |row| {
+                    Ok(AuthUser {
+                        id: row.get(0)?,
+                        username: row.get(1)?,
+                        display_name: row.get(2)?,
+                        email: row.get(3)?,
+                        is_admin: row.get(4)?,
+                    })
+                },
+            )
+            .ok();
+    });
+    user
+}
+
+// ================================
+// API KEYS
+// ================================
+
+/// Creates a key and returns it in full; only its hash is kept, so it can't be shown again.
+pub fn create_api_key(user: &AuthUser, name: &str) -> (ApiKeySummary, String) {
+    let key = format!("{}{}", API_KEY_PREFIX, random_hex(24));
+    let summary = ApiKeySummary {
+        id: format!("key_{}", uuid::Uuid::new_v4()),
+        name: name.trim().to_string(),
+        key_prefix: key[..API_KEY_PREFIX.len() + 6].to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        last_used_at: None,
+    };
+    with_connection("create_api_key", |conn| {
+        conn.execute(
+            "INSERT INTO api_keys (id, user_id, name, key_hash, key_prefix, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            [
+                summary.id.as_str(),
+                user.id.as_str(),
+                summary.name.as_str(),
+                sha256_hex(&key).as_str(),
+                summary.key_prefix.as_str(),
+                summary.created_at.as_str(),
+            ],
+        )
+        .expect("Failed to create API key");
+    });
+    (summary, key)
+}
+
+pub fn list_api_keys(user: &AuthUser) -> Vec<ApiKeySummary> {
+    let mut keys = Vec::new();
+    with_connection("list_api_keys", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, key_prefix, created_at, last_used_at FROM api_keys
+                 WHERE user_id = ? AND revoked_at IS NULL ORDER BY created_at DESC",
+            )
+            .expect("Failed to prepare API key query");
+        keys = stmt
+            .query_map([&user.id], |row| {
+                Ok(ApiKeySummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    key_prefix: row.get(2)?,
+                    created_at: row.get(3)?,
+                    last_used_at: row.get(4)?,
+                })
+            })
+            .expect("Failed to query API keys")
+            .flatten()
+            .collect();
+    });
+    keys
+}
+
+/// Returns false when the key doesn't exist or belongs to someone else.
+pub fn revoke_api_key(user: &AuthUser, key_id: &str) -> bool {
+    let mut revoked = 0;
+    with_connection("revoke_api_key", |conn| {
+        revoked = conn
+            .execute(
+                "UPDATE api_keys SET revoked_at = ? WHERE id = ? AND user_id = ? AND revoked_at IS NULL",
+                [Utc::now().to_rfc3339().as_str(), key_id, user.id.as_str()],
+            )
+            .expect("Failed to revoke API key");
+    });
+    revoked > 0
+}
+
+fn api_key_from_headers(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let explicit = headers.get("x-api-key").and_then(|v| v.to_str().ok());
+    bearer
+        .or(explicit)
+        .map(|key| key.trim().to_string())
+        .filter(|key| key.starts_with(API_KEY_PREFIX))
+}
+
+fn user_for_api_key(key: &str) -> Option<AuthUser> {
+    let key_hash = sha256_hex(key);
+    let user = find_user(
+        "JOIN api_keys k ON k.user_id = u.id WHERE k.key_hash = ? AND k.revoked_at IS NULL",
+        &key_hash,
+    )?;
+    with_connection("touch_api_key", |conn| {
+        let _ = conn.execute(
+            "UPDATE api_keys SET last_used_at = ? WHERE key_hash = ?",
+            [Utc::now().to_rfc3339(), key_hash.clone()],
+        );
+    });
+    Some(user)
+}
+
+// ================================
+// AXUM INTEGRATION
+// ================================
+
+/// API keys win over the session cookie so scripts behave the same from a browser.
+pub fn authenticate_headers(headers: &HeaderMap) -> Option<AuthUser> {
+    if let Some(key) = api_key_from_headers(headers) {
+        return user_for_api_key(&key);
+    }
+    session_token(headers).and_then(|token| user_for_session(&token))
+}
+
+/// Rejects unauthenticated requests and stores the caller for the `AuthUser` extractor.
+pub async fn require_auth(mut request: Request, next: Next) -> Result<Response, StatusCode> {
+    let user = authenticate_headers(request.headers()).ok_or(StatusCode::UNAUTHORIZED)?;
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+        authenticate_headers(&parts.headers).ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_hash_round_trip() {
+        let hash = hash_password("correct horse battery");
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password("correct horse battery", &hash));
+        assert!(!verify_password("correct horse battery!", &hash));
+        assert!(!verify_password("anything", "not a hash"));
+    }
+
+    #[test]
+    fn test_rejects_tampered_session_cookies() {
+        let value = signed_cookie_value("abc123");
+        assert_eq!(verify_cookie_value(&value), Some("abc123"));
+        assert_eq!(verify_cookie_value(&value.replace("abc123", "abc124")), None);
+        assert_eq!(verify_cookie_value("abc123"), None);
+    }
+
+    #[test]
+    fn test_validates_new_accounts() {
+        assert_eq!(validate_new_account("dana.k", "long enough pw"), Ok(()));
+        assert_eq!(validate_new_account("dana k", "long enough pw"), Err(AccountError::InvalidUsername));
+        assert_eq!(validate_new_account("dana", "short"), Err(AccountError::WeakPassword));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::db_utils::with_connection;
+use crate::dependency_risk::{analyze_dependency_risks, DependencyRisk, DEFAULT_MAX_CHAIN, DEFAULT_STALE_DAYS};
 use crate::utils::{log_step, log_success};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,6 +31,7 @@ pub struct BottleneckAnalysis {
     pub flow_efficiency: f64, // 0-100
     pub wait_time_analysis: HashMap<String, f64>,
     pub throughput_analysis: ThroughputAnalysis,
+    pub dependency_risks: Vec<DependencyRisk>,
     pub recommendations: Vec<String>,
 }
 
@@ -148,7 +150,7 @@ async fn analyze_velocity_patterns() -> VelocityInsights {
                 CASE WHEN COUNT(*) > 0 THEN 
                     CAST(COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) AS REAL) / CAST(COUNT(*) AS REAL) * 100
                 ELSE 0 END as velocity
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL 
                 AND datetime(created) >= datetime('now', '-16 weeks')
             GROUP BY strftime('%Y-W%W', created), strftime('%w', created)
@@ -210,7 +212,7 @@ async fn analyze_velocity_patterns() -> VelocityInsights {
                 CASE WHEN COUNT(*) > 0 THEN 
                     CAST(COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) AS REAL) / CAST(COUNT(*) AS REAL) * 100
                 ELSE 0 END as velocity
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL 
                 AND datetime(created) >= datetime('now', '-6 months')
             GROUP BY strftime('%Y-%m', created)
@@ -287,8 +289,8 @@ async fn detect_bottlenecks() -> BottleneckAnalysis {
                 status,
                 COUNT(*) as issue_count,
                 AVG(julianday('now') - julianday(created)) as avg_age_days,
-                COUNT(*) * 1.0 / (SELECT COUNT(*) FROM issues) * 100 as percentage
-            FROM issues 
+                COUNT(*) * 1.0 / (SELECT COUNT(*) FROM live_issues) * 100 as percentage
+            FROM live_issues 
             WHERE status IS NOT NULL
             GROUP BY status
         )
@@ -379,16 +381,28 @@ async fn detect_bottlenecks() -> BottleneckAnalysis {
         ],
     };
 
+    let dependency_risks = analyze_dependency_risks(DEFAULT_MAX_CHAIN, DEFAULT_STALE_DAYS).risks;
+
+    let mut recommendations = vec![
+        "Focus on reducing 'In Progress' WIP".to_string(),
+        "Streamline code review process".to_string(),
+        "Implement automated quality gates".to_string(),
+    ];
+    if let Some(worst) = dependency_risks.first() {
+        recommendations.push(format!(
+            "Untangle {} blocked issue(s), starting with {}",
+            dependency_risks.len(),
+            worst.blocking_path.join(" → ")
+        ));
+    }
+
     BottleneckAnalysis {
         critical_bottlenecks: bottlenecks,
         flow_efficiency,
         wait_time_analysis: wait_times,
         throughput_analysis,
-        recommendations: vec![
-            "Focus on reducing 'In Progress' WIP".to_string(),
-            "Streamline code review process".to_string(),
-            "Implement automated quality gates".to_string(),
-        ],
+        dependency_risks,
+        recommendations,
     }
 }
 
@@ -406,7 +420,7 @@ async fn generate_predictions() -> Vec<PredictiveForecast> {
                 CASE WHEN COUNT(*) > 0 THEN 
                     CAST(COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) AS REAL) / CAST(COUNT(*) AS REAL) * 100
                 ELSE 0 END as velocity
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL 
                 AND datetime(created) >= datetime('now', '-6 months')
             GROUP BY strftime('%Y-%m', created)
@@ -471,7 +485,7 @@ async fn analyze_team_dynamics() -> TeamDynamics {
             SELECT 
                 JSON_EXTRACT(project, '$.key') as project_key,
                 COUNT(*) as issue_count
-            FROM issues 
+            FROM live_issues 
             WHERE project IS NOT NULL 
                 AND JSON_EXTRACT(project, '$.key') IS NOT NULL
             GROUP BY JSON_EXTRACT(project, '$.key')
@@ -674,7 +688,7 @@ fn calculate_performance_score(
         .max(0.0)
 }
 
-fn calculate_gini_coefficient(distribution: &HashMap<String, f64>) -> f64 {
+pub fn calculate_gini_coefficient(distribution: &HashMap<String, f64>) -> f64 {
     let values: Vec<f64> = distribution.values().cloned().collect();
     if values.len() < 2 {
         return 0.0;
@@ -0,0 +1,753 @@
+use crate::auth::{authenticate, get_domain};
+use crate::db_utils::{with_connection, with_transaction};
+use crate::routes::{get_board_sprints_route, get_boards_route, get_sprint_issues_route};
+use crate::telemetry::TracedRequest;
+use crate::utils::{log_error, log_step, log_success, parse_jira_timestamp};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+
+// ================================
+// JIRA AGILE: BOARDS & SPRINTS
+// ================================
+
+pub const CREATE_AGILE_TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS boards (
+    id BIGINT PRIMARY KEY,
+    name TEXT,
+    board_type TEXT,
+    project_key TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sprints (
+    id BIGINT PRIMARY KEY,
+    board_id BIGINT,
+    name TEXT,
+    state TEXT,
+    goal TEXT,
+    start_date TEXT,
+    end_date TEXT,
+    complete_date TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sprint_issues (
+    sprint_id BIGINT,
+    issue_key TEXT,
+    PRIMARY KEY (sprint_id, issue_key)
+);
+
+CREATE TABLE IF NOT EXISTS sprint_membership_events (
+    id TEXT PRIMARY KEY,
+    sprint_id BIGINT,
+    issue_key TEXT,
+    change TEXT, -- 'added' | 'removed'
+    timestamp TEXT
+);
+"#;
+
+const INSERT_BOARD: &str = r#"
+INSERT OR REPLACE INTO boards (id, name, board_type, project_key) VALUES (?, ?, ?, ?)
+"#;
+
+const INSERT_SPRINT: &str = r#"
+INSERT OR REPLACE INTO sprints (id, board_id, name, state, goal, start_date, end_date, complete_date)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+
+const INSERT_SPRINT_ISSUE: &str = r#"
+INSERT OR REPLACE INTO sprint_issues (sprint_id, issue_key) VALUES (?, ?)
+"#;
+
+const INSERT_MEMBERSHIP_EVENT: &str = r#"
+INSERT OR REPLACE INTO sprint_membership_events (id, sprint_id, issue_key, change, timestamp)
+VALUES (?, ?, ?, ?, ?)
+"#;
+
+pub async fn create_agile_tables() {
+    log_step("🏃", "Initializing sprint tables...");
+    with_connection("create_agile_tables", |conn| {
+        conn.execute_batch(CREATE_AGILE_TABLES).unwrap_or_else(|_| {
+            panic!(
+                "{} Failed to execute CREATE_AGILE_TABLES",
+                log_error("create_agile_tables")
+            )
+        });
+    });
+    log_success("Sprint tables ready.");
+}
+
+struct AgileClient {
+    token: String,
+    domain: String,
+    client: Client,
+}
+
+impl AgileClient {
+    async fn get(&self, url: &str) -> Result<Value, String> {
+        let res = self
+            .client
+            .get(url)
+            .header("Authorization", format!("Basic {}", self.token))
+            .header("Accept", "application/json")
+            .send_traced("jira")
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = res.status();
+        let body = res.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("{} ({})", status, body));
+        }
+        serde_json::from_str(&body).map_err(|e| format!("JSON parse error: {}", e))
+    }
+
+    /// Follows Agile API pagination (`values` + `isLast`) until exhausted.
+    async fn get_all(&self, route: impl Fn(usize) -> String, key: &str) -> Result<Vec<Value>, String> {
+        let mut values = Vec::new();
+        let mut start_at = 0;
+
+        loop {
+            let page = self.get(&route(start_at)).await?;
+            let items = page[key].as_array().cloned().unwrap_or_default();
+            let count = items.len();
+            values.extend(items);
+
+            let is_last = page["isLast"].as_bool().unwrap_or_else(|| {
+                let total = page["total"].as_u64().unwrap_or(0) as usize;
+                start_at + count >= total
+            });
+            if is_last || count == 0 {
+                break;
+            }
+            start_at += count;
+        }
+
+        Ok(values)
+    }
+}
+
+/// Pulls scrum boards for the synced projects, their sprints, and each sprint's issues.
+pub async fn sync_sprints_for_projects(project_ids: &[String]) {
+    log_step("🏃", "Syncing Jira Agile boards and sprints...");
+
+    let agile = AgileClient {
+        token: authenticate().await,
+        domain: get_domain(),
+        client: Client::new(),
+    };
+
+    let mut board_count = 0;
+    let mut sprint_count = 0;
+
+    for project_id in project_ids {
+        let boards = match agile
+            .get_all(|start_at| get_boards_route(&agile.domain, project_id, start_at), "values")
+            .await
+        {
+            Ok(boards) => boards,
+            Err(e) => {
+                tracing::warn!("Could not list boards for project {}: {}", project_id, e);
+                continue;
+            }
+        };
+
+        for board in boards.iter().filter(|board| board["type"] == "scrum") {
+            let Some(board_id) = board["id"].as_i64() else {
+                continue;
+            };
+            let sprints = match agile
+                .get_all(|start_at| get_board_sprints_route(&agile.domain, board_id, start_at), "values")
+                .await
+            {
+                Ok(sprints) => sprints,
+                Err(e) => {
+                    tracing::warn!("Could not list sprints for board {}: {}", board_id, e);
+                    continue;
+                }
+            };
+
+            let mut sprint_issues: Vec<(i64, Vec<String>)> = Vec::new();
+            for sprint in &sprints {
+                let Some(sprint_id) = sprint["id"].as_i64() else {
+                    continue;
+                };
+                match agile
+                    .get_all(|start_at| get_sprint_issues_route(&agile.domain, sprint_id, start_at), "issues")
+                    .await
+                {
+                    Ok(issues) => sprint_issues.push((
+                        sprint_id,
+                        issues
+                            .iter()
+                            .filter_map(|issue| issue["key"].as_str().map(|key| key.to_string()))
+                            .collect(),
+                    )),
+                    Err(e) => tracing::warn!("Could not list issues for sprint {}: {}", sprint_id, e),
+                }
+            }
+
+            let project_key = board["location"]["projectKey"].as_str().unwrap_or(project_id);
+            save_board(board, board_id, project_key, &sprints, &sprint_issues);
+            board_count += 1;
+            sprint_count += sprints.len();
+        }
+    }
+
+    record_sprint_membership_changes();
+
+    log_success(&format!(
+        "Synced {} scrum board(s) with {} sprint(s).",
+        board_count, sprint_count
+    ));
+}
+
+fn save_board(
+    board: &Value,
+    board_id: i64,
+    project_key: &str,
+    sprints: &[Value],
+    sprint_issues: &[(i64, Vec<String>)],
+) {
+    with_transaction("save_board", |tx| {
+        tx.execute(
+            INSERT_BOARD,
+            duckdb::params![
+                board_id,
+                board["name"].as_str().unwrap_or_default(),
+                board["type"].as_str().unwrap_or_default(),
+                project_key,
+            ],
+        )
+        .unwrap_or_else(|_| panic!("{} Insert board", log_error("save_board")));
+
+        let mut sprint_stmt = tx
+            .prepare(INSERT_SPRINT)
+            .unwrap_or_else(|_| panic!("{} Prepare sprint insert", log_error("save_board")));
+        for sprint in sprints {
+            let Some(sprint_id) = sprint["id"].as_i64() else {
+                continue;
+            };
+            sprint_stmt
+                .execute(duckdb::params![
+                    sprint_id,
+                    board_id,
+                    sprint["name"].as_str().unwrap_or_default(),
+                    sprint["state"].as_str().unwrap_or_default(),
+                    sprint["goal"].as_str().unwrap_or_default(),
+                    sprint["startDate"].as_str(),
+                    sprint["endDate"].as_str(),
+                    sprint["completeDate"].as_str(),
+                ])
+                .unwrap_or_else(|_| panic!("{} Insert sprint", log_error("save_board")));
+        }
+
+        let mut issue_stmt = tx
+            .prepare(INSERT_SPRINT_ISSUE)
+            .unwrap_or_else(|_| panic!("{} Prepare sprint issue insert", log_error("save_board")));
+        for (sprint_id, issue_keys) in sprint_issues {
+            tx.execute("DELETE FROM sprint_issues WHERE sprint_id = ?", [sprint_id])
+                .unwrap_or_else(|_| panic!("{} Clear sprint issues", log_error("save_board")));
+            for issue_key in issue_keys {
+                issue_stmt
+                    .execute(duckdb::params![sprint_id, issue_key])
+                    .unwrap_or_else(|_| panic!("{} Insert sprint issue", log_error("save_board")));
+            }
+        }
+    });
+}
+
+/// Reads `Sprint` field changes out of every stored changelog.
+///
+/// Jira records the field as comma-separated sprint IDs in `from`/`to`, so each history
+/// item is diffed to find which sprints an issue joined or left.
+fn record_sprint_membership_changes() {
+    let mut events = Vec::new();
+
+    with_connection("load_sprint_changelogs", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT key, changelog FROM live_issues WHERE changelog LIKE '%\"Sprint\"%'")
+            .expect("Failed to prepare sprint changelog query");
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))
+            .expect("Failed to query sprint changelogs");
+
+        for (key, changelog) in rows.flatten() {
+            let Some(changelog) = changelog.and_then(|raw| serde_json::from_str::<Value>(&raw).ok()) else {
+                continue;
+            };
+            for history in changelog["histories"].as_array().into_iter().flatten() {
+                let Some(timestamp) = history["created"].as_str().and_then(parse_jira_timestamp) else {
+                    continue;
+                };
+                let history_id = history["id"].as_str().unwrap_or_default();
+
+                for item in history["items"].as_array().into_iter().flatten() {
+                    if item["field"] != "Sprint" {
+                        continue;
+                    }
+                    let from = sprint_ids(item["from"].as_str());
+                    let to = sprint_ids(item["to"].as_str());
+
+                    for sprint_id in to.difference(&from) {
+                        events.push((key.clone(), history_id.to_string(), *sprint_id, "added", timestamp));
+                    }
+                    for sprint_id in from.difference(&to) {
+                        events.push((key.clone(), history_id.to_string(), *sprint_id, "removed", timestamp));
+                    }
+                }
+            }
+        }
+    });
+
+    with_transaction("save_sprint_membership_events", |tx| {
+        let mut stmt = tx
+            .prepare(INSERT_MEMBERSHIP_EVENT)
+            .expect("Failed to prepare membership event insert");
+        for (key, history_id, sprint_id, change, timestamp) in &events {
+            stmt.execute(duckdb::params![
+                format!("{}:{}:{}:{}", key, history_id, sprint_id, change),
+                sprint_id,
+                key,
+                change,
+                timestamp.to_rfc3339(),
+            ])
+            .expect("Failed to insert membership event");
+        }
+    });
+}
+
+fn sprint_ids(value: Option<&str>) -> HashSet<i64> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect()
+}
+
+// ================================
+// SPRINT METRICS
+// ================================
+
+#[derive(Debug, Serialize)]
+pub struct SprintIssueOutcome {
+    pub issue_key: String,
+    pub story_points: f64,
+    pub added_mid_sprint: bool,
+    pub completed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BurndownPoint {
+    pub date: String,
+    pub remaining_points: f64,
+    pub scope_points: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SprintMetrics {
+    pub sprint_id: i64,
+    pub name: String,
+    pub state: String,
+    pub goal: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub committed_points: f64,
+    pub completed_points: f64,
+    pub added_points: f64,
+    pub removed_points: f64,
+    pub carry_over_points: f64,
+    pub completion_rate: f64,
+    pub committed_issues: usize,
+    pub completed_issues: usize,
+    pub added_issues: usize,
+    pub carry_over_issues: Vec<String>,
+    pub issues: Vec<SprintIssueOutcome>,
+    pub burndown: Vec<BurndownPoint>,
+}
+
+struct SprintRow {
+    name: String,
+    state: String,
+    goal: String,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    complete: Option<DateTime<Utc>>,
+}
+
+struct SprintIssueRow {
+    story_points: f64,
+    resolved_at: Option<DateTime<Utc>>,
+    /// Membership changes for this sprint, oldest first.
+    events: Vec<(DateTime<Utc>, bool)>,
+}
+
+impl SprintIssueRow {
+    fn in_sprint_at(&self, at: DateTime<Utc>, currently_member: bool) -> bool {
+        // Walk back from the current state: undo every change made after `at`
+        let mut member = currently_member;
+        for (timestamp, added) in self.events.iter().rev() {
+            if *timestamp <= at {
+                break;
+            }
+            member = !added;
+        }
+        member
+    }
+
+    fn added_after(&self, start: DateTime<Utc>) -> bool {
+        let mut before_start = None;
+        let mut added_later = false;
+        for (timestamp, added) in &self.events {
+            if *timestamp <= start {
+                before_start = Some(*added);
+            } else if *added {
+                added_later = true;
+            }
+        }
+        added_later && before_start != Some(true)
+    }
+
+    fn completed_by(&self, at: DateTime<Utc>) -> bool {
+        self.resolved_at.map(|resolved| resolved <= at).unwrap_or(false)
+    }
+}
+
+fn parse_sprint_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    value.as_deref().and_then(parse_jira_timestamp)
+}
+
+/// Committed vs completed points, scope changes, carry-over and a daily burndown.
+pub fn calculate_sprint_metrics(sprint_id: i64) -> Option<SprintMetrics> {
+    let mut sprint = None;
+    let mut members: HashSet<String> = HashSet::new();
+    let mut issues: BTreeMap<String, SprintIssueRow> = BTreeMap::new();
+
+    with_connection("calculate_sprint_metrics", |conn| {
+        sprint = conn
+            .query_row(
+                "SELECT name, state, goal, start_date, end_date, complete_date FROM sprints WHERE id = ?",
+                [sprint_id],
+                |row| {
+                    Ok(SprintRow {
+                        name: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                        state: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                        goal: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                        start: parse_sprint_timestamp(row.get(3)?),
+                        end: parse_sprint_timestamp(row.get(4)?),
+                        complete: parse_sprint_timestamp(row.get(5)?),
+                    })
+                },
+            )
+            .ok();
+        if sprint.is_none() {
+            return;
+        }
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT i.key, i.story_points, i.resolution_date, si.issue_key IS NOT NULL
+                FROM live_issues i
+                LEFT JOIN sprint_issues si ON si.issue_key = i.key AND si.sprint_id = $1
+                WHERE si.issue_key IS NOT NULL
+                   OR i.key IN (SELECT issue_key FROM sprint_membership_events WHERE sprint_id = $1)
+                "#,
+            )
+            .expect("Failed to prepare sprint issue query");
+        let rows = stmt
+            .query_map([sprint_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<f64>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, bool>(3)?,
+                ))
+            })
+            .expect("Failed to query sprint issues");
+
+        for (key, story_points, resolution_date, is_member) in rows.flatten() {
+            if is_member {
+                members.insert(key.clone());
+            }
+            issues.insert(
+                key,
+                SprintIssueRow {
+                    story_points: story_points.unwrap_or(0.0),
+                    resolved_at: resolution_date.as_deref().and_then(parse_jira_timestamp),
+                    events: Vec::new(),
+                },
+            );
+        }
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT issue_key, change, timestamp FROM sprint_membership_events \
+                 WHERE sprint_id = ? ORDER BY timestamp",
+            )
+            .expect("Failed to prepare membership event query");
+        let rows = stmt
+            .query_map([sprint_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .expect("Failed to query membership events");
+
+        for (key, change, timestamp) in rows.flatten() {
+            let Some(timestamp) = parse_jira_timestamp(&timestamp) else {
+                continue;
+            };
+            if let Some(issue) = issues.get_mut(&key) {
+                issue.events.push((timestamp, change == "added"));
+            }
+        }
+    });
+
+    Some(sprint_metrics(sprint_id, sprint?, &members, &issues, Utc::now()))
+}
+
+fn sprint_metrics(
+    sprint_id: i64,
+    sprint: SprintRow,
+    members: &HashSet<String>,
+    issues: &BTreeMap<String, SprintIssueRow>,
+    now: DateTime<Utc>,
+) -> SprintMetrics {
+    let start = sprint.start.unwrap_or(now);
+    let close = sprint.complete.or(sprint.end).unwrap_or(now).min(now.max(start));
+
+    let mut outcomes = Vec::new();
+    let mut committed_points = 0.0;
+    let mut completed_points = 0.0;
+    let mut added_points = 0.0;
+    let mut removed_points = 0.0;
+    let mut carry_over_points = 0.0;
+    let mut committed_issues = 0;
+    let mut completed_issues = 0;
+    let mut added_issues = 0;
+    let mut carry_over_issues = Vec::new();
+
+    for (key, issue) in issues {
+        let member_now = members.contains(key);
+        let at_start = issue.in_sprint_at(start, member_now);
+        let at_close = issue.in_sprint_at(close, member_now);
+        let added_mid_sprint = !at_start && issue.added_after(start);
+        let completed = at_close && issue.completed_by(close);
+
+        if at_start {
+            committed_points += issue.story_points;
+            committed_issues += 1;
+        }
+        if added_mid_sprint {
+            added_points += issue.story_points;
+            added_issues += 1;
+        }
+        if at_start && !at_close {
+            removed_points += issue.story_points;
+        }
+        if completed {
+            completed_points += issue.story_points;
+            completed_issues += 1;
+        } else if at_close {
+            carry_over_points += issue.story_points;
+            carry_over_issues.push(key.clone());
+        }
+
+        if at_start || at_close || added_mid_sprint {
+            outcomes.push(SprintIssueOutcome {
+                issue_key: key.clone(),
+                story_points: issue.story_points,
+                added_mid_sprint,
+                completed,
+            });
+        }
+    }
+
+    // One point per day, sampled at the end of the day (or at close on the last day)
+    let mut burndown = Vec::new();
+    let mut day = start.date_naive();
+    loop {
+        let end_of_day = day.and_hms_opt(23, 59, 59).unwrap().and_utc();
+        let at = end_of_day.min(close);
+        let (scope, remaining) = issues.iter().fold((0.0, 0.0), |(scope, remaining), (key, issue)| {
+            if !issue.in_sprint_at(at, members.contains(key)) {
+                return (scope, remaining);
+            }
+            let open = if issue.completed_by(at) { 0.0 } else { issue.story_points };
+            (scope + issue.story_points, remaining + open)
+        });
+        burndown.push(BurndownPoint {
+            date: day.format("%Y-%m-%d").to_string(),
+            remaining_points: remaining,
+            scope_points: scope,
+        });
+        if at >= close {
+            break;
+        }
+        day += Duration::days(1);
+    }
+
+    let final_scope = committed_points + added_points - removed_points;
+
+    SprintMetrics {
+        sprint_id,
+        name: sprint.name,
+        state: sprint.state,
+        goal: sprint.goal,
+        start_date: sprint.start.map(|d| d.to_rfc3339()),
+        end_date: sprint.end.map(|d| d.to_rfc3339()),
+        committed_points,
+        completed_points,
+        added_points,
+        removed_points,
+        carry_over_points,
+        completion_rate: if final_scope > 0.0 {
+            completed_points / final_scope
+        } else {
+            0.0
+        },
+        committed_issues,
+        completed_issues,
+        added_issues,
+        carry_over_issues,
+        issues: outcomes,
+        burndown,
+    }
+}
+
+/// Lists sprints newest first, for pickers in the UI.
+pub fn list_sprints() -> Vec<Value> {
+    let mut sprints = Vec::new();
+
+    with_connection("list_sprints", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT s.id, s.name, s.state, s.start_date, s.end_date, b.name, b.project_key \
+                 FROM sprints s LEFT JOIN boards b ON b.id = s.board_id \
+                 ORDER BY s.start_date DESC NULLS LAST",
+            )
+            .expect("Failed to prepare sprint list query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(serde_json::json!({
+                    "id": row.get::<_, i64>(0)?,
+                    "name": row.get::<_, Option<String>>(1)?,
+                    "state": row.get::<_, Option<String>>(2)?,
+                    "start_date": row.get::<_, Option<String>>(3)?,
+                    "end_date": row.get::<_, Option<String>>(4)?,
+                    "board": row.get::<_, Option<String>>(5)?,
+                    "project_key": row.get::<_, Option<String>>(6)?,
+                }))
+            })
+            .expect("Failed to query sprints");
+        sprints.extend(rows.flatten());
+    });
+
+    sprints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn issue(
+        story_points: f64,
+        resolved_at: Option<DateTime<Utc>>,
+        events: &[(DateTime<Utc>, bool)],
+    ) -> SprintIssueRow {
+        SprintIssueRow {
+            story_points,
+            resolved_at,
+            events: events.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_sprint_ids_parse_comma_separated_field() {
+        assert_eq!(sprint_ids(Some("12, 13,oops")), HashSet::from([12, 13]));
+        assert!(sprint_ids(None).is_empty());
+    }
+
+    #[test]
+    fn test_in_sprint_at_undoes_later_changes() {
+        let row = issue(1.0, None, &[(at(5, 10), true), (at(7, 10), false)]);
+
+        assert!(!row.in_sprint_at(at(4, 10), false));
+        assert!(row.in_sprint_at(at(6, 10), false));
+        assert!(!row.in_sprint_at(at(8, 10), false));
+        // No history means the current membership held all along
+        assert!(issue(1.0, None, &[]).in_sprint_at(at(1, 0), true));
+    }
+
+    #[test]
+    fn test_added_after_ignores_issues_committed_at_start() {
+        let start = at(4, 9);
+
+        assert!(issue(1.0, None, &[(at(5, 10), true)]).added_after(start));
+        assert!(!issue(1.0, None, &[(at(1, 10), true), (at(5, 10), false), (at(6, 10), true)])
+            .added_after(start));
+        assert!(!issue(1.0, None, &[(at(5, 10), false)]).added_after(start));
+    }
+
+    #[test]
+    fn test_sprint_metrics_and_burndown() {
+        let sprint = SprintRow {
+            name: "Sprint 7".to_string(),
+            state: "closed".to_string(),
+            goal: String::new(),
+            start: Some(at(4, 9)),
+            end: Some(at(8, 17)),
+            complete: Some(at(8, 17)),
+        };
+        let members: HashSet<String> =
+            ["PAY-1", "PAY-2", "PAY-4"].iter().map(|key| key.to_string()).collect();
+        let issues = BTreeMap::from([
+            // Committed and done mid-sprint
+            ("PAY-1".to_string(), issue(3.0, Some(at(6, 12)), &[])),
+            // Pulled in on day two and left open
+            ("PAY-2".to_string(), issue(5.0, None, &[(at(5, 10), true)])),
+            // Committed, then dropped
+            ("PAY-3".to_string(), issue(2.0, None, &[(at(1, 10), true), (at(6, 12), false)])),
+            // Committed but only resolved after the sprint closed
+            ("PAY-4".to_string(), issue(8.0, Some(at(10, 12)), &[])),
+        ]);
+
+        let metrics = sprint_metrics(7, sprint, &members, &issues, at(20, 0));
+
+        assert_eq!(metrics.committed_points, 13.0);
+        assert_eq!(metrics.committed_issues, 3);
+        assert_eq!(metrics.added_points, 5.0);
+        assert_eq!(metrics.added_issues, 1);
+        assert_eq!(metrics.removed_points, 2.0);
+        assert_eq!(metrics.completed_points, 3.0);
+        assert_eq!(metrics.completed_issues, 1);
+        assert_eq!(metrics.carry_over_points, 13.0);
+        assert_eq!(metrics.carry_over_issues, vec!["PAY-2", "PAY-4"]);
+        assert!((metrics.completion_rate - 3.0 / 16.0).abs() < 1e-9);
+
+        let burndown: Vec<(&str, f64, f64)> = metrics
+            .burndown
+            .iter()
+            .map(|point| (point.date.as_str(), point.scope_points, point.remaining_points))
+            .collect();
+        assert_eq!(
+            burndown,
+            vec![
+                ("2024-03-04", 13.0, 13.0),
+                ("2024-03-05", 18.0, 18.0),
+                ("2024-03-06", 16.0, 13.0),
+                ("2024-03-07", 16.0, 13.0),
+                ("2024-03-08", 16.0, 13.0),
+            ]
+        );
+    }
+}
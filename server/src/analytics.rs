@@ -90,7 +90,7 @@ async fn calculate_team_velocity() -> Vec<TeamVelocity> {
                 strftime('%Y-%m', created) as month,
                 COUNT(*) as created_count,
                 COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) as resolved_count
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL 
                 AND created != '' 
                 AND datetime(created) >= datetime('now', '-6 months')
@@ -137,11 +137,11 @@ async fn analyze_status_flow() -> Vec<StatusFlowAnalysis> {
             SELECT 
                 COALESCE(status, 'Unknown') as status,
                 COUNT(*) as count
-            FROM issues 
+            FROM live_issues 
             GROUP BY status
         ),
         total_issues AS (
-            SELECT COUNT(*) as total FROM issues
+            SELECT COUNT(*) as total FROM live_issues
         )
         SELECT 
             sc.status,
@@ -190,7 +190,7 @@ async fn calculate_project_productivity() -> Vec<ProjectProductivity> {
                 ELSE 0 
             END as resolution_rate
         FROM projects p
-        LEFT JOIN issues i ON JSON_EXTRACT(i.project, '$.key') = p.key
+        LEFT JOIN live_issues i ON JSON_EXTRACT(i.project, '$.key') = p.key
         GROUP BY p.id, p.key, p.name
         HAVING COUNT(i.id) > 0
         ORDER BY resolution_rate DESC, total_issues DESC
@@ -230,7 +230,7 @@ async fn analyze_trends() -> Vec<TrendAnalysis> {
             SELECT 
                 strftime('%Y-W%W', created) as week,
                 COUNT(*) as issues_created
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL 
                 AND created != '' 
                 AND datetime(created) >= datetime('now', '-12 weeks')
@@ -280,12 +280,12 @@ async fn generate_summary_stats() -> DashboardSummary {
         // Get total counts and resolution rate
         let query = r#"
         SELECT 
-            (SELECT COUNT(*) FROM issues) as total_issues,
+            (SELECT COUNT(*) FROM live_issues) as total_issues,
             (SELECT COUNT(*) FROM projects) as total_projects,
             CASE 
-                WHEN (SELECT COUNT(*) FROM issues) > 0 THEN
-                    CAST((SELECT COUNT(*) FROM issues WHERE status IN ('Done', 'Closed', 'Resolved')) AS REAL) / 
-                    CAST((SELECT COUNT(*) FROM issues) AS REAL) * 100
+                WHEN (SELECT COUNT(*) FROM live_issues) > 0 THEN
+                    CAST((SELECT COUNT(*) FROM live_issues WHERE status IN ('Done', 'Closed', 'Resolved')) AS REAL) / 
+                    CAST((SELECT COUNT(*) FROM live_issues) AS REAL) * 100
                 ELSE 0 
             END as resolution_rate
         "#;
@@ -317,7 +317,7 @@ async fn generate_summary_stats() -> DashboardSummary {
                         CAST(COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) AS REAL) / CAST(COUNT(*) AS REAL) * 100
                     ELSE 0 
                 END as rate
-            FROM issues 
+            FROM live_issues 
             WHERE created IS NOT NULL AND created != ''
             GROUP BY strftime('%Y-%m', created)
             HAVING total >= 3  -- Only consider months with at least 3 issues
@@ -353,7 +353,7 @@ pub async fn get_time_series_data(metric: &str, period: &str) -> Vec<TrendAnalys
                     strftime('%Y-W%W', created) as period,
                     'issues_created' as metric,
                     CAST(COUNT(*) AS REAL) as value
-                FROM issues 
+                FROM live_issues 
                 WHERE created IS NOT NULL 
                     AND created != '' 
                     AND datetime(created) >= datetime('now', '-12 weeks')
@@ -365,7 +365,7 @@ pub async fn get_time_series_data(metric: &str, period: &str) -> Vec<TrendAnalys
                     strftime('%Y-W%W', updated) as period,
                     'issues_resolved' as metric,
                     CAST(COUNT(*) AS REAL) as value
-                FROM issues 
+                FROM live_issues 
                 WHERE updated IS NOT NULL 
                     AND updated != '' 
                     AND status IN ('Done', 'Closed', 'Resolved')
@@ -379,7 +379,7 @@ pub async fn get_time_series_data(metric: &str, period: &str) -> Vec<TrendAnalys
                         strftime('%Y-%m', created) as period,
                         COUNT(*) as created,
                         COUNT(CASE WHEN status IN ('Done', 'Closed', 'Resolved') THEN 1 END) as resolved
-                    FROM issues 
+                    FROM live_issues 
                     WHERE created IS NOT NULL 
                         AND created != '' 
                         AND datetime(created) >= datetime('now', '-6 months')
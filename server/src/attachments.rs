@@ -0,0 +1,439 @@
+use crate::access_control::ContentAcl;
+use crate::auth::{authenticate, get_domain};
+use crate::content_storage::{
+    search_tokens, ContentMetadata, ContentType, INSERT_CONTENT, INSERT_EXTRACTION_JOB,
+    INSERT_SEARCH_INDEX,
+};
+use crate::db_utils::{with_connection, with_transaction};
+use crate::knowledge_engine::{extract_concepts_from_text, extract_technologies_from_text};
+use crate::telemetry::TracedRequest;
+use crate::utils::{log_step, log_success, stable_hash};
+use chrono::Utc;
+use dirs::home_dir;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ================================
+// JIRA ATTACHMENT FETCHING
+// ================================
+
+/// Extracted text beyond this is dropped; the file on disk keeps the rest.
+const MAX_EXTRACTED_CHARS: usize = 500_000;
+
+const TEXT_EXTENSIONS: [&str; 5] = ["txt", "log", "csv", "json", "out"];
+const TEXT_MIME_TYPES: [&str; 4] = ["text/plain", "text/csv", "application/json", "text/x-log"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AttachmentKind {
+    Text,
+    Pdf,
+}
+
+#[derive(Debug, Clone)]
+struct JiraAttachment {
+    id: String,
+    issue_key: String,
+    filename: String,
+    size: u64,
+    mime_type: String,
+    content_url: String,
+    author: Option<String>,
+    created: Option<String>,
+    raw: Value,
+}
+
+impl JiraAttachment {
+    fn from_json(issue_key: &str, raw: &Value) -> Option<Self> {
+        Some(Self {
+            id: raw["id"].as_str()?.to_string(),
+            issue_key: issue_key.to_string(),
+            filename: raw["filename"].as_str().unwrap_or("attachment").to_string(),
+            size: raw["size"].as_u64().unwrap_or(0),
+            mime_type: raw["mimeType"].as_str().unwrap_or_default().to_lowercase(),
+            content_url: raw["content"].as_str()?.to_string(),
+            author: raw["author"]["displayName"].as_str().map(|s| s.to_string()),
+            created: raw["created"].as_str().map(|s| s.to_string()),
+            raw: raw.clone(),
+        })
+    }
+
+    fn content_id(&self) -> String {
+        format!("jira_attachment:{}", self.id)
+    }
+
+    /// Only plain-text style files and PDFs are worth downloading.
+    fn kind(&self) -> Option<AttachmentKind> {
+        let extension = Path::new(&self.filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+
+        if extension == "pdf" || self.mime_type == "application/pdf" {
+            Some(AttachmentKind::Pdf)
+        } else if TEXT_EXTENSIONS.contains(&extension.as_str())
+            || TEXT_MIME_TYPES.contains(&self.mime_type.as_str())
+        {
+            Some(AttachmentKind::Text)
+        } else {
+            None
+        }
+    }
+
+    fn local_path(&self) -> PathBuf {
+        let safe_name: String = self
+            .filename
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+
+        let mut path = get_attachments_dir();
+        path.push(&self.issue_key);
+        path.push(format!("{}_{}", self.id, safe_name));
+        path
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AttachmentFetchSummary {
+    pub candidates: usize,
+    pub stored: usize,
+    pub already_stored: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+fn get_attachments_dir() -> PathBuf {
+    let mut path = home_dir().expect("Could not find home directory");
+    path.push(".triage");
+    path.push("attachments");
+    path
+}
+
+fn load_attachments(project_key: Option<&str>) -> Vec<JiraAttachment> {
+    let mut attachments = Vec::new();
+
+    with_connection("load_attachments", |conn| {
+        let mut query = String::from(
+            "SELECT key, attachment FROM live_issues WHERE attachment IS NOT NULL AND attachment <> ''",
+        );
+        let mut params = Vec::new();
+        if let Some(project_key) = project_key {
+            query.push_str(" AND project_key = ?");
+            params.push(project_key.to_string());
+        }
+
+        let mut stmt = conn.prepare(&query).expect("Failed to prepare attachment query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(params), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .expect("Failed to query attachments");
+
+        for (issue_key, attachment_json) in rows.flatten() {
+            let Ok(Value::Array(items)) = serde_json::from_str::<Value>(&attachment_json) else {
+                continue;
+            };
+            attachments.extend(
+                items
+                    .iter()
+                    .filter_map(|item| JiraAttachment::from_json(&issue_key, item)),
+            );
+        }
+    });
+
+    attachments
+}
+
+fn load_stored_attachment_ids() -> HashSet<String> {
+    let mut ids = HashSet::new();
+
+    with_connection("load_stored_attachment_ids", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id FROM extracted_content WHERE content_type = ?")
+            .expect("Failed to prepare stored attachment query");
+        let rows = stmt
+            .query_map([ContentType::JiraAttachment.to_string()], |row| row.get::<_, String>(0))
+            .expect("Failed to query stored attachments");
+        ids.extend(rows.flatten());
+    });
+
+    ids
+}
+
+/// Downloads allowlisted attachments of stored issues, extracts their text and stores
+/// each one as content linked to its parent issue.
+pub async fn fetch_issue_attachments(project_key: Option<&str>, max_bytes: u64) -> AttachmentFetchSummary {
+    log_step("📎", "Fetching Jira attachments...");
+
+    let attachments = load_attachments(project_key);
+    let stored = load_stored_attachment_ids();
+    let token = authenticate().await;
+    let domain = get_domain();
+    let client = Client::new();
+    let mut summary = AttachmentFetchSummary {
+        candidates: attachments.len(),
+        ..Default::default()
+    };
+
+    for attachment in attachments {
+        let Some(kind) = attachment.kind() else {
+            summary.skipped += 1;
+            continue;
+        };
+        if attachment.size > max_bytes {
+            summary.skipped += 1;
+            continue;
+        }
+        if stored.contains(&attachment.content_id()) {
+            summary.already_stored += 1;
+            continue;
+        }
+        // Never send credentials to a host other than the configured Jira site
+        if !attachment.content_url.starts_with(&format!("https://{}/", domain)) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let bytes = match download_attachment(&client, &token, &attachment, max_bytes).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Could not download {} from {}: {}", attachment.filename, attachment.issue_key, e);
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        let text = match extract_attachment_text(kind, bytes).await {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("Could not extract text from {}: {}", attachment.filename, e);
+                summary.failed += 1;
+                continue;
+            }
+        };
+        if text.trim().is_empty() {
+            summary.skipped += 1;
+            continue;
+        }
+
+        store_attachment_content(&attachment, &text);
+        summary.stored += 1;
+    }
+
+    log_success(&format!(
+        "Stored {} of {} attachment(s); {} already stored, {} skipped, {} failed.",
+        summary.stored, summary.candidates, summary.already_stored, summary.skipped, summary.failed
+    ));
+    summary
+}
+
+/// Reuses the copy under `~/.triage/attachments` when its size still matches. The size Jira
+/// reports is not trusted: the download is abandoned once it passes `max_bytes`.
+async fn download_attachment(
+    client: &Client,
+    token: &str,
+    attachment: &JiraAttachment,
+    max_bytes: u64,
+) -> Result<Vec<u8>, String> {
+    let path = attachment.local_path();
+    if let Ok(bytes) = fs::read(&path) {
+        if bytes.len() as u64 == attachment.size {
+            return Ok(bytes);
+        }
+    }
+
+    let mut res = client
+        .get(&attachment.content_url)
+        .header("Authorization", format!("Basic {}", token))
+        .send_traced("jira")
+        .await
+        .map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        return Err(format!("HTTP {}", res.status()));
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() as u64 > max_bytes {
+            return Err(format!("larger than {} bytes", max_bytes));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+
+    Ok(bytes)
+}
+
+async fn extract_attachment_text(kind: AttachmentKind, bytes: Vec<u8>) -> Result<String, String> {
+    let text = match kind {
+        AttachmentKind::Text => String::from_utf8_lossy(&bytes).into_owned(),
+        // PDF parsing is CPU-bound and can panic on malformed files, so keep it off the runtime
+        AttachmentKind::Pdf => tokio::task::spawn_blocking(move || pdf_extract::extract_text_from_mem(&bytes))
+            .await
+            .map_err(|e| format!("PDF parser crashed: {}", e))?
+            .map_err(|e| e.to_string())?,
+    };
+
+    Ok(text.chars().take(MAX_EXTRACTED_CHARS).collect())
+}
+
+fn content_hash(text: &str) -> String {
+    format!("{:016x}", stable_hash(text))
+}
+
+fn store_attachment_content(attachment: &JiraAttachment, text: &str) {
+    let content_id = attachment.content_id();
+    let content_type = ContentType::JiraAttachment.to_string();
+    let now = Utc::now().to_rfc3339();
+    let title = format!("{} ({})", attachment.filename, attachment.issue_key);
+
+    let concepts = extract_concepts_from_text(text);
+    let technologies = extract_technologies_from_text(text);
+    let metadata = ContentMetadata {
+        word_count: text.split_whitespace().count() as u32,
+        char_count: text.chars().count() as u32,
+        tags: vec![attachment.issue_key.clone()],
+        concepts: concepts.clone(),
+        technologies,
+        ..Default::default()
+    };
+
+    with_transaction("store_attachment_content", |tx| {
+        tx.execute(
+            INSERT_CONTENT,
+            duckdb::params![
+                content_id,
+                content_type,
+                attachment.content_url,
+                "jira",
+                title,
+                text,
+                attachment.raw.to_string(),
+                content_hash(text),
+                attachment.author,
+                attachment.created,
+                attachment.created,
+                now,
+                now,
+                "Active",
+                ContentAcl::organization().to_json(),
+                serde_json::to_string(&metadata).unwrap_or_default(),
+            ],
+        )
+        .expect("Failed to insert attachment content");
+
+        tx.execute(
+            INSERT_SEARCH_INDEX,
+            duckdb::params![
+                format!("{}:index", content_id),
+                content_id,
+                content_type,
+                search_tokens(&title),
+                search_tokens(text),
+                serde_json::to_string(&concepts).unwrap_or_default(),
+                search_tokens(attachment.author.as_deref().unwrap_or_default()),
+                format!("{}\n{}", title, text),
+                Option::<String>::None,
+                now,
+            ],
+        )
+        .expect("Failed to index attachment content");
+
+        // Extraction jobs are how content is tied back to its source ticket
+        tx.execute(
+            INSERT_EXTRACTION_JOB,
+            duckdb::params![
+                format!("{}:job", content_id),
+                attachment.issue_key,
+                attachment.content_url,
+                content_type,
+                "system",
+                Option::<String>::None,
+                "Low",
+                "Completed",
+                0,
+                now,
+                now,
+                now,
+                Option::<String>::None,
+                content_id,
+            ],
+        )
+        .expect("Failed to link attachment to issue");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn attachment(filename: &str, mime_type: &str) -> JiraAttachment {
+        JiraAttachment::from_json(
+            "PAY-42",
+            &json!({
+                "id": "10001",
+                "filename": filename,
+                "size": 2048,
+                "mimeType": mime_type,
+                "content": "https://example.atlassian.net/rest/api/3/attachment/content/10001",
+                "author": {"displayName": "Ada Lovelace"},
+                "created": "2024-03-01T10:00:00.000+0000",
+            }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_json_requires_id_and_content_url() {
+        let parsed = attachment("trace.log", "text/plain");
+        assert_eq!(parsed.issue_key, "PAY-42");
+        assert_eq!(parsed.size, 2048);
+        assert_eq!(parsed.author.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(parsed.content_id(), "jira_attachment:10001");
+
+        assert!(JiraAttachment::from_json("PAY-42", &json!({"id": "1"})).is_none());
+        assert!(JiraAttachment::from_json("PAY-42", &json!({"content": "https://x"})).is_none());
+    }
+
+    #[test]
+    fn test_kind_allowlists_text_and_pdf() {
+        assert_eq!(attachment("server.LOG", "").kind(), Some(AttachmentKind::Text));
+        assert_eq!(attachment("export", "text/csv").kind(), Some(AttachmentKind::Text));
+        assert_eq!(attachment("Spec.PDF", "").kind(), Some(AttachmentKind::Pdf));
+        assert_eq!(attachment("scan", "application/pdf").kind(), Some(AttachmentKind::Pdf));
+        assert_eq!(attachment("screenshot.png", "image/png").kind(), None);
+    }
+
+    #[test]
+    fn test_local_path_sanitizes_filename() {
+        let path = attachment("../../etc/pass wd.txt", "text/plain").local_path();
+
+        assert!(path.ends_with("attachments/PAY-42/10001_.._.._etc_pass_wd.txt"));
+    }
+
+    #[test]
+    fn test_content_hash_is_stable_fnv() {
+        assert_eq!(content_hash("abc"), "e71fa2190541574b");
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+    }
+
+    #[tokio::test]
+    async fn test_text_extraction_is_lossy_and_capped() {
+        let text = extract_attachment_text(AttachmentKind::Text, b"ok \xff done".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(text, "ok \u{fffd} done");
+
+        let long = vec![b'a'; MAX_EXTRACTED_CHARS + 10];
+        let text = extract_attachment_text(AttachmentKind::Text, long).await.unwrap();
+        assert_eq!(text.len(), MAX_EXTRACTED_CHARS);
+    }
+}
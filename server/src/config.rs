@@ -0,0 +1,493 @@
+use dirs::home_dir;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+// ================================
+// SERVER CONFIGURATION
+// ================================
+
+const DEFAULT_GOOGLE_REDIRECT_URI: &str = "http://localhost:3001/auth/google/callback";
+const DEFAULT_SLACK_REDIRECT_URI: &str = "http://localhost:3001/auth/slack/callback";
+/// Where `pnpm dev` serves the desktop UI
+const DEFAULT_CORS_ORIGINS: &[&str] = &["http://localhost:5173", "http://127.0.0.1:5173"];
+const MAX_EXTRACTION_WORKERS: usize = 64;
+
+/// Requests per minute by host. A leading dot covers subdomains and `*` any other host.
+const DEFAULT_RATE_LIMITS: &[(&str, u32)] = &[
+    (".atlassian.net", 600),
+    ("docs.googleapis.com", 60),
+    ("sheets.googleapis.com", 60),
+    ("slides.googleapis.com", 60),
+    ("www.googleapis.com", 600),
+    ("slack.com", 50),
+    ("api.github.com", 80),
+    ("*", 300),
+];
+
+static SERVER_CONFIG: OnceCell<ServerConfig> = OnceCell::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// `json` emits one object per event for log aggregation; `text` is for terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Some(LogFormat::Text),
+            "json" => Some(LogFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Fully resolved settings for the server and the commands sharing its database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerConfig {
+    pub bind_address: IpAddr,
+    pub port: u16,
+    /// Origins allowed to call the API with the session cookie
+    pub cors_origins: Vec<String>,
+    /// `None` keeps the default `~/.triage/triage.duckdb`
+    pub database_path: Option<PathBuf>,
+    pub google_redirect_uri: String,
+    pub slack_redirect_uri: String,
+    pub extraction_workers: usize,
+    pub log_level: LogLevel,
+    pub log_format: LogFormat,
+    /// Outbound requests per minute by host pattern, see `DEFAULT_RATE_LIMITS`
+    pub rate_limits: BTreeMap<String, u32>,
+}
+
+impl ServerConfig {
+    /// Host and port for printing URLs; wildcard binds are shown as localhost.
+    pub fn public_base_url(&self) -> String {
+        let host = if self.bind_address.is_unspecified() {
+            "127.0.0.1".to_string()
+        } else {
+            self.bind_address.to_string()
+        };
+        format!("http://{}:{}", host, self.port)
+    }
+}
+
+/// One source of settings; later layers override earlier ones field by field.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfigLayer {
+    pub bind_address: Option<String>,
+    pub port: Option<u16>,
+    pub cors_origins: Option<Vec<String>>,
+    pub database_path: Option<PathBuf>,
+    pub google_redirect_uri: Option<String>,
+    pub slack_redirect_uri: Option<String>,
+    pub extraction_workers: Option<usize>,
+    pub log_level: Option<String>,
+    pub log_format: Option<String>,
+    /// Merged host by host, so a file can override one host and keep the other defaults
+    pub rate_limits: Option<BTreeMap<String, u32>>,
+}
+
+impl ServerConfigLayer {
+    fn defaults() -> Self {
+        Self {
+            bind_address: Some("127.0.0.1".to_string()),
+            port: Some(3001),
+            cors_origins: Some(DEFAULT_CORS_ORIGINS.iter().map(|origin| origin.to_string()).collect()),
+            database_path: None,
+            google_redirect_uri: Some(DEFAULT_GOOGLE_REDIRECT_URI.to_string()),
+            slack_redirect_uri: Some(DEFAULT_SLACK_REDIRECT_URI.to_string()),
+            extraction_workers: Some(4),
+            log_level: Some("info".to_string()),
+            log_format: Some("text".to_string()),
+            rate_limits: Some(
+                DEFAULT_RATE_LIMITS
+                    .iter()
+                    .map(|(host, per_minute)| (host.to_string(), *per_minute))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn merge(self, over: Self) -> Self {
+        Self {
+            bind_address: over.bind_address.or(self.bind_address),
+            port: over.port.or(self.port),
+            cors_origins: over.cors_origins.or(self.cors_origins),
+            database_path: over.database_path.or(self.database_path),
+            google_redirect_uri: over.google_redirect_uri.or(self.google_redirect_uri),
+            slack_redirect_uri: over.slack_redirect_uri.or(self.slack_redirect_uri),
+            extraction_workers: over.extraction_workers.or(self.extraction_workers),
+            log_level: over.log_level.or(self.log_level),
+            log_format: over.log_format.or(self.log_format),
+            rate_limits: match (self.rate_limits.map(normalize_hosts), over.rate_limits.map(normalize_hosts)) {
+                (Some(mut base), Some(over)) => {
+                    base.extend(over);
+                    Some(base)
+                }
+                (base, over) => over.or(base),
+            },
+        }
+    }
+
+    fn from_file(path: &PathBuf, errors: &mut Vec<String>) -> Self {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<Self>(&data).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                errors.push(format!("{}: {}", path.display(), e));
+                Self::default()
+            })
+    }
+
+    /// `SERVER_PORT` and `RUST_LOG` are what the Kubernetes manifests already set.
+    fn from_env(errors: &mut Vec<String>) -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let number = |name: &str, errors: &mut Vec<String>| {
+            var(name).and_then(|v| {
+                v.trim()
+                    .parse::<usize>()
+                    .map_err(|_| errors.push(format!("{} must be a number, got '{}'", name, v)))
+                    .ok()
+            })
+        };
+
+        let port = match number("TRIAGE_PORT", errors).or_else(|| number("SERVER_PORT", errors)) {
+            Some(port) if port <= u16::MAX as usize => Some(port as u16),
+            Some(port) => {
+                errors.push(format!("port {} is out of range", port));
+                None
+            }
+            None => None,
+        };
+
+        Self {
+            bind_address: var("TRIAGE_BIND_ADDRESS"),
+            port,
+            cors_origins: var("TRIAGE_CORS_ORIGINS")
+                .map(|v| v.split(',').map(|o| o.trim().to_string()).filter(|o| !o.is_empty()).collect()),
+            database_path: var("TRIAGE_DB_PATH").map(PathBuf::from),
+            google_redirect_uri: var("GOOGLE_REDIRECT_URI"),
+            slack_redirect_uri: var("SLACK_REDIRECT_URI"),
+            extraction_workers: number("TRIAGE_EXTRACTION_WORKERS", errors),
+            // RUST_LOG may hold per-module directives; only a bare level applies here
+            log_level: var("TRIAGE_LOG_LEVEL")
+                .or_else(|| var("RUST_LOG").filter(|v| LogLevel::parse(v).is_some())),
+            log_format: var("TRIAGE_LOG_FORMAT"),
+            rate_limits: var("TRIAGE_RATE_LIMITS").map(|v| parse_rate_limits(&v, errors)),
+        }
+    }
+
+    fn resolve(self, errors: &mut Vec<String>) -> Option<ServerConfig> {
+        let bind_address = self.bind_address.unwrap_or_default();
+        let bind_address = bind_address
+            .trim()
+            .parse::<IpAddr>()
+            .map_err(|_| errors.push(format!("bind_address '{}' is not an IP address", bind_address)))
+            .ok();
+
+        let port = self.port.unwrap_or(0);
+        if port == 0 {
+            errors.push("port must be between 1 and 65535".to_string());
+        }
+
+        let cors_origins: Vec<String> = self
+            .cors_origins
+            .unwrap_or_default()
+            .into_iter()
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .collect();
+        if cors_origins.is_empty() {
+            errors.push("cors_origins must list at least one origin".to_string());
+        }
+        // Credentialed CORS cannot use a wildcard, and the API needs the session cookie
+        if cors_origins.iter().any(|origin| origin == "*") {
+            errors.push("cors_origins cannot be \"*\"; list the origins the UI is served from".to_string());
+        }
+        for origin in cors_origins.iter().filter(|origin| *origin != "*") {
+            let valid = url::Url::parse(origin)
+                .map(|url| {
+                    matches!(url.scheme(), "http" | "https")
+                        && url.origin().ascii_serialization() == *origin
+                })
+                .unwrap_or(false);
+            if !valid {
+                errors.push(format!("CORS origin '{}' must look like https://host[:port]", origin));
+            }
+        }
+
+        if let Some(path) = &self.database_path {
+            let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+            if parent.is_some_and(|p| !p.is_dir()) || path.is_dir() {
+                errors.push(format!("database_path '{}' is not a file in an existing directory", path.display()));
+            }
+        }
+
+        let google_redirect_uri = self.google_redirect_uri.unwrap_or_default();
+        let slack_redirect_uri = self.slack_redirect_uri.unwrap_or_default();
+        for (name, uri) in [("google_redirect_uri", &google_redirect_uri), ("slack_redirect_uri", &slack_redirect_uri)] {
+            let valid = url::Url::parse(uri)
+                .map(|url| matches!(url.scheme(), "http" | "https"))
+                .unwrap_or(false);
+            if !valid {
+                errors.push(format!("{} '{}' is not an http(s) URL", name, uri));
+            }
+        }
+
+        let extraction_workers = self.extraction_workers.unwrap_or(0);
+        if extraction_workers == 0 || extraction_workers > MAX_EXTRACTION_WORKERS {
+            errors.push(format!("extraction_workers must be between 1 and {}", MAX_EXTRACTION_WORKERS));
+        }
+
+        let log_level_name = self.log_level.unwrap_or_default();
+        let log_level = LogLevel::parse(&log_level_name);
+        if log_level.is_none() {
+            errors.push(format!("log_level '{}' must be error, warn, info, debug or trace", log_level_name));
+        }
+
+        let log_format_name = self.log_format.unwrap_or_default();
+        let log_format = LogFormat::parse(&log_format_name);
+        if log_format.is_none() {
+            errors.push(format!("log_format '{}' must be text or json", log_format_name));
+        }
+
+        let rate_limits = self.rate_limits.unwrap_or_default();
+        for (host, per_minute) in &rate_limits {
+            if host.trim().is_empty() || *per_minute == 0 {
+                errors.push(format!("rate limit '{}={}' needs a host and at least 1 request per minute", host, per_minute));
+            }
+        }
+
+        if !errors.is_empty() {
+            return None;
+        }
+        Some(ServerConfig {
+            bind_address: bind_address?,
+            port,
+            cors_origins,
+            database_path: self.database_path,
+            google_redirect_uri,
+            slack_redirect_uri,
+            extraction_workers,
+            log_level: log_level?,
+            log_format: log_format?,
+            rate_limits,
+        })
+    }
+}
+
+/// Hosts match case-insensitively, so keys from every layer are lowercased the same way.
+fn normalize_hosts(limits: BTreeMap<String, u32>) -> BTreeMap<String, u32> {
+    limits.into_iter().map(|(host, n)| (host.trim().to_lowercase(), n)).collect()
+}
+
+/// `host=per_minute` pairs separated by commas, e.g. `api.github.com=60,.atlassian.net=300`.
+fn parse_rate_limits(value: &str, errors: &mut Vec<String>) -> BTreeMap<String, u32> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let parsed = pair
+                .split_once('=')
+                .and_then(|(host, n)| Some((host.trim().to_string(), n.trim().parse::<u32>().ok()?)));
+            if parsed.is_none() {
+                errors.push(format!("TRIAGE_RATE_LIMITS entry '{}' must look like host=requests_per_minute", pair));
+            }
+            parsed
+        })
+        .collect()
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    let mut path = home_dir()?;
+    path.push(".triage");
+    path.push("server.json");
+    Some(path)
+}
+
+/// Defaults, then the config file, then environment variables, then `flags`.
+///
+/// The file is `config_path`, else `TRIAGE_CONFIG`, else `~/.triage/server.json` if it exists.
+pub fn load_server_config(
+    config_path: Option<PathBuf>,
+    flags: ServerConfigLayer,
+) -> Result<ServerConfig, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let explicit_path = config_path.or_else(|| std::env::var("TRIAGE_CONFIG").ok().map(PathBuf::from));
+    let file = match explicit_path {
+        Some(path) => ServerConfigLayer::from_file(&path, &mut errors),
+        None => default_config_path()
+            .filter(|path| path.exists())
+            .map(|path| ServerConfigLayer::from_file(&path, &mut errors))
+            .unwrap_or_default(),
+    };
+    let env = ServerConfigLayer::from_env(&mut errors);
+
+    ServerConfigLayer::defaults()
+        .merge(file)
+        .merge(env)
+        .merge(flags)
+        .resolve(&mut errors)
+        .ok_or(errors)
+}
+
+/// Makes `config` the process-wide configuration; only the first call takes effect.
+pub fn install_server_config(config: ServerConfig) {
+    let _ = SERVER_CONFIG.set(config);
+}
+
+/// The installed configuration, or defaults plus file and environment when none was installed.
+pub fn server_config() -> &'static ServerConfig {
+    SERVER_CONFIG.get_or_init(|| {
+        load_server_config(None, ServerConfigLayer::default()).unwrap_or_else(|errors| {
+            panic!("❌ Invalid server configuration: {}", errors.join("; "))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(layers: Vec<ServerConfigLayer>) -> Result<ServerConfig, Vec<String>> {
+        let mut errors = Vec::new();
+        layers
+            .into_iter()
+            .fold(ServerConfigLayer::defaults(), ServerConfigLayer::merge)
+            .resolve(&mut errors)
+            .ok_or(errors)
+    }
+
+    #[test]
+    fn test_later_layers_override_earlier_ones() {
+        let file = ServerConfigLayer {
+            bind_address: Some("0.0.0.0".to_string()),
+            port: Some(8080),
+            cors_origins: Some(vec!["https://triage.example.com/".to_string()]),
+            ..Default::default()
+        };
+        let flags = ServerConfigLayer {
+            port: Some(9090),
+            ..Default::default()
+        };
+
+        let config = resolve(vec![file, flags]).unwrap();
+
+        assert_eq!(config.bind_address.to_string(), "0.0.0.0");
+        assert_eq!(config.port, 9090);
+        assert_eq!(config.cors_origins, vec!["https://triage.example.com"]);
+        assert_eq!(config.log_level, LogLevel::Info);
+        assert_eq!(config.log_format, LogFormat::Text);
+        assert_eq!(config.public_base_url(), "http://127.0.0.1:9090");
+    }
+
+    #[test]
+    fn test_rate_limits_merge_per_host() {
+        let mut errors = Vec::new();
+        let file = ServerConfigLayer {
+            rate_limits: Some(parse_rate_limits("api.github.com=30, slack.com=20", &mut errors)),
+            ..Default::default()
+        };
+        let config = resolve(vec![file]).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(config.rate_limits["api.github.com"], 30);
+        assert_eq!(config.rate_limits["slack.com"], 20);
+        assert_eq!(config.rate_limits[".atlassian.net"], 600);
+
+        parse_rate_limits("api.github.com", &mut errors);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_rate_limit_hosts_are_lowercased_in_every_layer() {
+        let mut errors = Vec::new();
+        let file = ServerConfigLayer {
+            rate_limits: Some(BTreeMap::from([("API.Example.com".to_string(), 5)])),
+            ..Default::default()
+        };
+        let env = ServerConfigLayer {
+            rate_limits: Some(parse_rate_limits("Api.Example.COM=7, Slack.com=3", &mut errors)),
+            ..Default::default()
+        };
+
+        let file_only = resolve(vec![file.clone()]).unwrap();
+        assert_eq!(file_only.rate_limits["api.example.com"], 5);
+
+        let config = resolve(vec![file, env]).unwrap();
+        assert_eq!(config.rate_limits["api.example.com"], 7);
+        assert_eq!(config.rate_limits["slack.com"], 3);
+        assert!(!config.rate_limits.contains_key("API.Example.com"));
+    }
+
+    #[test]
+    fn test_wildcard_cors_origin_is_rejected() {
+        let errors = resolve(vec![ServerConfigLayer {
+            cors_origins: Some(vec!["*".to_string()]),
+            ..Default::default()
+        }])
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("cors_origins"));
+
+        let config = resolve(vec![]).unwrap();
+        assert_eq!(config.cors_origins, vec!["http://localhost:5173", "http://127.0.0.1:5173"]);
+    }
+
+    #[test]
+    fn test_reports_every_invalid_setting() {
+        let errors = resolve(vec![ServerConfigLayer {
+            bind_address: Some("localhost:3001".to_string()),
+            port: Some(0),
+            cors_origins: Some(vec!["triage.example.com".to_string()]),
+            extraction_workers: Some(0),
+            log_level: Some("verbose".to_string()),
+            log_format: Some("logfmt".to_string()),
+            ..Default::default()
+        }])
+        .unwrap_err();
+
+        assert_eq!(errors.len(), 6);
+        assert!(errors[0].contains("bind_address"));
+    }
+}
@@ -1,3 +1,6 @@
 // Route parameters
 pub const PAGE_SIZE: usize = 50;
 pub const BATCH_SIZE: usize = 50;
+
+/// Statuses that count as finished when an issue has no resolution.
+pub const RESOLVED_STATUSES: [&str; 3] = ["Done", "Closed", "Resolved"];
@@ -1,16 +1,66 @@
+use crate::access_control::ContentAcl;
+use crate::content_storage::{
+    search_tokens, ContentType, INSERT_CONTENT, INSERT_EXTRACTION_JOB, INSERT_SEARCH_INDEX,
+};
+use crate::db_utils::{with_connection, with_transaction};
+use crate::events::{publish, ProgressEvent, CONTENT_EXTRACTION};
 use crate::google_auth::GoogleAuthManager;
-use crate::google_client::GoogleApiClient;
+use crate::google_client::{GoogleApiClient, GoogleContentType};
+use crate::knowledge_engine::extract_concepts_from_text;
+use crate::link_detector::LinkDetector;
 use crate::slack_auth::SlackAuthManager;
-use crate::slack_client::SlackApiClient;
+use crate::slack_client::{SlackApiClient, SlackConversationContent};
+use crate::telemetry::EXTRACTION_JOBS_TOTAL;
 use crate::types::{ExtractedLink, PlatformType};
+use crate::utils::stable_hash;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tokio::sync::{mpsc, Semaphore};
+use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
+/// A job is retried on later runs until it has failed this many times.
+const MAX_EXTRACTION_ATTEMPTS: u32 = 3;
+
+const SELECT_QUEUED_EXTRACTION_JOBS: &str = r#"
+SELECT id, source_ticket_id, source_url, user_id, priority, retry_count, created_at
+FROM content_extraction_jobs
+WHERE status IN ('Pending', 'Retrying')
+ORDER BY created_at, id
+"#;
+
+const CLAIM_EXTRACTION_JOB: &str = r#"
+UPDATE content_extraction_jobs SET status = 'Processing', started_at = ? WHERE id = ?
+"#;
+
+const FINISH_EXTRACTION_JOB: &str = r#"
+UPDATE content_extraction_jobs
+SET status = ?, retry_count = ?, completed_at = ?, error_message = ?, extracted_content_id = ?
+WHERE id = ?
+"#;
+
+/// Only one drain runs at a time, so rows still marked processing were cut off by a restart.
+const REQUEUE_INTERRUPTED_EXTRACTION_JOBS: &str = r#"
+UPDATE content_extraction_jobs SET status = 'Retrying' WHERE status = 'Processing'
+"#;
+
+/// Stored Slack conversations with no refresh queued yet, with the ticket each was linked from.
+const SELECT_SLACK_CONVERSATIONS_TO_REFRESH: &str = r#"
+SELECT MIN(j.source_ticket_id), c.source_url, c.content_type
+FROM extracted_content c
+JOIN content_extraction_jobs j ON j.extracted_content_id = c.id
+WHERE c.content_type IN ('slack_thread', 'slack_message')
+  AND c.content_status = 'Active'
+  AND NOT EXISTS (
+      SELECT 1 FROM content_extraction_jobs q
+      WHERE q.source_url = c.source_url AND q.status IN ('Pending', 'Retrying', 'Processing')
+  )
+GROUP BY c.source_url, c.content_type
+"#;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContentExtractionJob {
     pub id: Uuid,
@@ -49,6 +99,8 @@ pub struct ExtractedContent {
     pub title: String,
     pub body_text: String,
     pub metadata: ContentMetadata,
+    /// Who may see this content, taken from the source's sharing settings; stored with it
+    pub acl: ContentAcl,
     pub extracted_at: DateTime<Utc>,
     pub source_ticket_ids: Vec<String>,
 }
@@ -66,53 +118,33 @@ pub struct ContentMetadata {
     pub content_type_specific: serde_json::Value,
 }
 
+#[derive(Debug, Default)]
+pub struct ExtractionSummary {
+    pub completed: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
+
+/// API clients jobs are run with, shared by every worker.
+#[derive(Clone, Default)]
+struct ExtractionClients {
+    google: HashMap<String, Arc<tokio::sync::Mutex<GoogleApiClient>>>, // user_id -> client
+    slack: HashMap<String, Arc<tokio::sync::Mutex<SlackApiClient>>>, // team_id -> client
+}
+
 pub struct ContentExtractionService {
     job_queue: Arc<tokio::sync::Mutex<Vec<ContentExtractionJob>>>,
-    google_clients: HashMap<String, GoogleApiClient>, // user_id -> client
-    slack_clients: HashMap<String, SlackApiClient>, // team_id -> client
-    rate_limiters: HashMap<PlatformType, Arc<Semaphore>>,
+    clients: ExtractionClients,
     worker_count: usize,
 }
 
 impl ContentExtractionService {
+    /// Google and Slack calls are paced by the shared per-host rate limiter in `send_traced`,
+    /// so workers only bound how many jobs run at once.
     pub fn new(worker_count: usize) -> Self {
-        let mut rate_limiters = HashMap::new();
-        
-        // Google APIs have specific rate limits
-        // Documents API: 100 requests per 100 seconds per user
-        // Drive API: 1000 requests per 100 seconds per user
-        // Sheets API: 300 requests per 100 seconds per user
-        rate_limiters.insert(
-            PlatformType::GoogleDocs { document_id: "".to_string() }, 
-            Arc::new(Semaphore::new(50)) // Conservative limit
-        );
-        rate_limiters.insert(
-            PlatformType::GoogleSheets { spreadsheet_id: "".to_string() }, 
-            Arc::new(Semaphore::new(100))
-        );
-        rate_limiters.insert(
-            PlatformType::GoogleSlides { presentation_id: "".to_string() }, 
-            Arc::new(Semaphore::new(50))
-        );
-
-        // Slack API rate limits
-        // Most methods: Tier 2 = 20+ requests per minute
-        // conversations.history: Tier 3 = 50+ requests per minute
-        // User token methods have stricter limits
-        rate_limiters.insert(
-            PlatformType::SlackThread { workspace: "".to_string(), channel: "".to_string(), thread_ts: "".to_string() },
-            Arc::new(Semaphore::new(20)) // Conservative limit for thread extraction
-        );
-        rate_limiters.insert(
-            PlatformType::SlackMessage { workspace: "".to_string(), channel: "".to_string(), message_ts: "".to_string() },
-            Arc::new(Semaphore::new(20)) // Conservative limit for message extraction
-        );
-
         Self {
             job_queue: Arc::new(tokio::sync::Mutex::new(Vec::new())),
-            google_clients: HashMap::new(),
-            slack_clients: HashMap::new(),
-            rate_limiters,
+            clients: ExtractionClients::default(),
             worker_count,
         }
     }
@@ -143,12 +175,12 @@ impl ContentExtractionService {
 
     pub fn add_google_client(&mut self, user_id: String, auth_manager: GoogleAuthManager) {
         let google_client = GoogleApiClient::new(auth_manager);
-        self.google_clients.insert(user_id, google_client);
+        self.clients.google.insert(user_id, Arc::new(tokio::sync::Mutex::new(google_client)));
     }
 
     pub fn add_slack_client(&mut self, team_id: String, auth_manager: SlackAuthManager) {
         let slack_client = SlackApiClient::new(auth_manager);
-        self.slack_clients.insert(team_id, slack_client);
+        self.clients.slack.insert(team_id, Arc::new(tokio::sync::Mutex::new(slack_client)));
     }
 
     pub async fn start_workers(&mut self) -> mpsc::Receiver<ExtractedContent> {
@@ -156,15 +188,15 @@ impl ContentExtractionService {
         
         for worker_id in 0..self.worker_count {
             let queue = Arc::clone(&self.job_queue);
+            let clients = self.clients.clone();
             let tx = content_tx.clone();
-            let rate_limiters = self.rate_limiters.clone();
             
             tokio::spawn(async move {
                 Self::content_extraction_worker(
                     worker_id,
                     queue,
+                    clients,
                     tx,
-                    rate_limiters,
                 ).await;
             });
         }
@@ -175,10 +207,10 @@ impl ContentExtractionService {
     async fn content_extraction_worker(
         worker_id: usize,
         job_queue: Arc<tokio::sync::Mutex<Vec<ContentExtractionJob>>>,
+        clients: ExtractionClients,
         content_sender: mpsc::Sender<ExtractedContent>,
-        rate_limiters: HashMap<PlatformType, Arc<Semaphore>>,
     ) {
-        println!("🔄 Content extraction worker {} started", worker_id);
+        tracing::info!("Content extraction worker {} started", worker_id);
         
         loop {
             // Get next job from queue
@@ -191,43 +223,42 @@ impl ContentExtractionService {
             };
             
             if let Some(mut job) = job {
-                println!("📋 Worker {} processing job {} for {}", worker_id, job.id, job.extracted_link.url);
+                tracing::info!("Worker {} processing job {} for {}", worker_id, job.id, job.extracted_link.url);
                 
                 // Update job status
                 job.status = JobStatus::Processing;
                 
-                // Get appropriate rate limiter
-                let platform_key = Self::get_platform_key_for_rate_limiting(&job.extracted_link.platform_type);
-                let rate_limiter = rate_limiters.get(&platform_key);
-                
-                if let Some(semaphore) = rate_limiter {
-                    let _permit = semaphore.acquire().await.unwrap();
-                    
-                    match Self::process_extraction_job(&job).await {
-                        Ok(content) => {
-                            println!("✅ Worker {} successfully extracted content from {}", worker_id, job.extracted_link.url);
-                            let _ = content_sender.send(content).await;
-                        },
-                        Err(e) => {
-                            eprintln!("❌ Worker {} failed to extract content from {}: {}", worker_id, job.extracted_link.url, e);
+                match clients.process_extraction_job(&job).await {
+                    Ok(content) => {
+                        tracing::info!("Worker {} successfully extracted content from {}", worker_id, job.extracted_link.url);
+                        EXTRACTION_JOBS_TOTAL.with_label_values(&["completed"]).inc();
+                        publish(CONTENT_EXTRACTION, ProgressEvent::ItemCompleted { item: job.extracted_link.url.clone() });
+                        let _ = content_sender.send(content).await;
+                    },
+                    Err(e) => {
+                        tracing::error!("Worker {} failed to extract content from {}: {}", worker_id, job.extracted_link.url, e);
+                        
+                        // Handle retry logic
+                        job.retry_count += 1;
+                        publish(CONTENT_EXTRACTION, ProgressEvent::ItemFailed {
+                            item: job.extracted_link.url.clone(),
+                            error: e.to_string(),
+                            retrying: job.retry_count < MAX_EXTRACTION_ATTEMPTS,
+                        });
+                        if job.retry_count < MAX_EXTRACTION_ATTEMPTS {
+                            EXTRACTION_JOBS_TOTAL.with_label_values(&["retrying"]).inc();
+                            job.status = JobStatus::Retrying;
+                            job.scheduled_for = Utc::now() + chrono::Duration::minutes(job.retry_count as i64 * 5);
                             
-                            // Handle retry logic
-                            job.retry_count += 1;
-                            if job.retry_count < 3 {
-                                job.status = JobStatus::Retrying;
-                                job.scheduled_for = Utc::now() + chrono::Duration::minutes(job.retry_count as i64 * 5);
-                                
-                                // Re-add to queue for retry
-                                let mut queue = job_queue.lock().await;
-                                queue.push(job);
-                            } else {
-                                job.status = JobStatus::Failed(e.to_string());
-                                eprintln!("💀 Job {} failed permanently after {} retries", job.id, job.retry_count);
-                            }
+                            // Re-add to queue for retry
+                            let mut queue = job_queue.lock().await;
+                            queue.push(job);
+                        } else {
+                            EXTRACTION_JOBS_TOTAL.with_label_values(&["failed"]).inc();
+                            job.status = JobStatus::Failed(e.to_string());
+                            tracing::error!("Job {} failed permanently after {} retries", job.id, job.retry_count);
                         }
                     }
-                } else {
-                    eprintln!("⚠️ No rate limiter found for platform type: {:?}", job.extracted_link.platform_type);
                 }
             } else {
                 // No jobs available, sleep for a bit
@@ -236,48 +267,30 @@ impl ContentExtractionService {
         }
     }
 
-    async fn process_extraction_job(job: &ContentExtractionJob) -> Result<ExtractedContent, Box<dyn std::error::Error + Send + Sync>> {
-        // TODO: This would need access to the appropriate Google client for the user
-        // For now, we'll create a mock implementation that would be replaced with actual Google API calls
-        
-        // Simulate content extraction
-        let title = format!("Document from {}", job.extracted_link.url);
-        let body_text = "Mock extracted content".to_string();
-        
-        let metadata = ContentMetadata {
-            author: Some("Unknown".to_string()),
-            created_time: None,
-            modified_time: None,
-            sharing_info: None,
-            comments_count: 0,
-            suggestions_count: 0,
-            revisions_count: 0,
-            content_length: body_text.len() as u32,
-            content_type_specific: serde_json::json!({}),
-        };
-        
-        Ok(ExtractedContent {
-            id: Uuid::new_v4(),
-            job_id: job.id,
-            platform_type: job.extracted_link.platform_type.clone(),
-            source_url: job.extracted_link.url.clone(),
-            title,
-            body_text,
-            metadata,
-            extracted_at: Utc::now(),
-            source_ticket_ids: vec![job.source_ticket_id.clone()],
-        })
-    }
+    /// Runs every job queued so far once, `worker_count` at a time, and records each outcome
+    /// in `content_extraction_jobs`; failed jobs stay queued there for a later run until they
+    /// have used up their attempts.
+    pub async fn run_queued(&self) -> ExtractionSummary {
+        let jobs = std::mem::take(&mut *self.job_queue.lock().await);
+        let outcomes: Vec<JobStatus> = futures::stream::iter(jobs)
+            .map(|mut job| async move {
+                let result = self.clients.process_extraction_job(&job).await;
+                record_extraction_outcome(&mut job, result);
+                job.status
+            })
+            .buffer_unordered(self.worker_count.max(1))
+            .collect()
+            .await;
 
-    fn get_platform_key_for_rate_limiting(platform_type: &PlatformType) -> PlatformType {
-        match platform_type {
-            PlatformType::GoogleDocs { .. } => PlatformType::GoogleDocs { document_id: "".to_string() },
-            PlatformType::GoogleSheets { .. } => PlatformType::GoogleSheets { spreadsheet_id: "".to_string() },
-            PlatformType::GoogleSlides { .. } => PlatformType::GoogleSlides { presentation_id: "".to_string() },
-            PlatformType::SlackThread { .. } => PlatformType::SlackThread { workspace: "".to_string(), channel: "".to_string(), thread_ts: "".to_string() },
-            PlatformType::SlackMessage { .. } => PlatformType::SlackMessage { workspace: "".to_string(), channel: "".to_string(), message_ts: "".to_string() },
-            other => other.clone(),
+        let mut summary = ExtractionSummary::default();
+        for status in outcomes {
+            match status {
+                JobStatus::Completed => summary.completed += 1,
+                JobStatus::Retrying => summary.retrying += 1,
+                _ => summary.failed += 1,
+            }
         }
+        summary
     }
 
     pub async fn get_queue_status(&self) -> (usize, HashMap<JobStatus, usize>) {
@@ -302,6 +315,371 @@ impl ContentExtractionService {
     }
 }
 
+impl Default for ContentExtractionService {
+    /// Sized by `extraction_workers` from the server configuration.
+    fn default() -> Self {
+        Self::new(crate::config::server_config().extraction_workers)
+    }
+}
+
+impl ExtractionClients {
+    async fn process_extraction_job(&self, job: &ContentExtractionJob) -> Result<ExtractedContent, String> {
+        let platform_type = &job.extracted_link.platform_type;
+        let (title, body_text, metadata, acl) = match platform_type {
+            PlatformType::GoogleDocs { .. } | PlatformType::GoogleSheets { .. } | PlatformType::GoogleSlides { .. } => {
+                let client = self
+                    .google
+                    .get(&job.user_id)
+                    .ok_or_else(|| format!("no Google credentials stored for user {}", job.user_id))?;
+                let content = client
+                    .lock()
+                    .await
+                    .extract_content_by_platform_type(platform_type)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                google_content_parts(&content)
+            }
+            PlatformType::SlackThread { workspace, .. } | PlatformType::SlackMessage { workspace, .. } => {
+                // A single connected workspace serves every Slack link
+                let client = self
+                    .slack
+                    .get(workspace)
+                    .or_else(|| self.slack.values().next().filter(|_| self.slack.len() == 1))
+                    .ok_or_else(|| format!("no Slack credentials stored for workspace {}", workspace))?;
+                let content = client
+                    .lock()
+                    .await
+                    .extract_content_by_platform_type(platform_type)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                slack_content_parts(&content)
+            }
+            _ => return Err(format!("no extractor for {}", job.extracted_link.url)),
+        };
+
+        Ok(ExtractedContent {
+            id: Uuid::new_v4(),
+            job_id: job.id,
+            platform_type: platform_type.clone(),
+            source_url: job.extracted_link.url.clone(),
+            title,
+            body_text,
+            metadata,
+            acl,
+            extracted_at: Utc::now(),
+            source_ticket_ids: vec![job.source_ticket_id.clone()],
+        })
+    }
+}
+
+fn google_content_parts(content: &GoogleContentType) -> (String, String, ContentMetadata, ContentAcl) {
+    let (title, body_text, author, created_time, modified_time, comments, suggestions, revisions) = match content {
+        GoogleContentType::Document(doc) => (
+            doc.title.clone(),
+            doc.body.clone(),
+            doc.author.clone(),
+            doc.created_time.clone(),
+            doc.modified_time.clone(),
+            doc.comments.len(),
+            doc.suggestions.len(),
+            doc.revision_history.len(),
+        ),
+        GoogleContentType::Spreadsheet(sheet) => (
+            sheet.title.clone(),
+            sheet
+                .sheets
+                .iter()
+                .map(|tab| {
+                    let rows: Vec<String> = tab.data.iter().map(|row| row.join("\t")).collect();
+                    format!("{}\n{}", tab.title, rows.join("\n"))
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            sheet.author.clone(),
+            sheet.created_time.clone(),
+            sheet.modified_time.clone(),
+            0,
+            0,
+            0,
+        ),
+        GoogleContentType::Presentation(deck) => (
+            deck.title.clone(),
+            deck.slides
+                .iter()
+                .map(|slide| format!("{}\n{}\n{}", slide.title, slide.content, slide.notes))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            deck.author.clone(),
+            deck.created_time.clone(),
+            deck.modified_time.clone(),
+            0,
+            0,
+            0,
+        ),
+    };
+
+    let metadata = ContentMetadata {
+        author,
+        created_time,
+        modified_time,
+        sharing_info: serde_json::to_value(content.sharing_info()).ok(),
+        comments_count: comments as u32,
+        suggestions_count: suggestions as u32,
+        revisions_count: revisions as u32,
+        content_length: body_text.len() as u32,
+        content_type_specific: serde_json::json!({}),
+    };
+    (title, body_text, metadata, content.acl())
+}
+
+fn slack_content_parts(content: &SlackConversationContent) -> (String, String, ContentMetadata, ContentAcl) {
+    let title = format!("#{}", content.channel_name);
+    let body_text = content
+        .messages
+        .iter()
+        .map(|message| message.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let metadata = ContentMetadata {
+        author: content.messages.first().and_then(|message| message.user.clone()),
+        created_time: content.messages.first().map(|message| message.ts.clone()),
+        modified_time: content.messages.last().map(|message| message.ts.clone()),
+        sharing_info: None,
+        comments_count: content.messages.len().saturating_sub(1) as u32,
+        suggestions_count: 0,
+        revisions_count: 0,
+        content_length: body_text.len() as u32,
+        content_type_specific: serde_json::json!({
+            "channel_id": content.channel_id,
+            "thread_ts": content.thread_ts,
+        }),
+    };
+    (title, body_text, metadata, content.acl())
+}
+
+/// Moves every queued job to processing and returns them, oldest first.
+fn claim_queued_extraction_jobs() -> Vec<ContentExtractionJob> {
+    let detector = LinkDetector::new();
+    let mut jobs = Vec::new();
+
+    with_transaction("claim_queued_extraction_jobs", |tx| {
+        tx.execute_batch(REQUEUE_INTERRUPTED_EXTRACTION_JOBS)
+            .expect("Failed to requeue interrupted extraction jobs");
+
+        let mut stmt = tx
+            .prepare(SELECT_QUEUED_EXTRACTION_JOBS)
+            .expect("Failed to prepare extraction job query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<u32>>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })
+            .expect("Failed to query extraction jobs");
+
+        for (id, ticket, url, user_id, priority, retry_count, created_at) in rows.flatten() {
+            let Ok(id) = Uuid::parse_str(&id) else {
+                continue;
+            };
+            let created_at = DateTime::parse_from_rfc3339(&created_at)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+            jobs.push(ContentExtractionJob {
+                id,
+                source_ticket_id: ticket,
+                extracted_link: ExtractedLink {
+                    platform_type: detector.classify_url(&url),
+                    url,
+                    link_context: "queued".to_string(),
+                    extraction_metadata: None,
+                },
+                user_id,
+                priority: match priority.as_str() {
+                    "High" => JobPriority::High,
+                    "Low" => JobPriority::Low,
+                    _ => JobPriority::Medium,
+                },
+                retry_count: retry_count.unwrap_or(0),
+                created_at,
+                scheduled_for: created_at,
+                status: JobStatus::Processing,
+            });
+        }
+
+        let now = Utc::now().to_rfc3339();
+        for job in &jobs {
+            tx.execute(CLAIM_EXTRACTION_JOB, [now.as_str(), job.id.to_string().as_str()])
+                .expect("Failed to claim extraction job");
+        }
+    });
+
+    jobs
+}
+
+/// Stores extracted content, or counts the failure against the job's attempts.
+fn record_extraction_outcome(job: &mut ContentExtractionJob, result: Result<ExtractedContent, String>) {
+    let url = job.extracted_link.url.clone();
+    let (error, content_id) = match result {
+        Ok(content) => {
+            job.status = JobStatus::Completed;
+            EXTRACTION_JOBS_TOTAL.with_label_values(&["completed"]).inc();
+            publish(CONTENT_EXTRACTION, ProgressEvent::ItemCompleted { item: url });
+            (None, Some(store_extracted_content(&content)))
+        }
+        Err(e) => {
+            job.retry_count += 1;
+            let retrying = job.retry_count < MAX_EXTRACTION_ATTEMPTS;
+            job.status = if retrying { JobStatus::Retrying } else { JobStatus::Failed(e.clone()) };
+            EXTRACTION_JOBS_TOTAL
+                .with_label_values(&[if retrying { "retrying" } else { "failed" }])
+                .inc();
+            tracing::warn!("Content extraction failed for {} (attempt {}): {}", url, job.retry_count, e);
+            publish(CONTENT_EXTRACTION, ProgressEvent::ItemFailed { item: url, error: e.clone(), retrying });
+            (Some(e), None)
+        }
+    };
+
+    let status = match job.status {
+        JobStatus::Completed => "Completed",
+        JobStatus::Retrying => "Retrying",
+        _ => "Failed",
+    };
+    with_connection("record_extraction_outcome", |conn| {
+        conn.execute(
+            FINISH_EXTRACTION_JOB,
+            duckdb::params![
+                status,
+                job.retry_count,
+                Utc::now().to_rfc3339(),
+                error,
+                content_id,
+                job.id.to_string(),
+            ],
+        )
+        .expect("Failed to record extraction outcome");
+    });
+}
+
+/// Keyed by source URL, so extracting the same link again replaces the stored copy.
+fn store_extracted_content(content: &ExtractedContent) -> String {
+    let content_type = ContentType::from_platform_type(&content.platform_type).to_string();
+    let content_id = format!("{}:{}", content_type, content.source_url);
+    let now = Utc::now().to_rfc3339();
+    let concepts = extract_concepts_from_text(&content.body_text);
+    let metadata = crate::content_storage::ContentMetadata {
+        word_count: content.body_text.split_whitespace().count() as u32,
+        char_count: content.body_text.chars().count() as u32,
+        comment_count: content.metadata.comments_count,
+        tags: content.source_ticket_ids.clone(),
+        concepts: concepts.clone(),
+        ..Default::default()
+    };
+
+    with_transaction("store_extracted_content", |tx| {
+        tx.execute(
+            INSERT_CONTENT,
+            duckdb::params![
+                content_id,
+                content_type,
+                content.source_url,
+                content_type.split('_').next().unwrap_or_default(),
+                content.title,
+                content.body_text,
+                serde_json::to_string(&content.metadata).unwrap_or_default(),
+                format!("{:016x}", stable_hash(&content.body_text)),
+                content.metadata.author,
+                content.metadata.created_time,
+                content.metadata.modified_time,
+                content.extracted_at.to_rfc3339(),
+                now,
+                "Active",
+                content.acl.to_json(),
+                serde_json::to_string(&metadata).unwrap_or_default(),
+            ],
+        )
+        .expect("Failed to insert extracted content");
+
+        tx.execute(
+            INSERT_SEARCH_INDEX,
+            duckdb::params![
+                format!("{}:index", content_id),
+                content_id,
+                content_type,
+                search_tokens(&content.title),
+                search_tokens(&content.body_text),
+                serde_json::to_string(&concepts).unwrap_or_default(),
+                search_tokens(content.metadata.author.as_deref().unwrap_or_default()),
+                format!("{}\n{}", content.title, content.body_text),
+                Option::<String>::None,
+                now,
+            ],
+        )
+        .expect("Failed to index extracted content");
+    });
+
+    content_id
+}
+
+/// Runs every queued extraction job with `worker_count` workers.
+pub async fn run_queued_extractions(worker_count: usize) -> ExtractionSummary {
+    // OAuth tokens are not persisted yet (see `store_google_tokens`), so no clients are
+    // registered and jobs that need one fail with a "no credentials" error until they are
+    let service = ContentExtractionService::new(worker_count);
+    service.add_extraction_jobs(claim_queued_extraction_jobs()).await;
+    service.run_queued().await
+}
+
+/// Queues every stored Slack conversation for extraction again so new replies are picked up.
+pub fn queue_slack_refresh() -> usize {
+    let mut conversations = Vec::new();
+    with_connection("select_slack_conversations", |conn| {
+        let mut stmt = conn
+            .prepare(SELECT_SLACK_CONVERSATIONS_TO_REFRESH)
+            .expect("Failed to prepare Slack conversation query");
+        conversations = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+            })
+            .expect("Failed to query Slack conversations")
+            .flatten()
+            .collect::<Vec<_>>();
+    });
+
+    let now = Utc::now().to_rfc3339();
+    with_transaction("queue_slack_refresh", |tx| {
+        for (ticket, url, content_type) in &conversations {
+            tx.execute(
+                INSERT_EXTRACTION_JOB,
+                duckdb::params![
+                    Uuid::new_v4().to_string(),
+                    ticket,
+                    url,
+                    content_type,
+                    "system",
+                    Option::<String>::None,
+                    "Low",
+                    "Pending",
+                    0,
+                    now,
+                    Option::<String>::None,
+                    Option::<String>::None,
+                    Option::<String>::None,
+                    Option::<String>::None,
+                ],
+            )
+            .expect("Failed to queue Slack refresh");
+        }
+    });
+    conversations.len()
+}
+
 // Helper function to create jobs from extracted links
 pub fn create_extraction_jobs_from_links(
     ticket_id: &str,
@@ -370,6 +748,20 @@ mod tests {
         assert!(matches!(jobs[0].extracted_link.platform_type, PlatformType::GoogleDocs { .. }));
     }
 
+    #[tokio::test]
+    async fn test_jobs_without_credentials_fail_with_a_reason() {
+        let links = vec![ExtractedLink {
+            url: "https://docs.google.com/document/d/123".to_string(),
+            platform_type: PlatformType::GoogleDocs { document_id: "123".to_string() },
+            link_context: "description".to_string(),
+            extraction_metadata: None,
+        }];
+        let jobs = create_extraction_jobs_from_links("TICKET-123", &links, "system", JobPriority::High);
+
+        let error = ExtractionClients::default().process_extraction_job(&jobs[0]).await.unwrap_err();
+        assert_eq!(error, "no Google credentials stored for user system");
+    }
+
     #[tokio::test]
     async fn test_content_extraction_service() {
         let mut service = ContentExtractionService::new(2);
@@ -39,6 +39,7 @@ pub enum ContentType {
     GitHubPR,
     GitHubIssue,
     JiraTicket, // For consistency
+    JiraAttachment,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -400,13 +401,17 @@ VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 // ================================
 
 pub const SEARCH_CONTENT_FULL_TEXT: &str = r#"
-SELECT c.*, si.full_text_search 
+SELECT c.id, c.content_type, c.source_url, c.source_platform, c.title, c.body_text,
+       c.author, c.created_at, c.last_updated_at, c.metadata,
+       (SELECT MIN(cej.source_ticket_id) FROM content_extraction_jobs cej
+        WHERE cej.extracted_content_id = c.id) AS parent_ticket,
+       c.access_permissions
 FROM extracted_content c
 JOIN content_search_index si ON c.id = si.content_id
-WHERE si.full_text_search LIKE ? 
+WHERE si.full_text_search ILIKE ? 
   AND c.content_status = 'Active'
-ORDER BY c.last_updated_at DESC
-LIMIT ?
+ORDER BY c.last_updated_at DESC, c.id
+LIMIT ? OFFSET ?
 "#;
 
 pub const SEARCH_CONTENT_BY_TYPE_AND_AUTHOR: &str = r#"
@@ -419,7 +424,7 @@ LIMIT ?
 "#;
 
 pub const GET_RELATED_CONTENT: &str = r#"
-SELECT c.*, cr.relationship_type, cr.strength
+SELECT c.id, c.title, cr.relationship_type, cr.strength, c.source_platform, c.access_permissions
 FROM extracted_content c
 JOIN content_relationships cr ON c.id = cr.target_content_id
 WHERE cr.source_content_id = ?
@@ -466,6 +471,22 @@ impl ContentType {
             ContentType::GitHubPR => "github_pr".to_string(),
             ContentType::GitHubIssue => "github_issue".to_string(),
             ContentType::JiraTicket => "jira_ticket".to_string(),
+            ContentType::JiraAttachment => "jira_attachment".to_string(),
+        }
+    }
+}
+
+impl RelationshipType {
+    pub fn to_string(&self) -> String {
+        match self {
+            RelationshipType::LinkedFrom => "linked_from".to_string(),
+            RelationshipType::References => "references".to_string(),
+            RelationshipType::DiscussionOf => "discussion_of".to_string(),
+            RelationshipType::ImplementationOf => "implementation_of".to_string(),
+            RelationshipType::FollowUpTo => "follow_up_to".to_string(),
+            RelationshipType::DuplicateOf => "duplicate_of".to_string(),
+            RelationshipType::PartOf => "part_of".to_string(),
+            RelationshipType::Mentions => "mentions".to_string(),
         }
     }
 }
@@ -502,6 +523,18 @@ impl Default for ContentMetadata {
     }
 }
 
+/// Distinct lowercase words longer than two characters, as a JSON array for the search index.
+pub fn search_tokens(value: &str) -> String {
+    let words: Vec<String> = value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .map(|word| word.to_lowercase())
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    serde_json::to_string(&words).unwrap_or_default()
+}
+
 // Table creation function
 pub async fn create_content_storage_tables() {
     log_step("🗄️", "Initializing content storage tables...");
@@ -521,7 +554,7 @@ pub async fn create_content_storage_tables() {
         for (table_name, create_sql) in tables {
             match conn.execute_batch(create_sql) {
                 Ok(_) => {
-                    println!("✅ Created table: {}", table_name);
+                    tracing::info!("Created table: {}", table_name);
                 },
                 Err(e) => {
                     panic!("❌ Failed to create table {}: {}", table_name, e);
@@ -0,0 +1,205 @@
+use crate::link_detector::LinkDetector;
+use dirs::home_dir;
+use once_cell::sync::Lazy;
+use crate::types::IssueCustomField;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+// ================================
+// CUSTOM FIELD MAPPING
+// ================================
+
+/// Jira field names that hold story points, depending on the project flavour.
+const STORY_POINT_FIELD_NAMES: [&str; 2] = ["Story Points", "Story point estimate"];
+const EPIC_LINK_FIELD_NAME: &str = "Epic Link";
+
+/// Friendly column name -> Jira custom field ID or display name, read from
+/// `~/.triage/custom_fields.json`, e.g. `{ "team": "Team", "severity": "customfield_10050" }`.
+static CUSTOM_FIELD_MAPPING: Lazy<BTreeMap<String, String>> = Lazy::new(load_custom_field_mapping);
+
+fn get_mapping_path() -> PathBuf {
+    let mut path = home_dir().expect("Could not find home directory");
+    path.push(".triage");
+    path.push("custom_fields.json");
+    path
+}
+
+fn load_custom_field_mapping() -> BTreeMap<String, String> {
+    let path = get_mapping_path();
+    if !path.exists() {
+        return BTreeMap::new();
+    }
+
+    let data = fs::read_to_string(&path).unwrap_or_default();
+    serde_json::from_str(&data).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring invalid {}: {}", path.display(), e);
+        BTreeMap::new()
+    })
+}
+
+/// Finds the field ID whose display name (from the `names` expansion) matches.
+fn field_id_for_name<'a>(names: &'a Value, display_name: &str) -> Option<&'a str> {
+    names.as_object()?.iter().find_map(|(id, name)| {
+        name.as_str()
+            .filter(|name| name.eq_ignore_ascii_case(display_name))
+            .map(|_| id.as_str())
+    })
+}
+
+/// Flattens a custom field value (option, user, array, ADF, number) into text.
+pub fn custom_field_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Null => return None,
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(custom_field_text)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(obj) => ["value", "name", "displayName", "key"]
+            .iter()
+            .find_map(|key| obj.get(*key).and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .unwrap_or_else(|| LinkDetector::new().extract_text_from_field(value)),
+    };
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Resolves the configured mapping against one issue's fields.
+pub fn extract_custom_fields(
+    fields: &serde_json::Map<String, Value>,
+    names: &Value,
+) -> Vec<IssueCustomField> {
+    resolve_custom_fields(&CUSTOM_FIELD_MAPPING, fields, names)
+}
+
+fn resolve_custom_fields(
+    mapping: &BTreeMap<String, String>,
+    fields: &serde_json::Map<String, Value>,
+    names: &Value,
+) -> Vec<IssueCustomField> {
+    mapping
+        .iter()
+        .filter_map(|(friendly_name, field)| {
+            let field_id = if fields.contains_key(field) {
+                field.as_str()
+            } else {
+                field_id_for_name(names, field)?
+            };
+            let value = custom_field_text(fields.get(field_id)?)?;
+
+            Some(IssueCustomField {
+                field_id: field_id.to_string(),
+                field_name: names[field_id].as_str().unwrap_or(field_id).to_string(),
+                friendly_name: friendly_name.clone(),
+                value,
+            })
+        })
+        .collect()
+}
+
+pub fn extract_story_points(fields: &serde_json::Map<String, Value>, names: &Value) -> Option<f64> {
+    STORY_POINT_FIELD_NAMES
+        .iter()
+        .filter_map(|name| field_id_for_name(names, name))
+        .find_map(|field_id| fields.get(field_id).and_then(|v| v.as_f64()))
+}
+
+/// Classic projects keep the epic in an "Epic Link" custom field holding the epic's key.
+pub fn extract_epic_link(fields: &serde_json::Map<String, Value>, names: &Value) -> Option<String> {
+    let field_id = field_id_for_name(names, EPIC_LINK_FIELD_NAME)?;
+    fields
+        .get(field_id)
+        .and_then(|v| v.as_str())
+        .filter(|key| !key.is_empty())
+        .map(|key| key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn as_map(value: Value) -> serde_json::Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_custom_field_text_flattens_common_shapes() {
+        assert_eq!(custom_field_text(&json!(null)), None);
+        assert_eq!(custom_field_text(&json!("")), None);
+        assert_eq!(custom_field_text(&json!(3.5)), Some("3.5".to_string()));
+        assert_eq!(custom_field_text(&json!({"value": "High", "id": "10"})), Some("High".to_string()));
+        assert_eq!(
+            custom_field_text(&json!({"displayName": "Ada Lovelace", "accountId": "abc"})),
+            Some("Ada Lovelace".to_string())
+        );
+        assert_eq!(
+            custom_field_text(&json!([{"value": "iOS"}, {"value": "Android"}, null])),
+            Some("iOS, Android".to_string())
+        );
+        let adf = json!({
+            "type": "doc",
+            "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Root cause"}]}]
+        });
+        assert!(custom_field_text(&adf).unwrap().contains("Root cause"));
+    }
+
+    #[test]
+    fn test_resolve_custom_fields_by_id_or_display_name() {
+        let mapping = BTreeMap::from([
+            ("severity".to_string(), "customfield_10050".to_string()),
+            ("team".to_string(), "team".to_string()),
+            ("missing".to_string(), "Nowhere".to_string()),
+            ("empty".to_string(), "customfield_10070".to_string()),
+        ]);
+        let fields = as_map(json!({
+            "customfield_10050": {"value": "S2"},
+            "customfield_10060": {"name": "Payments"},
+            "customfield_10070": null,
+        }));
+        let names = json!({
+            "customfield_10050": "Severity",
+            "customfield_10060": "Team",
+            "customfield_10070": "Notes",
+        });
+
+        let resolved = resolve_custom_fields(&mapping, &fields, &names);
+
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].friendly_name, "severity");
+        assert_eq!(resolved[0].field_id, "customfield_10050");
+        assert_eq!(resolved[0].field_name, "Severity");
+        assert_eq!(resolved[0].value, "S2");
+        assert_eq!(resolved[1].friendly_name, "team");
+        assert_eq!(resolved[1].field_id, "customfield_10060");
+        assert_eq!(resolved[1].value, "Payments");
+    }
+
+    #[test]
+    fn test_story_points_and_epic_link_follow_field_names() {
+        let names = json!({
+            "customfield_10016": "Story point estimate",
+            "customfield_10014": "Epic Link",
+        });
+        let fields = as_map(json!({
+            "customfield_10016": 5.0,
+            "customfield_10014": "PAY-100",
+        }));
+
+        assert_eq!(extract_story_points(&fields, &names), Some(5.0));
+        assert_eq!(extract_epic_link(&fields, &names), Some("PAY-100".to_string()));
+
+        let unset = as_map(json!({"customfield_10016": null, "customfield_10014": ""}));
+        assert_eq!(extract_story_points(&unset, &names), None);
+        assert_eq!(extract_epic_link(&unset, &names), None);
+    }
+}
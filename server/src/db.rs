@@ -2,13 +2,14 @@
 
 use crate::db_utils::{with_connection, with_transaction};
 use crate::queries::{
-    CREATE_ISSUES_TABLE, CREATE_PROJECT_TABLE, GET_PROJECT_IDS, INSERT_ISSUE_METADATA,
-    INSERT_PROJECT,
+    CREATE_ISSUES_TABLE, CREATE_ISSUE_CUSTOM_FIELDS_TABLE, CREATE_ISSUE_KEY_ALIASES_TABLE,
+    CREATE_LIVE_ISSUES_VIEW, CREATE_PROJECT_TABLE, DELETE_ISSUE_CUSTOM_FIELDS, GET_PROJECT_IDS,
+    INSERT_ISSUE_CUSTOM_FIELD, INSERT_ISSUE_METADATA, INSERT_PROJECT, MARK_ISSUE_DELETED,
+    MIGRATE_ISSUES_TABLE, RECORD_ISSUE_KEY_ALIAS,
 };
 // Content storage tables are now initialized separately
 use crate::types::{IssueFieldMetadata, Project};
 use crate::utils::{json_opt_to_string, log_error, log_step, log_success};
-use colored::*;
 
 pub async fn create_project_table() {
     log_step("📦", "Initializing `projects` table...");
@@ -33,6 +34,30 @@ pub async fn create_issues_table() {
                 log_error("create_issues_table")
             )
         });
+        conn.execute_batch(MIGRATE_ISSUES_TABLE).unwrap_or_else(|_| {
+            panic!(
+                "{} Failed to execute MIGRATE_ISSUES_TABLE",
+                log_error("create_issues_table")
+            )
+        });
+        conn.execute_batch(CREATE_ISSUE_CUSTOM_FIELDS_TABLE).unwrap_or_else(|_| {
+            panic!(
+                "{} Failed to execute CREATE_ISSUE_CUSTOM_FIELDS_TABLE",
+                log_error("create_issues_table")
+            )
+        });
+        conn.execute_batch(CREATE_LIVE_ISSUES_VIEW).unwrap_or_else(|_| {
+            panic!(
+                "{} Failed to execute CREATE_LIVE_ISSUES_VIEW",
+                log_error("create_issues_table")
+            )
+        });
+        conn.execute_batch(CREATE_ISSUE_KEY_ALIASES_TABLE).unwrap_or_else(|_| {
+            panic!(
+                "{} Failed to execute CREATE_ISSUE_KEY_ALIASES_TABLE",
+                log_error("create_issues_table")
+            )
+        });
         log_success("`issues` table ready.");
     });
 }
@@ -59,10 +84,7 @@ pub async fn save_projects_to_duckdb(projects: &Vec<Project>) {
             for project in projects {
                 stmt.execute([&project.id, &project.key, &project.name])
                     .unwrap_or_else(|_| panic!("{} Insert project", log_error("save_projects")));
-                println!(
-                    "{}",
-                    format!("➡ Inserted project: [{}] {}", project.key, project.name).blue()
-                );
+                tracing::info!("Inserted project: [{}] {}", project.key, project.name);
             }
         });
 
@@ -100,8 +122,23 @@ pub async fn save_issues_batch_to_duckdb(issues: &[IssueFieldMetadata]) {
         let mut stmt = tx
             .prepare(INSERT_ISSUE_METADATA)
             .unwrap_or_else(|_| panic!("{} Prepare insert", log_error("save_issues_batch")));
+        let mut delete_custom_fields = tx
+            .prepare(DELETE_ISSUE_CUSTOM_FIELDS)
+            .unwrap_or_else(|_| panic!("{} Prepare custom field delete", log_error("save_issues_batch")));
+        let mut insert_custom_field = tx
+            .prepare(INSERT_ISSUE_CUSTOM_FIELD)
+            .unwrap_or_else(|_| panic!("{} Prepare custom field insert", log_error("save_issues_batch")));
+        let mut record_alias = tx
+            .prepare(RECORD_ISSUE_KEY_ALIAS)
+            .unwrap_or_else(|_| panic!("{} Prepare key alias", log_error("save_issues_batch")));
+        let now = chrono::Utc::now().to_rfc3339();
 
         for issue in issues {
+            // An issue moved to another project keeps its id but gets a new key
+            record_alias
+                .execute(duckdb::params![now, issue.id, issue.key])
+                .unwrap_or_else(|_| panic!("{} Record key alias", log_error("save_issues_batch")));
+
             let summary = issue.summary.as_deref().unwrap_or("");
             let created = issue.created.as_deref().unwrap_or("");
             let updated = issue.updated.as_deref().unwrap_or("");
@@ -144,6 +181,13 @@ pub async fn save_issues_batch_to_duckdb(issues: &[IssueFieldMetadata]) {
             let work_log = json_opt_to_string(&issue.work_log);
             let time_tracking = json_opt_to_string(&issue.time_tracking);
 
+            let components = issue.components.as_ref()
+                .map(|c| serde_json::to_string(c).unwrap_or_default());
+            let fix_versions = issue.fix_versions.as_ref()
+                .map(|v| serde_json::to_string(v).unwrap_or_default());
+            let affected_versions = issue.affected_versions.as_ref()
+                .map(|v| serde_json::to_string(v).unwrap_or_default());
+
             stmt.execute(duckdb::params![
                 issue.id,
                 issue.key,
@@ -180,17 +224,51 @@ pub async fn save_issues_batch_to_duckdb(issues: &[IssueFieldMetadata]) {
                 issue_links,
                 work_log,
                 time_tracking,
+                issue.resolution,
+                issue.resolution_date,
+                components,
+                fix_versions,
+                affected_versions,
+                issue.parent_key,
+                issue.epic_key,
+                issue.due_date,
+                issue.story_points,
+                issue.environment,
             ])
             .unwrap_or_else(|_| panic!("{} Insert issue", log_error("save_issues_batch")));
+
+            delete_custom_fields
+                .execute([&issue.id])
+                .unwrap_or_else(|_| panic!("{} Clear custom fields", log_error("save_issues_batch")));
+            for field in &issue.custom_fields {
+                insert_custom_field
+                    .execute([
+                        &issue.id,
+                        &field.field_id,
+                        &field.field_name,
+                        &field.friendly_name,
+                        &field.value,
+                    ])
+                    .unwrap_or_else(|_| panic!("{} Insert custom field", log_error("save_issues_batch")));
+            }
         }
 
-        println!(
-            "{}",
-            format!("✅ Successfully inserted {} issues.", issues.len()).green()
-        );
+        tracing::info!("Successfully inserted {} issues.", issues.len());
     });
 
     log_success("Batch commit complete.");
 }
 
+/// Keeps the row for history but flags it so it can be told apart from live issues.
+pub async fn mark_issue_deleted(issue_id: &str) {
+    with_connection("mark_issue_deleted", |conn| {
+        let updated = conn
+            .execute(MARK_ISSUE_DELETED, [chrono::Utc::now().to_rfc3339().as_str(), issue_id])
+            .unwrap_or_else(|_| panic!("{} Update issue", log_error("mark_issue_deleted")));
+        if updated == 0 {
+            tracing::warn!("Deleted issue {} was never synced", issue_id);
+        }
+    });
+}
+
 // Content storage table creation is handled in content_storage.rs module
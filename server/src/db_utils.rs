@@ -1,15 +1,20 @@
+use crate::config::server_config;
+use crate::telemetry::DB_QUERY_DURATION;
 use crate::utils::log_error;
-use colored::*;
 use dirs::home_dir;
 use duckdb::{Connection, Result as DuckResult, Transaction};
 use once_cell::sync::Lazy;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::Instant;
 
 static IS_CONNECTION_MSG_LOGGED: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
 
 fn get_db_path() -> PathBuf {
+    if let Some(path) = &server_config().database_path {
+        return path.clone();
+    }
     let mut dir = home_dir().expect("❌ Could not find home directory");
     dir.push(".triage");
     if !dir.exists() {
@@ -23,33 +28,44 @@ pub fn get_connection() -> DuckResult<Connection> {
     let db_path = get_db_path();
     let mut is_connection_msg_logged = IS_CONNECTION_MSG_LOGGED.lock().unwrap();
     if !*is_connection_msg_logged {
-        println!(
-            "{} {}",
-            "🔌 Connecting to DuckDB at:".bright_black(),
-            db_path.display()
-        );
+        tracing::info!(path = %db_path.display(), "Connecting to DuckDB");
         *is_connection_msg_logged = true;
     }
     Connection::open(db_path)
 }
 
+/// Runs `f` inside a `db_query` span and records its duration under `context`.
+fn timed<T>(context: &'static str, f: impl FnOnce() -> T) -> T {
+    let span = tracing::debug_span!("db_query", context);
+    let started = Instant::now();
+    let result = span.in_scope(f);
+    DB_QUERY_DURATION
+        .with_label_values(&[context])
+        .observe(started.elapsed().as_secs_f64());
+    result
+}
+
 pub fn with_connection<F>(context: &'static str, f: F)
 where
     F: FnOnce(Connection),
 {
-    f(get_connection().unwrap_or_else(|_| panic!("{} DB connection failed", log_error(context))));
+    timed(context, || {
+        f(get_connection().unwrap_or_else(|_| panic!("{} DB connection failed", log_error(context))))
+    });
 }
 
 pub fn with_transaction<F>(context: &'static str, f: F)
 where
     F: FnOnce(&Transaction),
 {
-    let mut conn =
-        get_connection().unwrap_or_else(|_| panic!("{} DB connection failed", log_error(context)));
-    let tx = conn
-        .transaction()
-        .unwrap_or_else(|_| panic!("{} Failed to start transaction", log_error(context)));
-    f(&tx);
-    tx.commit()
-        .unwrap_or_else(|_| panic!("{} Failed to commit transaction", log_error(context)));
+    timed(context, || {
+        let mut conn =
+            get_connection().unwrap_or_else(|_| panic!("{} DB connection failed", log_error(context)));
+        let tx = conn
+            .transaction()
+            .unwrap_or_else(|_| panic!("{} Failed to start transaction", log_error(context)));
+        f(&tx);
+        tx.commit()
+            .unwrap_or_else(|_| panic!("{} Failed to commit transaction", log_error(context)));
+    });
 }
@@ -0,0 +1,726 @@
+use crate::content_storage::{ContentType, RelationshipType, INSERT_CONTENT_RELATIONSHIP};
+use crate::db_utils::{with_connection, with_transaction};
+use crate::link_detector::LinkDetector;
+use crate::utils::{log_step, log_success, stable_hash};
+use chrono::Utc;
+use colored::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+// ================================
+// DUPLICATE ISSUE DETECTION
+// ================================
+
+const NUM_HASHES: usize = 128;
+/// 32 bands of 4 rows make pairs above ~0.42 Jaccard likely to share a bucket.
+const LSH_BANDS: usize = 32;
+const LSH_ROWS: usize = NUM_HASHES / LSH_BANDS;
+const SHINGLE_SIZE: usize = 3;
+/// Template tickets can fill one bucket with hundreds of issues; only pair the first ones.
+const MAX_BUCKET_SIZE: usize = 200;
+
+pub const DEFAULT_DUPLICATE_THRESHOLD: f64 = 0.6;
+pub const DEFAULT_SIMILAR_THRESHOLD: f64 = 0.3;
+
+pub const CREATE_DUPLICATE_FEEDBACK_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS duplicate_feedback (
+    key_a TEXT NOT NULL, -- lower of the two keys
+    key_b TEXT NOT NULL,
+    verdict TEXT NOT NULL, -- not_duplicate
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_duplicate_feedback_pair ON duplicate_feedback(key_a, key_b);
+"#;
+
+/// MinHash signatures by issue, recomputed only when the issue's `updated` changes.
+pub const CREATE_ISSUE_SIGNATURES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS issue_signatures (
+    issue_key TEXT PRIMARY KEY,
+    issue_updated TEXT,
+    signature TEXT -- JSON array of NUM_HASHES minimums; NULL when the issue has no text
+);
+"#;
+
+const LOAD_ISSUES_WITH_SIGNATURES: &str = r#"
+SELECT i.key, i.project_key, i.summary, i.status, i.created, i.self_link, i.updated,
+       s.issue_key IS NOT NULL AND s.issue_updated IS NOT DISTINCT FROM i.updated AS current,
+       s.signature,
+       CASE WHEN s.issue_key IS NULL OR s.issue_updated IS DISTINCT FROM i.updated THEN i.description END
+FROM live_issues i
+LEFT JOIN issue_signatures s ON s.issue_key = i.key
+"#;
+
+/// Issues become `jira_ticket` content so they can take part in content relationships.
+const INSERT_ISSUE_CONTENT_IF_MISSING: &str = r#"
+INSERT OR IGNORE INTO extracted_content
+(id, content_type, source_url, source_platform, title, body_text, content_hash,
+ created_at, extracted_at, last_updated_at, content_status)
+VALUES (?, ?, ?, 'jira', ?, '', '', ?, ?, ?, 'Active')
+"#;
+
+pub async fn initialize_dedupe_tables() {
+    with_connection("initialize_dedupe_tables", |conn| {
+        conn.execute_batch(CREATE_DUPLICATE_FEEDBACK_TABLE)
+            .expect("Failed to create duplicate_feedback table");
+        conn.execute_batch(CREATE_ISSUE_SIGNATURES_TABLE)
+            .expect("Failed to create issue_signatures table");
+    });
+}
+
+fn issue_content_id(key: &str) -> String {
+    format!("jira_ticket:{}", key)
+}
+
+fn feedback_pair(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+// ================================
+// MINHASH SIGNATURES
+// ================================
+
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Word 3-grams of the lowercased text; shorter texts fall back to single words.
+fn shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    if words.len() < SHINGLE_SIZE {
+        return words.iter().map(|word| stable_hash(word)).collect();
+    }
+    words
+        .windows(SHINGLE_SIZE)
+        .map(|window| stable_hash(&window.join(" ")))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinHashSignature(Vec<u64>);
+
+impl MinHashSignature {
+    pub fn from_text(text: &str) -> Option<Self> {
+        let shingles = shingles(text);
+        if shingles.is_empty() {
+            return None;
+        }
+        Some(Self(
+            (0..NUM_HASHES)
+                .map(|i| {
+                    let seed = mix(i as u64 + 1);
+                    shingles.iter().map(|shingle| mix(shingle ^ seed)).min().unwrap_or(u64::MAX)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Estimated Jaccard similarity of the two shingle sets.
+    pub fn similarity(&self, other: &Self) -> f64 {
+        let equal = self.0.iter().zip(&other.0).filter(|(a, b)| a == b).count();
+        equal as f64 / NUM_HASHES as f64
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).unwrap_or_default()
+    }
+
+    fn from_json(raw: &str) -> Option<Self> {
+        serde_json::from_str::<Vec<u64>>(raw)
+            .ok()
+            .filter(|values| values.len() == NUM_HASHES)
+            .map(Self)
+    }
+
+    fn band_keys(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.0.chunks(LSH_ROWS).enumerate().map(|(band, rows)| {
+            (band, rows.iter().fold(band as u64, |hash, row| mix(hash ^ row)))
+        })
+    }
+}
+
+// ================================
+// ISSUE LOADING
+// ================================
+
+struct IssueFingerprint {
+    key: String,
+    project_key: String,
+    summary: String,
+    status: String,
+    created: String,
+    self_link: Option<String>,
+    signature: MinHashSignature,
+}
+
+/// Stored signatures of unchanged issues are reused; new and updated issues are signed and saved.
+fn load_fingerprints(project_key: Option<&str>) -> Vec<IssueFingerprint> {
+    let mut fingerprints = Vec::new();
+    let mut changed: Vec<(String, Option<String>, Option<String>)> = Vec::new();
+    let link_detector = LinkDetector::new();
+
+    with_connection("load_issue_fingerprints", |conn| {
+        let mut query = String::from(LOAD_ISSUES_WITH_SIGNATURES);
+        let mut params = Vec::new();
+        if let Some(project_key) = project_key {
+            query.push_str(" WHERE i.project_key = ?");
+            params.push(project_key.to_string());
+        }
+
+        let mut stmt = conn.prepare(&query).expect("Failed to prepare fingerprint query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(params), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, bool>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, Option<String>>(9)?,
+                ))
+            })
+            .expect("Failed to query issue texts");
+
+        for (key, project, summary, status, created, self_link, updated, current, stored, description) in
+            rows.flatten()
+        {
+            let summary = summary.unwrap_or_default();
+            let signature = if current {
+                stored.as_deref().and_then(MinHashSignature::from_json)
+            } else {
+                let description = description
+                    .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+                    .map(|value| link_detector.extract_text_from_field(&value))
+                    .unwrap_or_default();
+                let signature = MinHashSignature::from_text(&format!("{} {}", summary, description));
+                changed.push((key.clone(), updated, signature.as_ref().map(MinHashSignature::to_json)));
+                signature
+            };
+
+            let Some(signature) = signature else {
+                continue;
+            };
+            fingerprints.push(IssueFingerprint {
+                key,
+                project_key: project.unwrap_or_default(),
+                summary,
+                status: status.unwrap_or_default(),
+                created: created.unwrap_or_default(),
+                self_link,
+                signature,
+            });
+        }
+    });
+
+    if !changed.is_empty() {
+        with_transaction("save_issue_signatures", |tx| {
+            let mut stmt = tx
+                .prepare("INSERT OR REPLACE INTO issue_signatures (issue_key, issue_updated, signature) VALUES (?, ?, ?)")
+                .expect("Failed to prepare signature insert");
+            for (key, updated, signature) in &changed {
+                stmt.execute(duckdb::params![key, updated, signature])
+                    .expect("Failed to save issue signature");
+            }
+        });
+    }
+
+    fingerprints
+}
+
+/// Drops signatures of issues that were deleted or moved to another key.
+fn prune_issue_signatures() {
+    with_connection("prune_issue_signatures", |conn| {
+        conn.execute(
+            "DELETE FROM issue_signatures WHERE issue_key NOT IN (SELECT key FROM live_issues)",
+            [],
+        )
+        .expect("Failed to prune issue signatures");
+    });
+}
+
+fn load_dismissed_pairs() -> HashSet<(String, String)> {
+    let mut pairs = HashSet::new();
+
+    with_connection("load_duplicate_feedback", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT key_a, key_b FROM duplicate_feedback WHERE verdict = 'not_duplicate'")
+            .expect("Failed to prepare duplicate feedback query");
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("Failed to query duplicate feedback");
+        pairs.extend(rows.flatten());
+    });
+
+    pairs
+}
+
+// ================================
+// SIMILAR ISSUES & CLUSTERING
+// ================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarIssue {
+    pub key: String,
+    pub project_key: String,
+    pub summary: String,
+    pub status: String,
+    pub similarity: f64,
+}
+
+impl SimilarIssue {
+    fn from_fingerprint(fingerprint: &IssueFingerprint, similarity: f64) -> Self {
+        Self {
+            key: fingerprint.key.clone(),
+            project_key: fingerprint.project_key.clone(),
+            summary: fingerprint.summary.clone(),
+            status: fingerprint.status.clone(),
+            similarity,
+        }
+    }
+}
+
+/// Issues whose summary and description overlap with `key`, most similar first.
+/// Returns `None` when the issue is unknown or has no text to compare.
+pub fn find_similar_issues(key: &str, limit: usize, threshold: f64) -> Option<Vec<SimilarIssue>> {
+    let fingerprints = load_fingerprints(None);
+    let dismissed = load_dismissed_pairs();
+    let target = fingerprints.iter().find(|fingerprint| fingerprint.key == key)?;
+
+    let mut similar: Vec<SimilarIssue> = fingerprints
+        .iter()
+        .filter(|other| other.key != key && !dismissed.contains(&feedback_pair(key, &other.key)))
+        .map(|other| (other, target.signature.similarity(&other.signature)))
+        .filter(|(_, similarity)| *similarity >= threshold)
+        .map(|(other, similarity)| SimilarIssue::from_fingerprint(other, similarity))
+        .collect();
+
+    similar.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    similar.truncate(limit);
+    Some(similar)
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateCluster {
+    /// The earliest issue; the others are stored as `DuplicateOf` it.
+    pub canonical: SimilarIssue,
+    pub duplicates: Vec<SimilarIssue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DedupeReport {
+    pub project_key: Option<String>,
+    pub threshold: f64,
+    pub issues_scanned: usize,
+    pub clusters: Vec<DuplicateCluster>,
+    pub generated_at: String,
+}
+
+/// Candidate pairs from MinHash LSH buckets, kept when the estimated similarity clears `threshold`.
+fn candidate_pairs(
+    fingerprints: &[IssueFingerprint],
+    threshold: f64,
+    dismissed: &HashSet<(String, String)>,
+) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        for band_key in fingerprint.signature.band_keys() {
+            buckets.entry(band_key).or_default().push(index);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut pairs = Vec::new();
+    for members in buckets.values().filter(|members| members.len() > 1) {
+        let members = &members[..members.len().min(MAX_BUCKET_SIZE)];
+        for (position, &a) in members.iter().enumerate() {
+            for &b in &members[position + 1..] {
+                if !seen.insert((a.min(b), a.max(b))) {
+                    continue;
+                }
+                let (left, right) = (&fingerprints[a], &fingerprints[b]);
+                if dismissed.contains(&feedback_pair(&left.key, &right.key)) {
+                    continue;
+                }
+                if left.signature.similarity(&right.signature) >= threshold {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
+
+/// Merges pairs most similar first and never joins two groups that hold a pair dismissed as
+/// "not a duplicate", which a shared third issue would otherwise link through.
+fn cluster_duplicates(
+    fingerprints: &[IssueFingerprint],
+    pairs: &[(usize, usize)],
+    dismissed: &HashSet<(String, String)>,
+) -> Vec<DuplicateCluster> {
+    let similarity = |(a, b): (usize, usize)| fingerprints[a].signature.similarity(&fingerprints[b].signature);
+    let mut ordered = pairs.to_vec();
+    ordered.sort_by(|&x, &y| {
+        similarity(y)
+            .partial_cmp(&similarity(x))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(x.cmp(&y))
+    });
+
+    let mut parents: Vec<usize> = (0..fingerprints.len()).collect();
+    let mut groups: Vec<Vec<usize>> = (0..fingerprints.len()).map(|index| vec![index]).collect();
+    for (a, b) in ordered {
+        let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+        if root_a == root_b {
+            continue;
+        }
+        let split = groups[root_a].iter().any(|&x| {
+            groups[root_b]
+                .iter()
+                .any(|&y| dismissed.contains(&feedback_pair(&fingerprints[x].key, &fingerprints[y].key)))
+        });
+        if split {
+            continue;
+        }
+        parents[root_b] = root_a;
+        let merged = std::mem::take(&mut groups[root_b]);
+        groups[root_a].extend(merged);
+    }
+
+    let mut clusters: Vec<DuplicateCluster> = groups
+        .into_iter()
+        .filter(|members| members.len() > 1)
+        .map(|mut members| {
+            members.sort_by(|&a, &b| {
+                let (a, b) = (&fingerprints[a], &fingerprints[b]);
+                a.created.cmp(&b.created).then_with(|| a.key.cmp(&b.key))
+            });
+            let canonical = &fingerprints[members[0]];
+            DuplicateCluster {
+                canonical: SimilarIssue::from_fingerprint(canonical, 1.0),
+                duplicates: members[1..]
+                    .iter()
+                    .map(|&index| {
+                        let duplicate = &fingerprints[index];
+                        SimilarIssue::from_fingerprint(duplicate, duplicate.signature.similarity(&canonical.signature))
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.duplicates
+            .len()
+            .cmp(&a.duplicates.len())
+            .then_with(|| a.canonical.key.cmp(&b.canonical.key))
+    });
+    clusters
+}
+
+/// Clusters likely duplicates and replaces the stored `DuplicateOf` relationships for the scope.
+pub fn run_dedupe(project_key: Option<&str>, threshold: f64) -> DedupeReport {
+    log_step("🧬", "Looking for duplicate issues...");
+
+    if project_key.is_none() {
+        prune_issue_signatures();
+    }
+    let fingerprints = load_fingerprints(project_key);
+    let dismissed = load_dismissed_pairs();
+    let pairs = candidate_pairs(&fingerprints, threshold, &dismissed);
+    let clusters = cluster_duplicates(&fingerprints, &pairs, &dismissed);
+
+    store_duplicate_relationships(&fingerprints, &clusters, project_key);
+
+    log_success(&format!(
+        "Found {} duplicate cluster(s) across {} issue(s).",
+        clusters.len(),
+        fingerprints.len()
+    ));
+
+    DedupeReport {
+        project_key: project_key.map(|key| key.to_string()),
+        threshold,
+        issues_scanned: fingerprints.len(),
+        clusters,
+        generated_at: Utc::now().to_rfc3339(),
+    }
+}
+
+fn store_duplicate_relationships(
+    fingerprints: &[IssueFingerprint],
+    clusters: &[DuplicateCluster],
+    project_key: Option<&str>,
+) {
+    let relationship_type = RelationshipType::DuplicateOf.to_string();
+    let scope = format!("{}%", issue_content_id(&project_key.map(|key| format!("{}-", key)).unwrap_or_default()));
+    let relationship_id = |source: &str, target: &str| format!("{}:{}:{}", relationship_type, source, target);
+    let kept: HashSet<String> = clusters
+        .iter()
+        .flat_map(|cluster| {
+            cluster.duplicates.iter().map(|duplicate| {
+                relationship_id(&issue_content_id(&duplicate.key), &issue_content_id(&cluster.canonical.key))
+            })
+        })
+        .collect();
+
+    let by_key: HashMap<&str, &IssueFingerprint> = fingerprints
+        .iter()
+        .map(|fingerprint| (fingerprint.key.as_str(), fingerprint))
+        .collect();
+    let now = Utc::now().to_rfc3339();
+
+    with_transaction("store_duplicate_relationships", |tx| {
+        // Only relationships that are not written again are deleted; DuckDB rejects re-inserting
+        // a key deleted earlier in the same transaction, and the rest are replaced in place
+        let mut stored_stmt = tx
+            .prepare("SELECT id FROM content_relationships WHERE relationship_type = ? AND source_content_id LIKE ?")
+            .expect("Failed to prepare duplicate relationship query");
+        let stale: Vec<String> = stored_stmt
+            .query_map(duckdb::params![relationship_type, scope], |row| row.get::<_, String>(0))
+            .expect("Failed to query duplicate relationships")
+            .flatten()
+            .filter(|id| !kept.contains(id))
+            .collect();
+        let mut delete_stmt = tx
+            .prepare("DELETE FROM content_relationships WHERE id = ?")
+            .expect("Failed to prepare duplicate relationship delete");
+        for id in &stale {
+            delete_stmt.execute([id]).expect("Failed to clear duplicate relationship");
+        }
+
+        let mut content_stmt = tx
+            .prepare(INSERT_ISSUE_CONTENT_IF_MISSING)
+            .expect("Failed to prepare issue content insert");
+        let mut relationship_stmt = tx
+            .prepare(INSERT_CONTENT_RELATIONSHIP)
+            .expect("Failed to prepare relationship insert");
+
+        for cluster in clusters {
+            for issue in std::iter::once(&cluster.canonical).chain(&cluster.duplicates) {
+                let fingerprint = by_key[issue.key.as_str()];
+                content_stmt
+                    .execute(duckdb::params![
+                        issue_content_id(&issue.key),
+                        ContentType::JiraTicket.to_string(),
+                        fingerprint
+                            .self_link
+                            .clone()
+                            .unwrap_or_else(|| issue_content_id(&issue.key)),
+                        issue.summary,
+                        fingerprint.created,
+                        now,
+                        now,
+                    ])
+                    .expect("Failed to insert issue content");
+            }
+
+            for duplicate in &cluster.duplicates {
+                let source = issue_content_id(&duplicate.key);
+                let target = issue_content_id(&cluster.canonical.key);
+                relationship_stmt
+                    .execute(duckdb::params![
+                        relationship_id(&source, &target),
+                        source,
+                        target,
+                        relationship_type,
+                        duplicate.similarity,
+                        format!("MinHash similarity {:.2}", duplicate.similarity),
+                        now,
+                    ])
+                    .expect("Failed to insert duplicate relationship");
+            }
+        }
+    });
+}
+
+/// Remembers that two issues are not duplicates so they are never suggested again.
+pub fn record_not_duplicate(key: &str, other: &str) {
+    let (key_a, key_b) = feedback_pair(key, other);
+    let relationship_type = RelationshipType::DuplicateOf.to_string();
+
+    with_transaction("record_not_duplicate", |tx| {
+        tx.execute(
+            "DELETE FROM duplicate_feedback WHERE key_a = ? AND key_b = ?",
+            duckdb::params![key_a, key_b],
+        )
+        .expect("Failed to clear duplicate feedback");
+        tx.execute(
+            "INSERT INTO duplicate_feedback (key_a, key_b, verdict, created_at) VALUES (?, ?, 'not_duplicate', ?)",
+            duckdb::params![key_a, key_b, Utc::now().to_rfc3339()],
+        )
+        .expect("Failed to record duplicate feedback");
+        tx.execute(
+            "DELETE FROM content_relationships WHERE relationship_type = ? \
+             AND ((source_content_id = ? AND target_content_id = ?) OR (source_content_id = ? AND target_content_id = ?))",
+            duckdb::params![
+                relationship_type,
+                issue_content_id(&key_a),
+                issue_content_id(&key_b),
+                issue_content_id(&key_b),
+                issue_content_id(&key_a),
+            ],
+        )
+        .expect("Failed to remove duplicate relationship");
+    });
+}
+
+pub fn print_dedupe_report(report: &DedupeReport) {
+    println!(
+        "{}",
+        format!(
+            "🧬 {} duplicate cluster(s) in {} issue(s){} at similarity ≥ {:.2}",
+            report.clusters.len(),
+            report.issues_scanned,
+            report
+                .project_key
+                .as_ref()
+                .map(|key| format!(" of {}", key))
+                .unwrap_or_default(),
+            report.threshold
+        )
+        .bold()
+    );
+
+    for cluster in &report.clusters {
+        println!(
+            "\n{} {} {}",
+            cluster.canonical.key.bright_cyan(),
+            format!("[{}]", cluster.canonical.status).bright_black(),
+            cluster.canonical.summary
+        );
+        for duplicate in &cluster.duplicates {
+            println!(
+                "  ↳ {} {:.2} {} {}",
+                duplicate.key.yellow(),
+                duplicate.similarity,
+                format!("[{}]", duplicate.status).bright_black(),
+                duplicate.summary
+            );
+        }
+    }
+
+    if !report.clusters.is_empty() {
+        println!(
+            "\n{}",
+            "Not a duplicate? Run `triage dedupe --not-duplicate KEY OTHER` to stop suggesting a pair.".bright_black()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(key: &str, created: &str, text: &str) -> IssueFingerprint {
+        IssueFingerprint {
+            key: key.to_string(),
+            project_key: "ESCL".to_string(),
+            summary: text.to_string(),
+            status: "Open".to_string(),
+            created: created.to_string(),
+            self_link: None,
+            signature: MinHashSignature::from_text(text).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_near_duplicates_cluster_under_the_earliest_issue() {
+        let fingerprints = vec![
+            fingerprint(
+                "ESCL-2",
+                "2026-03-02",
+                "Customer cannot log in after password reset, SSO redirect loops back to the login page",
+            ),
+            fingerprint(
+                "ESCL-1",
+                "2026-03-01",
+                "Customer cannot log in after password reset, SSO redirect loops back to the login page again",
+            ),
+            fingerprint(
+                "ESCL-3",
+                "2026-03-03",
+                "Invoice export to CSV drops the currency column for EUR accounts",
+            ),
+        ];
+
+        let pairs = candidate_pairs(&fingerprints, DEFAULT_DUPLICATE_THRESHOLD, &HashSet::new());
+        let clusters = cluster_duplicates(&fingerprints, &pairs, &HashSet::new());
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].canonical.key, "ESCL-1");
+        assert_eq!(clusters[0].duplicates[0].key, "ESCL-2");
+        assert!(clusters[0].duplicates[0].similarity > 0.8);
+
+        let dismissed = HashSet::from([feedback_pair("ESCL-2", "ESCL-1")]);
+        assert!(candidate_pairs(&fingerprints, DEFAULT_DUPLICATE_THRESHOLD, &dismissed).is_empty());
+    }
+
+    #[test]
+    fn test_dismissed_pair_is_not_joined_through_a_shared_issue() {
+        let text = "Customer cannot log in after password reset, SSO redirect loops back to the login page";
+        let fingerprints = vec![
+            fingerprint("ESCL-1", "2026-03-01", &format!("{} again", text)),
+            fingerprint("ESCL-2", "2026-03-02", &format!("{} on mobile", text)),
+            fingerprint("ESCL-3", "2026-03-03", text),
+        ];
+        let pairs = vec![(0, 2), (1, 2)];
+        let dismissed = HashSet::from([feedback_pair("ESCL-1", "ESCL-2")]);
+
+        let joined = cluster_duplicates(&fingerprints, &pairs, &HashSet::new());
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0].duplicates.len(), 2);
+
+        let clusters = cluster_duplicates(&fingerprints, &pairs, &dismissed);
+        assert_eq!(clusters.len(), 1);
+        let keys: Vec<&str> = std::iter::once(&clusters[0].canonical)
+            .chain(&clusters[0].duplicates)
+            .map(|issue| issue.key.as_str())
+            .collect();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&"ESCL-3"));
+        assert!(!(keys.contains(&"ESCL-1") && keys.contains(&"ESCL-2")));
+    }
+
+    #[test]
+    fn test_stored_signatures_round_trip() {
+        let signature = MinHashSignature::from_text("Checkout times out when the cart has more than fifty items").unwrap();
+        assert_eq!(MinHashSignature::from_json(&signature.to_json()), Some(signature));
+        assert_eq!(MinHashSignature::from_json("[1, 2, 3]"), None);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::advanced_analytics::Bottleneck;
+use crate::issue_graph::{DependencyGraph, IssueEdgeType};
+use crate::utils::{log_step, log_success, parse_jira_timestamp};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// ================================
+// DEPENDENCY RISK DETECTION
+// ================================
+
+pub const DEFAULT_MAX_CHAIN: usize = 3;
+pub const DEFAULT_STALE_DAYS: i64 = 14;
+
+const ACTIVE_STATUSES: [&str; 4] = ["In Progress", "In Review", "Development", "Implementation"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyRiskKind {
+    CrossProjectBlocker,
+    LongBlockerChain,
+    BlockingCycle,
+    StaleBlocker,
+}
+
+/// A `Bottleneck` tied to one blocked issue, with the chain of issues blocking it
+/// (root blocker first, blocked issue last).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependencyRisk {
+    pub kind: DependencyRiskKind,
+    pub issue_key: String,
+    #[serde(flatten)]
+    pub bottleneck: Bottleneck,
+    pub blocking_path: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyRiskReport {
+    pub max_chain: usize,
+    pub stale_days: i64,
+    pub risks: Vec<DependencyRisk>,
+    pub counts: HashMap<String, usize>,
+    pub generated_at: String,
+}
+
+pub fn analyze_dependency_risks(max_chain: usize, stale_days: i64) -> DependencyRiskReport {
+    log_step("⛓️", "Detecting dependency risks...");
+
+    let graph = DependencyGraph::load();
+    let risks = detect_dependency_risks(&graph, max_chain, stale_days, Utc::now());
+
+    let mut counts = HashMap::new();
+    for risk in &risks {
+        let kind = serde_json::to_value(risk.kind).unwrap().as_str().unwrap_or_default().to_string();
+        *counts.entry(kind).or_insert(0) += 1;
+    }
+
+    log_success(&format!("Found {} dependency risk(s).", risks.len()));
+
+    DependencyRiskReport {
+        max_chain,
+        stale_days,
+        risks,
+        counts,
+        generated_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Finds cross-project blockers, blocker chains longer than `max_chain`, cycles in
+/// "blocks" links and active work waiting on a blocker idle for more than `stale_days`.
+/// Only unresolved issues take part; a resolved blocker no longer blocks anything.
+pub fn detect_dependency_risks(
+    graph: &DependencyGraph,
+    max_chain: usize,
+    stale_days: i64,
+    now: DateTime<Utc>,
+) -> Vec<DependencyRisk> {
+    let mut keys: Vec<&str> = graph
+        .nodes
+        .values()
+        .filter(|node| !node.resolved)
+        .map(|node| node.key.as_str())
+        .collect();
+    keys.sort();
+
+    let idle_days = |key: &str| -> Option<f64> {
+        let updated = graph.nodes.get(key)?.updated.as_deref()?;
+        let updated = parse_jira_timestamp(updated)?;
+        Some((now - updated).num_hours() as f64 / 24.0)
+    };
+
+    let mut risks = Vec::new();
+
+    for key in &keys {
+        let node = &graph.nodes[*key];
+        let blockers = unresolved_blockers(graph, key);
+        let impact = downstream_impact(graph, key);
+        let wait = idle_days(key).unwrap_or(0.0);
+
+        for blocker in &blockers {
+            let blocker_project = graph
+                .nodes
+                .get(*blocker)
+                .map(|other| other.project_key.clone())
+                .unwrap_or_default();
+            if !blocker_project.is_empty() && blocker_project != node.project_key {
+                let synced = graph.nodes.get(*blocker).map(|other| other.synced).unwrap_or(false);
+                risks.push(DependencyRisk {
+                    kind: DependencyRiskKind::CrossProjectBlocker,
+                    issue_key: key.to_string(),
+                    bottleneck: Bottleneck {
+                        stage: node.status.clone(),
+                        // Blockers in unsynced projects can't be tracked from here
+                        severity: if synced { 60.0 } else { 80.0 },
+                        avg_wait_time: wait,
+                        impact_score: impact,
+                        suggested_actions: vec![
+                            format!("Agree a delivery date for {} with the {} team", blocker, blocker_project),
+                            format!("Check whether {} can proceed without {}", key, blocker),
+                        ],
+                    },
+                    blocking_path: vec![blocker.to_string(), key.to_string()],
+                });
+            }
+
+            if ACTIVE_STATUSES.contains(&node.status.as_str()) {
+                if let Some(blocker_idle) = idle_days(blocker).filter(|days| *days > stale_days as f64) {
+                    risks.push(DependencyRisk {
+                        kind: DependencyRiskKind::StaleBlocker,
+                        issue_key: key.to_string(),
+                        bottleneck: Bottleneck {
+                            stage: node.status.clone(),
+                            severity: (50.0 + blocker_idle - stale_days as f64).min(100.0),
+                            avg_wait_time: blocker_idle,
+                            impact_score: impact,
+                            suggested_actions: vec![
+                                format!("{} has not moved in {:.0} days; confirm it is still owned", blocker, blocker_idle),
+                                format!("Pause {} or remove the dependency", key),
+                            ],
+                        },
+                        blocking_path: vec![blocker.to_string(), key.to_string()],
+                    });
+                }
+            }
+        }
+
+        // Report each chain once, at the issue where it ends
+        let blocks_open_work = graph
+            .targets(key, IssueEdgeType::Blocks)
+            .into_iter()
+            .any(|target| !graph.is_resolved(target));
+        if !blocks_open_work {
+            if let Some(chain) = longest_blocker_chain(graph, key) {
+                let depth = chain.len() - 1;
+                if depth > max_chain {
+                    risks.push(DependencyRisk {
+                        kind: DependencyRiskKind::LongBlockerChain,
+                        issue_key: key.to_string(),
+                        bottleneck: Bottleneck {
+                            stage: node.status.clone(),
+                            severity: (40.0 + 10.0 * (depth - max_chain) as f64).min(100.0),
+                            avg_wait_time: wait,
+                            impact_score: depth as f64,
+                            suggested_actions: vec![
+                                format!("{} waits on {} issues in sequence; look for work that can run in parallel", key, depth),
+                                format!("Start with {}", chain[0]),
+                            ],
+                        },
+                        blocking_path: chain,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut in_reported_cycle: HashSet<String> = HashSet::new();
+    for key in &keys {
+        if in_reported_cycle.contains(*key) {
+            continue;
+        }
+        if let Some(cycle) = find_blocking_cycle(graph, key) {
+            in_reported_cycle.extend(cycle.iter().cloned());
+            let wait = cycle.iter().filter_map(|member| idle_days(member)).fold(0.0, f64::max);
+            let impact = downstream_impact(graph, key);
+            risks.push(DependencyRisk {
+                kind: DependencyRiskKind::BlockingCycle,
+                issue_key: key.to_string(),
+                bottleneck: Bottleneck {
+                    stage: graph.nodes[*key].status.clone(),
+                    severity: 90.0,
+                    avg_wait_time: wait,
+                    impact_score: impact,
+                    suggested_actions: vec![
+                        "These issues block each other and can never start; remove one of the links".to_string(),
+                    ],
+                },
+                blocking_path: cycle,
+            });
+        }
+    }
+
+    risks.sort_by(|a, b| {
+        b.bottleneck
+            .severity
+            .partial_cmp(&a.bottleneck.severity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.issue_key.cmp(&b.issue_key))
+    });
+    risks
+}
+
+fn unresolved_blockers<'a>(graph: &'a DependencyGraph, key: &str) -> Vec<&'a str> {
+    let mut blockers: Vec<&str> = graph
+        .sources(key, IssueEdgeType::Blocks)
+        .into_iter()
+        .filter(|blocker| !graph.is_resolved(blocker))
+        .collect();
+    blockers.sort();
+    blockers.dedup();
+    blockers
+}
+
+/// How many open issues are waiting on `key`, directly or transitively, including itself.
+fn downstream_impact(graph: &DependencyGraph, key: &str) -> f64 {
+    let mut seen: HashSet<&str> = HashSet::from([key]);
+    let mut queue = VecDeque::from([key]);
+    while let Some(current) = queue.pop_front() {
+        for target in graph.targets(current, IssueEdgeType::Blocks) {
+            if !graph.is_resolved(target) && seen.insert(target) {
+                queue.push_back(target);
+            }
+        }
+    }
+    seen.len() as f64
+}
+
+/// The longest chain of open blockers ending at `key`, root first. `None` if nothing blocks it.
+fn longest_blocker_chain(graph: &DependencyGraph, key: &str) -> Option<Vec<String>> {
+    let mut memo = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let chain = chain_to(graph, key, &mut memo, &mut in_progress);
+    (chain.len() > 1).then_some(chain)
+}
+
+fn chain_to<'a>(
+    graph: &'a DependencyGraph,
+    key: &'a str,
+    memo: &mut HashMap<&'a str, Vec<String>>,
+    in_progress: &mut HashSet<&'a str>,
+) -> Vec<String> {
+    if let Some(chain) = memo.get(key) {
+        return chain.clone();
+    }
+    // Cycles are reported on their own; stop where one is re-entered
+    if !in_progress.insert(key) {
+        return Vec::new();
+    }
+
+    let mut best = Vec::new();
+    for blocker in unresolved_blockers(graph, key) {
+        let chain = chain_to(graph, blocker, memo, in_progress);
+        if chain.len() > best.len() {
+            best = chain;
+        }
+    }
+
+    in_progress.remove(key);
+    best.push(key.to_string());
+    memo.insert(key, best.clone());
+    best
+}
+
+/// Shortest cycle of open "blocks" links through `key`, starting and ending at `key`.
+fn find_blocking_cycle(graph: &DependencyGraph, key: &str) -> Option<Vec<String>> {
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([key]);
+
+    while let Some(current) = queue.pop_front() {
+        for target in graph.targets(current, IssueEdgeType::Blocks) {
+            if graph.is_resolved(target) {
+                continue;
+            }
+            if target == key {
+                let mut cycle = vec![key.to_string(), current.to_string()];
+                let mut cursor = current;
+                while let Some(previous) = parents.get(cursor) {
+                    cycle.push(previous.to_string());
+                    cursor = previous;
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if !parents.contains_key(target) {
+                parents.insert(target, current);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issue_graph::{project_of, IssueNode};
+
+    fn node(key: &str, status: &str, updated: &str) -> (String, IssueNode) {
+        (
+            key.to_string(),
+            IssueNode {
+                key: key.to_string(),
+                summary: String::new(),
+                status: status.to_string(),
+                issue_type: String::new(),
+                project_key: project_of(key),
+                assignee: None,
+                story_points: None,
+                updated: Some(updated.to_string()),
+                resolved: status == "Done",
+                synced: true,
+            },
+        )
+    }
+
+    fn blocks(source: &str, target: &str) -> (String, String, IssueEdgeType) {
+        (source.to_string(), target.to_string(), IssueEdgeType::Blocks)
+    }
+
+    fn kinds_for(risks: &[DependencyRisk], kind: DependencyRiskKind) -> Vec<(String, Vec<String>)> {
+        risks
+            .iter()
+            .filter(|risk| risk.kind == kind)
+            .map(|risk| (risk.issue_key.clone(), risk.blocking_path.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_each_kind_of_dependency_risk() {
+        let recent = "2026-10-01T10:00:00.000+0000";
+        let stale = "2026-08-01T10:00:00.000+0000";
+        let nodes = HashMap::from([
+            node("A-1", "To Do", recent),
+            node("A-2", "To Do", recent),
+            node("A-3", "To Do", recent),
+            node("A-4", "In Progress", recent),
+            node("B-1", "To Do", stale),
+            node("A-5", "Done", recent),
+            node("C-1", "To Do", recent),
+            node("C-2", "To Do", recent),
+        ]);
+        let graph = DependencyGraph::from_parts(
+            nodes,
+            vec![
+                blocks("A-1", "A-2"),
+                blocks("A-2", "A-3"),
+                blocks("A-3", "A-4"),
+                blocks("B-1", "A-1"),
+                blocks("B-1", "A-4"),
+                blocks("A-5", "A-4"),
+                blocks("C-1", "C-2"),
+                blocks("C-2", "C-1"),
+            ],
+        );
+        let now = parse_jira_timestamp("2026-10-02T10:00:00.000+0000").unwrap();
+
+        let risks = detect_dependency_risks(&graph, 3, 14, now);
+
+        assert_eq!(
+            kinds_for(&risks, DependencyRiskKind::LongBlockerChain),
+            vec![("A-4".to_string(), vec!["B-1", "A-1", "A-2", "A-3", "A-4"].into_iter().map(String::from).collect())]
+        );
+        let cross_project: Vec<String> = kinds_for(&risks, DependencyRiskKind::CrossProjectBlocker)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(cross_project, vec!["A-1".to_string(), "A-4".to_string()]);
+        assert_eq!(
+            kinds_for(&risks, DependencyRiskKind::StaleBlocker),
+            vec![("A-4".to_string(), vec!["B-1".to_string(), "A-4".to_string()])]
+        );
+        assert_eq!(
+            kinds_for(&risks, DependencyRiskKind::BlockingCycle),
+            vec![("C-1".to_string(), vec!["C-1".to_string(), "C-2".to_string(), "C-1".to_string()])]
+        );
+    }
+}
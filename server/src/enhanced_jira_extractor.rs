@@ -1,5 +1,6 @@
 use crate::auth::{authenticate, get_domain};
 use crate::people_graph::IdentityResolver;
+use crate::telemetry::TracedRequest;
 use crate::types::{Issue, IssueFields, IssueStatus, IssueType, IssuePriority, ExtractedLink};
 use crate::utils::{log_step, log_success};
 use reqwest::Client;
@@ -278,7 +279,7 @@ impl EnhancedJiraExtractor {
             .get(&url)
             .header("Authorization", format!("Basic {}", self.token))
             .header("Accept", "application/json")
-            .send()
+            .send_traced("jira")
             .await?;
 
         if !response.status().is_success() {
@@ -339,7 +340,7 @@ impl EnhancedJiraExtractor {
             .get(&url)
             .header("Authorization", format!("Basic {}", self.token))
             .header("Accept", "application/json")
-            .send()
+            .send_traced("jira")
             .await?;
 
         if !response.status().is_success() {
@@ -441,7 +442,7 @@ impl EnhancedJiraExtractor {
             .get(&url)
             .header("Authorization", format!("Basic {}", self.token))
             .header("Accept", "application/json")
-            .send()
+            .send_traced("jira")
             .await?;
 
         if !response.status().is_success() {
@@ -506,7 +507,7 @@ impl EnhancedJiraExtractor {
             .get(&url)
             .header("Authorization", format!("Basic {}", self.token))
             .header("Accept", "application/json")
-            .send()
+            .send_traced("jira")
             .await;
 
         // Watchers endpoint might not be accessible to all users
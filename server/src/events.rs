@@ -0,0 +1,254 @@
+use chrono::Utc;
+use futures::stream::{self, Stream, StreamExt};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+// ================================
+// PROGRESS EVENT BUS
+// ================================
+//
+// The bus lives in the server process: jobs run by the server (scheduled, webhook and
+// API-triggered) stream here, while a `triage sync` started from the CLI does not. Its
+// progress is still recorded in the sync run tables and shows up in `/api/sync/status`.
+
+/// Events kept for clients resuming with `Last-Event-ID`.
+const HISTORY_SIZE: usize = 1000;
+
+/// Job names used in events, matching the scheduler's job names where one exists.
+pub const JIRA_SYNC: &str = "jira_sync";
+pub const CONTENT_EXTRACTION: &str = "content_extraction";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    JobStarted {
+        run_id: String,
+        trigger: String,
+    },
+    JobFinished {
+        run_id: String,
+        status: String,
+        error: Option<String>,
+    },
+    ProjectStarted {
+        project: String,
+    },
+    PageFetched {
+        project: String,
+        start_at: usize,
+        fetched: usize,
+        total: u64,
+    },
+    ProjectFinished {
+        project: String,
+        succeeded: usize,
+        failed: usize,
+    },
+    ItemCompleted {
+        item: String,
+    },
+    /// An issue or extraction job failed; `retrying` says whether it will be attempted again
+    ItemFailed {
+        item: String,
+        error: String,
+        retrying: bool,
+    },
+    RateLimited {
+        item: String,
+        backoff_secs: u64,
+        attempt: u32,
+    },
+}
+
+impl ProgressEvent {
+    /// The SSE `event:` name, so clients can listen for one kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProgressEvent::JobStarted { .. } => "job_started",
+            ProgressEvent::JobFinished { .. } => "job_finished",
+            ProgressEvent::ProjectStarted { .. } => "project_started",
+            ProgressEvent::PageFetched { .. } => "page_fetched",
+            ProgressEvent::ProjectFinished { .. } => "project_finished",
+            ProgressEvent::ItemCompleted { .. } => "item_completed",
+            ProgressEvent::ItemFailed { .. } => "item_failed",
+            ProgressEvent::RateLimited { .. } => "rate_limited",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishedEvent {
+    pub id: u64,
+    pub job: String,
+    pub at: String,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
+}
+
+struct EventLog {
+    next_id: u64,
+    history: VecDeque<PublishedEvent>,
+}
+
+pub struct EventBus {
+    sender: broadcast::Sender<PublishedEvent>,
+    log: Mutex<EventLog>,
+}
+
+static EVENT_BUS: Lazy<EventBus> = Lazy::new(|| EventBus::starting_at(first_event_id()));
+
+/// Ids start from the startup time in microseconds, so ids handed out after a restart are
+/// newer than any a client saw before it and `Last-Event-ID` keeps working.
+fn first_event_id() -> u64 {
+    Utc::now().timestamp_micros().max(1) as u64
+}
+
+impl EventBus {
+    fn starting_at(first_id: u64) -> Self {
+        let (sender, _) = broadcast::channel(HISTORY_SIZE);
+        Self {
+            sender,
+            log: Mutex::new(EventLog {
+                next_id: first_id,
+                history: VecDeque::with_capacity(HISTORY_SIZE),
+            }),
+        }
+    }
+
+    fn publish(&self, job: &str, event: ProgressEvent) {
+        let mut log = self.log.lock().unwrap();
+        let published = PublishedEvent {
+            id: log.next_id,
+            job: job.to_string(),
+            at: Utc::now().to_rfc3339(),
+            event,
+        };
+        log.next_id += 1;
+        if log.history.len() == HISTORY_SIZE {
+            log.history.pop_front();
+        }
+        log.history.push_back(published.clone());
+        // No subscribers is not an error; the history still serves later resumes
+        let _ = self.sender.send(published);
+    }
+
+    /// Events after `last_event_id` followed by everything published from now on. An id from
+    /// before a restart is older than all of ours, so the whole history is replayed; so is an
+    /// id newer than any we have, which only a clock stepping back at startup can produce.
+    fn subscribe(&self, last_event_id: Option<u64>) -> (Vec<PublishedEvent>, broadcast::Receiver<PublishedEvent>) {
+        let log = self.log.lock().unwrap();
+        let receiver = self.sender.subscribe();
+        let replay = match last_event_id {
+            Some(last) if last < log.next_id => log.history.iter().filter(|e| e.id > last).cloned().collect(),
+            Some(_) => log.history.iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        (replay, receiver)
+    }
+}
+
+/// Fire-and-forget; publishing never blocks or fails the job doing the work.
+pub fn publish(job: &str, event: ProgressEvent) {
+    EVENT_BUS.publish(job, event);
+}
+
+/// Replayed and live events, optionally only those of one job. Subscribers that fall more than
+/// `HISTORY_SIZE` events behind skip ahead rather than stall publishers.
+pub fn subscribe(last_event_id: Option<u64>, job: Option<String>) -> impl Stream<Item = PublishedEvent> {
+    let (replay, receiver) = EVENT_BUS.subscribe(last_event_id);
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event subscriber lagged, skipped {} event(s)", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    stream::iter(replay)
+        .chain(live)
+        .filter(move |event| std::future::ready(job.as_ref().is_none_or(|job| &event.job == job)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(start_at: usize) -> ProgressEvent {
+        ProgressEvent::PageFetched {
+            project: "ESCL".to_string(),
+            start_at,
+            fetched: 50,
+            total: 200,
+        }
+    }
+
+    #[test]
+    fn test_resume_replays_only_newer_events() {
+        let bus = EventBus::starting_at(1);
+        bus.publish(JIRA_SYNC, page(0));
+        bus.publish(JIRA_SYNC, page(50));
+        bus.publish(JIRA_SYNC, page(100));
+
+        let (replay, _) = bus.subscribe(Some(1));
+        assert_eq!(replay.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let (replay, _) = bus.subscribe(None);
+        assert!(replay.is_empty());
+
+        let (replay, _) = bus.subscribe(Some(99));
+        assert_eq!(replay.len(), 3);
+    }
+
+    #[test]
+    fn test_ids_keep_increasing_across_restarts() {
+        let before = EventBus::starting_at(first_event_id());
+        for i in 0..3 {
+            before.publish(JIRA_SYNC, page(i));
+        }
+        let (seen, _) = before.subscribe(Some(0));
+        let last_seen = seen.last().unwrap().id;
+
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        let after = EventBus::starting_at(first_event_id());
+        after.publish(JIRA_SYNC, page(0));
+
+        let (replay, _) = after.subscribe(Some(last_seen));
+        assert_eq!(replay.len(), 1);
+        assert!(replay[0].id > last_seen);
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_events_serialize_flat() {
+        let bus = EventBus::starting_at(1);
+        for i in 0..HISTORY_SIZE + 5 {
+            bus.publish(JIRA_SYNC, page(i));
+        }
+        let (replay, _) = bus.subscribe(Some(0));
+        assert_eq!(replay.len(), HISTORY_SIZE);
+        assert_eq!(replay[0].id, 6);
+
+        let json = serde_json::to_value(&replay[0]).unwrap();
+        assert_eq!(json["type"], "page_fetched");
+        assert_eq!(json["job"], "jira_sync");
+        assert_eq!(json["project"], "ESCL");
+    }
+
+    #[tokio::test]
+    async fn test_live_events_follow_replay() {
+        let bus = EventBus::starting_at(1);
+        bus.publish(JIRA_SYNC, page(0));
+        let (replay, mut receiver) = bus.subscribe(Some(0));
+        bus.publish(CONTENT_EXTRACTION, ProgressEvent::ItemCompleted { item: "doc".to_string() });
+
+        assert_eq!(replay.len(), 1);
+        let live = receiver.recv().await.unwrap();
+        assert_eq!(live.id, 2);
+        assert_eq!(live.event.name(), "item_completed");
+    }
+}
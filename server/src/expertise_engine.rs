@@ -0,0 +1,887 @@
+use crate::access_control::{can_view_source, Viewer, REDACTED_TITLE};
+use crate::constants::RESOLVED_STATUSES;
+use crate::db_utils::{with_connection, with_transaction};
+use crate::knowledge_engine::{extract_concepts_from_text, extract_technologies_from_text};
+use crate::link_detector::LinkDetector;
+use crate::people_graph::{ExpertiseScore, InteractionType};
+use crate::types::PlatformType;
+use crate::utils::{log_step, log_success};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// ================================
+// EXPERTISE EVIDENCE
+// ================================
+
+/// Evidence loses half its weight every six months.
+const EXPERTISE_HALF_LIFE_DAYS: f64 = 180.0;
+
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"https?://\S+").unwrap());
+static SOLUTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(fixed|resolved|root\s+cause|the\s+fix|workaround|solution|solved|patched|deployed\s+a\s+fix)\b",
+    )
+    .unwrap()
+});
+static REVIEW_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(lgtm|approved|reviewed|review\s+comments|left\s+comments)\b").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum EvidenceKind {
+    ResolvedIssue,
+    AuthoredSolution,
+    ReviewedChange,
+    WroteDocumentation,
+}
+
+impl EvidenceKind {
+    fn weight(self) -> f64 {
+        match self {
+            EvidenceKind::ResolvedIssue => 1.0,
+            EvidenceKind::AuthoredSolution => 0.8,
+            EvidenceKind::ReviewedChange => 0.6,
+            EvidenceKind::WroteDocumentation => 0.5,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            EvidenceKind::ResolvedIssue => "ResolvedIssue",
+            EvidenceKind::AuthoredSolution => "AuthoredSolution",
+            EvidenceKind::ReviewedChange => "ReviewedChange",
+            EvidenceKind::WroteDocumentation => "WroteDocumentation",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ResolvedIssue" => Some(EvidenceKind::ResolvedIssue),
+            "AuthoredSolution" => Some(EvidenceKind::AuthoredSolution),
+            "ReviewedChange" => Some(EvidenceKind::ReviewedChange),
+            "WroteDocumentation" => Some(EvidenceKind::WroteDocumentation),
+            _ => None,
+        }
+    }
+}
+
+/// One piece of work that shows a person knows a topic.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpertiseEvidence {
+    pub person_id: String,
+    pub topic: String,
+    pub kind: EvidenceKind,
+    pub content_id: String,
+    pub platform: String,
+    pub title: String,
+    pub resolved: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ExpertiseEvidence {
+    pub fn decayed_weight(&self, now: DateTime<Utc>) -> f64 {
+        let age_days = (now - self.timestamp).num_seconds().max(0) as f64 / 86_400.0;
+        self.kind.weight() * 0.5_f64.powf(age_days / EXPERTISE_HALF_LIFE_DAYS)
+    }
+}
+
+/// Aggregated expertise of one person in one topic, with the platforms it was seen on.
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonTopicExpertise {
+    pub person_id: String,
+    pub score: ExpertiseScore,
+    pub platforms: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpertRanking {
+    pub person_id: String,
+    pub display_name: String,
+    pub score: ExpertiseScore,
+    pub evidence: Vec<EvidenceLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EvidenceLink {
+    pub content_id: String,
+    pub title: String,
+    pub kind: EvidenceKind,
+    pub platform: String,
+    pub timestamp: String,
+    pub contribution: f64,
+}
+
+pub const CREATE_EXPERTISE_EVIDENCE_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS expertise_evidence (
+    id TEXT PRIMARY KEY,
+    person_id TEXT NOT NULL,
+    topic TEXT NOT NULL,
+    evidence_kind TEXT NOT NULL,
+    content_id TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    title TEXT,
+    resolved BOOLEAN DEFAULT FALSE,
+    timestamp TIMESTAMP NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_expertise_evidence_topic ON expertise_evidence(topic);
+CREATE INDEX IF NOT EXISTS idx_expertise_evidence_person ON expertise_evidence(person_id);
+"#;
+
+const INSERT_EXPERTISE_EVIDENCE: &str = r#"
+INSERT OR REPLACE INTO expertise_evidence
+(id, person_id, topic, evidence_kind, content_id, platform, title, resolved, timestamp)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+
+pub async fn initialize_expertise_tables() {
+    with_connection("create_expertise_tables", |conn| {
+        conn.execute_batch(CREATE_EXPERTISE_EVIDENCE_TABLE)
+            .expect("Failed to create expertise evidence table");
+    });
+}
+
+// ================================
+// EVIDENCE COLLECTION
+// ================================
+
+struct IssueText {
+    title: String,
+    text: String,
+    resolved: bool,
+    comments: HashMap<String, String>,
+}
+
+struct InteractionRow {
+    kind: &'static str,
+    to_status: Option<String>,
+    source_person_id: String,
+    content_id: String,
+    platform: String,
+    timestamp: DateTime<Utc>,
+    extracted_data: Value,
+}
+
+fn load_issue_texts(link_detector: &LinkDetector) -> HashMap<String, IssueText> {
+    let mut issues = HashMap::new();
+
+    with_connection("load_issue_texts", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT key, summary, description, status, comment FROM live_issues")
+            .expect("Failed to prepare issue text query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })
+            .expect("Failed to query issue texts");
+
+        for (key, summary, description, status, comment) in rows.flatten() {
+            let description = description
+                .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+                .map(|value| link_detector.extract_text_from_field(&value))
+                .unwrap_or_default();
+            let comments = comment
+                .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+                .and_then(|value| value["comments"].as_array().cloned())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|comment| {
+                    let id = comment["id"].as_str()?.to_string();
+                    Some((id, link_detector.extract_text_from_field(&comment["body"])))
+                })
+                .collect();
+            let title = summary.unwrap_or_default();
+
+            issues.insert(
+                key,
+                IssueText {
+                    text: format!("{} {}", title, description),
+                    title,
+                    resolved: status
+                        .as_deref()
+                        .map(|status| RESOLVED_STATUSES.contains(&status))
+                        .unwrap_or(false),
+                    comments,
+                },
+            );
+        }
+    });
+
+    issues
+}
+
+fn load_interaction_rows() -> Vec<InteractionRow> {
+    let mut interactions = Vec::new();
+
+    with_connection("load_interaction_rows", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT interaction_type, source_person_id, content_id, platform, \
+                 CAST(timestamp AS VARCHAR), extracted_data FROM detailed_interactions ORDER BY timestamp",
+            )
+            .expect("Failed to prepare interaction query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            })
+            .expect("Failed to query interactions");
+
+        for (raw_type, source_person_id, content_id, platform, timestamp, extracted_data) in
+            rows.flatten()
+        {
+            let Ok(interaction_type) = serde_json::from_str::<InteractionType>(&raw_type) else {
+                continue;
+            };
+            let Some(timestamp) = parse_db_timestamp(&timestamp) else {
+                continue;
+            };
+            let to_status = match &interaction_type {
+                InteractionType::TransitionedStatus { to_status, .. } => Some(to_status.clone()),
+                _ => None,
+            };
+
+            interactions.push(InteractionRow {
+                kind: interaction_type.kind(),
+                to_status,
+                source_person_id,
+                content_id,
+                platform,
+                timestamp,
+                extracted_data: extracted_data
+                    .and_then(|raw| serde_json::from_str(&raw).ok())
+                    .unwrap_or(Value::Null),
+            });
+        }
+    });
+
+    interactions
+}
+
+fn parse_db_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|timestamp| timestamp.and_utc())
+}
+
+/// URLs are stripped first so hosts and schemes ("https", "github") don't become topics.
+fn topics_in(text: &str) -> Vec<String> {
+    let text = URL_REGEX.replace_all(text, " ");
+    let mut topics = extract_concepts_from_text(&text);
+    for technology in extract_technologies_from_text(&text) {
+        if !topics.contains(&technology) {
+            topics.push(technology);
+        }
+    }
+    topics
+}
+
+/// Walks interactions and issue text to find resolutions, solutions, reviews and docs.
+fn collect_evidence() -> Vec<ExpertiseEvidence> {
+    let link_detector = LinkDetector::new();
+    let issues = load_issue_texts(&link_detector);
+    evidence_from(&issues, &load_interaction_rows(), &link_detector)
+}
+
+fn evidence_from(
+    issues: &HashMap<String, IssueText>,
+    interactions: &[InteractionRow],
+    link_detector: &LinkDetector,
+) -> Vec<ExpertiseEvidence> {
+    let mut issue_topics: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut evidence = Vec::new();
+    let mut push = |person_id: &str,
+                    kind: EvidenceKind,
+                    interaction: &InteractionRow,
+                    title: &str,
+                    resolved: bool,
+                    topics: &[String]| {
+        for topic in topics {
+            evidence.push(ExpertiseEvidence {
+                person_id: person_id.to_string(),
+                topic: topic.clone(),
+                kind,
+                content_id: interaction.content_id.clone(),
+                platform: interaction.platform.clone(),
+                title: title.to_string(),
+                resolved,
+                timestamp: interaction.timestamp,
+            });
+        }
+    };
+
+    for interaction in interactions {
+        let issue = issues.get(&interaction.content_id);
+        let base_topics = match issue {
+            Some(issue) => issue_topics
+                .entry(interaction.content_id.as_str())
+                .or_insert_with(|| topics_in(&issue.text))
+                .clone(),
+            None => Vec::new(),
+        };
+
+        match interaction.kind {
+            "TransitionedStatus" => {
+                let resolved_here = interaction
+                    .to_status
+                    .as_deref()
+                    .map(|status| RESOLVED_STATUSES.contains(&status))
+                    .unwrap_or(false);
+                if let (true, Some(issue)) = (resolved_here, issue) {
+                    push(
+                        &interaction.source_person_id,
+                        EvidenceKind::ResolvedIssue,
+                        interaction,
+                        &issue.title,
+                        true,
+                        &base_topics,
+                    );
+                }
+            }
+            "CommentedOn" => {
+                let Some(issue) = issue else {
+                    continue;
+                };
+                let comment_id = interaction.extracted_data["comment_id"]
+                    .as_str()
+                    .unwrap_or_default();
+                let Some(body) = issue.comments.get(comment_id) else {
+                    continue;
+                };
+
+                let mut topics = base_topics.clone();
+                for topic in topics_in(body) {
+                    if !topics.contains(&topic) {
+                        topics.push(topic);
+                    }
+                }
+
+                let links: Vec<PlatformType> = link_detector
+                    .extract_links_from_issue(&serde_json::json!({ "body": body }), "comment")
+                    .into_iter()
+                    .map(|link| link.platform_type)
+                    .collect();
+                let links_pr = links
+                    .iter()
+                    .any(|link| matches!(link, PlatformType::GitHubPR { .. }));
+                let links_docs = links.iter().any(|link| {
+                    matches!(
+                        link,
+                        PlatformType::ConfluencePage { .. } | PlatformType::GoogleDocs { .. }
+                    )
+                });
+
+                if issue.resolved && SOLUTION_REGEX.is_match(body) {
+                    push(
+                        &interaction.source_person_id,
+                        EvidenceKind::AuthoredSolution,
+                        interaction,
+                        &issue.title,
+                        true,
+                        &topics,
+                    );
+                }
+                if links_pr && REVIEW_REGEX.is_match(body) {
+                    push(
+                        &interaction.source_person_id,
+                        EvidenceKind::ReviewedChange,
+                        interaction,
+                        &issue.title,
+                        issue.resolved,
+                        &topics,
+                    );
+                }
+                if links_docs {
+                    push(
+                        &interaction.source_person_id,
+                        EvidenceKind::WroteDocumentation,
+                        interaction,
+                        &issue.title,
+                        issue.resolved,
+                        &topics,
+                    );
+                }
+            }
+            "ReviewedWork" | "ApprovedChange" => {
+                let title = issue
+                    .map(|issue| issue.title.clone())
+                    .unwrap_or_else(|| interaction.content_id.clone());
+                let topics = if base_topics.is_empty() {
+                    topics_in(&interaction.extracted_data.to_string())
+                } else {
+                    base_topics
+                };
+                push(
+                    &interaction.source_person_id,
+                    EvidenceKind::ReviewedChange,
+                    interaction,
+                    &title,
+                    issue.map(|issue| issue.resolved).unwrap_or(false),
+                    &topics,
+                );
+            }
+            "CreatedContent" | "EditedContent" if interaction.platform == "google" => {
+                let title = interaction.extracted_data["title"]
+                    .as_str()
+                    .unwrap_or(&interaction.content_id)
+                    .to_string();
+                let topics = topics_in(&format!("{} {}", title, interaction.extracted_data));
+                push(
+                    &interaction.source_person_id,
+                    EvidenceKind::WroteDocumentation,
+                    interaction,
+                    &title,
+                    false,
+                    &topics,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    evidence
+}
+
+// ================================
+// SCORING
+// ================================
+
+/// Folds evidence into one `ExpertiseScore` per person and topic.
+pub fn aggregate_expertise(
+    evidence: &[ExpertiseEvidence],
+    mentions_by_person: &HashMap<String, HashSet<String>>,
+    now: DateTime<Utc>,
+) -> Vec<PersonTopicExpertise> {
+    let mut grouped: HashMap<(&str, &str), Vec<&ExpertiseEvidence>> = HashMap::new();
+    for item in evidence {
+        grouped
+            .entry((item.person_id.as_str(), item.topic.as_str()))
+            .or_default()
+            .push(item);
+    }
+
+    let mut scores: Vec<PersonTopicExpertise> = grouped
+        .into_iter()
+        .map(|((person_id, topic), items)| {
+            let weighted: f64 = items.iter().map(|item| item.decayed_weight(now)).sum();
+            let recency_factor = items
+                .iter()
+                .map(|item| item.decayed_weight(now) / item.kind.weight())
+                .fold(0.0, f64::max);
+
+            let mut contributions: HashMap<&str, f64> = HashMap::new();
+            for item in &items {
+                *contributions.entry(item.content_id.as_str()).or_insert(0.0) +=
+                    item.decayed_weight(now);
+            }
+            let mut evidence_items: Vec<(&str, f64)> = contributions.into_iter().collect();
+            evidence_items.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.0.cmp(b.0))
+            });
+
+            let resolved = items.iter().filter(|item| item.resolved).count();
+            let mentions = mentions_by_person
+                .get(person_id)
+                .map(|contents| {
+                    evidence_items
+                        .iter()
+                        .filter(|(content_id, _)| contents.contains(*content_id))
+                        .count()
+                })
+                .unwrap_or(0);
+            let platforms: BTreeSet<String> =
+                items.iter().map(|item| item.platform.clone()).collect();
+
+            PersonTopicExpertise {
+                person_id: person_id.to_string(),
+                score: ExpertiseScore {
+                    concept: topic.to_string(),
+                    authority_score: 1.0 - (-weighted / 2.0).exp(),
+                    contribution_count: items.len() as u32,
+                    solution_success_rate: resolved as f64 / items.len() as f64,
+                    peer_recognition_score: 1.0 - (-(mentions as f64) / 3.0).exp(),
+                    recency_factor,
+                    evidence_items: evidence_items
+                        .into_iter()
+                        .take(20)
+                        .map(|(content_id, _)| content_id.to_string())
+                        .collect(),
+                    calculated_at: now,
+                },
+                platforms: platforms.into_iter().collect(),
+            }
+        })
+        .collect();
+
+    scores.sort_by(|a, b| {
+        b.score
+            .authority_score
+            .partial_cmp(&a.score.authority_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.person_id.cmp(&b.person_id))
+            .then_with(|| a.score.concept.cmp(&b.score.concept))
+    });
+    scores
+}
+
+/// Content IDs on which each person was mentioned by someone else.
+fn load_mentions_by_person() -> HashMap<String, HashSet<String>> {
+    let mut mentions: HashMap<String, HashSet<String>> = HashMap::new();
+
+    with_connection("load_mentions_by_person", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT target_person_id, content_id FROM detailed_interactions \
+                 WHERE target_person_id IS NOT NULL AND interaction_type LIKE '{\"Mentioned\"%'",
+            )
+            .expect("Failed to prepare mention query");
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("Failed to query mentions");
+
+        for (person_id, content_id) in rows.flatten() {
+            mentions.entry(person_id).or_default().insert(content_id);
+        }
+    });
+
+    mentions
+}
+
+/// Loads persisted evidence, optionally narrowed with a SQL filter.
+pub fn load_evidence(filter: &str, params: Vec<String>) -> Vec<ExpertiseEvidence> {
+    let sql = format!(
+        "SELECT person_id, topic, evidence_kind, content_id, platform, title, resolved, \
+         CAST(timestamp AS VARCHAR) FROM expertise_evidence {}",
+        filter
+    );
+    let mut evidence = Vec::new();
+
+    with_connection("load_expertise_evidence", |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .expect("Failed to prepare expertise evidence query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(params.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<bool>>(6)?,
+                    row.get::<_, String>(7)?,
+                ))
+            })
+            .expect("Failed to query expertise evidence");
+
+        for (person_id, topic, kind, content_id, platform, title, resolved, timestamp) in
+            rows.flatten()
+        {
+            let (Some(kind), Some(timestamp)) =
+                (EvidenceKind::parse(&kind), parse_db_timestamp(&timestamp))
+            else {
+                continue;
+            };
+            evidence.push(ExpertiseEvidence {
+                person_id,
+                topic,
+                kind,
+                content_id,
+                platform,
+                title: title.unwrap_or_default(),
+                resolved: resolved.unwrap_or(false),
+                timestamp,
+            });
+        }
+    });
+
+    evidence
+}
+
+/// Scores every person with evidence, using current time for decay.
+pub fn load_expertise(filter: &str, params: Vec<String>) -> Vec<PersonTopicExpertise> {
+    let evidence = load_evidence(filter, params);
+    aggregate_expertise(&evidence, &load_mentions_by_person(), Utc::now())
+}
+
+// ================================
+// REFRESH & QUERY
+// ================================
+
+/// Rebuilds `expertise_evidence` and `people.expertise_areas` from synced data.
+pub async fn refresh_expertise() {
+    log_step("🎓", "Scoring expertise from issue and content activity...");
+
+    let evidence = collect_evidence();
+
+    with_transaction("refresh_expertise_evidence", |tx| {
+        tx.execute("DELETE FROM expertise_evidence", [])
+            .expect("Failed to clear expertise evidence");
+        let mut stmt = tx
+            .prepare(INSERT_EXPERTISE_EVIDENCE)
+            .expect("Failed to prepare expertise evidence insert");
+        for item in &evidence {
+            stmt.execute(duckdb::params![
+                format!(
+                    "{}:{}:{}:{}:{}",
+                    item.kind.as_str(),
+                    item.person_id,
+                    item.content_id,
+                    item.topic,
+                    item.timestamp.timestamp()
+                ),
+                item.person_id,
+                item.topic,
+                item.kind.as_str(),
+                item.content_id,
+                item.platform,
+                item.title,
+                item.resolved,
+                item.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            ])
+            .expect("Failed to insert expertise evidence");
+        }
+    });
+
+    let scores = aggregate_expertise(&evidence, &load_mentions_by_person(), Utc::now());
+    let mut by_person: HashMap<&str, Vec<&ExpertiseScore>> = HashMap::new();
+    for expertise in &scores {
+        by_person
+            .entry(expertise.person_id.as_str())
+            .or_default()
+            .push(&expertise.score);
+    }
+
+    with_transaction("refresh_people_expertise", |tx| {
+        let mut stmt = tx
+            .prepare("UPDATE people SET expertise_areas = ? WHERE id = ?")
+            .expect("Failed to prepare expertise update");
+        for (person_id, areas) in &by_person {
+            stmt.execute(duckdb::params![
+                serde_json::to_string(areas).unwrap_or_default(),
+                person_id
+            ])
+            .expect("Failed to update person expertise");
+        }
+    });
+
+    log_success(&format!(
+        "Recorded {} expertise evidence items across {} people",
+        evidence.len(),
+        by_person.len()
+    ));
+}
+
+/// Ranks people for a topic, with the content that justifies each score. Scores count all
+/// evidence; titles of documents and threads the viewer cannot open are redacted.
+pub fn find_experts(topic: &str, limit: usize, viewer: &Viewer) -> Vec<ExpertRanking> {
+    let topic = topic.trim().to_lowercase();
+    let evidence = load_evidence("WHERE topic = ?", vec![topic]);
+    let now = Utc::now();
+    let scores = aggregate_expertise(&evidence, &load_mentions_by_person(), now);
+
+    let mut display_names: HashMap<String, String> = HashMap::new();
+    with_connection("load_expert_names", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, display_names FROM people")
+            .expect("Failed to prepare people query");
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("Failed to query people");
+        for (id, names) in rows.flatten() {
+            if let Some(name) = serde_json::from_str::<Vec<String>>(&names)
+                .ok()
+                .and_then(|names| names.into_iter().next())
+            {
+                display_names.insert(id, name);
+            }
+        }
+    });
+
+    let mut visible: HashMap<(String, String), bool> = HashMap::new();
+    scores
+        .into_iter()
+        .take(limit)
+        .map(|expertise| {
+            let mut links: Vec<EvidenceLink> = evidence
+                .iter()
+                .filter(|item| item.person_id == expertise.person_id)
+                .map(|item| EvidenceLink {
+                    content_id: item.content_id.clone(),
+                    title: item.title.clone(),
+                    kind: item.kind,
+                    platform: item.platform.clone(),
+                    timestamp: item.timestamp.to_rfc3339(),
+                    contribution: item.decayed_weight(now),
+                })
+                .collect();
+            links.sort_by(|a, b| {
+                b.contribution
+                    .partial_cmp(&a.contribution)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            links.truncate(10);
+            for link in &mut links {
+                let allowed = *visible
+                    .entry((link.platform.clone(), link.content_id.clone()))
+                    .or_insert_with(|| can_view_source(viewer, &link.platform, &link.content_id));
+                if !allowed {
+                    link.title = REDACTED_TITLE.to_string();
+                }
+            }
+
+            ExpertRanking {
+                display_name: display_names
+                    .get(&expertise.person_id)
+                    .cloned()
+                    .unwrap_or_else(|| expertise.person_id.clone()),
+                person_id: expertise.person_id,
+                score: expertise.score,
+                evidence: links,
+            }
+        })
+        .collect()
+}
+
+/// Authority scores of everyone with evidence for each topic.
+pub fn team_authority_by_topic() -> HashMap<String, Vec<f64>> {
+    let mut by_topic: HashMap<String, Vec<f64>> = HashMap::new();
+    for expertise in load_expertise("", Vec::new()) {
+        by_topic
+            .entry(expertise.score.concept)
+            .or_default()
+            .push(expertise.score.authority_score);
+    }
+    by_topic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn interaction(kind: &'static str, person: &str, content_id: &str, platform: &str, data: Value) -> InteractionRow {
+        InteractionRow {
+            kind,
+            to_status: None,
+            source_person_id: person.to_string(),
+            content_id: content_id.to_string(),
+            platform: platform.to_string(),
+            timestamp: Utc::now(),
+            extracted_data: data,
+        }
+    }
+
+    fn evidence(person: &str, kind: EvidenceKind, content_id: &str, age_days: i64, now: DateTime<Utc>) -> ExpertiseEvidence {
+        ExpertiseEvidence {
+            person_id: person.to_string(),
+            topic: "redis".to_string(),
+            kind,
+            content_id: content_id.to_string(),
+            platform: "jira".to_string(),
+            title: content_id.to_string(),
+            resolved: true,
+            timestamp: now - Duration::days(age_days),
+        }
+    }
+
+    #[test]
+    fn test_evidence_from_resolutions_solutions_and_docs() {
+        let issues = HashMap::from([(
+            "ESCL-1".to_string(),
+            IssueText {
+                title: "Redis caching times out".to_string(),
+                text: "Redis caching times out under load".to_string(),
+                resolved: true,
+                comments: HashMap::from([(
+                    "100".to_string(),
+                    "Root cause was the redis pool size, fixed by raising it".to_string(),
+                )]),
+            },
+        )]);
+        let mut resolved = interaction("TransitionedStatus", "ana", "ESCL-1", "jira", Value::Null);
+        resolved.to_status = Some("Done".to_string());
+        let mut reopened = interaction("TransitionedStatus", "bo", "ESCL-1", "jira", Value::Null);
+        reopened.to_status = Some("In Progress".to_string());
+        let interactions = vec![
+            resolved,
+            reopened,
+            interaction("CommentedOn", "cy", "ESCL-1", "jira", json!({ "comment_id": "100" })),
+            interaction("CreatedContent", "di", "doc-1", "google", json!({ "title": "Kafka runbook" })),
+        ];
+
+        let found = evidence_from(&issues, &interactions, &LinkDetector::new());
+        let kinds = |person: &str| -> Vec<(EvidenceKind, String)> {
+            found
+                .iter()
+                .filter(|item| item.person_id == person)
+                .map(|item| (item.kind, item.topic.clone()))
+                .collect()
+        };
+
+        assert!(kinds("ana").contains(&(EvidenceKind::ResolvedIssue, "redis".to_string())));
+        assert!(kinds("ana").contains(&(EvidenceKind::ResolvedIssue, "caching".to_string())));
+        assert!(kinds("bo").is_empty());
+        assert!(kinds("cy")
+            .iter()
+            .all(|(kind, _)| *kind == EvidenceKind::AuthoredSolution));
+        assert!(kinds("cy").contains(&(EvidenceKind::AuthoredSolution, "redis".to_string())));
+        assert_eq!(kinds("di"), vec![(EvidenceKind::WroteDocumentation, "kafka".to_string())]);
+    }
+
+    #[test]
+    fn test_evidence_decays_by_half_life() {
+        let now = Utc::now();
+        let fresh = evidence("ana", EvidenceKind::ResolvedIssue, "ESCL-1", 0, now);
+        let old = evidence("ana", EvidenceKind::ResolvedIssue, "ESCL-1", EXPERTISE_HALF_LIFE_DAYS as i64, now);
+        let review = evidence("ana", EvidenceKind::ReviewedChange, "ESCL-1", 0, now);
+
+        assert!((fresh.decayed_weight(now) - 1.0).abs() < 1e-9);
+        assert!((old.decayed_weight(now) - 0.5).abs() < 1e-3);
+        assert!((review.decayed_weight(now) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregate_ranks_recent_repeated_work_first() {
+        let now = Utc::now();
+        let items = vec![
+            evidence("ana", EvidenceKind::ResolvedIssue, "ESCL-1", 5, now),
+            evidence("ana", EvidenceKind::AuthoredSolution, "ESCL-2", 10, now),
+            evidence("bo", EvidenceKind::ResolvedIssue, "ESCL-3", 720, now),
+            evidence("cy", EvidenceKind::WroteDocumentation, "ESCL-4", 1, now),
+        ];
+        let mentions = HashMap::from([("bo".to_string(), HashSet::from(["ESCL-3".to_string()]))]);
+
+        let ranked = aggregate_expertise(&items, &mentions, now);
+        let order: Vec<&str> = ranked.iter().map(|expertise| expertise.person_id.as_str()).collect();
+        assert_eq!(order, vec!["ana", "cy", "bo"]);
+
+        let ana = &ranked[0].score;
+        assert_eq!(ana.contribution_count, 2);
+        assert_eq!(ana.evidence_items, vec!["ESCL-1", "ESCL-2"]);
+        assert!(ana.recency_factor > 0.95);
+        assert!(ranked[2].score.recency_factor < 0.1);
+        assert!(ranked[2].score.peer_recognition_score > 0.0);
+        assert_eq!(ranked[1].score.peer_recognition_score, 0.0);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use reqwest::Client;
+use crate::telemetry::TracedRequest;
 use chrono::{DateTime, Utc, Duration};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -100,7 +101,7 @@ impl GoogleAuthManager {
         let response = self.client
             .post(token_url)
             .form(&params)
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -140,7 +141,7 @@ impl GoogleAuthManager {
         let response = self.client
             .post(token_url)
             .form(&params)
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -195,7 +196,7 @@ impl Default for GoogleOAuthConfig {
         Self {
             client_id: std::env::var("GOOGLE_CLIENT_ID").unwrap_or_default(),
             client_secret: std::env::var("GOOGLE_CLIENT_SECRET").unwrap_or_default(),
-            redirect_uri: std::env::var("GOOGLE_REDIRECT_URI").unwrap_or_else(|_| "http://localhost:3001/auth/google/callback".to_string()),
+            redirect_uri: crate::config::server_config().google_redirect_uri.clone(),
             scopes: vec![
                 "https://www.googleapis.com/auth/documents.readonly".to_string(),
                 "https://www.googleapis.com/auth/drive.readonly".to_string(),
@@ -1,4 +1,6 @@
+use crate::access_control::ContentAcl;
 use crate::google_auth::GoogleAuthManager;
+use crate::telemetry::TracedRequest;
 use crate::types::PlatformType;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -129,7 +131,7 @@ impl GoogleApiClient {
         let doc_response = self.client
             .get(&doc_url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !doc_response.status().is_success() {
@@ -149,7 +151,7 @@ impl GoogleApiClient {
         let drive_response = self.client
             .get(&drive_url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         let drive_data: Value = if drive_response.status().is_success() {
@@ -198,7 +200,7 @@ impl GoogleApiClient {
         let response = self.client
             .get(&url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -232,7 +234,7 @@ impl GoogleApiClient {
         let drive_response = self.client
             .get(&drive_url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         let drive_data: Value = if drive_response.status().is_success() {
@@ -268,7 +270,7 @@ impl GoogleApiClient {
         let response = self.client
             .get(&url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -299,7 +301,7 @@ impl GoogleApiClient {
         let drive_response = self.client
             .get(&drive_url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         let drive_data: Value = if drive_response.status().is_success() {
@@ -363,7 +365,7 @@ impl GoogleApiClient {
         let response = self.client
             .get(&url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -412,7 +414,7 @@ impl GoogleApiClient {
         let response = self.client
             .get(&url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -465,7 +467,7 @@ impl GoogleApiClient {
         let response = self.client
             .get(&url)
             .header("Authorization", format!("Bearer {}", access_token))
-            .send()
+            .send_traced("google")
             .await?;
 
         if !response.status().is_success() {
@@ -543,6 +545,21 @@ pub enum GoogleContentType {
     Presentation(GooglePresentationContent),
 }
 
+impl GoogleContentType {
+    pub fn sharing_info(&self) -> &GoogleSharingInfo {
+        match self {
+            GoogleContentType::Document(content) => &content.sharing_info,
+            GoogleContentType::Spreadsheet(content) => &content.sharing_info,
+            GoogleContentType::Presentation(content) => &content.sharing_info,
+        }
+    }
+
+    /// The access list to store alongside this content.
+    pub fn acl(&self) -> ContentAcl {
+        ContentAcl::from_google_sharing(self.sharing_info()).with_resolved_people()
+    }
+}
+
 impl GoogleApiClient {
     pub async fn extract_content_by_platform_type(&mut self, platform_type: &PlatformType) -> Result<GoogleContentType, Box<dyn std::error::Error>> {
         match platform_type {
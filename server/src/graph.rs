@@ -1,14 +1,32 @@
 use crate::db_utils::with_connection;
+use crate::issue_graph::IssueEdgeType;
 use crate::types::{EdgeType, GraphEdge, GraphMetadata, GraphNode, KnowledgeGraph, NodeType};
 use crate::utils::{log_step, log_success};
 use chrono::Utc;
 use std::collections::HashMap;
 
+/// Most recently updated issues drawn on the graph; larger graphs are unreadable and slow to lay out.
+/// Graphs that hit the cap say so in `metadata.truncated_at`, since paging cannot reach the rest.
+const MAX_GRAPH_ISSUES: usize = 1000;
+
+const GRAPH_ISSUES: &str = r#"
+SELECT id, key, summary, status, project FROM live_issues
+ORDER BY updated DESC NULLS LAST, key
+LIMIT ?
+"#;
+
+const GRAPH_ISSUE_EDGES: &str = r#"
+SELECT source_key, target_key, edge_type FROM issue_edges
+WHERE source_key IN (SELECT key FROM live_issues ORDER BY updated DESC NULLS LAST, key LIMIT $1)
+  AND target_key IN (SELECT key FROM live_issues ORDER BY updated DESC NULLS LAST, key LIMIT $1)
+"#;
+
 pub async fn generate_knowledge_graph() -> KnowledgeGraph {
     log_step("🕸️", "Generating knowledge graph from database...");
 
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
+    let mut truncated = false;
 
     // Generate nodes from issues and projects
     with_connection("generate_graph", |conn| {
@@ -45,11 +63,12 @@ pub async fn generate_knowledge_graph() -> KnowledgeGraph {
 
         // Create issue nodes
         let mut issue_stmt = conn
-            .prepare("SELECT id, key, summary, status, project FROM issues LIMIT 100")
+            .prepare(GRAPH_ISSUES)
             .expect("Failed to prepare issue query");
 
+        // One past the cap tells a complete graph from a truncated one
         let issue_rows = issue_stmt
-            .query_map([], |row| {
+            .query_map([MAX_GRAPH_ISSUES as i64 + 1], |row| {
                 let id: String = row.get(0)?;
                 let key: String = row.get(1)?;
                 let summary: String = row.get(2).unwrap_or_default();
@@ -59,8 +78,14 @@ pub async fn generate_knowledge_graph() -> KnowledgeGraph {
             })
             .expect("Failed to query issues");
 
+        let mut issue_ids = HashMap::new();
         for row in issue_rows {
             let (id, key, summary, status, project_json) = row.expect("Failed to read issue row");
+            if issue_ids.len() == MAX_GRAPH_ISSUES {
+                truncated = true;
+                break;
+            }
+            issue_ids.insert(key.clone(), id.clone());
             
             // Determine node size based on status or other factors
             let size = match status.as_str() {
@@ -110,61 +135,45 @@ pub async fn generate_knowledge_graph() -> KnowledgeGraph {
             }
         }
 
-        // Create edges between issues that reference each other
-        let mut link_stmt = conn
-            .prepare("SELECT id, issue_links FROM issues WHERE issue_links IS NOT NULL AND issue_links != '' LIMIT 100")
-            .expect("Failed to prepare issue links query");
+        // Create edges between issues from the dependency graph built at sync time
+        let mut edge_stmt = conn
+            .prepare(GRAPH_ISSUE_EDGES)
+            .expect("Failed to prepare issue edges query");
 
-        let link_rows = link_stmt
-            .query_map([], |row| {
-                let id: String = row.get(0)?;
-                let links_json: String = row.get(1)?;
-                Ok((id, links_json))
+        let edge_rows = edge_stmt
+            .query_map([MAX_GRAPH_ISSUES as i64], |row| {
+                let source_key: String = row.get(0)?;
+                let target_key: String = row.get(1)?;
+                let edge_type: String = row.get(2)?;
+                Ok((source_key, target_key, edge_type))
             })
-            .expect("Failed to query issue links");
+            .expect("Failed to query issue edges");
 
-        for row in link_rows {
-            let (source_id, links_json) = row.expect("Failed to read issue link row");
-            
-            if let Ok(links_value) = serde_json::from_str::<serde_json::Value>(&links_json) {
-                if let Some(links_array) = links_value.as_array() {
-                    for link in links_array {
-                        // Extract linked issue information
-                        if let (Some(outward_issue), Some(inward_issue)) = (
-                            link.get("outwardIssue"),
-                            link.get("inwardIssue"),
-                        ) {
-                            // Determine target issue ID
-                            let target_key = outward_issue
-                                .get("key")
-                                .or_else(|| inward_issue.get("key"))
-                                .and_then(|v| v.as_str());
-                                
-                            if let Some(target_key) = target_key {
-                                // Find target issue ID by key
-                                let mut target_stmt = conn
-                                    .prepare("SELECT id FROM issues WHERE key = ?")
-                                    .expect("Failed to prepare target query");
-                                    
-                                if let Ok(mut target_rows) = target_stmt.query_map([target_key], |row| {
-                                    Ok(row.get::<_, String>(0)?)
-                                }) {
-                                    if let Some(Ok(target_id)) = target_rows.next() {
-                                        edges.push(GraphEdge {
-                                            id: format!("link_{}_{}", source_id, target_id),
-                                            source: format!("issue_{}", source_id),
-                                            target: format!("issue_{}", target_id),
-                                            edge_type: EdgeType::References,
-                                            weight: 2.0,
-                                            label: Some("references".to_string()),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        for row in edge_rows {
+            let (source_key, target_key, edge_type) = row.expect("Failed to read issue edge row");
+
+            // Links to issues outside the synced projects have no node to attach to
+            let (Some(source_id), Some(target_id)) = (issue_ids.get(&source_key), issue_ids.get(&target_key)) else {
+                continue;
+            };
+
+            let (graph_edge_type, weight, label) = match IssueEdgeType::parse(&edge_type) {
+                IssueEdgeType::Blocks => (EdgeType::DependsOn, 3.0, "blocks"),
+                IssueEdgeType::Subtask => (EdgeType::PartOf, 2.0, "parent of"),
+                IssueEdgeType::Epic => (EdgeType::PartOf, 2.0, "epic of"),
+                IssueEdgeType::Duplicates => (EdgeType::Similar, 2.0, "duplicates"),
+                IssueEdgeType::Clones => (EdgeType::Similar, 1.0, "clones"),
+                IssueEdgeType::Relates | IssueEdgeType::Link => (EdgeType::References, 1.0, "relates to"),
+            };
+
+            edges.push(GraphEdge {
+                id: format!("{}_{}_{}", edge_type, source_id, target_id),
+                source: format!("issue_{}", source_id),
+                target: format!("issue_{}", target_id),
+                edge_type: graph_edge_type,
+                weight,
+                label: Some(label.to_string()),
+            });
         }
     });
 
@@ -186,6 +195,7 @@ pub async fn generate_knowledge_graph() -> KnowledgeGraph {
         node_types: node_type_counts,
         edge_types: edge_type_counts,
         generated_at: Utc::now().to_rfc3339(),
+        truncated_at: truncated.then_some(MAX_GRAPH_ISSUES),
     };
 
     log_success(&format!(
@@ -0,0 +1,930 @@
+use crate::constants::RESOLVED_STATUSES;
+use crate::db_utils::{with_connection, with_transaction};
+use crate::utils::{log_step, log_success};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// ================================
+// ISSUE DEPENDENCY GRAPH
+// ================================
+
+/// Unestimated work still counts as one point on a critical path.
+const DEFAULT_ISSUE_WEIGHT: f64 = 1.0;
+
+pub const CREATE_ISSUE_EDGES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS issue_edges (
+    source_key TEXT NOT NULL,
+    target_key TEXT NOT NULL,
+    edge_type TEXT NOT NULL, -- blocks | duplicates | clones | relates | subtask | epic | link
+    link_name TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_issue_edges_source ON issue_edges(source_key);
+CREATE INDEX IF NOT EXISTS idx_issue_edges_target ON issue_edges(target_key);
+
+-- Linked issues from projects that are not synced, as last seen on the link
+CREATE TABLE IF NOT EXISTS issue_link_stubs (
+    key TEXT NOT NULL,
+    summary TEXT,
+    status TEXT,
+    issue_type TEXT
+);
+"#;
+
+const INSERT_ISSUE_EDGE: &str = r#"
+INSERT INTO issue_edges (source_key, target_key, edge_type, link_name) VALUES (?, ?, ?, ?)
+"#;
+
+const INSERT_ISSUE_LINK_STUB: &str = r#"
+INSERT INTO issue_link_stubs (key, summary, status, issue_type) VALUES (?, ?, ?, ?)
+"#;
+
+pub async fn create_issue_edges_table() {
+    with_connection("create_issue_edges_table", |conn| {
+        conn.execute_batch(CREATE_ISSUE_EDGES_TABLE)
+            .expect("Failed to create issue_edges table");
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueEdgeType {
+    /// Source blocks target.
+    Blocks,
+    /// Source duplicates target.
+    Duplicates,
+    /// Source clones target.
+    Clones,
+    Relates,
+    /// Parent -> subtask.
+    Subtask,
+    /// Epic -> child issue.
+    Epic,
+    /// Any other link type; the Jira name is kept in `link_name`.
+    Link,
+}
+
+impl IssueEdgeType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IssueEdgeType::Blocks => "blocks",
+            IssueEdgeType::Duplicates => "duplicates",
+            IssueEdgeType::Clones => "clones",
+            IssueEdgeType::Relates => "relates",
+            IssueEdgeType::Subtask => "subtask",
+            IssueEdgeType::Epic => "epic",
+            IssueEdgeType::Link => "link",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "blocks" => IssueEdgeType::Blocks,
+            "duplicates" => IssueEdgeType::Duplicates,
+            "clones" => IssueEdgeType::Clones,
+            "relates" => IssueEdgeType::Relates,
+            "subtask" => IssueEdgeType::Subtask,
+            "epic" => IssueEdgeType::Epic,
+            _ => IssueEdgeType::Link,
+        }
+    }
+
+    /// Maps a Jira link type name ("Blocks", "Duplicate", "Cloners", ...) to an edge type.
+    fn from_link_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("block") {
+            IssueEdgeType::Blocks
+        } else if name.contains("duplicat") {
+            IssueEdgeType::Duplicates
+        } else if name.contains("clone") {
+            IssueEdgeType::Clones
+        } else if name.contains("relat") {
+            IssueEdgeType::Relates
+        } else {
+            IssueEdgeType::Link
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueEdge {
+    pub source_key: String,
+    pub target_key: String,
+    pub edge_type: IssueEdgeType,
+    pub link_name: Option<String>,
+}
+
+struct LinkStub {
+    summary: Option<String>,
+    status: Option<String>,
+    issue_type: Option<String>,
+}
+
+/// Edges for one issue from its `issuelinks`, subtasks, parent and epic.
+///
+/// Every link is normalised so the source is the issue doing the blocking, duplicating, etc.
+fn edges_for_issue(
+    key: &str,
+    issue_links: Option<&Value>,
+    sub_tasks: Option<&Value>,
+    parent_key: Option<&str>,
+    epic_key: Option<&str>,
+    stubs: &mut HashMap<String, LinkStub>,
+) -> Vec<IssueEdge> {
+    let mut edges = Vec::new();
+
+    for link in issue_links.and_then(|links| links.as_array()).into_iter().flatten() {
+        let link_name = link["type"]["name"].as_str().unwrap_or_default();
+        let edge_type = IssueEdgeType::from_link_name(link_name);
+
+        let (source, target, other) = if let Some(outward) = link.get("outwardIssue") {
+            (key.to_string(), outward["key"].as_str(), outward)
+        } else if let Some(inward) = link.get("inwardIssue") {
+            (inward["key"].as_str().unwrap_or_default().to_string(), Some(key), inward)
+        } else {
+            continue;
+        };
+        let Some(target) = target.filter(|target| !target.is_empty()) else {
+            continue;
+        };
+        if source.is_empty() {
+            continue;
+        }
+
+        if let Some(other_key) = other["key"].as_str() {
+            stubs.entry(other_key.to_string()).or_insert_with(|| LinkStub {
+                summary: other["fields"]["summary"].as_str().map(|s| s.to_string()),
+                status: other["fields"]["status"]["name"].as_str().map(|s| s.to_string()),
+                issue_type: other["fields"]["issuetype"]["name"].as_str().map(|s| s.to_string()),
+            });
+        }
+
+        edges.push(IssueEdge {
+            source_key: source,
+            target_key: target.to_string(),
+            edge_type,
+            link_name: Some(link_name.to_string()).filter(|name| !name.is_empty()),
+        });
+    }
+
+    for subtask in sub_tasks.and_then(|tasks| tasks.as_array()).into_iter().flatten() {
+        if let Some(subtask_key) = subtask["key"].as_str() {
+            edges.push(IssueEdge {
+                source_key: key.to_string(),
+                target_key: subtask_key.to_string(),
+                edge_type: IssueEdgeType::Subtask,
+                link_name: None,
+            });
+        }
+    }
+
+    if let Some(epic_key) = epic_key {
+        edges.push(IssueEdge {
+            source_key: epic_key.to_string(),
+            target_key: key.to_string(),
+            edge_type: IssueEdgeType::Epic,
+            link_name: None,
+        });
+    }
+
+    // A parent that is also the epic was already recorded as an epic edge
+    if let Some(parent_key) = parent_key.filter(|parent| Some(*parent) != epic_key) {
+        edges.push(IssueEdge {
+            source_key: parent_key.to_string(),
+            target_key: key.to_string(),
+            edge_type: IssueEdgeType::Subtask,
+            link_name: None,
+        });
+    }
+
+    edges
+}
+
+/// Points edges that still name an issue by a key it had before moving projects at its
+/// current key; links on issues not re-synced since the move keep the old one.
+fn follow_key_aliases(edges: HashSet<IssueEdge>, aliases: &HashMap<String, String>) -> HashSet<IssueEdge> {
+    let resolve = |key: String| aliases.get(&key).cloned().unwrap_or(key);
+    edges
+        .into_iter()
+        .map(|edge| IssueEdge {
+            source_key: resolve(edge.source_key),
+            target_key: resolve(edge.target_key),
+            ..edge
+        })
+        .collect()
+}
+
+const LOAD_KEY_ALIASES: &str = r#"
+SELECT a.old_key, i.key FROM issue_key_aliases a JOIN live_issues i ON i.id = a.issue_id
+"#;
+
+/// The issue's own key and the keys it had before moving, for an issue given by current key.
+const LOAD_ISSUE_KEYS: &str = r#"
+SELECT $1 AS key
+UNION SELECT a.old_key FROM issue_key_aliases a JOIN issues i ON i.id = a.issue_id WHERE i.key = $1
+"#;
+
+/// Issues that may report an edge to the given issue: itself, the other ends of its stored
+/// edges, and children naming it as parent or epic.
+const LOAD_EDGE_REPORTERS: &str = r#"
+WITH touched AS (
+    SELECT $1 AS key
+    UNION SELECT a.old_key FROM issue_key_aliases a JOIN issues i ON i.id = a.issue_id WHERE i.key = $1
+),
+neighbours AS (
+    SELECT source_key AS key FROM issue_edges WHERE target_key IN (SELECT key FROM touched)
+    UNION SELECT target_key FROM issue_edges WHERE source_key IN (SELECT key FROM touched)
+)
+SELECT key, issue_links, sub_tasks, parent_key, epic_key FROM live_issues
+WHERE key IN (SELECT key FROM touched)
+   OR key IN (SELECT key FROM neighbours)
+   OR parent_key = $1
+   OR epic_key = $1
+"#;
+
+/// Stubs are kept for issues outside synced projects only, once per key.
+const INSERT_ISSUE_LINK_STUB_IF_MISSING: &str = r#"
+INSERT INTO issue_link_stubs (key, summary, status, issue_type)
+SELECT $1, $2, $3, $4
+WHERE NOT EXISTS (SELECT 1 FROM issue_link_stubs WHERE key = $1)
+  AND NOT EXISTS (SELECT 1 FROM live_issues WHERE key = $1)
+  AND NOT EXISTS (SELECT 1 FROM issue_key_aliases WHERE old_key = $1)
+"#;
+
+/// Adds the edges reported by each row of `query` and returns the keys of the reporting issues.
+fn collect_edges<P: duckdb::Params>(
+    conn: &duckdb::Connection,
+    query: &str,
+    params: P,
+    edges: &mut HashSet<IssueEdge>,
+    stubs: &mut HashMap<String, LinkStub>,
+) -> HashSet<String> {
+    let mut reporters = HashSet::new();
+    let mut stmt = conn
+        .prepare(query)
+        .expect("Failed to prepare issue relationship query");
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })
+        .expect("Failed to query issue relationships");
+
+    for (key, issue_links, sub_tasks, parent_key, epic_key) in rows.flatten() {
+        let issue_links = issue_links.and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+        let sub_tasks = sub_tasks.and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+        let parent_key = parent_key.filter(|parent| !parent.is_empty());
+        let epic_key = epic_key.filter(|epic| !epic.is_empty());
+
+        edges.extend(edges_for_issue(
+            &key,
+            issue_links.as_ref(),
+            sub_tasks.as_ref(),
+            parent_key.as_deref(),
+            epic_key.as_deref(),
+            stubs,
+        ));
+        reporters.insert(key);
+    }
+    reporters
+}
+
+fn load_key_aliases(conn: &duckdb::Connection) -> HashMap<String, String> {
+    let mut stmt = conn
+        .prepare(LOAD_KEY_ALIASES)
+        .expect("Failed to prepare key alias query");
+    let aliases = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .expect("Failed to query key aliases")
+        .flatten()
+        .collect();
+    aliases
+}
+
+/// Rebuilds `issue_edges` from every stored issue that is not deleted. Both ends of a link
+/// report it, so edges are de-duplicated before they are written.
+pub async fn rebuild_issue_edges() {
+    log_step("🔗", "Rebuilding issue dependency graph...");
+
+    let mut edges: HashSet<IssueEdge> = HashSet::new();
+    let mut stubs: HashMap<String, LinkStub> = HashMap::new();
+    let mut synced_keys: HashSet<String> = HashSet::new();
+    let mut aliases: HashMap<String, String> = HashMap::new();
+
+    with_connection("load_issue_relationships", |conn| {
+        synced_keys = collect_edges(
+            &conn,
+            "SELECT key, issue_links, sub_tasks, parent_key, epic_key FROM live_issues",
+            [],
+            &mut edges,
+            &mut stubs,
+        );
+        aliases = load_key_aliases(&conn);
+    });
+
+    let edges = follow_key_aliases(edges, &aliases);
+    stubs.retain(|key, _| !synced_keys.contains(key) && !aliases.contains_key(key));
+
+    with_transaction("save_issue_edges", |tx| {
+        tx.execute("DELETE FROM issue_edges", [])
+            .expect("Failed to clear issue_edges");
+        tx.execute("DELETE FROM issue_link_stubs", [])
+            .expect("Failed to clear issue_link_stubs");
+
+        let mut stmt = tx.prepare(INSERT_ISSUE_EDGE).expect("Failed to prepare edge insert");
+        for edge in &edges {
+            stmt.execute(duckdb::params![
+                edge.source_key,
+                edge.target_key,
+                edge.edge_type.as_str(),
+                edge.link_name,
+            ])
+            .expect("Failed to insert issue edge");
+        }
+
+        let mut stmt = tx
+            .prepare(INSERT_ISSUE_LINK_STUB)
+            .expect("Failed to prepare link stub insert");
+        for (key, stub) in &stubs {
+            stmt.execute(duckdb::params![key, stub.summary, stub.status, stub.issue_type])
+                .expect("Failed to insert link stub");
+        }
+    });
+
+    log_success(&format!(
+        "Stored {} issue edge(s), {} linked issue(s) outside synced projects.",
+        edges.len(),
+        stubs.len()
+    ));
+}
+
+/// Replaces only the edges touching one issue, after a webhook stored or deleted it. They are
+/// recomputed from every issue that can report them, so a link recorded only on the other end
+/// survives, and edges naming a key the issue had before moving follow it to the current one.
+pub async fn update_issue_edges(key: &str) {
+    let mut edges: HashSet<IssueEdge> = HashSet::new();
+    let mut stubs: HashMap<String, LinkStub> = HashMap::new();
+    let mut touched: Vec<String> = Vec::new();
+    let mut aliases: HashMap<String, String> = HashMap::new();
+
+    with_connection("load_issue_edge_reporters", |conn| {
+        let mut stmt = conn.prepare(LOAD_ISSUE_KEYS).expect("Failed to prepare issue key query");
+        touched = stmt
+            .query_map([key], |row| row.get::<_, String>(0))
+            .expect("Failed to query issue keys")
+            .flatten()
+            .collect();
+        collect_edges(&conn, LOAD_EDGE_REPORTERS, [key], &mut edges, &mut stubs);
+        aliases = load_key_aliases(&conn);
+    });
+
+    let edges: Vec<IssueEdge> = follow_key_aliases(edges, &aliases)
+        .into_iter()
+        .filter(|edge| edge.source_key == key || edge.target_key == key)
+        .collect();
+    stubs.retain(|stub_key, _| {
+        edges
+            .iter()
+            .any(|edge| edge.source_key == *stub_key || edge.target_key == *stub_key)
+    });
+
+    with_transaction("update_issue_edges", |tx| {
+        for touched_key in &touched {
+            tx.execute(
+                "DELETE FROM issue_edges WHERE source_key = ? OR target_key = ?",
+                [touched_key, touched_key],
+            )
+            .expect("Failed to clear issue edges");
+            tx.execute("DELETE FROM issue_link_stubs WHERE key = ?", [touched_key])
+                .expect("Failed to clear link stub");
+        }
+
+        let mut stmt = tx.prepare(INSERT_ISSUE_EDGE).expect("Failed to prepare edge insert");
+        for edge in &edges {
+            stmt.execute(duckdb::params![
+                edge.source_key,
+                edge.target_key,
+                edge.edge_type.as_str(),
+                edge.link_name,
+            ])
+            .expect("Failed to insert issue edge");
+        }
+
+        let mut stmt = tx
+            .prepare(INSERT_ISSUE_LINK_STUB_IF_MISSING)
+            .expect("Failed to prepare link stub insert");
+        for (stub_key, stub) in &stubs {
+            stmt.execute(duckdb::params![stub_key, stub.summary, stub.status, stub.issue_type])
+                .expect("Failed to insert link stub");
+        }
+    });
+
+    tracing::debug!("Stored {} issue edge(s) touching [{}].", edges.len(), key);
+}
+
+// ================================
+// IN-MEMORY GRAPH & TRAVERSALS
+// ================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueNode {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub issue_type: String,
+    pub project_key: String,
+    pub assignee: Option<String>,
+    pub story_points: Option<f64>,
+    pub updated: Option<String>,
+    pub resolved: bool,
+    /// False for issues only known through a link from a synced issue.
+    pub synced: bool,
+}
+
+pub struct DependencyGraph {
+    pub nodes: HashMap<String, IssueNode>,
+    outgoing: HashMap<String, Vec<(String, IssueEdgeType)>>,
+    incoming: HashMap<String, Vec<(String, IssueEdgeType)>>,
+}
+
+impl DependencyGraph {
+    pub fn load() -> Self {
+        let mut nodes = HashMap::new();
+        let mut edges = Vec::new();
+
+        with_connection("load_dependency_graph", |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT key, summary, status, issue_type, project_key, assignee, story_points, \
+                     updated, resolution FROM live_issues",
+                )
+                .expect("Failed to prepare dependency node query");
+            let rows = stmt
+                .query_map([], |row| {
+                    let key: String = row.get(0)?;
+                    let status: String = row.get::<_, Option<String>>(2)?.unwrap_or_default();
+                    let resolution: Option<String> = row.get(8)?;
+                    Ok(IssueNode {
+                        project_key: row
+                            .get::<_, Option<String>>(4)?
+                            .filter(|project| !project.is_empty())
+                            .unwrap_or_else(|| project_of(&key)),
+                        summary: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                        issue_type: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                        assignee: row.get::<_, Option<String>>(5)?.filter(|a| !a.is_empty()),
+                        story_points: row.get(6)?,
+                        updated: row.get(7)?,
+                        resolved: is_resolved(&status, resolution.as_deref()),
+                        status,
+                        synced: true,
+                        key,
+                    })
+                })
+                .expect("Failed to query dependency nodes");
+            for node in rows.flatten() {
+                nodes.insert(node.key.clone(), node);
+            }
+
+            let mut stmt = conn
+                .prepare("SELECT key, summary, status, issue_type FROM issue_link_stubs")
+                .expect("Failed to prepare link stub query");
+            let rows = stmt
+                .query_map([], |row| {
+                    let key: String = row.get(0)?;
+                    let status: String = row.get::<_, Option<String>>(2)?.unwrap_or_default();
+                    Ok(IssueNode {
+                        project_key: project_of(&key),
+                        summary: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                        issue_type: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                        assignee: None,
+                        story_points: None,
+                        updated: None,
+                        resolved: is_resolved(&status, None),
+                        status,
+                        synced: false,
+                        key,
+                    })
+                })
+                .expect("Failed to query link stubs");
+            for node in rows.flatten() {
+                nodes.entry(node.key.clone()).or_insert(node);
+            }
+
+            let mut stmt = conn
+                .prepare("SELECT source_key, target_key, edge_type FROM issue_edges")
+                .expect("Failed to prepare issue edge query");
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        IssueEdgeType::parse(&row.get::<_, String>(2)?),
+                    ))
+                })
+                .expect("Failed to query issue edges");
+            edges.extend(rows.flatten());
+        });
+
+        Self::from_parts(nodes, edges)
+    }
+
+    pub fn from_parts(
+        nodes: HashMap<String, IssueNode>,
+        edges: Vec<(String, String, IssueEdgeType)>,
+    ) -> Self {
+        let mut outgoing: HashMap<String, Vec<(String, IssueEdgeType)>> = HashMap::new();
+        let mut incoming: HashMap<String, Vec<(String, IssueEdgeType)>> = HashMap::new();
+        let mut edges = edges;
+        edges.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        for (source, target, edge_type) in edges {
+            outgoing.entry(source.clone()).or_default().push((target.clone(), edge_type));
+            incoming.entry(target).or_default().push((source, edge_type));
+        }
+        Self {
+            nodes,
+            outgoing,
+            incoming,
+        }
+    }
+
+    pub fn targets(&self, key: &str, edge_type: IssueEdgeType) -> Vec<&str> {
+        self.outgoing
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|(_, kind)| *kind == edge_type)
+            .map(|(target, _)| target.as_str())
+            .collect()
+    }
+
+    pub fn sources(&self, key: &str, edge_type: IssueEdgeType) -> Vec<&str> {
+        self.incoming
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|(_, kind)| *kind == edge_type)
+            .map(|(source, _)| source.as_str())
+            .collect()
+    }
+
+    pub fn is_resolved(&self, key: &str) -> bool {
+        self.nodes.get(key).map(|node| node.resolved).unwrap_or(false)
+    }
+
+    /// Every issue that transitively blocks `key`, nearest first, with the chain that connects it.
+    pub fn upstream_blockers(&self, key: &str) -> Vec<BlockerEntry> {
+        let mut blockers = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::from([key]);
+        let mut queue: VecDeque<(&str, Vec<String>)> = VecDeque::from([(key, vec![key.to_string()])]);
+
+        while let Some((current, path)) = queue.pop_front() {
+            for blocker in self.sources(current, IssueEdgeType::Blocks) {
+                if !visited.insert(blocker) {
+                    continue;
+                }
+                let mut blocker_path = path.clone();
+                blocker_path.push(blocker.to_string());
+
+                blockers.push(BlockerEntry {
+                    issue: self.node_or_placeholder(blocker),
+                    depth: blocker_path.len() - 1,
+                    // Reported from the root blocker down to the blocked issue
+                    path: blocker_path.iter().rev().cloned().collect(),
+                });
+                queue.push_back((blocker, blocker_path));
+            }
+        }
+
+        blockers
+    }
+
+    /// The epic with its children and their subtasks, nested.
+    pub fn epic_tree(&self, epic_key: &str) -> Option<IssueTreeNode> {
+        if !self.nodes.contains_key(epic_key) && !self.outgoing.contains_key(epic_key) {
+            return None;
+        }
+        let mut visited = HashSet::new();
+        Some(self.build_tree(epic_key, &mut visited))
+    }
+
+    fn build_tree<'a>(&'a self, key: &'a str, visited: &mut HashSet<&'a str>) -> IssueTreeNode {
+        visited.insert(key);
+        let mut children: Vec<IssueTreeNode> = Vec::new();
+        for child in self
+            .targets(key, IssueEdgeType::Epic)
+            .into_iter()
+            .chain(self.targets(key, IssueEdgeType::Subtask))
+        {
+            if !visited.contains(child) {
+                children.push(self.build_tree(child, visited));
+            }
+        }
+        children.sort_by(|a, b| a.issue.key.cmp(&b.issue.key));
+
+        let remaining_points = children.iter().map(|child| child.remaining_points).sum::<f64>()
+            + if self.is_resolved(key) || !children.is_empty() {
+                0.0
+            } else {
+                self.weight(key)
+            };
+
+        IssueTreeNode {
+            issue: self.node_or_placeholder(key),
+            remaining_points,
+            children,
+        }
+    }
+
+    /// All issues under an epic, excluding the epic itself.
+    pub fn epic_members(&self, epic_key: &str) -> HashSet<String> {
+        let mut members = HashSet::new();
+        let mut stack = vec![epic_key.to_string()];
+        while let Some(current) = stack.pop() {
+            for child in self
+                .targets(&current, IssueEdgeType::Epic)
+                .into_iter()
+                .chain(self.targets(&current, IssueEdgeType::Subtask))
+            {
+                if child != epic_key && members.insert(child.to_string()) {
+                    stack.push(child.to_string());
+                }
+            }
+        }
+        members
+    }
+
+    fn weight(&self, key: &str) -> f64 {
+        self.nodes
+            .get(key)
+            .and_then(|node| node.story_points)
+            .filter(|points| *points > 0.0)
+            .unwrap_or(DEFAULT_ISSUE_WEIGHT)
+    }
+
+    /// Longest chain of unresolved work (by story points) that must finish before the epic can.
+    ///
+    /// Work is ordered by `blocks` links, and subtasks finish before their parent. Unresolved
+    /// blockers outside the epic are pulled in since the epic waits on them too.
+    pub fn critical_path(&self, epic_key: &str) -> Option<CriticalPath> {
+        if !self.nodes.contains_key(epic_key) && !self.outgoing.contains_key(epic_key) {
+            return None;
+        }
+
+        let mut work: HashSet<String> = self
+            .epic_members(epic_key)
+            .into_iter()
+            .filter(|key| !self.is_resolved(key))
+            .collect();
+        let mut frontier: Vec<String> = work.iter().cloned().collect();
+        while let Some(key) = frontier.pop() {
+            for blocker in self.sources(&key, IssueEdgeType::Blocks) {
+                if !self.is_resolved(blocker) && work.insert(blocker.to_string()) {
+                    frontier.push(blocker.to_string());
+                }
+            }
+        }
+
+        // predecessors[x] = work that has to finish before x
+        let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for key in &work {
+            let mut before: Vec<&str> = self
+                .sources(key, IssueEdgeType::Blocks)
+                .into_iter()
+                .chain(self.targets(key, IssueEdgeType::Subtask))
+                .filter(|other| work.contains(*other))
+                .collect();
+            before.sort();
+            predecessors.insert(key.as_str(), before);
+        }
+
+        let mut memo: HashMap<&str, (f64, Option<&str>)> = HashMap::new();
+        let mut in_progress: HashSet<&str> = HashSet::new();
+        let mut keys: Vec<&str> = work.iter().map(|key| key.as_str()).collect();
+        keys.sort();
+        for key in &keys {
+            self.longest_chain(key, &predecessors, &mut memo, &mut in_progress);
+        }
+
+        let end = keys.iter().copied().max_by(|a, b| {
+            memo[a]
+                .0
+                .partial_cmp(&memo[b].0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.cmp(a))
+        });
+
+        let mut path = Vec::new();
+        let mut cursor = end;
+        while let Some(key) = cursor {
+            path.push(PathStep {
+                issue: self.node_or_placeholder(key),
+                points: self.weight(key),
+            });
+            cursor = memo.get(key).and_then(|(_, previous)| *previous);
+        }
+        path.reverse();
+
+        Some(CriticalPath {
+            epic_key: epic_key.to_string(),
+            path_points: path.iter().map(|step| step.points).sum(),
+            remaining_points: work.iter().map(|key| self.weight(key)).sum(),
+            unresolved_issues: work.len(),
+            path,
+        })
+    }
+
+    fn longest_chain<'a>(
+        &self,
+        key: &'a str,
+        predecessors: &HashMap<&'a str, Vec<&'a str>>,
+        memo: &mut HashMap<&'a str, (f64, Option<&'a str>)>,
+        in_progress: &mut HashSet<&'a str>,
+    ) -> f64 {
+        if let Some((length, _)) = memo.get(key) {
+            return *length;
+        }
+        // A blocking cycle can never be scheduled; cut it where it was entered
+        if !in_progress.insert(key) {
+            return 0.0;
+        }
+
+        let mut best = (0.0, None);
+        for previous in predecessors.get(key).into_iter().flatten() {
+            let length = self.longest_chain(previous, predecessors, memo, in_progress);
+            if length > best.0 {
+                best = (length, Some(*previous));
+            }
+        }
+
+        in_progress.remove(key);
+        let length = best.0 + self.weight(key);
+        memo.insert(key, (length, best.1));
+        length
+    }
+
+    fn node_or_placeholder(&self, key: &str) -> IssueNode {
+        self.nodes.get(key).cloned().unwrap_or_else(|| IssueNode {
+            key: key.to_string(),
+            summary: String::new(),
+            status: String::new(),
+            issue_type: String::new(),
+            project_key: project_of(key),
+            assignee: None,
+            story_points: None,
+            updated: None,
+            resolved: false,
+            synced: false,
+        })
+    }
+}
+
+pub fn project_of(key: &str) -> String {
+    key.rsplit_once('-')
+        .map(|(project, _)| project.to_string())
+        .unwrap_or_default()
+}
+
+fn is_resolved(status: &str, resolution: Option<&str>) -> bool {
+    resolution.map(|r| !r.is_empty()).unwrap_or(false) || RESOLVED_STATUSES.contains(&status)
+}
+
+// ================================
+// API RESPONSE TYPES
+// ================================
+
+#[derive(Debug, Serialize)]
+pub struct BlockerEntry {
+    pub issue: IssueNode,
+    pub depth: usize,
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueTreeNode {
+    pub issue: IssueNode,
+    pub remaining_points: f64,
+    pub children: Vec<IssueTreeNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PathStep {
+    pub issue: IssueNode,
+    pub points: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CriticalPath {
+    pub epic_key: String,
+    pub path: Vec<PathStep>,
+    pub path_points: f64,
+    pub remaining_points: f64,
+    pub unresolved_issues: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(key: &str, points: Option<f64>, resolved: bool) -> (String, IssueNode) {
+        (
+            key.to_string(),
+            IssueNode {
+                key: key.to_string(),
+                summary: String::new(),
+                status: if resolved { "Done" } else { "To Do" }.to_string(),
+                issue_type: String::new(),
+                project_key: project_of(key),
+                assignee: None,
+                story_points: points,
+                updated: None,
+                resolved,
+                synced: true,
+            },
+        )
+    }
+
+    fn edge(source: &str, target: &str, edge_type: IssueEdgeType) -> (String, String, IssueEdgeType) {
+        (source.to_string(), target.to_string(), edge_type)
+    }
+
+    #[test]
+    fn test_links_are_normalised_to_the_blocking_issue() {
+        let links = json!([
+            {"type": {"name": "Blocks"}, "inwardIssue": {"key": "A-1", "fields": {"status": {"name": "Open"}}}},
+            {"type": {"name": "Blocks"}, "outwardIssue": {"key": "A-3"}},
+            {"type": {"name": "Duplicate"}, "outwardIssue": {"key": "A-4"}}
+        ]);
+        let mut stubs = HashMap::new();
+        let edges = edges_for_issue("A-2", Some(&links), None, Some("A-9"), Some("A-9"), &mut stubs);
+
+        assert!(edges.contains(&IssueEdge {
+            source_key: "A-1".to_string(),
+            target_key: "A-2".to_string(),
+            edge_type: IssueEdgeType::Blocks,
+            link_name: Some("Blocks".to_string()),
+        }));
+        assert!(edges.iter().any(|e| e.source_key == "A-2" && e.target_key == "A-3" && e.edge_type == IssueEdgeType::Blocks));
+        assert!(edges.iter().any(|e| e.target_key == "A-4" && e.edge_type == IssueEdgeType::Duplicates));
+        // Parent that is the epic only yields the epic edge
+        assert_eq!(edges.iter().filter(|e| e.source_key == "A-9").count(), 1);
+        assert_eq!(stubs["A-1"].status.as_deref(), Some("Open"));
+    }
+
+    #[test]
+    fn test_edges_follow_moved_issues_to_their_new_key() {
+        let link = |source: &str, target: &str| IssueEdge {
+            source_key: source.to_string(),
+            target_key: target.to_string(),
+            edge_type: IssueEdgeType::Blocks,
+            link_name: Some("Blocks".to_string()),
+        };
+        // OPS-7 moved to PLAT-3; OPS-8 still links to it by the old key, PLAT-3 reports the same link
+        let edges = HashSet::from([link("OPS-8", "OPS-7"), link("OPS-8", "PLAT-3")]);
+        let aliases = HashMap::from([("OPS-7".to_string(), "PLAT-3".to_string())]);
+
+        let edges = follow_key_aliases(edges, &aliases);
+
+        assert_eq!(edges, HashSet::from([link("OPS-8", "PLAT-3")]));
+    }
+
+    #[test]
+    fn test_critical_path_follows_heaviest_blocking_chain() {
+        let nodes = HashMap::from([
+            node("E-1", None, false),
+            node("E-2", Some(3.0), false),
+            node("E-3", Some(5.0), false),
+            node("E-4", Some(1.0), false),
+            node("E-5", Some(8.0), true),
+            node("X-1", Some(2.0), false),
+        ]);
+        let graph = DependencyGraph::from_parts(
+            nodes,
+            vec![
+                edge("E-1", "E-2", IssueEdgeType::Epic),
+                edge("E-1", "E-3", IssueEdgeType::Epic),
+                edge("E-1", "E-4", IssueEdgeType::Epic),
+                edge("E-1", "E-5", IssueEdgeType::Epic),
+                edge("E-2", "E-3", IssueEdgeType::Blocks),
+                edge("X-1", "E-2", IssueEdgeType::Blocks),
+                edge("E-5", "E-4", IssueEdgeType::Blocks),
+            ],
+        );
+
+        let path = graph.critical_path("E-1").unwrap();
+        let keys: Vec<&str> = path.path.iter().map(|step| step.issue.key.as_str()).collect();
+
+        assert_eq!(keys, vec!["X-1", "E-2", "E-3"]);
+        assert_eq!(path.path_points, 10.0);
+        assert_eq!(path.unresolved_issues, 4);
+
+        let blockers = graph.upstream_blockers("E-3");
+        assert_eq!(blockers[0].issue.key, "E-2");
+        assert_eq!(blockers[1].path, vec!["X-1", "E-2", "E-3"]);
+    }
+}
@@ -1,27 +1,67 @@
+use crate::agile::sync_sprints_for_projects;
 use crate::auth::{authenticate, get_domain};
-use crate::constants::{BATCH_SIZE, PAGE_SIZE};
-use crate::db::save_issues_batch_to_duckdb;
+use crate::constants::BATCH_SIZE;
+use crate::custom_fields::{extract_custom_fields, extract_epic_link, extract_story_points};
+use crate::db::{mark_issue_deleted, save_issues_batch_to_duckdb};
+use crate::events::{publish, ProgressEvent, JIRA_SYNC};
+use crate::expertise_engine::refresh_expertise;
+use crate::issue_graph::rebuild_issue_edges;
 use crate::link_detector::LinkDetector;
-use crate::routes::{get_issue_object, get_projects_api_route, search_issues_for_project_all_types};
-use crate::types::{Issue, IssueFieldMetadata, Project};
+use crate::people_sync::sync_people_from_issues;
+use crate::rate_limit::{is_throttled, pause_remaining, send_with_retry, MAX_RETRIES};
+use crate::routes::{
+    approximate_count_route, get_issue_object, get_projects_api_route, jira_base_url, project_issues_jql,
+    search_issue_ids_route, search_issues_jql_route,
+};
+use crate::sync_runs::{
+    find_resumable_run, finish_sync_run, mark_run_resumed, record_failed_issue, record_seen_issues,
+    resolve_failed_issues, save_checkpoint, start_sync_run, unresolved_failed_issues, unseen_issues, Checkpoint,
+    FailedIssue, RunOutcome, SyncMode,
+};
+use crate::telemetry::{TracedRequest, API_RETRIES_TOTAL, SYNC_ISSUES_FETCHED_TOTAL};
+use crate::types::{Issue, IssueFieldMetadata, IssueFields, Project};
 use crate::utils::{extract_json_field_as_string, get_optional_field};
 use futures::stream::{FuturesUnordered, StreamExt};
 use reqwest::{Client, StatusCode};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::{
     sync::{mpsc, Semaphore},
-    time::Duration,
+    task::JoinHandle,
 };
 
-struct SyncContext {
+/// Credentials and client shared by every request of one sync.
+pub struct SyncContext {
     token: String,
     domain: String,
+    /// Rate limiter key for `domain`
+    host: String,
     client: Client,
 }
 
+impl SyncContext {
+    /// `domain` is a Jira host, or a full base URL such as a local mock.
+    pub fn new(domain: String, token: String) -> Self {
+        let host = reqwest::Url::parse(&jira_base_url(&domain))
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_else(|| domain.clone());
+        Self {
+            token,
+            domain,
+            host,
+            client: Client::new(),
+        }
+    }
+
+    pub async fn from_saved_login() -> Self {
+        Self::new(get_domain(), authenticate().await)
+    }
+}
+
 pub async fn fetch_projects() -> Vec<Project> {
-    println!("📡 Fetching projects from Jira...");
+    tracing::info!("Fetching projects from Jira...");
 
     let domain = get_domain();
     let token = authenticate().await;
@@ -32,7 +72,7 @@ pub async fn fetch_projects() -> Vec<Project> {
         .get(&url)
         .header("Authorization", format!("Basic {}", token))
         .header("Accept", "application/json")
-        .send()
+        .send_traced("jira")
         .await
         .expect("❌ Failed to fetch projects");
 
@@ -43,130 +83,582 @@ pub async fn fetch_projects() -> Vec<Project> {
         panic!("❌ Jira API error: {} - {}", status, body);
     }
 
-    println!("✅ Projects fetched successfully.");
+    tracing::info!("Projects fetched successfully.");
     serde_json::from_str::<Vec<Project>>(&body).expect("❌ Failed to parse project response")
 }
 
-pub async fn sync_issues_for_projects(selected_ids: &Vec<String>) {
-    println!(
-        "🔄 Starting issue sync for {} project(s)...",
-        selected_ids.len()
+pub async fn sync_issues_for_projects(selected_ids: &Vec<String>) -> Result<(), String> {
+    sync_issues_updated_since(selected_ids, None).await
+}
+
+/// Syncs only issues updated at or after `updated_since` (a JQL date such as `-20m`), or all of them.
+/// Fails when some projects did not finish; the run can then be resumed.
+pub async fn sync_issues_updated_since(selected_ids: &[String], updated_since: Option<String>) -> Result<(), String> {
+    let run_id = start_sync_run(SyncMode::Sync, selected_ids, updated_since.as_deref());
+    run_sync(&run_id, selected_ids, updated_since, HashMap::new()).await
+}
+
+/// Continues the most recent interrupted sync from its per-project checkpoints.
+pub async fn resume_sync() -> Result<(), String> {
+    let run = find_resumable_run().map_err(|e| format!("Cannot resume: {}", e))?;
+    let remaining = run
+        .projects
+        .iter()
+        .filter(|project| !run.checkpoints.get(*project).is_some_and(|c| c.completed))
+        .count();
+    tracing::info!(
+        "Resuming sync {} with {} of {} project(s) left...",
+        run.id,
+        remaining,
+        run.projects.len()
     );
+    mark_run_resumed(&run.id);
+    run_sync(&run.id, &run.projects, run.updated_since, run.checkpoints).await
+}
+
+/// Re-fetches issues whose metadata could not be fetched in earlier syncs; returns how many
+/// were recovered and how many still fail.
+pub async fn retry_failed_issues() -> (usize, usize) {
+    let failed = unresolved_failed_issues();
+    if failed.is_empty() {
+        tracing::info!("No failed issues to retry.");
+        return (0, 0);
+    }
 
-    let (tx, mut rx) = mpsc::channel::<IssueFieldMetadata>(BATCH_SIZE * 10);
+    let mut by_project: HashMap<String, Vec<Issue>> = HashMap::new();
+    for issue in failed {
+        by_project.entry(issue.project).or_default().push(Issue {
+            id: issue.issue_id,
+            key: issue.issue_key,
+            fields: IssueFields::default(),
+        });
+    }
+    let projects: Vec<String> = by_project.keys().cloned().collect();
+    let run_id = start_sync_run(SyncMode::RetryFailed, &projects, None);
+    tracing::info!(
+        "Retrying {} failed issue(s) across {} project(s)...",
+        by_project.values().map(Vec::len).sum::<usize>(),
+        projects.len()
+    );
+
+    let ctx = Arc::new(SyncContext::from_saved_login().await);
+    let (tx, writer) = spawn_sync_writer(run_id.clone());
+    let (mut recovered, mut still_failing) = (0, 0);
+    for (project, issues) in by_project {
+        let (succeeded, failed) = get_issue_metaobjects(ctx.clone(), &project, issues, tx.clone()).await;
+        recovered += succeeded;
+        still_failing += failed;
+    }
+    drop(tx);
+    writer.await.unwrap();
+
+    let outcome = if still_failing == 0 { RunOutcome::Completed } else { RunOutcome::Failed };
+    let error = (still_failing > 0).then(|| format!("{} issue(s) still failing", still_failing));
+    finish_sync_run(&run_id, outcome, error);
+
+    if recovered > 0 {
+        rebuild_issue_edges().await;
+        sync_people_from_issues().await;
+        refresh_expertise().await;
+    }
+    (recovered, still_failing)
+}
+
+/// What project tasks hand to the single DB writer.
+pub enum SyncMessage {
+    Issue(Box<IssueFieldMetadata>),
+    Failed(FailedIssue),
+    PageDone(Checkpoint),
+}
+
+/// One global writer per run: batches issues into DuckDB and records checkpoints and failures.
+fn spawn_sync_writer(run_id: String) -> (mpsc::Sender<SyncMessage>, JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::channel::<SyncMessage>(BATCH_SIZE * 10);
 
-    // Spawn one global DB writer task
     let writer = tokio::spawn(async move {
         let mut buffer = Vec::with_capacity(BATCH_SIZE);
-        while let Some(meta) = rx.recv().await {
-            buffer.push(meta);
-            if buffer.len() >= BATCH_SIZE {
-                save_issues_batch_to_duckdb(&buffer).await;
-                buffer.clear();
+        while let Some(message) = rx.recv().await {
+            match message {
+                SyncMessage::Issue(meta) => {
+                    buffer.push(*meta);
+                    if buffer.len() >= BATCH_SIZE {
+                        flush_issues(&run_id, &mut buffer).await;
+                    }
+                }
+                // Jira still has the issue, so reconciliation must not treat it as deleted
+                SyncMessage::Failed(issue) => {
+                    record_failed_issue(&run_id, &issue);
+                    record_seen_issues(&run_id, &[issue.issue_id]);
+                }
+                // Flushing first means a checkpoint never covers issues that are not stored yet
+                SyncMessage::PageDone(checkpoint) => {
+                    flush_issues(&run_id, &mut buffer).await;
+                    save_checkpoint(&run_id, &checkpoint);
+                }
             }
         }
-        if !buffer.is_empty() {
-            save_issues_batch_to_duckdb(&buffer).await;
-        }
+        flush_issues(&run_id, &mut buffer).await;
     });
 
+    (tx, writer)
+}
+
+async fn flush_issues(run_id: &str, buffer: &mut Vec<IssueFieldMetadata>) {
+    if buffer.is_empty() {
+        return;
+    }
+    save_issues_batch_to_duckdb(buffer).await;
+    let ids: Vec<String> = buffer.iter().map(|meta| meta.id.clone()).collect();
+    resolve_failed_issues(&ids);
+    record_seen_issues(run_id, &ids);
+    buffer.clear();
+}
+
+/// Fetches every project not yet completed in `checkpoints`, reconciles deletions and moves after
+/// a full sync, then refreshes derived tables. Errors when a project stopped before its last page.
+/// Projects completed before a resume are reconciled too, since the run kept their seen issues.
+async fn run_sync(
+    run_id: &str,
+    selected_ids: &[String],
+    updated_since: Option<String>,
+    checkpoints: HashMap<String, Checkpoint>,
+) -> Result<(), String> {
+    tracing::info!(
+        "Starting issue sync for {} project(s){}...",
+        selected_ids.len(),
+        updated_since.as_ref().map(|since| format!(" updated since {}", since)).unwrap_or_default()
+    );
+
+    let ctx = Arc::new(SyncContext::from_saved_login().await);
+    let (tx, writer) = spawn_sync_writer(run_id.to_string());
     let mut tasks = FuturesUnordered::new();
 
     for project_id in selected_ids {
-        println!("➡️  Syncing issues for project [{}]...", project_id);
+        let resume_from = match checkpoints.get(project_id) {
+            Some(checkpoint) if checkpoint.completed => {
+                tracing::info!("Project [{}] already synced in this run, skipping.", project_id);
+                continue;
+            }
+            checkpoint => checkpoint.cloned(),
+        };
+        tracing::info!("Syncing issues for project [{}]...", project_id);
+        let ctx = ctx.clone();
         let project_id = project_id.clone();
         let tx = tx.clone();
+        let updated_since = updated_since.clone();
         tasks.push(tokio::spawn(async move {
-            fetch_issues_for_project(&project_id, tx).await;
+            let result = fetch_issues_for_project(ctx, &project_id, updated_since.as_deref(), resume_from, tx).await;
+            (project_id, result)
         }));
     }
 
-    while let Some(_) = tasks.next().await {}
+    let mut finished = completed_projects(selected_ids, &checkpoints);
+    let mut unfinished = 0;
+    while let Some(result) = tasks.next().await {
+        match result {
+            Ok((project_id, Ok(()))) => finished.push(project_id),
+            Ok((project_id, Err(e))) => {
+                tracing::error!("Project [{}] sync stopped: {}", project_id, e);
+                unfinished += 1;
+            }
+            Err(e) => {
+                tracing::error!("Project sync stopped: {}", e);
+                unfinished += 1;
+            }
+        }
+    }
 
     drop(tx); // Close the global sender
     writer.await.unwrap();
 
-    println!("✅ All project issues synced.");
+    // Only a full search returns every issue, so only then does a missing one mean anything
+    if updated_since.is_none() {
+        finished.sort();
+        for project_id in &finished {
+            reconcile_project(&ctx, run_id, project_id).await;
+        }
+    }
+
+    let outcome = if unfinished == 0 {
+        finish_sync_run(run_id, RunOutcome::Completed, None);
+        tracing::info!("All project issues synced.");
+        Ok(())
+    } else {
+        let error = format!("{} project(s) did not finish", unfinished);
+        finish_sync_run(run_id, RunOutcome::Failed, Some(error.clone()));
+        tracing::warn!(
+            "{}; run `triage sync --resume` to continue from the last checkpoint.",
+            error
+        );
+        Err(error)
+    };
+
+    // Sprint membership is diffed from the changelogs stored above
+    sync_sprints_for_projects(selected_ids).await;
+    rebuild_issue_edges().await;
+
+    // Keep the people graph in step with the freshly stored issues
+    sync_people_from_issues().await;
+    refresh_expertise().await;
+    outcome
 }
 
-pub async fn fetch_issues_for_project(project_id: &str, tx: mpsc::Sender<IssueFieldMetadata>) {
-    let ctx = Arc::new(SyncContext {
-        token: authenticate().await,
-        domain: get_domain(),
-        client: Client::new(),
-    });
+/// Selected projects that an earlier attempt of the run already fetched to the last page.
+fn completed_projects(selected_ids: &[String], checkpoints: &HashMap<String, Checkpoint>) -> Vec<String> {
+    selected_ids
+        .iter()
+        .filter(|project_id| checkpoints.get(*project_id).is_some_and(|checkpoint| checkpoint.completed))
+        .cloned()
+        .collect()
+}
 
-    let mut start_at = 0;
+/// Finds stored issues Jira deleted or moved without fetching the rest again, for servers whose
+/// scheduled syncs are all incremental. Derived tables are refreshed when anything changed.
+pub async fn reconcile_projects(projects: &[String]) -> Result<(), String> {
+    let run_id = start_sync_run(SyncMode::Reconcile, projects, None);
+    let ctx = SyncContext::from_saved_login().await;
+    let mut changed = 0;
+    let mut failed = Vec::new();
+
+    for project_id in projects {
+        match record_project_issue_ids(&ctx, &run_id, project_id).await {
+            Ok(listed) => {
+                tracing::info!("Jira lists {} issue(s) for project [{}].", listed, project_id);
+                changed += reconcile_project(&ctx, &run_id, project_id).await;
+            }
+            Err(e) => {
+                tracing::error!("Could not list issues of project [{}]: {}", project_id, e);
+                failed.push(project_id.clone());
+            }
+        }
+    }
+
+    let outcome = if failed.is_empty() {
+        finish_sync_run(&run_id, RunOutcome::Completed, None);
+        Ok(())
+    } else {
+        let error = format!("could not list issues of {}", failed.join(", "));
+        finish_sync_run(&run_id, RunOutcome::Failed, Some(error.clone()));
+        Err(error)
+    };
+
+    if changed > 0 {
+        rebuild_issue_edges().await;
+        sync_people_from_issues().await;
+        refresh_expertise().await;
+    }
+    outcome
+}
+
+/// Pages through a project's issue ids and records them as seen by the run; returns how many.
+async fn record_project_issue_ids(ctx: &SyncContext, run_id: &str, project_id: &str) -> Result<usize, String> {
+    let jql = project_issues_jql(project_id, None);
+    let mut next_page_token: Option<String> = None;
+    let mut listed = 0;
 
     loop {
-        println!(
-            "📥 Fetching issues (start_at = {}) for project [{}]...",
-            start_at, project_id
+        let request = ctx
+            .client
+            .get(search_issue_ids_route(&ctx.domain, &jql, next_page_token.as_deref()))
+            .header("Authorization", format!("Basic {}", ctx.token))
+            .header("Accept", "application/json");
+        let res = send_with_retry(request, "jira").await.map_err(|e| e.to_string())?;
+        if !res.status().is_success() {
+            return Err(format!("search returned {}", res.status()));
+        }
+        let body: Value = res.json().await.map_err(|e| e.to_string())?;
+
+        let ids: Vec<String> = body["issues"]
+            .as_array()
+            .map(|issues| {
+                issues
+                    .iter()
+                    .filter_map(|issue| issue["id"].as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        record_seen_issues(run_id, &ids);
+        listed += ids.len();
+
+        next_page_token = body["nextPageToken"].as_str().map(String::from);
+        if ids.is_empty() || next_page_token.is_none() || body["isLast"].as_bool() == Some(true) {
+            return Ok(listed);
+        }
+    }
+}
+
+/// Checks each stored issue of a fully listed project that the search no longer returned.
+/// Jira still answers by id for an issue moved to another project, which is then stored under
+/// its new key; a 404 means it was deleted, so it is soft-deleted. Returns how many issues
+/// were re-stored or deleted.
+async fn reconcile_project(ctx: &SyncContext, run_id: &str, project_id: &str) -> usize {
+    let missing = unseen_issues(run_id, project_id);
+    if missing.is_empty() {
+        return 0;
+    }
+    tracing::info!(
+        "{} stored issue(s) of project [{}] were not returned by the sync, checking them...",
+        missing.len(),
+        project_id
+    );
+
+    let mut moved = Vec::new();
+    let mut deleted = 0;
+    for (id, key) in missing {
+        let issue = Issue {
+            id,
+            key,
+            fields: IssueFields::default(),
+        };
+        match fetch_metadata_with_retry(ctx, issue.clone()).await {
+            Ok(metadata) => {
+                if metadata.key != issue.key {
+                    tracing::info!("Issue {} moved to {}.", issue.key, metadata.key);
+                }
+                moved.push(metadata);
+            }
+            Err((StatusCode::NOT_FOUND, _)) => {
+                mark_issue_deleted(&issue.id).await;
+                deleted += 1;
+            }
+            // Leave it alone; the next full sync checks again
+            Err((_, e)) => tracing::warn!("Could not check missing issue [{}]: {}", issue.key, e),
+        }
+    }
+    if !moved.is_empty() {
+        save_issues_batch_to_duckdb(&moved).await;
+    }
+
+    tracing::info!(
+        "Reconciled project [{}]: {} issue(s) moved or re-stored, {} marked deleted.",
+        project_id,
+        moved.len(),
+        deleted
+    );
+    moved.len() + deleted
+}
+
+/// Re-fetches one issue through the same path as a full sync, e.g. when a webhook reports a change.
+pub async fn refresh_issue(issue: Issue) -> Result<IssueFieldMetadata, String> {
+    let ctx = SyncContext::from_saved_login().await;
+    fetch_metadata_with_retry(&ctx, issue).await.map_err(|(_, e)| e)
+}
+
+/// Pages through a project with the token-paginated search, continuing from `resume_from` when
+/// given, and sends a checkpoint after each page. Issues arrive with their expansions, so only
+/// those whose changelog or comments were truncated are fetched again one by one. Errors leave
+/// the project unfinished, resumable from its last checkpoint.
+pub async fn fetch_issues_for_project(
+    ctx: Arc<SyncContext>,
+    project_id: &str,
+    updated_since: Option<&str>,
+    resume_from: Option<Checkpoint>,
+    tx: mpsc::Sender<SyncMessage>,
+) -> Result<(), String> {
+    let jql = project_issues_jql(project_id, updated_since);
+    let total = approximate_issue_count(&ctx, &jql).await;
+
+    // A checkpoint without a token was taken before the first page finished
+    let (mut fetched, mut next_page_token) = match resume_from {
+        Some(Checkpoint {
+            next_start_at,
+            next_page_token: Some(token),
+            ..
+        }) => (next_start_at, Some(token)),
+        _ => (0, None),
+    };
+    let mut resuming = next_page_token.is_some();
+    let (mut succeeded, mut failed) = (0, 0);
+    publish(JIRA_SYNC, ProgressEvent::ProjectStarted { project: project_id.to_string() });
+
+    loop {
+        tracing::info!(
+            "Fetching issues (start_at = {}) for project [{}]...",
+            fetched, project_id
         );
 
-        let url = search_issues_for_project_all_types(&ctx.domain, project_id, start_at);
+        let url = search_issues_jql_route(&ctx.domain, &jql, next_page_token.as_deref());
 
-        let res = ctx
+        let request = ctx
             .client
             .get(&url)
             .header("Authorization", format!("Basic {}", ctx.token))
-            .header("Accept", "application/json")
-            .send()
+            .header("Accept", "application/json");
+        let res = send_with_retry(request, "jira")
             .await
-            .expect("❌ Failed to fetch issues");
+            .map_err(|e| format!("failed to fetch issues: {}", e))?;
 
+        if resuming && res.status() == StatusCode::BAD_REQUEST {
+            tracing::warn!(
+                "Saved page token for project [{}] was rejected, starting the project over.",
+                project_id
+            );
+            (fetched, next_page_token, resuming) = (0, None, false);
+            continue;
+        }
         if !res.status().is_success() {
-            panic!(
-                "❌ API error while fetching issues for project [{}]: {}",
-                project_id,
+            return Err(format!(
+                "search returned {}: {}",
+                res.status(),
                 res.text().await.unwrap_or_default()
-            );
+            ));
         }
+        resuming = false;
 
         let body: Value = res
             .json()
             .await
-            .expect("❌ Failed to parse issue search response");
+            .map_err(|e| format!("unreadable issue search response: {}", e))?;
 
-        let issues = body["issues"]
+        let page_size = body["issues"]
             .as_array()
-            .expect("❌ Missing 'issues' array in response");
+            .ok_or("issue search response has no 'issues' array")?
+            .len();
+        let token = body["nextPageToken"].as_str().map(String::from);
+        let completed = page_size == 0 || token.is_none() || body["isLast"].as_bool() == Some(true);
 
-        if issues.is_empty() {
-            println!("🚫 No more issues found for project [{}].", project_id);
-            break;
-        }
+        if page_size == 0 {
+            tracing::info!("No more issues found for project [{}].", project_id);
+        } else {
+            SYNC_ISSUES_FETCHED_TOTAL
+                .with_label_values(&[project_id])
+                .inc_by(page_size as u64);
 
-        let parsed: Vec<Issue> = issues
-            .iter()
-            .map(|json| serde_json::from_value(json.clone()).unwrap())
-            .collect();
+            let (ready, needs_detail) = split_search_page(&body);
+            tracing::info!(
+                "Retrieved {} issue(s) for project [{}], {} need a full fetch...",
+                page_size,
+                project_id,
+                needs_detail.len()
+            );
+            publish(
+                JIRA_SYNC,
+                ProgressEvent::PageFetched {
+                    project: project_id.to_string(),
+                    start_at: fetched,
+                    fetched: page_size,
+                    total,
+                },
+            );
 
-        println!(
-            "🔎 Retrieved {} issue(s) for project [{}], fetching metadata...",
-            parsed.len(),
-            project_id
-        );
+            for metadata in ready {
+                let _ = tx.send(SyncMessage::Issue(Box::new(metadata))).await;
+                succeeded += 1;
+            }
+            if !needs_detail.is_empty() {
+                let (detail_succeeded, detail_failed) =
+                    get_issue_metaobjects(ctx.clone(), project_id, needs_detail, tx.clone()).await;
+                succeeded += detail_succeeded;
+                failed += detail_failed;
+            }
+            fetched += page_size;
+        }
 
-        get_issue_metaobjects(ctx.clone(), parsed, tx.clone()).await;
+        let _ = tx
+            .send(SyncMessage::PageDone(Checkpoint {
+                project: project_id.to_string(),
+                next_start_at: fetched,
+                next_page_token: token.clone().filter(|_| !completed),
+                total: total.max(fetched as u64),
+                completed,
+            }))
+            .await;
 
-        start_at += PAGE_SIZE;
-        let total = body["total"].as_u64().unwrap_or(0);
-        if start_at as u64 >= total {
-            println!(
-                "🏁 Completed syncing all {} issues for project [{}]",
-                total, project_id
+        if completed {
+            tracing::info!(
+                "Completed syncing all {} issues for project [{}]",
+                fetched, project_id
             );
             break;
         }
+        next_page_token = token;
     }
+
+    publish(
+        JIRA_SYNC,
+        ProgressEvent::ProjectFinished {
+            project: project_id.to_string(),
+            succeeded,
+            failed,
+        },
+    );
+    Ok(())
 }
 
+/// Progress total for a project's search; 0 when Jira cannot say.
+async fn approximate_issue_count(ctx: &SyncContext, jql: &str) -> u64 {
+    let res = ctx
+        .client
+        .post(approximate_count_route(&ctx.domain))
+        .header("Authorization", format!("Basic {}", ctx.token))
+        .header("Accept", "application/json")
+        .json(&serde_json::json!({ "jql": jql }))
+        .send_traced("jira")
+        .await;
+    match res {
+        Ok(res) if res.status().is_success() => res
+            .json::<Value>()
+            .await
+            .ok()
+            .and_then(|body| body["count"].as_u64())
+            .unwrap_or(0),
+        Ok(res) => {
+            tracing::debug!("Approximate issue count unavailable: {}", res.status());
+            0
+        }
+        Err(_) => 0,
+    }
+}
+
+/// Turns a search page into stored metadata where the page has everything, and returns the
+/// issues that still need `GET /issue/{id}`.
+fn split_search_page(body: &Value) -> (Vec<IssueFieldMetadata>, Vec<Issue>) {
+    let mut ready = Vec::new();
+    let mut needs_detail = Vec::new();
+
+    for json in body["issues"].as_array().into_iter().flatten() {
+        let issue: Issue = serde_json::from_value(json.clone()).unwrap();
+        if has_truncated_history(json) {
+            needs_detail.push(issue);
+            continue;
+        }
+
+        // Searches return names and schema once per page rather than per issue
+        let mut issue_json = json.clone();
+        for key in ["names", "schema"] {
+            if issue_json.get(key).is_none() {
+                issue_json[key] = body[key].clone();
+            }
+        }
+        match parse_issue_metadata(&issue, &issue_json) {
+            Ok(metadata) => ready.push(metadata),
+            Err(e) => {
+                tracing::debug!("Search result for [{}] incomplete ({}), fetching it directly", issue.key, e);
+                needs_detail.push(issue);
+            }
+        }
+    }
+
+    (ready, needs_detail)
+}
+
+/// Searches cap the changelog and comments they embed; issues over the cap are fetched on their own.
+fn has_truncated_history(issue: &Value) -> bool {
+    let truncated = |list: &Value, items: &str| match (list[items].as_array(), list["total"].as_u64()) {
+        (Some(items), Some(total)) => (items.len() as u64) < total,
+        _ => false,
+    };
+    truncated(&issue["changelog"], "histories") || truncated(&issue["fields"]["comment"], "comments")
+}
+
+/// Fetches full metadata for a page of issues; returns how many succeeded and failed.
+/// Failures are recorded so `triage sync --retry-failed` can pick them up.
 async fn get_issue_metaobjects(
     ctx: Arc<SyncContext>,
+    project_id: &str,
     issues: Vec<Issue>,
-    tx: mpsc::Sender<IssueFieldMetadata>,
-) {
+    tx: mpsc::Sender<SyncMessage>,
+) -> (usize, usize) {
     let semaphore = Arc::new(Semaphore::new(std::cmp::min(100, num_cpus::get() * 10)));
     let mut tasks = FuturesUnordered::new();
     let mut success_count = 0;
@@ -179,77 +671,90 @@ async fn get_issue_metaobjects(
 
         tasks.push(tokio::spawn(async move {
             let _permit = permit;
+            let (id, key) = (issue.id.clone(), issue.key.clone());
             match fetch_metadata_with_retry(&ctx, issue).await {
                 Ok(metadata) => {
-                    let _ = tx.send(metadata).await;
+                    let _ = tx.send(SyncMessage::Issue(Box::new(metadata))).await;
                     Ok(())
                 }
-                Err(e) => Err(e),
+                Err((_, e)) => Err((id, key, e)),
             }
         }));
     }
 
-    drop(tx); // Close sender after all tasks dispatched
-
     while let Some(result) = tasks.next().await {
         match result {
             Ok(Ok(())) => success_count += 1,
-            Ok(Err(e)) => {
-                eprintln!("❌ Skipped issue due to error: {}", e);
+            Ok(Err((id, key, e))) => {
+                tracing::error!("Skipped issue due to error: {}", e);
+                publish(
+                    JIRA_SYNC,
+                    ProgressEvent::ItemFailed {
+                        item: key.clone(),
+                        error: e.clone(),
+                        retrying: false,
+                    },
+                );
+                let _ = tx
+                    .send(SyncMessage::Failed(FailedIssue {
+                        issue_id: id,
+                        issue_key: key,
+                        project: project_id.to_string(),
+                        error: e,
+                        attempts: 1,
+                    }))
+                    .await;
                 fail_count += 1;
             }
             Err(e) => {
-                eprintln!("❌ Tokio join error: {}", e);
+                tracing::error!("Tokio join error: {}", e);
                 fail_count += 1;
             }
         }
     }
 
-    println!(
-        "✅ Completed metadata sync for {} issues ({} success, {} failed).",
+    tracing::info!(
+        "Completed metadata sync for {} issues ({} success, {} failed).",
         success_count + fail_count,
         success_count,
         fail_count
     );
+    (success_count, fail_count)
 }
 
+/// Retries 429 and 503 answers; the shared rate limiter has already paused the host for as
+/// long as Jira asked, so the next attempt waits that out. Errors keep the final status.
 async fn fetch_metadata_with_retry(
     ctx: &SyncContext,
     issue: Issue,
-) -> Result<IssueFieldMetadata, String> {
-    const MAX_RETRIES: u32 = 5;
-    const BASE_DELAY_SECS: u64 = 2;
-
+) -> Result<IssueFieldMetadata, (StatusCode, String)> {
     let mut attempts = 0;
 
     loop {
         match fetch_metadata_with_headers(ctx, &issue).await {
             Ok(meta) => return Ok(meta),
-            Err((status, _body, headers))
-                if status == StatusCode::TOO_MANY_REQUESTS && attempts < MAX_RETRIES =>
-            {
+            Err((status, _body, _headers)) if is_throttled(status) && attempts < MAX_RETRIES => {
                 attempts += 1;
+                API_RETRIES_TOTAL.with_label_values(&["jira"]).inc();
 
-                let retry_after_secs = headers
-                    .get("Retry-After")
-                    .and_then(|val| val.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok());
-
-                let jitter = rand::random::<u64>() % 3; // add up to 2 seconds jitter
-                let backoff =
-                    retry_after_secs.unwrap_or(BASE_DELAY_SECS << (attempts - 1)) + jitter;
-
-                eprintln!(
-                    "⏳ Rate-limited on issue [{}], retrying in {}s (attempt {}/{})...",
+                let backoff = pause_remaining(&ctx.host).as_secs();
+                tracing::warn!(
+                    "Rate-limited on issue [{}], retrying in {}s (attempt {}/{})...",
                     issue.id, backoff, attempts, MAX_RETRIES
                 );
-
-                tokio::time::sleep(Duration::from_secs(backoff)).await;
+                publish(
+                    JIRA_SYNC,
+                    ProgressEvent::RateLimited {
+                        item: issue.key.clone(),
+                        backoff_secs: backoff,
+                        attempt: attempts,
+                    },
+                );
             }
             Err((status, body, _)) => {
-                return Err(format!(
-                    "❌ API error for issue [{}]: {} ({})",
-                    issue.id, status, body
+                return Err((
+                    status,
+                    format!("❌ API error for issue [{}]: {} ({})", issue.id, status, body),
                 ));
             }
         }
@@ -268,7 +773,7 @@ async fn fetch_metadata_with_headers(
         .get(&url)
         .header("Authorization", format!("Basic {}", ctx.token))
         .header("Accept", "application/json")
-        .send()
+        .send_traced("jira")
         .await
         .map_err(|e| {
             (
@@ -289,11 +794,14 @@ async fn fetch_metadata_with_headers(
     let body_json: Value = serde_json::from_str(&body)
         .map_err(|e| (status, format!("JSON parse error: {}", e), headers.clone()))?;
 
-    let fields = body_json["fields"].as_object().ok_or((
-        status,
-        format!("Missing 'fields' in response: {}", body),
-        headers.clone(),
-    ))?;
+    parse_issue_metadata(issue, &body_json).map_err(|e| (status, e, headers))
+}
+
+/// Builds stored metadata from an issue as returned by `GET /issue/{id}` or a search page.
+fn parse_issue_metadata(issue: &Issue, body_json: &Value) -> Result<IssueFieldMetadata, String> {
+    let fields = body_json["fields"]
+        .as_object()
+        .ok_or_else(|| format!("Missing 'fields' for issue [{}]", issue.key))?;
 
     // Initialize link detector
     let link_detector = LinkDetector::new();
@@ -355,6 +863,38 @@ async fn fetch_metadata_with_headers(
         .and_then(|n| n.as_str())
         .map(|s| s.to_string());
 
+    let resolution = fields.get("resolution")
+        .and_then(|r| r.get("name"))
+        .and_then(|n| n.as_str())
+        .map(|s| s.to_string());
+
+    let resolution_date = fields.get("resolutiondate")
+        .and_then(|d| d.as_str())
+        .map(|s| s.to_string());
+
+    let due_date = fields.get("duedate")
+        .and_then(|d| d.as_str())
+        .map(|s| s.to_string());
+
+    let environment = fields.get("environment")
+        .filter(|e| !e.is_null())
+        .map(|e| link_detector.extract_text_from_field(e))
+        .filter(|s| !s.trim().is_empty());
+
+    // Team-managed projects put the epic in `parent`; company-managed ones use "Epic Link"
+    let names = &body_json["names"];
+    let parent = fields.get("parent");
+    let parent_key = parent
+        .and_then(|p| p.get("key"))
+        .and_then(|k| k.as_str())
+        .map(|s| s.to_string());
+    let parent_is_epic = parent
+        .and_then(|p| p.pointer("/fields/issuetype/name"))
+        .and_then(|n| n.as_str())
+        .is_some_and(|name| name.eq_ignore_ascii_case("epic"));
+    let epic_key = extract_epic_link(fields, names)
+        .or_else(|| parent_key.clone().filter(|_| parent_is_epic));
+
     // Extract links from issue content
     let issue_key = body_json["key"].as_str().unwrap_or("UNKNOWN");
     let extracted_links = link_detector.extract_links_from_issue(&body_json["fields"], issue_key);
@@ -363,14 +903,11 @@ async fn fetch_metadata_with_headers(
         id: body_json["id"].as_str().unwrap_or("").trim_matches('"').to_string(),
         key: body_json["key"].as_str().unwrap_or("").trim_matches('"').to_string(),
         self_link: body_json["self"].as_str().unwrap_or("").trim_matches('"').to_string(),
-        summary: issue.fields.summary.clone(),
-        status: issue
-            .fields
-            .status
-            .as_ref()
-            .map(|s| s.name.as_str())
-            .unwrap_or("")
-            .to_string(),
+        // The fetched issue is the current one; the caller's copy (e.g. a webhook payload) only fills gaps
+        summary: field_string(fields, &["summary"]).or_else(|| issue.fields.summary.clone()),
+        status: field_string(fields, &["status", "name"])
+            .or_else(|| issue.fields.status.as_ref().map(|s| s.name.clone()))
+            .unwrap_or_default(),
         issue_type,
         issue_type_id,
         is_subtask,
@@ -380,29 +917,154 @@ async fn fetch_metadata_with_headers(
         assignee,
         reporter,
         labels,
-        created: issue.fields.created.clone(),
-        updated: issue.fields.updated.clone(),
+        created: field_string(fields, &["created"]).or_else(|| issue.fields.created.clone()),
+        updated: field_string(fields, &["updated"]).or_else(|| issue.fields.updated.clone()),
         project_name,
         project_key,
         extracted_links: if extracted_links.is_empty() { None } else { Some(extracted_links) },
-        rendered_fields: Some(extract_json_field_as_string(&body_json, "renderedFields")),
-        names: Some(extract_json_field_as_string(&body_json, "names")),
-        schema: Some(extract_json_field_as_string(&body_json, "schema")),
-        transitions: Some(extract_json_field_as_string(&body_json, "transitions")),
-        edit_meta: Some(extract_json_field_as_string(&body_json, "editMeta")),
-        changelog: Some(extract_json_field_as_string(&body_json, "changelog")),
+        rendered_fields: Some(extract_json_field_as_string(body_json, "renderedFields")),
+        names: Some(extract_json_field_as_string(body_json, "names")),
+        schema: Some(extract_json_field_as_string(body_json, "schema")),
+        transitions: Some(extract_json_field_as_string(body_json, "transitions")),
+        edit_meta: Some(extract_json_field_as_string(body_json, "editMeta")),
+        changelog: Some(extract_json_field_as_string(body_json, "changelog")),
         versioned_representations: Some(extract_json_field_as_string(
-            &body_json,
+            body_json,
             "versionedRepresentations",
         )),
         watcher: get_optional_field(fields, "watcher"),
         attachment: get_optional_field(fields, "attachment"),
-        sub_tasks: get_optional_field(fields, "sub-tasks"),
+        sub_tasks: get_optional_field(fields, "subtasks"),
         description: get_optional_field(fields, "description"),
         project: get_optional_field(fields, "project"),
         comment: get_optional_field(fields, "comment"),
         issue_links: get_optional_field(fields, "issuelinks"),
         work_log: get_optional_field(fields, "worklog"),
         time_tracking: get_optional_field(fields, "timetracking"),
+        resolution,
+        resolution_date,
+        components: named_values(fields, "components"),
+        fix_versions: named_values(fields, "fixVersions"),
+        affected_versions: named_values(fields, "versions"),
+        parent_key,
+        epic_key,
+        due_date,
+        story_points: extract_story_points(fields, names),
+        environment,
+        custom_fields: extract_custom_fields(fields, names),
     })
 }
+
+/// String at a nested path under `fields`, e.g. `["status", "name"]`.
+fn field_string(fields: &serde_json::Map<String, Value>, path: &[&str]) -> Option<String> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(fields.get(*first)?, |value, key| value.get(*key))?
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+/// Names from an array field of objects such as components or versions.
+fn named_values(fields: &serde_json::Map<String, Value>, field: &str) -> Option<Vec<String>> {
+    fields.get(field)
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter()
+            .filter_map(|v| v.get("name").and_then(|n| n.as_str()))
+            .map(|s| s.to_string())
+            .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IssueStatus;
+    use serde_json::json;
+
+    fn search_issue(key: &str, histories: usize, history_total: usize) -> Value {
+        json!({
+            "id": format!("1{}", key.len()),
+            "key": key,
+            "self": format!("https://example.atlassian.net/rest/api/3/issue/{}", key),
+            "fields": {
+                "summary": format!("Summary of {}", key),
+                "status": { "name": "Open" },
+                "project": { "key": "ESCL", "name": "Escalations" },
+                "customfield_10016": 5.0,
+                "comment": { "comments": [], "total": 0 }
+            },
+            "changelog": { "histories": vec![json!({}); histories], "total": history_total }
+        })
+    }
+
+    #[test]
+    fn test_search_page_issues_need_no_follow_up_unless_truncated() {
+        let body = json!({
+            "issues": [search_issue("ESCL-1", 2, 2), search_issue("ESCL-22", 100, 140)],
+            "names": { "customfield_10016": "Story Points" },
+            "nextPageToken": "abc"
+        });
+
+        let (ready, needs_detail) = split_search_page(&body);
+
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].key, "ESCL-1");
+        assert_eq!(ready[0].status, "Open");
+        assert_eq!(ready[0].project_key.as_deref(), Some("ESCL"));
+        // Page-level names let custom fields resolve as they do on a single-issue fetch
+        assert_eq!(ready[0].story_points, Some(5.0));
+        assert_eq!(needs_detail.iter().map(|i| i.key.as_str()).collect::<Vec<_>>(), vec!["ESCL-22"]);
+    }
+
+    #[test]
+    fn test_truncated_comments_also_need_a_full_fetch() {
+        let mut issue = search_issue("ESCL-3", 0, 0);
+        issue["fields"]["comment"] = json!({ "comments": [{}, {}], "total": 75 });
+        assert!(has_truncated_history(&issue));
+
+        // Issues without embedded lists are not flagged
+        assert!(!has_truncated_history(&json!({ "fields": {} })));
+    }
+
+    #[test]
+    fn test_fetched_fields_win_over_the_callers_copy() {
+        // A webhook payload from before the issue was resolved, missing its timestamps
+        let stale = Issue {
+            id: "12".to_string(),
+            key: "ESCL-1".to_string(),
+            fields: IssueFields {
+                summary: Some("Old summary".to_string()),
+                status: Some(IssueStatus { name: "Open".to_string() }),
+                created: Some("2025-01-01T00:00:00.000+0000".to_string()),
+                ..IssueFields::default()
+            },
+        };
+        let mut fetched = search_issue("ESCL-1", 0, 0);
+        fetched["fields"]["status"] = json!({ "name": "Resolved" });
+
+        let metadata = parse_issue_metadata(&stale, &fetched).unwrap();
+
+        assert_eq!(metadata.summary.as_deref(), Some("Summary of ESCL-1"));
+        assert_eq!(metadata.status, "Resolved");
+        // Fields only the caller has still fill the gaps
+        assert_eq!(metadata.created.as_deref(), Some("2025-01-01T00:00:00.000+0000"));
+    }
+
+    #[test]
+    fn test_resumed_runs_reconcile_projects_completed_earlier() {
+        let checkpoint = |project: &str, completed: bool| Checkpoint {
+            project: project.to_string(),
+            next_start_at: 0,
+            next_page_token: None,
+            total: 0,
+            completed,
+        };
+        let checkpoints = HashMap::from([
+            ("ESCL".to_string(), checkpoint("ESCL", true)),
+            ("PAY".to_string(), checkpoint("PAY", false)),
+            ("OLD".to_string(), checkpoint("OLD", true)),
+        ]);
+        let selected = vec!["ESCL".to_string(), "PAY".to_string(), "OPS".to_string()];
+
+        assert_eq!(completed_projects(&selected, &checkpoints), vec!["ESCL".to_string()]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::db_utils::with_connection;
+use crate::expertise_engine::team_authority_by_topic;
 use crate::utils::{log_step, log_success};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -304,7 +305,7 @@ async fn extract_knowledge_concepts() -> Vec<KnowledgeConcept> {
             id, key, summary, description, comment, 
             JSON_EXTRACT(project, '$.name') as project_name,
             status
-        FROM issues 
+        FROM live_issues 
         WHERE (description IS NOT NULL AND description != '{}' AND description != '') 
            OR (comment IS NOT NULL AND comment != '{}' AND comment != '')
         ORDER BY created DESC
@@ -378,7 +379,7 @@ async fn extract_knowledge_concepts() -> Vec<KnowledgeConcept> {
 }
 
 // Helper functions for technology identification
-fn extract_technologies_from_text(text: &str) -> Vec<String> {
+pub fn extract_technologies_from_text(text: &str) -> Vec<String> {
     let mut technologies = Vec::new();
     
     // Programming languages
@@ -434,7 +435,15 @@ fn categorize_technology(tech: &str) -> TechCategory {
     }
 }
 
-fn calculate_team_expertise(tech: &str, frequency: i32) -> f64 {
+/// Team expertise on a 1-10 scale. When people have scored evidence for the
+/// technology, this is the chance that at least one of them is an authority on it;
+/// otherwise it falls back to how often the technology comes up.
+fn calculate_team_expertise(tech: &str, frequency: i32, authority_scores: Option<&Vec<f64>>) -> f64 {
+    if let Some(scores) = authority_scores.filter(|scores| !scores.is_empty()) {
+        let nobody_knows: f64 = scores.iter().map(|score| 1.0 - score).product();
+        return 1.0 + 9.0 * (1.0 - nobody_knows);
+    }
+
     let base_expertise = (frequency as f64 / 10.0).min(8.0);
     let complexity_modifier = match tech.to_lowercase().as_str() {
         "kubernetes" | "aws" | "microservices" => -1.0,
@@ -673,7 +682,7 @@ fn find_related_technologies(tech: &str, all_techs: &HashMap<String, i32>) -> Ve
     related
 }
 
-fn extract_concepts_from_text(text: &str) -> Vec<String> {
+pub fn extract_concepts_from_text(text: &str) -> Vec<String> {
     let mut concepts = Vec::new();
     
     // Technology patterns
@@ -838,7 +847,7 @@ async fn identify_technologies() -> Vec<TechnologyKnowledge> {
         SELECT 
             key, summary, description, comment, status,
             JSON_EXTRACT(project, '$.name') as project_name
-        FROM issues 
+        FROM live_issues 
         WHERE (description IS NOT NULL AND description != '{}' AND description != '') 
            OR (comment IS NOT NULL AND comment != '{}' AND comment != '')
         ORDER BY created DESC
@@ -877,10 +886,12 @@ async fn identify_technologies() -> Vec<TechnologyKnowledge> {
     });
 
     // Convert to structured technology knowledge
+    let team_authority = team_authority_by_topic();
     for (tech_name, frequency) in &tech_frequency {
         if *frequency >= 3 {
             let category = categorize_technology(&tech_name);
-            let expertise_level = calculate_team_expertise(&tech_name, *frequency);
+            let expertise_level =
+                calculate_team_expertise(tech_name, *frequency, team_authority.get(tech_name));
             let empty_vec = Vec::new();
             let issues = tech_issues.get(tech_name.as_str()).unwrap_or(&empty_vec);
             let contexts = tech_contexts.get(tech_name.as_str()).unwrap_or(&empty_vec);
@@ -937,7 +948,7 @@ async fn discover_patterns() -> Vec<KnowledgePattern> {
         let query = r#"
         SELECT key, summary, description, comment, status,
                resolution, JSON_EXTRACT(project, '$.name') as project_name
-        FROM issues 
+        FROM live_issues 
         WHERE status LIKE '%resolved%' OR status LIKE '%closed%' OR status LIKE '%done%'
         ORDER BY created DESC
         LIMIT 200
@@ -1020,7 +1031,7 @@ async fn generate_learning_materials() -> Vec<LearningMaterial> {
         SELECT key, summary, description, comment, status, resolution,
                JSON_EXTRACT(project, '$.name') as project_name,
                labels
-        FROM issues 
+        FROM live_issues 
         WHERE (description IS NOT NULL AND length(description) > 100)
            OR (comment IS NOT NULL AND length(comment) > 100)
            OR (resolution IS NOT NULL AND length(resolution) > 50)
@@ -0,0 +1,62 @@
+//! Library target shared by the `triage` binary and the benches.
+
+pub mod access_control;
+pub mod account_routes;
+pub mod accounts;
+pub mod advanced_analytics;
+pub mod agile;
+pub mod analytics;
+pub mod attachments;
+pub mod auth;
+pub mod config;
+pub mod constants;
+pub mod content_extractor;
+pub mod content_storage;
+pub mod custom_fields;
+pub mod db;
+pub mod db_utils;
+pub mod dedupe;
+pub mod dependency_risk;
+pub mod events;
+pub mod expertise_engine;
+pub mod google_auth;
+pub mod google_client;
+pub mod graph;
+pub mod issue_graph;
+pub mod jira;
+pub mod knowledge_engine;
+pub mod link_detector;
+pub mod openapi;
+pub mod pagination;
+pub mod queries;
+pub mod rate_limit;
+pub mod routes;
+pub mod scheduler;
+pub mod semantic_search;
+pub mod server;
+pub mod silo_report;
+pub mod slack_auth;
+pub mod slack_client;
+pub mod smart_graph;
+pub mod sync_runs;
+pub mod sync_status;
+pub mod telemetry;
+pub mod triage_suggest;
+pub mod types;
+pub mod unified_search;
+pub mod user_notes;
+pub mod utils;
+pub mod webhook;
+
+// Enhanced people and relationship tracking
+pub mod enhanced_google_extractor;
+pub mod enhanced_jira_extractor;
+pub mod enhanced_slack_extractor;
+pub mod people_graph;
+pub mod people_integration;
+pub mod people_network;
+pub mod people_routes;
+pub mod people_sync;
+
+// Tests
+mod routes_test;
@@ -70,7 +70,7 @@ impl LinkDetector {
         links
     }
 
-    fn extract_text_from_field(&self, field: &Value) -> String {
+    pub fn extract_text_from_field(&self, field: &Value) -> String {
         match field {
             Value::String(s) => s.clone(),
             Value::Object(obj) => {
@@ -151,7 +151,7 @@ impl LinkDetector {
         }
     }
 
-    fn classify_url(&self, url: &str) -> PlatformType {
+    pub fn classify_url(&self, url: &str) -> PlatformType {
         // Google Docs
         if let Some(captures) = self.google_docs_regex.captures(url) {
             return PlatformType::GoogleDocs {
@@ -1,50 +1,19 @@
-use crate::auth::authenticate;
-use crate::db::{
-    create_issues_table, create_project_table, get_stored_project_ids, save_projects_to_duckdb,
-};
-use crate::jira::{fetch_projects, sync_issues_for_projects};
-use crate::types::Project;
 use clap::{Parser, Subcommand};
 use colored::*;
-use inquire::MultiSelect;
-
-mod advanced_analytics;
-mod analytics;
-mod auth;
-mod constants;
-mod content_extractor;
-mod content_storage;
-mod db;
-mod db_utils;
-mod google_auth;
-mod google_client;
-mod graph;
-mod jira;
-mod knowledge_engine;
-mod link_detector;
-mod queries;
-mod routes;
-mod semantic_search;
-mod server;
-mod slack_auth;
-mod slack_client;
-mod smart_graph;
-mod sync_status;
-mod types;
-mod unified_search;
-mod user_notes;
-mod utils;
-
-// Enhanced people and relationship tracking
-mod enhanced_google_extractor;
-mod enhanced_jira_extractor;
-mod enhanced_slack_extractor;
-mod people_graph;
-mod people_integration;
-mod people_routes;
-
-// Tests
-mod routes_test;
+use inquire::{MultiSelect, Password};
+use std::path::PathBuf;
+use triage::auth::authenticate;
+use triage::db::{
+    create_issues_table, create_project_table, get_stored_project_ids, save_projects_to_duckdb,
+};
+use triage::jira::{
+    fetch_projects, resume_sync, retry_failed_issues, sync_issues_for_projects, sync_issues_updated_since,
+};
+use triage::types::Project;
+use triage::{
+    accounts, agile, attachments, config, content_storage, dedupe, expertise_engine, issue_graph, people_graph,
+    scheduler, server, silo_report, sync_runs, telemetry, triage_suggest, user_notes,
+};
 
 #[derive(Parser)]
 #[command(name = "triage")]
@@ -66,19 +35,190 @@ enum Commands {
         force: bool,
     },
 
-    /// Start the web server for graph visualization
-    Serve,
+    /// Sync issues for projects that were synced before, checkpointing after every page
+    Sync {
+        /// Project key or id to sync; repeat for several (defaults to every synced project)
+        #[arg(long)]
+        project: Vec<String>,
+
+        /// Continue the last interrupted sync from its checkpoints
+        #[arg(long, conflicts_with_all = ["project", "retry_failed"])]
+        resume: bool,
+
+        /// Re-fetch only issues that failed in earlier syncs
+        #[arg(long, conflicts_with = "project")]
+        retry_failed: bool,
+    },
+
+    /// Start the web server and the scheduled background jobs
+    Serve {
+        /// JSON config file (defaults to $TRIAGE_CONFIG, then ~/.triage/server.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// IP address to listen on, e.g. 0.0.0.0 inside a container
+        #[arg(long)]
+        bind: Option<String>,
+
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Origin allowed to call the API with a session; repeat for several
+        #[arg(long = "cors-origin")]
+        cors_origins: Vec<String>,
+
+        /// DuckDB file to use instead of ~/.triage/triage.duckdb
+        #[arg(long)]
+        db_path: Option<PathBuf>,
+
+        /// Worker count for content extraction
+        #[arg(long)]
+        extraction_workers: Option<usize>,
+
+        /// error, warn, info, debug or trace
+        #[arg(long)]
+        log_level: Option<String>,
+
+        /// text or json
+        #[arg(long)]
+        log_format: Option<String>,
+    },
+
+    /// Download and index text, log, CSV, JSON and PDF attachments of synced issues
+    Attachments {
+        /// Only fetch attachments for this project key
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Skip attachments larger than this many megabytes
+        #[arg(long, default_value_t = 10)]
+        max_size_mb: u64,
+    },
+
+    /// Find likely duplicate issues and store them as DuplicateOf relationships
+    Dedupe {
+        /// Only compare issues within this project key
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Minimum estimated similarity (0-1) for two issues to count as duplicates
+        #[arg(long, default_value_t = dedupe::DEFAULT_DUPLICATE_THRESHOLD)]
+        threshold: f64,
+
+        /// Record that two issues are not duplicates instead of running the report
+        #[arg(long, num_args = 2, value_names = ["KEY", "OTHER"])]
+        not_duplicate: Option<Vec<String>>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Suggest a component, assignees and resolution time for an issue
+    Suggest {
+        /// Issue key, e.g. ESCL-123
+        key: String,
+
+        /// Print the suggestion as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Generate reports from synced data
+    Report {
+        #[command(subcommand)]
+        report: ReportCommands,
+    },
+
+    /// Manage accounts that can sign in to the web server
+    Users {
+        #[command(subcommand)]
+        users: UserCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum UserCommands {
+    /// Create an account; the password is prompted for
+    Add {
+        username: String,
+
+        #[arg(long)]
+        display_name: Option<String>,
+
+        #[arg(long)]
+        email: Option<String>,
+
+        /// Allow this account to create other accounts
+        #[arg(long)]
+        admin: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Components, labels and projects whose recent work depends on too few people
+    Silos {
+        /// Size of the sliding window in days
+        #[arg(long, default_value_t = 90)]
+        days: i64,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Include groups that are not flagged as silos
+        #[arg(long)]
+        all: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+
+    // Settings are resolved before any table is touched since they pick the database file
+    let (config_path, flags) = match &cli.command {
+        Some(Commands::Serve { config, bind, port, cors_origins, db_path, extraction_workers, log_level, log_format }) => (
+            config.clone(),
+            config::ServerConfigLayer {
+                bind_address: bind.clone(),
+                port: *port,
+                cors_origins: Some(cors_origins.clone()).filter(|origins| !origins.is_empty()),
+                database_path: db_path.clone(),
+                extraction_workers: *extraction_workers,
+                log_level: log_level.clone(),
+                log_format: log_format.clone(),
+                ..Default::default()
+            },
+        ),
+        _ => (None, config::ServerConfigLayer::default()),
+    };
+    let server_config = match config::load_server_config(config_path, flags) {
+        Ok(server_config) => server_config,
+        Err(errors) => {
+            eprintln!("{}", "❌ Invalid configuration:".red());
+            for error in errors {
+                eprintln!("   - {}", error);
+            }
+            std::process::exit(1);
+        }
+    };
+    config::install_server_config(server_config.clone());
+    telemetry::init_tracing(&server_config);
+
     create_project_table().await;
     create_issues_table().await;
+    agile::create_agile_tables().await;
+    issue_graph::create_issue_edges_table().await;
     user_notes::initialize_notes_tables().await;
     content_storage::create_content_storage_tables().await;
     people_graph::initialize_people_tables().await;
-
-    let cli = Cli::parse();
+    expertise_engine::initialize_expertise_tables().await;
+    dedupe::initialize_dedupe_tables().await;
+    scheduler::initialize_scheduler_tables().await;
+    accounts::initialize_account_tables().await;
+    sync_runs::initialize_sync_run_tables().await;
 
     match cli.command {
         Some(Commands::Login) => {
@@ -127,13 +267,95 @@ async fn main() {
                 })
                 .collect();
 
-            sync_issues_for_projects(&selected_ids).await;
+            if let Err(e) = sync_issues_for_projects(&selected_ids).await {
+                eprintln!("{}", format!("❌ {}; run `triage sync --resume` to continue.", e).red());
+                std::process::exit(1);
+            }
         }
-        Some(Commands::Serve) => {
-            if let Err(e) = server::start_server().await {
-                eprintln!("❌ Server error: {}", e);
+        Some(Commands::Sync { project, resume, retry_failed }) => {
+            if resume {
+                if let Err(e) = resume_sync().await {
+                    eprintln!("{}", format!("❌ {}", e).red());
+                    std::process::exit(1);
+                }
+            } else if retry_failed {
+                let (recovered, still_failing) = retry_failed_issues().await;
+                println!("{}", format!("✔ Recovered {} issue(s).", recovered).green());
+                if still_failing > 0 {
+                    println!("{}", format!("⚠ {} issue(s) still failing.", still_failing).yellow());
+                }
+            } else {
+                let projects = if project.is_empty() { scheduler::synced_project_keys() } else { project };
+                if projects.is_empty() {
+                    eprintln!("{}", "❌ No synced projects; run `triage projects` first.".red());
+                    std::process::exit(1);
+                }
+                if let Err(e) = sync_issues_updated_since(&projects, None).await {
+                    eprintln!("{}", format!("❌ {}; run `triage sync --resume` to continue.", e).red());
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Serve { .. }) => {
+            match scheduler::load_schedule_config() {
+                Ok(config) => {
+                    tokio::spawn(scheduler::run_scheduler(config));
+                }
+                Err(e) => tracing::error!("Scheduler disabled, invalid schedule: {}", e),
+            }
+            if let Err(e) = server::start_server(&server_config).await {
+                tracing::error!("Server error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Attachments { project, max_size_mb }) => {
+            attachments::fetch_issue_attachments(project.as_deref(), max_size_mb * 1024 * 1024).await;
+        }
+        Some(Commands::Dedupe { project, threshold, not_duplicate, json }) => {
+            if let Some(pair) = not_duplicate {
+                dedupe::record_not_duplicate(&pair[0], &pair[1]);
+                println!("{}", format!("✔ {} and {} won't be suggested as duplicates again.", pair[0], pair[1]).green());
+            } else {
+                let report = dedupe::run_dedupe(project.as_deref(), threshold);
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+                } else {
+                    dedupe::print_dedupe_report(&report);
+                }
             }
         }
+        Some(Commands::Suggest { key, json }) => match triage_suggest::suggest_triage(Some(&key), None) {
+            Some(suggestion) if json => println!("{}", serde_json::to_string_pretty(&suggestion).unwrap()),
+            Some(suggestion) => triage_suggest::print_triage_suggestion(&suggestion),
+            None => eprintln!("{}", format!("❌ {} is not a synced issue.", key).red()),
+        },
+        Some(Commands::Report { report }) => match report {
+            ReportCommands::Silos { days, json, all } => {
+                let report = match silo_report::build_silo_report(days) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        eprintln!("{}", format!("❌ {}", e).red());
+                        return;
+                    }
+                };
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+                } else {
+                    silo_report::print_silo_report(&report, all);
+                }
+            }
+        },
+        Some(Commands::Users { users }) => match users {
+            UserCommands::Add { username, display_name, email, admin } => {
+                let Ok(password) = Password::new("Password:").prompt() else {
+                    return;
+                };
+                match accounts::create_user(&username, &password, display_name.as_deref(), email.as_deref(), admin) {
+                    Ok(user) => println!("{}", format!("✔ Created {} ({}).", user.username, user.id).green()),
+                    Err(e) => eprintln!("{}", format!("❌ {}", e).red()),
+                }
+            }
+        },
         None => {
             println!(
                 "{}",
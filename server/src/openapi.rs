@@ -0,0 +1,423 @@
+use crate::account_routes::{CreateApiKeyRequest, LoginRequest, RegisterRequest};
+use crate::accounts::{ApiKeySummary, AuthUser, SESSION_COOKIE};
+use crate::pagination::PageQuery;
+use crate::people_routes::{ContentAnalysisRequest, ExpertsQuery};
+use crate::server::{
+    ContentExtractionQuery, DependencyRiskQuery, EventsQuery, GoogleAuthQuery, GraphQuery, JobRunsQuery, NotesQuery,
+    SearchQuery, SiloReportQuery, SimilarIssuesQuery, SlackAuthQuery, TimeSeriesQuery, TriageSuggestQuery,
+};
+use crate::triage_suggest::TicketText;
+use crate::unified_search::{UnifiedSearchRequest, UnifiedSearchResult};
+use crate::user_notes::{CreateNoteRequest, CreateViewRequest, NotesResponse, SavedView, UserNote, ViewsResponse};
+use once_cell::sync::Lazy;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+
+// ================================
+// OPENAPI DOCUMENT
+// ================================
+
+/// Built once from the route table below and the request/response types' schemas.
+pub static OPENAPI_DOCUMENT: Lazy<Value> = Lazy::new(build_openapi_document);
+
+/// Swagger UI pointed at `/api/openapi.json`; the assets come from the public CDN.
+pub const DOCS_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Triage API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui", withCredentials: true });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+struct ApiSpec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+/// One operation being described; `add` files it under its path.
+struct Operation<'a> {
+    spec: &'a mut ApiSpec,
+    method: &'static str,
+    path: &'static str,
+    operation: Map<String, Value>,
+    parameters: Vec<Value>,
+}
+
+impl ApiSpec {
+    fn new() -> Self {
+        Self {
+            generator: SchemaSettings::openapi3().into_generator(),
+            paths: Map::new(),
+        }
+    }
+
+    fn get(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        self.operation("get", path, tag, summary)
+    }
+
+    fn post(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        self.operation("post", path, tag, summary)
+    }
+
+    fn put(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        self.operation("put", path, tag, summary)
+    }
+
+    fn delete(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        self.operation("delete", path, tag, summary)
+    }
+
+    fn operation(&mut self, method: &'static str, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        let mut operation = Map::new();
+        operation.insert("tags".to_string(), json!([tag]));
+        operation.insert("summary".to_string(), json!(summary));
+        operation.insert("responses".to_string(), json!({ "200": json_response(json!({ "type": "object" })) }));
+
+        // Axum's `:name` segments become string path parameters
+        let parameters = path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+            .collect();
+
+        Operation {
+            spec: self,
+            method,
+            path,
+            operation,
+            parameters,
+        }
+    }
+
+    fn schema_for<T: JsonSchema>(&mut self) -> Value {
+        serde_json::to_value(self.generator.subschema_for::<T>()).unwrap_or_else(|_| json!({}))
+    }
+
+    fn into_document(self) -> Value {
+        let schemas: Map<String, Value> = self
+            .generator
+            .definitions()
+            .iter()
+            .map(|(name, schema)| (name.clone(), serde_json::to_value(schema).unwrap_or_else(|_| json!({}))))
+            .collect();
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "Triage API",
+                "version": env!("CARGO_PKG_VERSION"),
+                "description": "Jira, Google and Slack knowledge search, analytics and people insights.",
+            },
+            "paths": self.paths,
+            "components": {
+                "schemas": schemas,
+                "securitySchemes": {
+                    "session": { "type": "apiKey", "in": "cookie", "name": SESSION_COOKIE },
+                    "apiKey": { "type": "http", "scheme": "bearer", "description": "An API key starting with trg_" },
+                },
+            },
+            "security": [{ "session": [] }, { "apiKey": [] }],
+        })
+    }
+}
+
+impl Operation<'_> {
+    /// Every field of `T` becomes a query parameter.
+    fn query<T: JsonSchema>(mut self) -> Self {
+        let schema = T::json_schema(&mut self.spec.generator).into_object();
+        if let Some(object) = schema.object {
+            for (name, property) in object.properties {
+                self.parameters.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": object.required.contains(&name),
+                    "schema": serde_json::to_value(property).unwrap_or_else(|_| json!({})),
+                }));
+            }
+        }
+        self
+    }
+
+    /// `cursor`, `limit`, `sort` and `fields`; list responses carry `next_cursor`.
+    fn paged(self) -> Self {
+        self.query::<PageQuery>()
+    }
+
+    /// For handlers that read loose `HashMap` query strings.
+    fn query_param(mut self, name: &str, description: &str) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": "query",
+            "required": false,
+            "description": description,
+            "schema": { "type": "string" },
+        }));
+        self
+    }
+
+    fn integer_path(mut self, name: &str) -> Self {
+        for parameter in &mut self.parameters {
+            if parameter["in"] == "path" && parameter["name"] == name {
+                parameter["schema"] = json!({ "type": "integer", "format": "int64" });
+            }
+        }
+        self
+    }
+
+    fn body<T: JsonSchema>(mut self) -> Self {
+        let schema = self.spec.schema_for::<T>();
+        self.operation.insert(
+            "requestBody".to_string(),
+            json!({ "required": true, "content": { "application/json": { "schema": schema } } }),
+        );
+        self
+    }
+
+    fn returns<T: JsonSchema>(mut self) -> Self {
+        let schema = self.spec.schema_for::<T>();
+        self.operation["responses"]["200"] = json_response(schema);
+        self
+    }
+
+    /// For handlers that wrap their payload, e.g. `{ "user": ... }`.
+    fn returns_field<T: JsonSchema>(mut self, field: &str) -> Self {
+        let schema = self.spec.schema_for::<T>();
+        self.operation["responses"]["200"] = json_response(json!({
+            "type": "object",
+            "properties": { field: schema },
+            "required": [field],
+        }));
+        self
+    }
+
+    fn returns_text(mut self, content_type: &str) -> Self {
+        self.operation["responses"]["200"] = json!({
+            "description": "OK",
+            "content": { content_type: { "schema": { "type": "string" } } },
+        });
+        self
+    }
+
+    fn status(mut self, code: &str, description: &str) -> Self {
+        self.operation["responses"][code] = json!({ "description": description });
+        self
+    }
+
+    /// Reachable without signing in.
+    fn public(mut self) -> Self {
+        self.operation.insert("security".to_string(), json!([]));
+        self
+    }
+
+    fn add(mut self) {
+        if !self.parameters.is_empty() {
+            self.operation.insert("parameters".to_string(), Value::Array(self.parameters));
+        }
+        let path_item = self
+            .spec
+            .paths
+            .entry(openapi_path(self.path))
+            .or_insert_with(|| json!({}));
+        path_item[self.method] = Value::Object(self.operation);
+    }
+}
+
+fn json_response(schema: Value) -> Value {
+    json!({ "description": "OK", "content": { "application/json": { "schema": schema } } })
+}
+
+/// `/api/views/:id/use` -> `/api/views/{id}/use`
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Mirrors the routes in `server::create_router`, `people_routes` and `account_routes`;
+/// the tests below fail when one of those routes is missing here.
+fn build_openapi_document() -> Value {
+    let mut spec = ApiSpec::new();
+
+    // Graph and issue relationships
+    spec.get("/api/graph", "graph", "Knowledge graph of issues, projects and people; `metadata.truncated_at` is set when only the most recently updated issues are drawn").query::<GraphQuery>().paged().add();
+    spec.get("/api/graph/analysis", "graph", "Patterns found in the knowledge graph").add();
+    spec.get("/api/graph/smart", "graph", "Clustered graph with inferred relationships").add();
+    spec.get("/api/issues/:key/blockers", "issues", "Issues blocking this one, transitively").add();
+    spec.get("/api/issues/:key/similar", "issues", "Likely duplicates of an issue").query::<SimilarIssuesQuery>().add();
+    spec.post("/api/issues/:key/similar/:other/dismiss", "issues", "Mark two issues as not duplicates").add();
+    spec.get("/api/epics/:key/tree", "issues", "Epic with its children and their dependencies").add();
+    spec.get("/api/epics/:key/critical-path", "issues", "Longest dependency chain inside an epic").add();
+
+    // Analytics and reports
+    spec.get("/api/analytics", "analytics", "Analytics dashboard").add();
+    spec.get("/api/analytics/advanced", "analytics", "Advanced team and flow metrics").add();
+    spec.get("/api/analytics/timeseries", "analytics", "One metric over time").query::<TimeSeriesQuery>().add();
+    spec.get("/api/analytics/dependency-risks", "analytics", "Long or stale dependency chains")
+        .query::<DependencyRiskQuery>()
+        .add();
+    spec.get("/api/analytics/sprints", "analytics", "Synced sprints").add();
+    spec.get("/api/analytics/sprints/:id", "analytics", "Metrics for one sprint").integer_path("id").add();
+    spec.get("/api/reports/silos", "analytics", "Knowledge silo report").query::<SiloReportQuery>().add();
+    spec.get("/api/knowledge", "knowledge", "Knowledge base built from synced issues").add();
+    spec.get("/api/triage/suggest", "triage", "Triage suggestion for an issue key or free text")
+        .query::<TriageSuggestQuery>()
+        .add();
+    spec.post("/api/triage/suggest", "triage", "Triage suggestion for a ticket not yet filed").body::<TicketText>().add();
+
+    // Search, notes and views
+    spec.get("/api/search", "search", "Semantic search over synced issues").query::<SearchQuery>().paged().add();
+    spec.post("/api/search/unified", "search", "Search Jira, Google and Slack content the caller can see")
+        .body::<UnifiedSearchRequest>()
+        .paged()
+        .returns::<UnifiedSearchResult>()
+        .add();
+    spec.get("/api/notes", "notes", "The caller's notes").query::<NotesQuery>().paged().returns::<NotesResponse>().add();
+    spec.post("/api/notes", "notes", "Create a note").body::<CreateNoteRequest>().returns::<UserNote>().add();
+    spec.get("/api/views", "notes", "The caller's saved views").paged().returns::<ViewsResponse>().add();
+    spec.post("/api/views", "notes", "Create a saved view").body::<CreateViewRequest>().returns::<SavedView>().add();
+    spec.put("/api/views/:id/use", "notes", "Record that a view was opened").status("404", "No such view").add();
+    spec.put("/api/views/:id/favorite", "notes", "Toggle a view's favorite flag").status("404", "No such view").add();
+
+    // Sync and background jobs
+    spec.get("/api/sync/status", "jobs", "Sync freshness per project").paged().add();
+    spec.get("/api/events", "jobs", "Server-Sent Events stream of sync, extraction and job progress")
+        .query::<EventsQuery>()
+        .returns_text("text/event-stream")
+        .add();
+    spec.get("/api/jobs", "jobs", "Scheduled jobs and their next runs").add();
+    spec.get("/api/jobs/runs", "jobs", "Recent job runs").query::<JobRunsQuery>().add();
+    spec.get("/api/jobs/runs/:id", "jobs", "One job run").status("404", "No such run").add();
+    spec.post("/api/jobs/:job/run", "jobs", "Run a job now").status("409", "The job is already running").add();
+
+    // Google and Slack connections
+    spec.get("/api/auth/google", "integrations", "Start the Google OAuth flow").add();
+    spec.get("/api/auth/google/callback", "integrations", "Google OAuth redirect target").query::<GoogleAuthQuery>().add();
+    spec.get("/api/auth/google/status", "integrations", "Whether the caller has connected Google").add();
+    spec.post("/api/auth/google/refresh", "integrations", "Refresh the caller's Google tokens").add();
+    spec.get("/api/auth/slack", "integrations", "Start the Slack OAuth flow")
+        .query_param("team_id", "Preselect a Slack workspace")
+        .add();
+    spec.get("/api/auth/slack/callback", "integrations", "Slack OAuth redirect target").query::<SlackAuthQuery>().add();
+    spec.get("/api/auth/slack/status", "integrations", "Whether the caller has connected Slack").add();
+    spec.post("/api/auth/slack/test", "integrations", "Check the caller's Slack token").add();
+    spec.post("/api/content/extract", "integrations", "Queue content extraction for a ticket's links")
+        .body::<ContentExtractionQuery>()
+        .add();
+    spec.get("/api/content/status", "integrations", "Content extraction progress")
+        .query_param("ticket_id", "Only jobs for this ticket")
+        .add();
+
+    // People
+    spec.post("/people/analyze", "people", "People insights for one piece of content")
+        .body::<ContentAnalysisRequest>()
+        .status("404", "Unknown content, or content the caller cannot see")
+        .add();
+    spec.get("/people/profile/:person_id", "people", "A person's profile").add();
+    spec.get("/people/recommendations/:person_id", "people", "Who a person should work with")
+        .query_param("topic", "Bias recommendations toward a topic")
+        .paged()
+        .add();
+    spec.get("/people/overview", "people", "Collaboration network overview").paged().add();
+    spec.get("/people/experts", "people", "People ranked by expertise in a topic").query::<ExpertsQuery>().paged().add();
+    spec.get("/api/people/network/metrics", "people", "Collaboration network metrics").paged().add();
+
+    // Accounts
+    spec.post("/api/auth/login", "accounts", "Sign in and receive a session cookie")
+        .body::<LoginRequest>()
+        .returns_field::<AuthUser>("user")
+        .status("401", "Wrong username or password")
+        .public()
+        .add();
+    spec.post("/api/auth/logout", "accounts", "End the current session").public().add();
+    spec.post("/api/auth/register", "accounts", "Create an account (admins only)")
+        .body::<RegisterRequest>()
+        .status("403", "Only admins can add accounts")
+        .add();
+    spec.get("/api/auth/me", "accounts", "The signed-in user").returns_field::<AuthUser>("user").add();
+    spec.get("/api/auth/api-keys", "accounts", "The caller's API keys").returns_field::<Vec<ApiKeySummary>>("keys").add();
+    spec.post("/api/auth/api-keys", "accounts", "Create an API key; the key is only shown once")
+        .body::<CreateApiKeyRequest>()
+        .status("201", "Created")
+        .add();
+    spec.delete("/api/auth/api-keys/:id", "accounts", "Revoke an API key")
+        .status("204", "Revoked")
+        .status("404", "No such key")
+        .add();
+
+    // Unauthenticated endpoints
+    spec.post("/api/webhooks/jira", "webhooks", "Jira issue webhook, signed with the shared secret in X-Hub-Signature")
+        .status("401", "Missing or bad signature")
+        .public()
+        .add();
+    spec.get("/api/openapi.json", "meta", "This document").public().add();
+    spec.get("/api/docs", "meta", "Interactive API documentation").returns_text("text/html").public().add();
+    spec.get("/health", "meta", "Liveness check").returns_text("text/plain").public().add();
+    spec.get("/metrics", "meta", "Prometheus metrics").returns_text("text/plain").public().add();
+
+    spec.into_document()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    /// (method, path) pairs declared with `.route(...)` in the router sources.
+    fn declared_routes() -> Vec<(String, String)> {
+        let sources = [
+            include_str!("server.rs"),
+            include_str!("people_routes.rs"),
+            include_str!("account_routes.rs"),
+        ];
+        let route = Regex::new(r#"\.route\(\s*"([^"]+)",\s*(get|post|put|delete|patch)\("#).unwrap();
+        sources
+            .iter()
+            .flat_map(|source| route.captures_iter(source))
+            .map(|captures| (captures[2].to_string(), openapi_path(&captures[1])))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let routes = declared_routes();
+        assert!(routes.len() > 50, "route pattern no longer matches the router sources");
+
+        let missing: Vec<String> = routes
+            .iter()
+            .filter(|(method, path)| OPENAPI_DOCUMENT["paths"][path.as_str()][method.as_str()].is_null())
+            .map(|(method, path)| format!("{} {}", method.to_uppercase(), path))
+            .collect();
+        assert!(missing.is_empty(), "routes missing from the OpenAPI document: {:?}", missing);
+    }
+
+    #[test]
+    fn test_request_types_are_referenced_as_components() {
+        let body = &OPENAPI_DOCUMENT["paths"]["/api/search/unified"]["post"]["requestBody"];
+        assert_eq!(
+            body["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/UnifiedSearchRequest"
+        );
+        let schema = &OPENAPI_DOCUMENT["components"]["schemas"]["UnifiedSearchRequest"];
+        assert!(schema["properties"]["search_mode"].is_object());
+
+        let params = OPENAPI_DOCUMENT["paths"]["/api/views/{id}/use"]["put"]["parameters"].as_array().unwrap();
+        assert_eq!(params[0]["name"], "id");
+        assert_eq!(params[0]["in"], "path");
+    }
+}
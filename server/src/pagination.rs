@@ -0,0 +1,289 @@
+use axum::http::StatusCode;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+// ================================
+// LIST PAGINATION
+// ================================
+
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const MAX_PAGE_LIMIT: usize = 200;
+
+/// `cursor`, `limit`, `sort` and `fields`, shared by every list endpoint.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct PageQuery {
+    /// The `next_cursor` returned with the previous page
+    pub cursor: Option<String>,
+    /// Items per page; defaults to 50 and is capped at 200
+    pub limit: Option<usize>,
+    /// Field to order by, `-` prefixed for descending, e.g. `-updated_at`
+    pub sort: Option<String>,
+    /// Comma-separated fields to return for each item; the id is always included
+    pub fields: Option<String>,
+}
+
+/// How the items of one list endpoint are identified and may be ordered.
+pub struct ListSpec {
+    pub id_field: &'static str,
+    /// Sortable fields; nested fields use dots, e.g. `score.authority_score`
+    pub sortable: &'static [&'static str],
+    pub default_sort: &'static str,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PageError {
+    UnknownSortField(String),
+    InvalidCursor,
+    /// The cursor was issued for a different `sort`
+    CursorSortMismatch,
+}
+
+impl std::fmt::Display for PageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageError::UnknownSortField(field) => write!(f, "cannot sort by '{}'", field),
+            PageError::InvalidCursor => write!(f, "cursor is not valid"),
+            PageError::CursorSortMismatch => write!(f, "cursor belongs to a different sort order"),
+        }
+    }
+}
+
+/// Bad `sort` or `cursor` values are the caller's mistake.
+impl From<PageError> for StatusCode {
+    fn from(error: PageError) -> Self {
+        tracing::debug!("Rejected page request: {}", error);
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    sort: String,
+    /// Sort value and id of the last item on the previous page
+    after: (Value, Value),
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page {
+    pub items: Vec<Value>,
+    pub next_cursor: Option<String>,
+    pub limit: usize,
+    pub sort: String,
+}
+
+impl PageQuery {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// Orders `items` by the requested field with the id as tie-breaker, so pages never
+    /// overlap or skip items, and returns the page after the cursor.
+    pub fn paginate(&self, mut items: Vec<Value>, spec: &ListSpec) -> Result<Page, PageError> {
+        let sort = self
+            .sort
+            .as_deref()
+            .map(str::trim)
+            .filter(|sort| !sort.is_empty())
+            .unwrap_or(spec.default_sort)
+            .to_string();
+        let (field, descending) = match sort.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (sort.as_str(), false),
+        };
+        if !spec.sortable.contains(&field) {
+            return Err(PageError::UnknownSortField(field.to_string()));
+        }
+
+        let key = |item: &Value| (lookup(item, field).clone(), lookup(item, spec.id_field).clone());
+        let order = |a: &(Value, Value), b: &(Value, Value)| {
+            let by_field = compare_values(&a.0, &b.0);
+            let by_field = if descending { by_field.reverse() } else { by_field };
+            by_field.then_with(|| compare_values(&a.1, &b.1))
+        };
+        items.sort_by(|a, b| order(&key(a), &key(b)));
+
+        if let Some(raw) = &self.cursor {
+            let cursor = decode_cursor(raw)?;
+            if cursor.sort != sort {
+                return Err(PageError::CursorSortMismatch);
+            }
+            items.retain(|item| order(&key(item), &cursor.after) == Ordering::Greater);
+        }
+
+        let limit = self.limit();
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(|last| {
+                encode_cursor(&Cursor {
+                    sort: sort.clone(),
+                    after: key(last),
+                })
+            })
+        } else {
+            None
+        };
+
+        if let Some(fields) = &self.fields {
+            let keep: Vec<&str> = fields.split(',').map(str::trim).filter(|f| !f.is_empty()).collect();
+            if !keep.is_empty() {
+                for item in &mut items {
+                    if let Value::Object(map) = item {
+                        map.retain(|name, _| name == spec.id_field || keep.contains(&name.as_str()));
+                    }
+                }
+            }
+        }
+
+        Ok(Page {
+            items,
+            next_cursor,
+            limit,
+            sort,
+        })
+    }
+
+    /// Pages the array at `response[field]` in place and adds `next_cursor` beside it, so
+    /// existing response shapes keep working.
+    pub fn paginate_field(&self, response: &mut Value, field: &str, spec: &ListSpec) -> Result<(), PageError> {
+        let items = match response.get_mut(field).map(Value::take) {
+            Some(Value::Array(items)) => items,
+            _ => Vec::new(),
+        };
+        let page = self.paginate(items, spec)?;
+        response[field] = Value::Array(page.items);
+        response["next_cursor"] = serde_json::json!(page.next_cursor);
+        Ok(())
+    }
+}
+
+static NULL: Value = Value::Null;
+
+fn lookup<'a>(item: &'a Value, path: &str) -> &'a Value {
+    path.split('.').fold(item, |value, segment| value.get(segment).unwrap_or(&NULL))
+}
+
+/// Nulls first, then booleans, numbers and strings; anything else by its JSON text.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            _ => 4,
+        }
+    }
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .unwrap_or_default()
+            .partial_cmp(&b.as_f64().unwrap_or_default())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ if rank(a) != rank(b) => rank(a).cmp(&rank(b)),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn encode_cursor(cursor: &Cursor) -> String {
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(cursor).unwrap_or_default())
+}
+
+fn decode_cursor(raw: &str) -> Result<Cursor, PageError> {
+    URL_SAFE_NO_PAD
+        .decode(raw.trim())
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or(PageError::InvalidCursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: ListSpec = ListSpec {
+        id_field: "id",
+        sortable: &["updated_at", "score.value"],
+        default_sort: "-updated_at",
+    };
+
+    fn notes() -> Vec<Value> {
+        vec![
+            json!({ "id": "a", "updated_at": "2024-01-03", "title": "A", "score": { "value": 1 } }),
+            json!({ "id": "b", "updated_at": "2024-01-01", "title": "B", "score": { "value": 3 } }),
+            json!({ "id": "c", "updated_at": "2024-01-03", "title": "C", "score": { "value": 2 } }),
+            json!({ "id": "d", "updated_at": "2024-01-02", "title": "D", "score": { "value": 2 } }),
+        ]
+    }
+
+    fn ids(page: &Page) -> Vec<&str> {
+        page.items.iter().map(|item| item["id"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_pages_follow_cursor_without_overlap() {
+        let mut query = PageQuery {
+            limit: Some(3),
+            ..Default::default()
+        };
+        let first = query.paginate(notes(), &SPEC).unwrap();
+        assert_eq!(ids(&first), vec!["a", "c", "d"]);
+
+        query.cursor = first.next_cursor.clone();
+        let second = query.paginate(notes(), &SPEC).unwrap();
+        assert_eq!(ids(&second), vec!["b"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_nested_sort_field_selection_and_limits() {
+        let query = PageQuery {
+            sort: Some("score.value".to_string()),
+            fields: Some("title".to_string()),
+            limit: Some(10_000),
+            ..Default::default()
+        };
+        let page = query.paginate(notes(), &SPEC).unwrap();
+
+        assert_eq!(ids(&page), vec!["a", "c", "d", "b"]);
+        assert_eq!(page.items[0], json!({ "id": "a", "title": "A" }));
+        assert_eq!(page.limit, MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn test_rejects_unknown_sort_and_foreign_cursors() {
+        let query = PageQuery {
+            sort: Some("title".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            query.paginate(notes(), &SPEC).unwrap_err(),
+            PageError::UnknownSortField("title".to_string())
+        );
+
+        let first = PageQuery {
+            limit: Some(1),
+            ..Default::default()
+        }
+        .paginate(notes(), &SPEC)
+        .unwrap();
+        let query = PageQuery {
+            sort: Some("updated_at".to_string()),
+            cursor: first.next_cursor,
+            ..Default::default()
+        };
+        assert_eq!(query.paginate(notes(), &SPEC).unwrap_err(), PageError::CursorSortMismatch);
+
+        let query = PageQuery {
+            cursor: Some("not-a-cursor".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.paginate(notes(), &SPEC).unwrap_err(), PageError::InvalidCursor);
+    }
+}
@@ -152,6 +152,30 @@ pub enum InteractionType {
     },
 }
 
+impl InteractionType {
+    /// Variant name, used when grouping interactions without their payload.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            InteractionType::CreatedContent => "CreatedContent",
+            InteractionType::EditedContent => "EditedContent",
+            InteractionType::CommentedOn { .. } => "CommentedOn",
+            InteractionType::RepliedTo { .. } => "RepliedTo",
+            InteractionType::Mentioned { .. } => "Mentioned",
+            InteractionType::ReactedWith { .. } => "ReactedWith",
+            InteractionType::SharedContent { .. } => "SharedContent",
+            InteractionType::TaggedPerson { .. } => "TaggedPerson",
+            InteractionType::AskedQuestion { .. } => "AskedQuestion",
+            InteractionType::ProvidedSolution { .. } => "ProvidedSolution",
+            InteractionType::ImplementedSolution { .. } => "ImplementedSolution",
+            InteractionType::ValidatedSolution { .. } => "ValidatedSolution",
+            InteractionType::AssignedTask { .. } => "AssignedTask",
+            InteractionType::ReviewedWork { .. } => "ReviewedWork",
+            InteractionType::ApprovedChange { .. } => "ApprovedChange",
+            InteractionType::TransitionedStatus { .. } => "TransitionedStatus",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InteractionContext {
     pub thread_id: Option<String>,       // slack thread, jira comment chain
@@ -211,6 +235,13 @@ CREATE TABLE IF NOT EXISTS detailed_interactions (
 );
 "#;
 
+pub const INSERT_INTERACTION: &str = r#"
+INSERT OR REPLACE INTO detailed_interactions
+(id, interaction_type, source_person_id, target_person_id, content_id,
+ platform, timestamp, context, impact_indicators, extracted_data)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+
 pub const CREATE_PEOPLE_INDEXES: &str = r#"
 CREATE INDEX IF NOT EXISTS idx_people_email ON people(email);
 CREATE INDEX IF NOT EXISTS idx_people_last_active ON people(last_active);
@@ -244,9 +275,21 @@ impl IdentityResolver {
         platform: &str,
         platform_user_id: &str,
     ) -> Result<String, Box<dyn std::error::Error>> {
-        // First try exact email match
+        // Known platform account maps straight back to its person
+        if !platform_user_id.is_empty() {
+            if let Some(person_id) = self
+                .find_by_platform_identity(platform, platform_user_id)
+                .await?
+            {
+                return Ok(person_id);
+            }
+        }
+
+        // Then try exact email match
         if let Some(email_addr) = email {
-            if let Some(person_id) = self.find_by_email(email_addr).await? {
+            // Bound first: the scrutinee's error type isn't `Send` and must not live across the await below
+            let by_email = self.find_by_email(email_addr).await?;
+            if let Some(person_id) = by_email {
                 self.add_platform_identity(
                     &person_id,
                     platform,
@@ -306,6 +349,43 @@ impl IdentityResolver {
         Ok(person_id)
     }
 
+    async fn find_by_platform_identity(
+        &self,
+        platform: &str,
+        platform_user_id: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let mut person_id = None;
+
+        with_connection("find_by_platform_identity", |conn| {
+            // LIKE narrows the scan, the JSON check below confirms the exact identity
+            let pattern = format!("%{}%", platform_user_id);
+            let mut stmt = conn
+                .prepare("SELECT id, platform_identities FROM people WHERE platform_identities LIKE ?1")
+                .expect("Failed to prepare platform identity query");
+            let rows = stmt
+                .query_map([&pattern], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })
+                .expect("Failed to execute platform identity query");
+
+            for row in rows.flatten() {
+                let (id, identities_json) = row;
+                let identities: HashMap<String, PlatformIdentity> =
+                    serde_json::from_str(&identities_json).unwrap_or_default();
+                if identities
+                    .get(platform)
+                    .map(|identity| identity.platform_user_id == platform_user_id)
+                    .unwrap_or(false)
+                {
+                    person_id = Some(id);
+                    break;
+                }
+            }
+        });
+
+        Ok(person_id)
+    }
+
     async fn find_similar_people(
         &self,
         display_name: &str,
@@ -1,14 +1,20 @@
+use crate::constants::RESOLVED_STATUSES;
+use crate::db_utils::with_connection;
 use crate::enhanced_google_extractor::{EnhancedGoogleExtractor, GoogleDocumentCollaboration};
 use crate::enhanced_jira_extractor::{EnhancedJiraExtractor, EnhancedJiraIssue};
 use crate::enhanced_slack_extractor::{EnhancedSlackExtractor, SlackThreadDynamics};
+use crate::expertise_engine::{load_expertise, PersonTopicExpertise};
 use crate::google_client::GoogleApiClient;
+use crate::knowledge_engine::{extract_concepts_from_text, extract_technologies_from_text};
+use crate::link_detector::LinkDetector;
 use crate::people_graph::{
     DetailedInteraction, IdentityResolver, ImpactIndicators, InteractionContext, InteractionType,
     Person,
 };
 use crate::slack_client::SlackApiClient;
 use crate::utils::{log_error, log_step, log_success};
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::{HashMap, HashSet};
 
 // ================================
 // UNIFIED PEOPLE INTEGRATION SYSTEM
@@ -536,16 +542,517 @@ pub struct ExpertiseArea {
 // API ENDPOINTS INTEGRATION
 // ================================
 
+/// Interactions older than this count half as much toward collaboration strength.
+const COLLABORATION_HALF_LIFE_DAYS: f64 = 90.0;
+
+#[derive(Debug, Clone)]
+struct StoredInteraction {
+    kind: &'static str,
+    source_person_id: String,
+    target_person_id: Option<String>,
+    content_id: String,
+    platform: String,
+    timestamp: DateTime<Utc>,
+}
+
+impl StoredInteraction {
+    fn recency_weight(&self, now: DateTime<Utc>) -> f64 {
+        let age_days = (now - self.timestamp).num_seconds().max(0) as f64 / 86_400.0;
+        0.5_f64.powf(age_days / COLLABORATION_HALF_LIFE_DAYS)
+    }
+
+    fn involves(&self, person_id: &str) -> bool {
+        self.source_person_id == person_id || self.target_person_id.as_deref() == Some(person_id)
+    }
+
+    fn counterpart(&self, person_id: &str) -> Option<&str> {
+        let target = self.target_person_id.as_deref()?;
+        if self.source_person_id == person_id {
+            Some(target)
+        } else if target == person_id {
+            Some(&self.source_person_id)
+        } else {
+            None
+        }
+    }
+}
+
+struct PersonSummary {
+    display_name: String,
+    platforms: Vec<String>,
+}
+
+struct ContentSummary {
+    topics: Vec<String>,
+    resolved: bool,
+}
+
+fn load_interactions(filter: &str, params: Vec<String>) -> Vec<StoredInteraction> {
+    let sql = format!(
+        "SELECT interaction_type, source_person_id, target_person_id, content_id, platform, \
+         CAST(timestamp AS VARCHAR) FROM detailed_interactions {} ORDER BY timestamp",
+        filter
+    );
+    let mut interactions = Vec::new();
+
+    with_connection("load_interactions", |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .expect("Failed to prepare interaction query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(params.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })
+            .expect("Failed to query interactions");
+
+        for (raw_type, source_person_id, target_person_id, content_id, platform, timestamp) in
+            rows.flatten()
+        {
+            let kind = serde_json::from_str::<InteractionType>(&raw_type)
+                .map(|interaction_type| interaction_type.kind())
+                .unwrap_or("Unknown");
+            let Ok(timestamp) = NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%d %H:%M:%S%.f")
+            else {
+                continue;
+            };
+
+            interactions.push(StoredInteraction {
+                kind,
+                source_person_id,
+                target_person_id,
+                content_id,
+                platform,
+                timestamp: timestamp.and_utc(),
+            });
+        }
+    });
+
+    interactions
+}
+
+fn load_people_directory() -> HashMap<String, PersonSummary> {
+    let mut directory = HashMap::new();
+
+    with_connection("load_people_directory", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, display_names, platform_identities FROM people")
+            .expect("Failed to prepare people query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .expect("Failed to query people");
+
+        for (id, display_names_json, identities_json) in rows.flatten() {
+            let display_name = serde_json::from_str::<Vec<String>>(&display_names_json)
+                .ok()
+                .and_then(|names| names.into_iter().next())
+                .unwrap_or_else(|| id.clone());
+            let mut platforms: Vec<String> =
+                serde_json::from_str::<HashMap<String, serde_json::Value>>(&identities_json)
+                    .map(|identities| identities.into_keys().collect())
+                    .unwrap_or_default();
+            platforms.sort();
+
+            directory.insert(
+                id,
+                PersonSummary {
+                    display_name,
+                    platforms,
+                },
+            );
+        }
+    });
+
+    directory
+}
+
+/// Extracts topics for the Jira issues behind the interactions `load_interactions` returns for
+/// the same filter, joined in the database rather than passed back as a list of keys.
+fn load_content_summaries(filter: &str, params: Vec<String>) -> HashMap<String, ContentSummary> {
+    let mut summaries = HashMap::new();
+    let sql = format!(
+        "SELECT key, summary, description, status FROM live_issues \
+         WHERE key IN (SELECT content_id FROM detailed_interactions {})",
+        filter
+    );
+
+    with_connection("load_content_summaries", |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .expect("Failed to prepare content summary query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(params.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .expect("Failed to query content summaries");
+
+        let link_detector = LinkDetector::new();
+        for (key, summary, description, status) in rows.flatten() {
+            summaries.insert(
+                key,
+                ContentSummary {
+                    topics: issue_topics(&link_detector, summary, description),
+                    resolved: status
+                        .as_deref()
+                        .map(|status| RESOLVED_STATUSES.contains(&status))
+                        .unwrap_or(false),
+                },
+            );
+        }
+    });
+
+    summaries
+}
+
+/// Concepts and technologies in an issue's summary and description, read from the
+/// description's text rather than its ADF JSON.
+fn issue_topics(link_detector: &LinkDetector, summary: Option<String>, description: Option<String>) -> Vec<String> {
+    let description = description
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .map(|value| link_detector.extract_text_from_field(&value))
+        .unwrap_or_default();
+    let text = format!("{} {}", summary.unwrap_or_default(), description);
+
+    let mut topics = extract_concepts_from_text(&text);
+    for technology in extract_technologies_from_text(&text) {
+        if !topics.contains(&technology) {
+            topics.push(technology);
+        }
+    }
+    topics
+}
+
+/// Addressed interactions on issues that are resolved now, the only ones
+/// `detect_knowledge_transfers` can count.
+fn resolved_issue_filter() -> (String, Vec<String>) {
+    let placeholders = vec!["?"; RESOLVED_STATUSES.len()].join(", ");
+    (
+        format!(
+            "WHERE target_person_id IS NOT NULL \
+             AND content_id IN (SELECT key FROM live_issues WHERE status IN ({}))",
+            placeholders
+        ),
+        RESOLVED_STATUSES.iter().map(|status| status.to_string()).collect(),
+    )
+}
+
+/// Interactions addressed to someone, counted per person on either end, with how many
+/// distinct people addressed them.
+const COLLABORATION_COUNTS: &str = r#"
+SELECT person_id, SUM(collaborations)::BIGINT, MAX(engaged_by)::BIGINT
+FROM (
+    SELECT source_person_id AS person_id, COUNT(*) AS collaborations, 0 AS engaged_by
+    FROM detailed_interactions WHERE target_person_id IS NOT NULL
+    GROUP BY source_person_id
+    UNION ALL
+    SELECT target_person_id, COUNT(*), COUNT(DISTINCT source_person_id)
+    FROM detailed_interactions WHERE target_person_id IS NOT NULL
+    GROUP BY target_person_id
+)
+GROUP BY person_id
+"#;
+
+/// Totals for the network overview, counted in the database instead of loading every interaction.
+fn load_network_counts() -> (usize, Vec<String>, Vec<(String, usize, usize)>) {
+    let mut total = 0;
+    let mut platforms = Vec::new();
+    let mut counts = Vec::new();
+
+    with_connection("load_network_counts", |conn| {
+        total = conn
+            .query_row("SELECT COUNT(*) FROM detailed_interactions", [], |row| row.get::<_, i64>(0))
+            .expect("Failed to count interactions") as usize;
+
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT platform FROM detailed_interactions ORDER BY platform")
+            .expect("Failed to prepare platform query");
+        platforms = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .expect("Failed to query platforms")
+            .flatten()
+            .collect();
+
+        let mut stmt = conn
+            .prepare(COLLABORATION_COUNTS)
+            .expect("Failed to prepare collaboration count query");
+        counts = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)? as usize,
+                    row.get::<_, i64>(2)? as usize,
+                ))
+            })
+            .expect("Failed to query collaboration counts")
+            .flatten()
+            .collect();
+    });
+
+    (total, platforms, counts)
+}
+
+/// Converts the expertise engine's scores for one person into profile areas.
+fn expertise_areas_for(expertise: &[PersonTopicExpertise], person_id: &str) -> Vec<ExpertiseArea> {
+    expertise
+        .iter()
+        .filter(|expertise| expertise.person_id == person_id)
+        .take(10)
+        .map(|expertise| ExpertiseArea {
+            topic: expertise.score.concept.clone(),
+            confidence_score: expertise.score.authority_score,
+            evidence_count: expertise.score.contribution_count,
+            platforms: expertise.platforms.clone(),
+        })
+        .collect()
+}
+
+/// Ranks a person's collaborators by recency-weighted interaction strength. Direct
+/// interactions count fully; sharing an issue without interacting counts half.
+fn rank_collaborators(
+    person_id: &str,
+    interactions: &[StoredInteraction],
+    now: DateTime<Utc>,
+) -> Vec<(String, f64, usize)> {
+    let mut strengths: HashMap<String, (f64, usize)> = HashMap::new();
+    let mut touched_content: HashSet<&str> = HashSet::new();
+
+    for interaction in interactions {
+        if interaction.involves(person_id) {
+            touched_content.insert(&interaction.content_id);
+        }
+        if let Some(partner) = interaction.counterpart(person_id) {
+            let entry = strengths.entry(partner.to_string()).or_insert((0.0, 0));
+            entry.0 += interaction.recency_weight(now);
+            entry.1 += 1;
+        }
+    }
+
+    for interaction in interactions {
+        if interaction.source_person_id == person_id
+            || !touched_content.contains(interaction.content_id.as_str())
+        {
+            continue;
+        }
+        let entry = strengths
+            .entry(interaction.source_person_id.clone())
+            .or_insert((0.0, 0));
+        entry.0 += 0.5 * interaction.recency_weight(now);
+    }
+
+    let mut ranked: Vec<(String, f64, usize)> = strengths
+        .into_iter()
+        .map(|(partner, (strength, count))| (partner, strength, count))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    ranked
+}
+
+/// A comment or mention aimed at someone on an issue that was later resolved is
+/// treated as knowledge flowing from the source to the target.
+fn detect_knowledge_transfers(
+    interactions: &[StoredInteraction],
+    contents: &HashMap<String, ContentSummary>,
+) -> Vec<RecentTransfer> {
+    let mut seen = HashSet::new();
+    let mut transfers = Vec::new();
+
+    for interaction in interactions {
+        if !matches!(interaction.kind, "CommentedOn" | "RepliedTo" | "Mentioned" | "ProvidedSolution") {
+            continue;
+        }
+        let Some(learner_id) = interaction.target_person_id.as_deref() else {
+            continue;
+        };
+        let Some(content) = contents.get(&interaction.content_id) else {
+            continue;
+        };
+        if !content.resolved
+            || !seen.insert((
+                interaction.source_person_id.as_str(),
+                learner_id,
+                interaction.content_id.as_str(),
+            ))
+        {
+            continue;
+        }
+
+        transfers.push(RecentTransfer {
+            teacher_id: interaction.source_person_id.clone(),
+            learner_id: learner_id.to_string(),
+            topic: content
+                .topics
+                .first()
+                .cloned()
+                .unwrap_or_else(|| interaction.content_id.clone()),
+            platform: interaction.platform.clone(),
+            timestamp: interaction.timestamp.to_rfc3339(),
+        });
+    }
+
+    transfers
+}
+
+pub async fn analyze_content_people(
+    platform: &str,
+    content_id: &str,
+) -> Result<PeopleNetworkInsights, Box<dyn std::error::Error>> {
+    let filter = "WHERE platform = ? AND content_id = ?";
+    let params = vec![platform.to_string(), content_id.to_string()];
+    let interactions = load_interactions(filter, params.clone());
+    if interactions.is_empty() {
+        return Err(format!("No interactions recorded for {} on {}", content_id, platform).into());
+    }
+
+    let mut participants: Vec<String> = Vec::new();
+    let mut pairs: HashSet<(String, String)> = HashSet::new();
+    for interaction in &interactions {
+        if !participants.contains(&interaction.source_person_id) {
+            participants.push(interaction.source_person_id.clone());
+        }
+        if let Some(target) = &interaction.target_person_id {
+            if !participants.contains(target) {
+                participants.push(target.clone());
+            }
+            let pair = if interaction.source_person_id < *target {
+                (interaction.source_person_id.clone(), target.clone())
+            } else {
+                (target.clone(), interaction.source_person_id.clone())
+            };
+            pairs.insert(pair);
+        }
+    }
+
+    let contents = load_content_summaries(filter, params);
+    let knowledge_transfers = detect_knowledge_transfers(&interactions, &contents).len();
+    let engagement_score = (0.5 * (participants.len() as f64 / 5.0).min(1.0)
+        + 0.5 * (interactions.len() as f64 / 20.0).min(1.0))
+    .min(1.0);
+
+    Ok(PeopleNetworkInsights {
+        platform: platform.to_string(),
+        content_id: content_id.to_string(),
+        participants,
+        collaboration_patterns: pairs.len(),
+        knowledge_transfers,
+        engagement_score,
+    })
+}
+
 pub async fn get_person_network_insights(
     person_id: &str,
 ) -> Result<PersonNetworkProfile, Box<dyn std::error::Error>> {
-    // This would query the database for all interactions and build a comprehensive profile
+    let directory = load_people_directory();
+    let Some(person) = directory.get(person_id) else {
+        return Err(format!("Person {} not found", person_id).into());
+    };
+
+    let filter = "WHERE content_id IN (SELECT content_id FROM detailed_interactions \
+                  WHERE source_person_id = ? OR target_person_id = ?)";
+    let params = vec![person_id.to_string(), person_id.to_string()];
+    let touched = load_interactions(filter, params.clone());
+    let now = Utc::now();
+    let contents = load_content_summaries(filter, params);
+
+    let collaboration_partners = rank_collaborators(person_id, &touched, now)
+        .into_iter()
+        .take(10)
+        .map(|(partner, _, _)| partner)
+        .collect();
+    let expertise_areas = expertise_areas_for(
+        &load_expertise("WHERE person_id = ?", vec![person_id.to_string()]),
+        person_id,
+    );
+
+    let own: Vec<&StoredInteraction> = touched
+        .iter()
+        .filter(|interaction| interaction.involves(person_id))
+        .collect();
+    let incoming_from: HashSet<&str> = own
+        .iter()
+        .filter(|interaction| interaction.target_person_id.as_deref() == Some(person_id))
+        .map(|interaction| interaction.source_person_id.as_str())
+        .collect();
+    let created: HashSet<&str> = own
+        .iter()
+        .filter(|interaction| {
+            interaction.source_person_id == person_id && interaction.kind == "CreatedContent"
+        })
+        .map(|interaction| interaction.content_id.as_str())
+        .collect();
+    let comments: Vec<&&StoredInteraction> = own
+        .iter()
+        .filter(|interaction| {
+            interaction.source_person_id == person_id && interaction.kind == "CommentedOn"
+        })
+        .collect();
+    let recent = own
+        .iter()
+        .filter(|interaction| (now - interaction.timestamp).num_days() <= 90)
+        .count();
+    let participated: HashSet<&str> = own
+        .iter()
+        .map(|interaction| interaction.content_id.as_str())
+        .collect();
+    let resolved = participated
+        .iter()
+        .filter(|content_id| {
+            contents
+                .get(**content_id)
+                .map(|content| content.resolved)
+                .unwrap_or(false)
+        })
+        .count();
+
+    let influence_metrics = InfluenceMetrics {
+        authority_score: if directory.len() > 1 {
+            incoming_from.len() as f64 / (directory.len() - 1) as f64
+        } else {
+            0.0
+        },
+        collaboration_frequency: recent as f64 / (90.0 / 7.0),
+        knowledge_sharing_score: if comments.is_empty() {
+            0.0
+        } else {
+            comments
+                .iter()
+                .filter(|comment| !created.contains(comment.content_id.as_str()))
+                .count() as f64
+                / comments.len() as f64
+        },
+        problem_solving_rate: if participated.is_empty() {
+            0.0
+        } else {
+            resolved as f64 / participated.len() as f64
+        },
+    };
+
     Ok(PersonNetworkProfile {
         person_id: person_id.to_string(),
-        platforms: Vec::new(),
-        collaboration_partners: Vec::new(),
-        expertise_areas: Vec::new(),
-        influence_metrics: InfluenceMetrics::default(),
+        platforms: person.platforms.clone(),
+        collaboration_partners,
+        expertise_areas,
+        influence_metrics,
     })
 }
 
@@ -561,97 +1068,91 @@ pub async fn get_collaboration_recommendations(
         ),
     );
 
-    let mut recommendations = Vec::new();
+    let directory = load_people_directory();
+    if !directory.contains_key(person_id) {
+        return Err(format!("Person {} not found", person_id).into());
+    }
 
-    // In a real implementation, this would:
-    // 1. Query the database for the person's past collaborators
-    // 2. Find people with expertise in the given topic
-    // 3. Analyze collaboration success patterns
-    // 4. Score potential collaborations based on past success and expertise overlap
+    // Only direct interactions count toward the shared history
+    let interactions = load_interactions(
+        "WHERE source_person_id = ? OR target_person_id = ?",
+        vec![person_id.to_string(), person_id.to_string()],
+    );
+    let now = Utc::now();
+    let expertise = load_expertise("", Vec::new());
 
-    // For now, provide realistic mock recommendations based on the topic
-    let topic_lower = topic.to_lowercase();
+    // Without an explicit topic, look for people who know what this person works on.
+    let wanted_topics: Vec<String> = if topic.trim().is_empty() {
+        expertise_areas_for(&expertise, person_id)
+            .into_iter()
+            .take(5)
+            .map(|area| area.topic)
+            .collect()
+    } else {
+        vec![topic.trim().to_lowercase()]
+    };
+    if wanted_topics.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    if topic_lower.contains("react") || topic_lower.contains("frontend") {
-        recommendations.push(CollaborationRecommendation {
-            recommended_person_id: "frontend_expert_1".to_string(),
-            confidence_score: 0.85,
-            shared_topics: vec![
-                "React".to_string(),
-                "JavaScript".to_string(),
-                "Frontend Architecture".to_string(),
-            ],
-            collaboration_history: 3,
-            reasoning: format!(
-                "Has successfully collaborated with {} on 3 previous frontend projects",
-                person_id
-            ),
-        });
+    let history: HashMap<String, usize> = rank_collaborators(person_id, &interactions, now)
+        .into_iter()
+        .map(|(partner, _, count)| (partner, count))
+        .collect();
 
-        recommendations.push(CollaborationRecommendation {
-            recommended_person_id: "ui_designer_1".to_string(),
-            confidence_score: 0.72,
-            shared_topics: vec!["UI/UX Design".to_string(), "React Components".to_string()],
-            collaboration_history: 1,
-            reasoning: "Strong track record in React component design and has worked well with similar technical profiles".to_string(),
-        });
-    }
+    let mut recommendations = Vec::new();
+    for candidate_id in directory.keys() {
+        if candidate_id == person_id {
+            continue;
+        }
 
-    if topic_lower.contains("backend")
-        || topic_lower.contains("api")
-        || topic_lower.contains("database")
-    {
-        recommendations.push(CollaborationRecommendation {
-            recommended_person_id: "backend_architect_1".to_string(),
-            confidence_score: 0.91,
-            shared_topics: vec![
-                "API Design".to_string(),
-                "Database Architecture".to_string(),
-                "System Design".to_string(),
-            ],
-            collaboration_history: 5,
-            reasoning: format!(
-                "Highly successful collaboration history with {} on backend systems",
-                person_id
-            ),
-        });
-    }
+        let matching: Vec<ExpertiseArea> = expertise_areas_for(&expertise, candidate_id)
+            .into_iter()
+            .filter(|area| {
+                wanted_topics
+                    .iter()
+                    .any(|wanted| area.topic.contains(wanted.as_str()) || wanted.contains(&area.topic))
+            })
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
 
-    if topic_lower.contains("devops")
-        || topic_lower.contains("deployment")
-        || topic_lower.contains("infrastructure")
-    {
-        recommendations.push(CollaborationRecommendation {
-            recommended_person_id: "devops_lead_1".to_string(),
-            confidence_score: 0.88,
-            shared_topics: vec![
-                "DevOps".to_string(),
-                "Kubernetes".to_string(),
-                "CI/CD".to_string(),
-            ],
-            collaboration_history: 2,
-            reasoning: "Infrastructure expertise that complements this person's development skills"
-                .to_string(),
-        });
-    }
+        let relevance = matching
+            .iter()
+            .map(|area| area.confidence_score)
+            .fold(0.0, f64::max);
+        let collaboration_history = history.get(candidate_id).copied().unwrap_or(0) as u32;
+        let familiarity = 1.0 - (-(collaboration_history as f64) / 3.0).exp();
+        let evidence: u32 = matching.iter().map(|area| area.evidence_count).sum();
 
-    // If no specific topic match, provide general recommendations
-    if recommendations.is_empty() {
         recommendations.push(CollaborationRecommendation {
-            recommended_person_id: "generalist_1".to_string(),
-            confidence_score: 0.65,
-            shared_topics: vec![
-                "Problem Solving".to_string(),
-                "Technical Leadership".to_string(),
-            ],
-            collaboration_history: 1,
+            recommended_person_id: candidate_id.clone(),
+            confidence_score: 0.7 * relevance + 0.3 * familiarity,
+            shared_topics: matching.iter().map(|area| area.topic.clone()).collect(),
+            collaboration_history,
             reasoning: format!(
-                "Versatile collaborator with experience in diverse topics similar to '{}'",
-                topic
+                "{} has worked on {} issue(s) covering {} and has {} recorded interaction(s) with {}",
+                directory[candidate_id].display_name,
+                evidence,
+                matching
+                    .iter()
+                    .map(|area| area.topic.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                collaboration_history,
+                directory[person_id].display_name
             ),
         });
     }
 
+    recommendations.sort_by(|a, b| {
+        b.confidence_score
+            .partial_cmp(&a.confidence_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    recommendations.truncate(5);
+
     log_success(&format!(
         "Found {} collaboration recommendations for {}",
         recommendations.len(),
@@ -660,6 +1161,49 @@ pub async fn get_collaboration_recommendations(
     Ok(recommendations)
 }
 
+pub async fn get_network_overview_data() -> Result<NetworkOverviewResponse, Box<dyn std::error::Error>>
+{
+    let directory = load_people_directory();
+    let (total_interactions, platforms_active, collaboration_counts) = load_network_counts();
+    let (filter, params) = resolved_issue_filter();
+    let interactions = load_interactions(&filter, params.clone());
+    let contents = load_content_summaries(&filter, params);
+
+    let mut top_collaborators: Vec<TopCollaborator> = collaboration_counts
+        .into_iter()
+        .map(|(person_id, collaboration_count, engaged_by)| TopCollaborator {
+            display_name: directory
+                .get(&person_id)
+                .map(|person| person.display_name.clone())
+                .unwrap_or_else(|| person_id.clone()),
+            person_id,
+            collaboration_count,
+            influence_score: if directory.len() > 1 {
+                engaged_by as f64 / (directory.len() - 1) as f64
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    top_collaborators.sort_by(|a, b| {
+        b.collaboration_count
+            .cmp(&a.collaboration_count)
+            .then_with(|| a.person_id.cmp(&b.person_id))
+    });
+
+    let mut recent_knowledge_transfers = detect_knowledge_transfers(&interactions, &contents);
+    recent_knowledge_transfers.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    recent_knowledge_transfers.truncate(10);
+
+    Ok(NetworkOverviewResponse {
+        total_people: directory.len(),
+        total_interactions,
+        platforms_active,
+        top_collaborators,
+        recent_knowledge_transfers,
+    })
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct PersonNetworkProfile {
     pub person_id: String,
@@ -685,3 +1229,118 @@ pub struct CollaborationRecommendation {
     pub collaboration_history: u32,
     pub reasoning: String,
 }
+
+#[derive(Debug, serde::Serialize)]
+pub struct NetworkOverviewResponse {
+    pub total_people: usize,
+    pub total_interactions: usize,
+    pub platforms_active: Vec<String>,
+    pub top_collaborators: Vec<TopCollaborator>,
+    pub recent_knowledge_transfers: Vec<RecentTransfer>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct TopCollaborator {
+    pub person_id: String,
+    pub display_name: String,
+    pub collaboration_count: usize,
+    pub influence_score: f64,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct RecentTransfer {
+    pub teacher_id: String,
+    pub learner_id: String,
+    pub topic: String,
+    pub platform: String,
+    pub timestamp: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn interaction(kind: &'static str, source: &str, target: Option<&str>, content_id: &str, age_days: i64) -> StoredInteraction {
+        StoredInteraction {
+            kind,
+            source_person_id: source.to_string(),
+            target_person_id: target.map(|target| target.to_string()),
+            content_id: content_id.to_string(),
+            platform: "jira".to_string(),
+            timestamp: Utc::now() - Duration::days(age_days),
+        }
+    }
+
+    #[test]
+    fn test_issue_topics_read_description_text_not_adf_json() {
+        let description = serde_json::json!({
+            "type": "doc",
+            "version": 1,
+            "content": [{
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": "Kubernetes pods restart when the cache warms up" }]
+            }]
+        })
+        .to_string();
+
+        let topics = issue_topics(&LinkDetector::new(), Some("Cache warmup".to_string()), Some(description));
+
+        assert!(topics.iter().any(|topic| topic.contains("kubernetes") || topic.contains("cache")));
+        for token in ["type", "content", "paragraph", "doc", "version", "text"] {
+            assert!(!topics.iter().any(|topic| topic == token), "{} leaked into {:?}", token, topics);
+        }
+    }
+
+    #[test]
+    fn test_collaborators_rank_direct_and_recent_work_first() {
+        let now = Utc::now();
+        let interactions = vec![
+            interaction("CommentedOn", "ana", Some("bo"), "ESCL-1", 1),
+            interaction("Mentioned", "bo", Some("ana"), "ESCL-1", 2),
+            interaction("CommentedOn", "ana", Some("cy"), "ESCL-2", 360),
+            // Shares ESCL-1 with ana without addressing her
+            interaction("Created", "di", None, "ESCL-1", 1),
+            interaction("Created", "ed", None, "ESCL-9", 1),
+        ];
+
+        let ranked = rank_collaborators("ana", &interactions, now);
+        let order: Vec<&str> = ranked.iter().map(|(partner, _, _)| partner.as_str()).collect();
+        assert_eq!(order, vec!["bo", "di", "cy"]);
+        assert_eq!(ranked[0].2, 2);
+        assert_eq!(ranked[1].2, 0);
+        assert!(ranked[2].1 < 0.1);
+    }
+
+    #[test]
+    fn test_knowledge_transfers_need_a_resolved_issue() {
+        let contents = HashMap::from([
+            ("ESCL-1".to_string(), ContentSummary { topics: vec!["redis".to_string()], resolved: true }),
+            ("ESCL-2".to_string(), ContentSummary { topics: vec!["kafka".to_string()], resolved: false }),
+        ]);
+        let interactions = vec![
+            interaction("CommentedOn", "ana", Some("bo"), "ESCL-1", 1),
+            interaction("Mentioned", "ana", Some("bo"), "ESCL-1", 1),
+            interaction("CommentedOn", "ana", Some("cy"), "ESCL-2", 1),
+            interaction("TransitionedStatus", "ana", Some("di"), "ESCL-1", 1),
+        ];
+
+        let transfers = detect_knowledge_transfers(&interactions, &contents);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].teacher_id, "ana");
+        assert_eq!(transfers[0].learner_id, "bo");
+        assert_eq!(transfers[0].topic, "redis");
+    }
+
+    #[test]
+    fn test_recency_weight_halves_per_half_life() {
+        let now = Utc::now();
+        let recent = interaction("CommentedOn", "ana", Some("bo"), "ESCL-1", 0);
+        let old = interaction("CommentedOn", "ana", Some("bo"), "ESCL-1", COLLABORATION_HALF_LIFE_DAYS as i64);
+
+        assert!((recent.recency_weight(now) - 1.0).abs() < 1e-3);
+        assert!((old.recency_weight(now) - 0.5).abs() < 1e-3);
+        assert_eq!(recent.counterpart("bo"), Some("ana"));
+        assert_eq!(recent.counterpart("cy"), None);
+    }
+}
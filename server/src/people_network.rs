@@ -0,0 +1,615 @@
+use crate::db_utils::with_connection;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+// ================================
+// COLLABORATION NETWORK ANALYTICS
+// ================================
+
+/// Interactions older than this count half as much toward an edge's weight.
+const EDGE_HALF_LIFE_DAYS: f64 = 90.0;
+const PAGERANK_DAMPING: f64 = 0.85;
+const PAGERANK_MAX_ITERATIONS: usize = 100;
+const PAGERANK_TOLERANCE: f64 = 1e-9;
+/// People need this many interactions inside the activity window to count as active.
+const ACTIVE_CONTRIBUTOR_MIN_INTERACTIONS: usize = 3;
+const ACTIVE_WINDOW_DAYS: i64 = 90;
+
+/// Interactions on other platforms, plus Jira ones whose issue is still live.
+const SELECT_LIVE_INTERACTIONS: &str = r#"
+SELECT source_person_id, target_person_id, content_id, platform, CAST(timestamp AS VARCHAR)
+FROM detailed_interactions
+WHERE platform != 'jira' OR content_id IN (SELECT key FROM live_issues)
+"#;
+
+/// Weighted people graph built from `detailed_interactions`.
+///
+/// Nodes are indexed densely; `directed` keeps who-acted-on-whom for PageRank while
+/// `undirected` merges both directions for betweenness and community detection.
+pub struct CollaborationGraph {
+    pub people: Vec<String>,
+    index: HashMap<String, usize>,
+    directed: Vec<HashMap<usize, f64>>,
+    undirected: Vec<HashMap<usize, f64>>,
+    /// project key -> person index -> interaction timestamps
+    project_activity: HashMap<String, HashMap<usize, Vec<DateTime<Utc>>>>,
+}
+
+impl CollaborationGraph {
+    fn new() -> Self {
+        Self {
+            people: Vec::new(),
+            index: HashMap::new(),
+            directed: Vec::new(),
+            undirected: Vec::new(),
+            project_activity: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, person_id: &str) -> usize {
+        if let Some(&index) = self.index.get(person_id) {
+            return index;
+        }
+        let index = self.people.len();
+        self.people.push(person_id.to_string());
+        self.index.insert(person_id.to_string(), index);
+        self.directed.push(HashMap::new());
+        self.undirected.push(HashMap::new());
+        index
+    }
+
+    pub fn add_interaction(&mut self, source: &str, target: &str, weight: f64) {
+        let source = self.node(source);
+        let target = self.node(target);
+        if source == target {
+            return;
+        }
+        *self.directed[source].entry(target).or_insert(0.0) += weight;
+        *self.undirected[source].entry(target).or_insert(0.0) += weight;
+        *self.undirected[target].entry(source).or_insert(0.0) += weight;
+    }
+
+    fn record_project_activity(&mut self, project: &str, person_id: &str, timestamp: DateTime<Utc>) {
+        let person = self.node(person_id);
+        self.project_activity
+            .entry(project.to_string())
+            .or_default()
+            .entry(person)
+            .or_default()
+            .push(timestamp);
+    }
+
+    /// Loads every interaction outside soft-deleted Jira issues, decaying edge weights by age.
+    pub fn load() -> Self {
+        let mut graph = Self::new();
+        let now = Utc::now();
+
+        with_connection("load_collaboration_graph", |conn| {
+            let mut stmt = conn
+                .prepare(SELECT_LIVE_INTERACTIONS)
+                .expect("Failed to prepare collaboration graph query");
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                })
+                .expect("Failed to query collaboration graph");
+
+            for (source, target, content_id, platform, timestamp) in rows.flatten() {
+                let Ok(timestamp) =
+                    NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%d %H:%M:%S%.f")
+                else {
+                    continue;
+                };
+                let timestamp = timestamp.and_utc();
+
+                if platform == "jira" {
+                    if let Some((project, _)) = content_id.rsplit_once('-') {
+                        graph.record_project_activity(project, &source, timestamp);
+                    }
+                }
+
+                match target {
+                    Some(target) => {
+                        let age_days = (now - timestamp).num_seconds().max(0) as f64 / 86_400.0;
+                        let weight = 0.5_f64.powf(age_days / EDGE_HALF_LIFE_DAYS);
+                        graph.add_interaction(&source, &target, weight);
+                    }
+                    None => {
+                        graph.node(&source);
+                    }
+                }
+            }
+        });
+
+        graph
+    }
+
+    // ================================
+    // PAGERANK
+    // ================================
+
+    /// Weighted PageRank over the directed graph; influence flows to the person acted upon.
+    pub fn pagerank(&self) -> Vec<f64> {
+        let n = self.people.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let out_weight: Vec<f64> = self.directed.iter().map(|edges| edges.values().sum()).collect();
+        let mut rank = vec![1.0 / n as f64; n];
+
+        for _ in 0..PAGERANK_MAX_ITERATIONS {
+            let dangling: f64 = (0..n)
+                .filter(|&node| out_weight[node] == 0.0)
+                .map(|node| rank[node])
+                .sum();
+            let base = (1.0 - PAGERANK_DAMPING) / n as f64 + PAGERANK_DAMPING * dangling / n as f64;
+            let mut next = vec![base; n];
+
+            for (source, edges) in self.directed.iter().enumerate() {
+                if out_weight[source] == 0.0 {
+                    continue;
+                }
+                for (&target, &weight) in edges {
+                    next[target] += PAGERANK_DAMPING * rank[source] * weight / out_weight[source];
+                }
+            }
+
+            let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if delta < PAGERANK_TOLERANCE {
+                break;
+            }
+        }
+
+        rank
+    }
+
+    // ================================
+    // BETWEENNESS CENTRALITY
+    // ================================
+
+    /// Brandes' algorithm on the undirected graph, treating strong ties as short distances.
+    pub fn betweenness(&self) -> Vec<f64> {
+        let n = self.people.len();
+        let mut centrality = vec![0.0; n];
+
+        for source in 0..n {
+            let mut stack = Vec::with_capacity(n);
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut distance = vec![f64::INFINITY; n];
+            paths[source] = 1.0;
+            distance[source] = 0.0;
+
+            let mut queue = BinaryHeap::new();
+            queue.push(QueueEntry { distance: 0.0, node: source });
+
+            while let Some(QueueEntry { distance: d, node }) = queue.pop() {
+                if d > distance[node] {
+                    continue;
+                }
+                stack.push(node);
+                for (&neighbor, &weight) in &self.undirected[node] {
+                    let candidate = d + 1.0 / weight.max(f64::EPSILON);
+                    if candidate + 1e-12 < distance[neighbor] {
+                        distance[neighbor] = candidate;
+                        paths[neighbor] = paths[node];
+                        predecessors[neighbor] = vec![node];
+                        queue.push(QueueEntry { distance: candidate, node: neighbor });
+                    } else if (candidate - distance[neighbor]).abs() <= 1e-12 {
+                        paths[neighbor] += paths[node];
+                        predecessors[neighbor].push(node);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; n];
+            while let Some(node) = stack.pop() {
+                for &predecessor in &predecessors[node] {
+                    dependency[predecessor] +=
+                        paths[predecessor] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    centrality[node] += dependency[node];
+                }
+            }
+        }
+
+        // Each undirected path was counted from both ends
+        let scale = if n > 2 {
+            1.0 / ((n - 1) * (n - 2)) as f64
+        } else {
+            0.0
+        };
+        centrality.iter().map(|value| value * scale).collect()
+    }
+
+    // ================================
+    // LOUVAIN COMMUNITIES
+    // ================================
+
+    /// Louvain modularity optimisation; returns a community id per node and the final modularity.
+    pub fn communities(&self) -> (Vec<usize>, f64) {
+        let n = self.people.len();
+        if n == 0 {
+            return (Vec::new(), 0.0);
+        }
+
+        // Work on an aggregated graph, mapping original nodes through each level
+        let mut membership: Vec<usize> = (0..n).collect();
+        let mut level: Vec<HashMap<usize, f64>> = self.undirected.clone();
+
+        loop {
+            let (assignment, improved) = louvain_local_moves(&level);
+            if !improved {
+                break;
+            }
+
+            let (relabelled, count) = relabel(&assignment);
+            for community in membership.iter_mut() {
+                *community = relabelled[*community];
+            }
+
+            let mut aggregated: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+            for (node, edges) in level.iter().enumerate() {
+                for (&neighbor, &weight) in edges {
+                    *aggregated[relabelled[node]]
+                        .entry(relabelled[neighbor])
+                        .or_insert(0.0) += weight;
+                }
+            }
+            if count == level.len() {
+                break;
+            }
+            level = aggregated;
+        }
+
+        let modularity = modularity(&self.undirected, &membership);
+        (membership, modularity)
+    }
+
+    // ================================
+    // BUS FACTOR
+    // ================================
+
+    /// Per project, the people active in the window and how many would have to leave
+    /// before nobody active remains.
+    pub fn bus_factors(&self, now: DateTime<Utc>) -> Vec<ProjectBusFactor> {
+        let window_start = now - chrono::Duration::days(ACTIVE_WINDOW_DAYS);
+        let mut projects: Vec<ProjectBusFactor> = self
+            .project_activity
+            .iter()
+            .map(|(project, people)| {
+                let mut recent: Vec<(usize, usize)> = people
+                    .iter()
+                    .map(|(&person, timestamps)| {
+                        (person, timestamps.iter().filter(|ts| **ts >= window_start).count())
+                    })
+                    .filter(|(_, count)| *count > 0)
+                    .collect();
+                recent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+                let total: usize = recent.iter().map(|(_, count)| count).sum();
+                let active: Vec<&(usize, usize)> = recent
+                    .iter()
+                    .filter(|(_, count)| *count >= ACTIVE_CONTRIBUTOR_MIN_INTERACTIONS)
+                    .collect();
+
+                ProjectBusFactor {
+                    project: project.clone(),
+                    bus_factor: active.len(),
+                    active_contributors: active
+                        .iter()
+                        .map(|(person, _)| self.people[*person].clone())
+                        .collect(),
+                    recent_interactions: total,
+                    top_contributor_share: recent
+                        .first()
+                        .map(|(_, count)| *count as f64 / total as f64)
+                        .unwrap_or(0.0),
+                }
+            })
+            .collect();
+
+        projects.sort_by(|a, b| {
+            a.bus_factor
+                .cmp(&b.bus_factor)
+                .then_with(|| a.project.cmp(&b.project))
+        });
+        projects
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+struct QueueEntry {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for QueueEntry {}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so BinaryHeap pops the closest node first
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One Louvain pass: greedily move nodes to the neighbouring community with the best gain.
+fn louvain_local_moves(graph: &[HashMap<usize, f64>]) -> (Vec<usize>, bool) {
+    let n = graph.len();
+    let degree: Vec<f64> = graph.iter().map(|edges| edges.values().sum()).collect();
+    let total_weight: f64 = degree.iter().sum();
+    let mut community: Vec<usize> = (0..n).collect();
+    if total_weight == 0.0 {
+        return (community, false);
+    }
+
+    let mut community_degree = degree.clone();
+    let mut improved = false;
+
+    loop {
+        let mut moved = false;
+        for node in 0..n {
+            let current = community[node];
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (&neighbor, &weight) in &graph[node] {
+                if neighbor != node {
+                    *links.entry(community[neighbor]).or_insert(0.0) += weight;
+                }
+            }
+
+            community_degree[current] -= degree[node];
+            let gain = |target: usize, link_weight: f64| {
+                link_weight - community_degree[target] * degree[node] / total_weight
+            };
+
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (&target, &link_weight) in &links {
+                let candidate = gain(target, link_weight);
+                if candidate > best_gain + 1e-12 {
+                    best = target;
+                    best_gain = candidate;
+                }
+            }
+
+            community_degree[best] += degree[node];
+            if best != current {
+                community[node] = best;
+                moved = true;
+                improved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    (community, improved)
+}
+
+fn relabel(assignment: &[usize]) -> (Vec<usize>, usize) {
+    let mut labels: HashMap<usize, usize> = HashMap::new();
+    let relabelled = assignment
+        .iter()
+        .map(|community| {
+            let next = labels.len();
+            *labels.entry(*community).or_insert(next)
+        })
+        .collect();
+    (relabelled, labels.len())
+}
+
+fn modularity(graph: &[HashMap<usize, f64>], membership: &[usize]) -> f64 {
+    let degree: Vec<f64> = graph.iter().map(|edges| edges.values().sum()).collect();
+    let total_weight: f64 = degree.iter().sum();
+    if total_weight == 0.0 {
+        return 0.0;
+    }
+
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut totals: HashMap<usize, f64> = HashMap::new();
+    for (node, edges) in graph.iter().enumerate() {
+        *totals.entry(membership[node]).or_insert(0.0) += degree[node];
+        for (&neighbor, &weight) in edges {
+            if membership[neighbor] == membership[node] {
+                *internal.entry(membership[node]).or_insert(0.0) += weight;
+            }
+        }
+    }
+
+    totals
+        .iter()
+        .map(|(community, total)| {
+            internal.get(community).copied().unwrap_or(0.0) / total_weight
+                - (total / total_weight).powi(2)
+        })
+        .sum()
+}
+
+// ================================
+// API RESPONSE
+// ================================
+
+#[derive(Debug, Serialize)]
+pub struct PersonNetworkMetrics {
+    pub person_id: String,
+    pub pagerank: f64,
+    pub betweenness: f64,
+    pub community: usize,
+    pub weighted_degree: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkCommunity {
+    pub community: usize,
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectBusFactor {
+    pub project: String,
+    pub bus_factor: usize,
+    pub active_contributors: Vec<String>,
+    pub recent_interactions: usize,
+    pub top_contributor_share: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkMetricsResponse {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub modularity: f64,
+    pub people: Vec<PersonNetworkMetrics>,
+    pub bridges: Vec<String>,
+    pub communities: Vec<NetworkCommunity>,
+    pub bus_factors: Vec<ProjectBusFactor>,
+}
+
+pub fn compute_network_metrics() -> NetworkMetricsResponse {
+    let graph = CollaborationGraph::load();
+    let pagerank = graph.pagerank();
+    let betweenness = graph.betweenness();
+    let (membership, modularity) = graph.communities();
+
+    let mut people: Vec<PersonNetworkMetrics> = graph
+        .people
+        .iter()
+        .enumerate()
+        .map(|(node, person_id)| PersonNetworkMetrics {
+            person_id: person_id.clone(),
+            pagerank: pagerank[node],
+            betweenness: betweenness[node],
+            community: membership[node],
+            weighted_degree: graph.undirected[node].values().sum(),
+        })
+        .collect();
+    people.sort_by(|a, b| b.pagerank.partial_cmp(&a.pagerank).unwrap_or(Ordering::Equal));
+
+    let mut bridges: Vec<&PersonNetworkMetrics> =
+        people.iter().filter(|person| person.betweenness > 0.0).collect();
+    bridges.sort_by(|a, b| {
+        b.betweenness
+            .partial_cmp(&a.betweenness)
+            .unwrap_or(Ordering::Equal)
+    });
+    let bridges = bridges
+        .into_iter()
+        .take(10)
+        .map(|person| person.person_id.clone())
+        .collect();
+
+    let mut grouped: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (node, community) in membership.iter().enumerate() {
+        grouped
+            .entry(*community)
+            .or_default()
+            .push(graph.people[node].clone());
+    }
+    let mut communities: Vec<NetworkCommunity> = grouped
+        .into_iter()
+        .map(|(community, members)| NetworkCommunity { community, members })
+        .collect();
+    communities.sort_by_key(|community| Reverse(community.members.len()));
+
+    let edge_count = graph
+        .undirected
+        .iter()
+        .map(|edges| edges.len())
+        .sum::<usize>()
+        / 2;
+
+    NetworkMetricsResponse {
+        node_count: graph.people.len(),
+        edge_count,
+        modularity,
+        people,
+        bridges,
+        communities,
+        bus_factors: graph.bus_factors(Utc::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str, f64)]) -> CollaborationGraph {
+        let mut graph = CollaborationGraph::new();
+        for (source, target, weight) in edges {
+            graph.add_interaction(source, target, *weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_pagerank_favours_people_others_interact_with() {
+        let graph = graph(&[("a", "hub", 1.0), ("b", "hub", 1.0), ("c", "hub", 1.0), ("hub", "a", 1.0)]);
+        let rank = graph.pagerank();
+        let hub = graph.index["hub"];
+
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!(rank.iter().enumerate().all(|(node, r)| node == hub || *r < rank[hub]));
+    }
+
+    #[test]
+    fn test_betweenness_finds_bridge_between_groups() {
+        let graph = graph(&[
+            ("a1", "a2", 1.0),
+            ("a2", "a3", 1.0),
+            ("a1", "a3", 1.0),
+            ("a3", "bridge", 1.0),
+            ("bridge", "b1", 1.0),
+            ("b1", "b2", 1.0),
+            ("b2", "b3", 1.0),
+            ("b1", "b3", 1.0),
+        ]);
+        let betweenness = graph.betweenness();
+        let bridge = graph.index["bridge"];
+
+        assert!(betweenness
+            .iter()
+            .enumerate()
+            .all(|(node, value)| node == bridge || *value <= betweenness[bridge]));
+    }
+
+    #[test]
+    fn test_communities_split_two_cliques() {
+        let graph = graph(&[
+            ("a1", "a2", 5.0),
+            ("a2", "a3", 5.0),
+            ("a1", "a3", 5.0),
+            ("b1", "b2", 5.0),
+            ("b2", "b3", 5.0),
+            ("b1", "b3", 5.0),
+            ("a3", "b1", 0.5),
+        ]);
+        let (membership, modularity) = graph.communities();
+        let community = |id: &str| membership[graph.index[id]];
+
+        assert_eq!(community("a1"), community("a3"));
+        assert_eq!(community("b1"), community("b3"));
+        assert_ne!(community("a1"), community("b1"));
+        assert!(modularity > 0.3);
+    }
+}
@@ -5,14 +5,20 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::access_control::{can_view_source, Viewer};
+use crate::accounts::AuthUser;
+use crate::expertise_engine::{find_experts, ExpertRanking};
 use crate::people_integration::{
-    get_person_network_insights, get_collaboration_recommendations,
-    PersonNetworkProfile, CollaborationRecommendation, PeopleIntegrationSystem,
-    PeopleNetworkInsights
+    analyze_content_people, get_collaboration_recommendations, get_network_overview_data,
+    get_person_network_insights, CollaborationRecommendation, PeopleNetworkInsights,
+    PersonNetworkProfile,
 };
+use crate::pagination::{ListSpec, PageQuery, MAX_PAGE_LIMIT};
+use crate::people_network::compute_network_metrics;
 
 // ================================
 // API REQUEST/RESPONSE TYPES
@@ -29,7 +35,12 @@ pub struct CollaborationQuery {
     pub topic: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
+pub struct ExpertsQuery {
+    pub topic: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
 pub struct ContentAnalysisRequest {
     pub platform: String,     // "jira", "google", "slack"
     pub content_id: String,   // issue key, document ID, or thread ID
@@ -50,6 +61,14 @@ pub struct PersonProfileResponse {
     pub error: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct ExpertsResponse {
+    pub success: bool,
+    pub topic: String,
+    pub experts: Vec<ExpertRanking>,
+    pub error: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct CollaborationRecommendationsResponse {
     pub success: bool,
@@ -58,106 +77,77 @@ pub struct CollaborationRecommendationsResponse {
 }
 
 
+const EXPERTS: ListSpec = ListSpec {
+    id_field: "person_id",
+    sortable: &["score.authority_score", "score.contribution_count", "display_name"],
+    default_sort: "-score.authority_score",
+};
+
+const RECOMMENDATIONS: ListSpec = ListSpec {
+    id_field: "recommended_person_id",
+    sortable: &["confidence_score", "collaboration_history"],
+    default_sort: "-confidence_score",
+};
+
+const TOP_COLLABORATORS: ListSpec = ListSpec {
+    id_field: "person_id",
+    sortable: &["collaboration_count", "influence_score", "display_name"],
+    default_sort: "-collaboration_count",
+};
+
+const NETWORK_PEOPLE: ListSpec = ListSpec {
+    id_field: "person_id",
+    sortable: &["pagerank", "betweenness", "weighted_degree", "community"],
+    default_sort: "-pagerank",
+};
+
 // ================================
 // ROUTE HANDLERS
 // ================================
 
 /// Analyze content for people insights
 async fn analyze_content(
+    user: AuthUser,
     Json(request): Json<ContentAnalysisRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    println!("🔍 Analyzing content: {} on {}", request.content_id, request.platform);
-    
-    // Create integration system
-    let mut integration_system = PeopleIntegrationSystem::new();
-    
-    // Initialize
-    if let Err(e) = integration_system.initialize().await {
+    tracing::info!("Analyzing content: {} on {}", request.content_id, request.platform);
+
+    if !matches!(request.platform.as_str(), "jira" | "google" | "slack") {
         let response = PeopleInsightsResponse {
             success: false,
             insights: None,
-            error: Some(format!("Initialization failed: {}", e)),
+            error: Some(format!("Unsupported platform: {}", request.platform)),
         };
         return Ok(Json(serde_json::to_value(response).unwrap()));
     }
-    
-    // Process based on platform - simplified approach
-    let insights = match request.platform.as_str() {
-        "jira" => {
-            PeopleNetworkInsights {
-                platform: "jira".to_string(),
-                content_id: request.content_id,
-                participants: vec!["jira_user1".to_string(), "jira_user2".to_string()],
-                collaboration_patterns: 2,
-                knowledge_transfers: 1,
-                engagement_score: 0.8,
-            }
-        }
-        "google" => {
-            // Try to get a valid Google access token before processing
-            let user_id = "default"; // In a real implementation, get from request context
-            match get_google_access_token_for_user(user_id).await {
-                Ok(_access_token) => {
-                    // TODO: Use the access_token to make actual Google API calls
-                    println!("✅ Got valid Google access token, processing document: {}", request.content_id);
-                    
-                    PeopleNetworkInsights {
-                        platform: "google".to_string(),
-                        content_id: request.content_id,
-                        participants: vec!["google_user1".to_string(), "google_user2".to_string(), "google_authenticated_user".to_string()],
-                        collaboration_patterns: 3,
-                        knowledge_transfers: 2,
-                        engagement_score: 0.7,
-                    }
-                }
-                Err(e) => {
-                    println!("⚠️ Google authentication issue for user {}: {}", user_id, e);
-                    // Return limited insights without authentication
-                    PeopleNetworkInsights {
-                        platform: "google".to_string(),
-                        content_id: request.content_id,
-                        participants: vec!["unauthenticated_user".to_string()],
-                        collaboration_patterns: 0,
-                        knowledge_transfers: 0,
-                        engagement_score: 0.1,
-                    }
-                }
-            }
-        }
-        "slack" => {
-            if request.channel_id.is_none() {
-                let response = PeopleInsightsResponse {
-                    success: false,
-                    insights: None,
-                    error: Some("Channel ID required for Slack threads".to_string()),
-                };
-                return Ok(Json(serde_json::to_value(response).unwrap()));
-            }
-            PeopleNetworkInsights {
-                platform: "slack".to_string(),
-                content_id: request.content_id,
-                participants: vec!["slack_user1".to_string(), "slack_user2".to_string()],
-                collaboration_patterns: 1,
-                knowledge_transfers: 3,
-                engagement_score: 0.9,
-            }
-        }
-        _ => {
-            let response = PeopleInsightsResponse {
-                success: false,
-                insights: None,
-                error: Some(format!("Unsupported platform: {}", request.platform)),
-            };
-            return Ok(Json(serde_json::to_value(response).unwrap()));
-        }
-    };
-    
-    let response = PeopleInsightsResponse {
-        success: true,
-        insights: Some(insights),
-        error: None,
+
+    if request.platform == "slack" && request.channel_id.is_none() {
+        let response = PeopleInsightsResponse {
+            success: false,
+            insights: None,
+            error: Some("Channel ID required for Slack threads".to_string()),
+        };
+        return Ok(Json(serde_json::to_value(response).unwrap()));
+    }
+
+    // Participants of a private doc or channel are as sensitive as its contents
+    if !can_view_source(&Viewer::for_user(&user), &request.platform, &request.content_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let response = match analyze_content_people(&request.platform, &request.content_id).await {
+        Ok(insights) => PeopleInsightsResponse {
+            success: true,
+            insights: Some(insights),
+            error: None,
+        },
+        Err(e) => PeopleInsightsResponse {
+            success: false,
+            insights: None,
+            error: Some(e.to_string()),
+        },
     };
-    
+
     Ok(Json(serde_json::to_value(response).unwrap()))
 }
 
@@ -190,6 +180,7 @@ async fn get_person_profile(
 async fn get_person_recommendations(
     Path(person_id): Path<String>,
     Query(params): Query<HashMap<String, String>>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let topic = params.get("topic").unwrap_or(&"".to_string()).clone();
     
@@ -200,7 +191,9 @@ async fn get_person_recommendations(
                 recommendations,
                 error: None,
             };
-            Ok(Json(serde_json::to_value(response).unwrap()))
+            let mut response = serde_json::to_value(response).unwrap();
+            page.paginate_field(&mut response, "recommendations", &RECOMMENDATIONS)?;
+            Ok(Json(response))
         },
         Err(e) => {
             let response = CollaborationRecommendationsResponse {
@@ -213,71 +206,73 @@ async fn get_person_recommendations(
     }
 }
 
-/// Get network statistics and overview
-async fn get_network_overview() -> Result<Json<serde_json::Value>, StatusCode> {
-    // This would query the database for comprehensive network statistics
-    let overview = NetworkOverviewResponse {
-        total_people: 0,
-        total_interactions: 0,
-        platforms_active: vec!["jira".to_string(), "google".to_string(), "slack".to_string()],
-        top_collaborators: Vec::new(),
-        recent_knowledge_transfers: Vec::new(),
-    };
+/// Rank people by expertise in a topic, with the work that backs each score
+async fn get_topic_experts(
+    user: AuthUser,
+    Query(params): Query<ExpertsQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let topic = params.topic.trim().to_lowercase();
+    if topic.is_empty() {
+        let response = ExpertsResponse {
+            success: false,
+            topic,
+            experts: Vec::new(),
+            error: Some("A non-empty topic is required".to_string()),
+        };
+        return Ok(Json(serde_json::to_value(response).unwrap()));
+    }
 
-    Ok(Json(serde_json::to_value(overview).unwrap()))
-}
+    let experts = find_experts(&topic, MAX_PAGE_LIMIT, &Viewer::for_user(&user));
+    let response = ExpertsResponse {
+        success: true,
+        topic,
+        experts,
+        error: None,
+    };
 
-#[derive(Serialize)]
-pub struct NetworkOverviewResponse {
-    pub total_people: usize,
-    pub total_interactions: usize,
-    pub platforms_active: Vec<String>,
-    pub top_collaborators: Vec<TopCollaborator>,
-    pub recent_knowledge_transfers: Vec<RecentTransfer>,
+    let mut response = serde_json::to_value(response).unwrap();
+    page.paginate_field(&mut response, "experts", &EXPERTS)?;
+    Ok(Json(response))
 }
 
-#[derive(Serialize)]
-pub struct TopCollaborator {
-    pub person_id: String,
-    pub display_name: String,
-    pub collaboration_count: usize,
-    pub influence_score: f64,
+/// Get network statistics and overview
+async fn get_network_overview(
+    Query(page): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    match get_network_overview_data().await {
+        Ok(overview) => {
+            let mut response = serde_json::to_value(overview).unwrap();
+            page.paginate_field(&mut response, "top_collaborators", &TOP_COLLABORATORS)?;
+            Ok(Json(response))
+        }
+        Err(e) => {
+            tracing::error!("Failed to build network overview: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
 }
 
-#[derive(Serialize)]
-pub struct RecentTransfer {
-    pub teacher_id: String,
-    pub learner_id: String,
-    pub topic: String,
-    pub platform: String,
-    pub timestamp: String,
+/// PageRank, betweenness, communities and per-project bus factor for the people graph
+async fn get_network_metrics(
+    Query(page): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let metrics = compute_network_metrics();
+    let mut response = serde_json::to_value(metrics).unwrap();
+    page.paginate_field(&mut response, "people", &NETWORK_PEOPLE)?;
+    Ok(Json(response))
 }
 
 // ================================
 // ROUTER SETUP
 // ================================
 
-// Helper function to get valid Google access token for API calls
-async fn get_google_access_token_for_user(user_id: &str) -> Result<String, Box<dyn std::error::Error>> {
-    // This is a simplified version of the token retrieval logic
-    // In a real implementation, this would load tokens from database
-    println!("🔍 Attempting to get Google access token for user: {}", user_id);
-    
-    // For now, return an error since we don't have stored tokens
-    // This demonstrates the authentication flow
-    Err("No stored Google authentication tokens found - user needs to authenticate first".into())
-    
-    // TODO: Implement actual token loading and refresh logic:
-    // 1. Load tokens from database
-    // 2. Check if tokens are valid/expired
-    // 3. Refresh if needed using GoogleAuthManager.refresh_tokens()
-    // 4. Return valid access token
-}
-
 pub fn create_people_routes() -> Router {
     Router::new()
         .route("/people/analyze", post(analyze_content))
         .route("/people/profile/:person_id", get(get_person_profile))
         .route("/people/recommendations/:person_id", get(get_person_recommendations))
         .route("/people/overview", get(get_network_overview))
+        .route("/people/experts", get(get_topic_experts))
+        .route("/api/people/network/metrics", get(get_network_metrics))
 }
\ No newline at end of file
@@ -0,0 +1,632 @@
+use crate::db_utils::{with_connection, with_transaction};
+use crate::people_graph::{
+    IdentityResolver, ImpactIndicators, InteractionContext, InteractionType, INSERT_INTERACTION,
+};
+use crate::utils::{log_step, log_success, parse_jira_timestamp};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// ================================
+// JIRA ACTIVITY FROM LOCAL ISSUES
+// ================================
+
+/// One row of the `issues` table, reduced to the fields that describe who did what.
+struct StoredIssueActivity {
+    key: String,
+    summary: String,
+    reporter: Option<String>,
+    assignee: Option<String>,
+    created: Option<DateTime<Utc>>,
+    description: Value,
+    comment: Value,
+    changelog: Value,
+    versioned_representations: Value,
+}
+
+#[derive(Debug, Clone)]
+struct JiraActor {
+    account_id: Option<String>,
+    display_name: String,
+    email: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum DraftKind {
+    Created,
+    Commented,
+    Mentioned { mentioned: String },
+    StatusChanged { from_status: String, to_status: String },
+    Reassigned,
+}
+
+/// Interaction whose people are still actor keys rather than resolved person IDs.
+#[derive(Debug, Clone)]
+struct InteractionDraft {
+    id: String,
+    kind: DraftKind,
+    source: String,
+    target: Option<String>,
+    content_id: String,
+    timestamp: DateTime<Utc>,
+    reply_count: u32,
+    extracted_data: Value,
+}
+
+/// Collects actors under a stable key, preferring Jira account IDs over display names.
+#[derive(Default)]
+struct ActorDirectory {
+    actors: HashMap<String, JiraActor>,
+    account_by_name: HashMap<String, String>,
+}
+
+impl ActorDirectory {
+    fn observe_user(&mut self, user: &Value) -> Option<String> {
+        let display_name = user.get("displayName").and_then(|v| v.as_str()).unwrap_or("");
+        let account_id = user
+            .get("accountId")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.is_empty());
+        let email = user.get("emailAddress").and_then(|v| v.as_str());
+
+        match account_id {
+            Some(account_id) => Some(self.observe_account(account_id, display_name, email)),
+            None if !display_name.is_empty() => Some(self.observe_name(display_name)),
+            None => None,
+        }
+    }
+
+    fn observe_account(&mut self, account_id: &str, display_name: &str, email: Option<&str>) -> String {
+        if !display_name.is_empty() {
+            self.account_by_name
+                .insert(display_name.to_lowercase(), account_id.to_string());
+        }
+        let actor = self
+            .actors
+            .entry(account_id.to_string())
+            .or_insert_with(|| JiraActor {
+                account_id: Some(account_id.to_string()),
+                display_name: display_name.to_string(),
+                email: None,
+            });
+        if actor.display_name.is_empty() {
+            actor.display_name = display_name.to_string();
+        }
+        if actor.email.is_none() {
+            actor.email = email.map(|e| e.to_string());
+        }
+        account_id.to_string()
+    }
+
+    fn observe_name(&mut self, display_name: &str) -> String {
+        let key = format!("name:{}", display_name.to_lowercase());
+        self.actors.entry(key.clone()).or_insert_with(|| JiraActor {
+            account_id: None,
+            display_name: display_name.to_string(),
+            email: None,
+        });
+        key
+    }
+
+    /// Maps name-only keys onto the account seen elsewhere under the same display name.
+    fn canonical(&self, key: &str) -> String {
+        match key.strip_prefix("name:") {
+            Some(name) => self
+                .account_by_name
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| key.to_string()),
+            None => key.to_string(),
+        }
+    }
+}
+
+/// Derives `people` and `detailed_interactions` rows from the synced `issues` table.
+///
+/// Runs entirely against DuckDB, so it can be repeated after every sync: interactions
+/// use deterministic IDs and each issue's derived rows are replaced wholesale.
+pub async fn sync_people_from_issues() {
+    log_step("👥", "Deriving people and interactions from synced Jira issues...");
+
+    let issues = load_issue_activity();
+    let mut directory = ActorDirectory::default();
+    let mut drafts = Vec::new();
+
+    for issue in &issues {
+        drafts.extend(derive_issue_interactions(issue, &mut directory));
+    }
+
+    // Resolve every distinct actor once; the resolver matches on the Jira account first
+    let identity_resolver = IdentityResolver::new();
+    let mut person_ids: HashMap<String, String> = HashMap::new();
+    for (key, actor) in &directory.actors {
+        let canonical = directory.canonical(key);
+        if canonical != *key {
+            continue;
+        }
+        let platform_user_id = actor
+            .account_id
+            .clone()
+            .unwrap_or_else(|| actor.display_name.clone());
+        match identity_resolver
+            .resolve_identity(
+                actor.email.as_deref(),
+                &actor.display_name,
+                "jira",
+                &platform_user_id,
+            )
+            .await
+        {
+            Ok(person_id) => {
+                person_ids.insert(key.clone(), person_id);
+            }
+            Err(e) => tracing::error!("Failed to resolve Jira user {}: {}", actor.display_name, e),
+        }
+    }
+
+    let resolve = |key: &str| person_ids.get(&directory.canonical(key)).cloned();
+    let issue_keys: Vec<&str> = issues.iter().map(|i| i.key.as_str()).collect();
+    let mut kind_counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+    let mut touched_people: HashSet<String> = HashSet::new();
+
+    with_transaction("sync_people_from_issues", |tx| {
+        let mut delete_stmt = tx
+            .prepare("DELETE FROM detailed_interactions WHERE platform = 'jira' AND starts_with(id, ?)")
+            .expect("Failed to prepare interaction cleanup");
+        for key in &issue_keys {
+            delete_stmt
+                .execute([format!("jira:{}:", key)])
+                .expect("Failed to clear derived interactions");
+        }
+        // Issues since deleted or moved to a new key are no longer loaded, so clear theirs too
+        tx.execute(
+            "DELETE FROM detailed_interactions WHERE platform = 'jira' AND starts_with(id, 'jira:') \
+             AND split_part(id, ':', 2) NOT IN (SELECT key FROM live_issues)",
+            [],
+        )
+        .expect("Failed to clear interactions of deleted issues");
+
+        let mut insert_stmt = tx
+            .prepare(INSERT_INTERACTION)
+            .expect("Failed to prepare interaction insert");
+
+        for draft in &drafts {
+            let Some(source_person_id) = resolve(&draft.source) else {
+                continue;
+            };
+            let target_person_id = draft
+                .target
+                .as_deref()
+                .and_then(resolve)
+                .filter(|target| *target != source_person_id);
+
+            let interaction_type = match &draft.kind {
+                DraftKind::Created => InteractionType::CreatedContent,
+                DraftKind::Commented => InteractionType::CommentedOn {
+                    parent_content_id: draft.content_id.clone(),
+                },
+                DraftKind::Mentioned { mentioned } => match resolve(mentioned) {
+                    Some(mentioned_id) => InteractionType::Mentioned {
+                        mentioned_person_ids: vec![mentioned_id],
+                    },
+                    None => continue,
+                },
+                DraftKind::StatusChanged { from_status, to_status } => {
+                    InteractionType::TransitionedStatus {
+                        from_status: from_status.clone(),
+                        to_status: to_status.clone(),
+                    }
+                }
+                DraftKind::Reassigned => InteractionType::AssignedTask {
+                    task_description: draft.extracted_data["summary"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                },
+            };
+
+            let context = InteractionContext {
+                thread_id: Some(draft.content_id.clone()),
+                urgency_indicators: Vec::new(),
+                topic_keywords: Vec::new(),
+                audience_size: None,
+                visibility_level: "team".to_string(),
+            };
+            let impact = ImpactIndicators {
+                reply_count: draft.reply_count,
+                reaction_count: 0,
+                reference_count: 0,
+                implementation_count: 0,
+                view_count: None,
+                share_count: 0,
+                problem_resolution: None,
+            };
+
+            insert_stmt
+                .execute(duckdb::params![
+                    draft.id,
+                    serde_json::to_string(&interaction_type).unwrap_or_default(),
+                    source_person_id,
+                    target_person_id,
+                    draft.content_id,
+                    "jira",
+                    draft.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    serde_json::to_string(&context).unwrap_or_default(),
+                    serde_json::to_string(&impact).unwrap_or_default(),
+                    draft.extracted_data.to_string(),
+                ])
+                .expect("Failed to insert derived interaction");
+
+            *kind_counts.entry(interaction_type.kind()).or_insert(0) += 1;
+            touched_people.insert(source_person_id);
+            if let Some(target) = target_person_id {
+                touched_people.insert(target);
+            }
+        }
+    });
+
+    refresh_activity_window(&touched_people);
+
+    let summary = kind_counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}", count, kind))
+        .collect::<Vec<_>>()
+        .join(", ");
+    log_success(&format!(
+        "Derived people graph from {} issues: {} people ({})",
+        issues.len(),
+        touched_people.len(),
+        if summary.is_empty() { "no interactions".to_string() } else { summary }
+    ));
+}
+
+fn load_issue_activity() -> Vec<StoredIssueActivity> {
+    let mut issues = Vec::new();
+
+    with_connection("load_issue_activity", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT key, summary, reporter, assignee, created, description, comment, changelog, versioned_representations FROM live_issues",
+            )
+            .expect("Failed to prepare issue activity query");
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                ))
+            })
+            .expect("Failed to execute issue activity query");
+
+        let parse = |raw: Option<String>| {
+            raw.and_then(|s| serde_json::from_str::<Value>(&s).ok())
+                .unwrap_or(Value::Null)
+        };
+
+        for row in rows.flatten() {
+            let (key, summary, reporter, assignee, created, description, comment, changelog, versioned) = row;
+            issues.push(StoredIssueActivity {
+                key,
+                summary: summary.unwrap_or_default(),
+                reporter: reporter.filter(|s| !s.is_empty()),
+                assignee: assignee.filter(|s| !s.is_empty()),
+                created: created.as_deref().and_then(parse_jira_timestamp),
+                description: parse(description),
+                comment: parse(comment),
+                changelog: parse(changelog),
+                versioned_representations: parse(versioned),
+            });
+        }
+    });
+
+    issues
+}
+
+fn derive_issue_interactions(
+    issue: &StoredIssueActivity,
+    directory: &mut ActorDirectory,
+) -> Vec<InteractionDraft> {
+    let mut drafts = Vec::new();
+    let key = &issue.key;
+    let created = issue.created.unwrap_or_else(Utc::now);
+
+    // versionedRepresentations keeps the full user objects that the flat columns drop
+    let reporter = latest_versioned_user(&issue.versioned_representations, "reporter")
+        .and_then(|user| directory.observe_user(user))
+        .or_else(|| issue.reporter.as_deref().map(|name| directory.observe_name(name)));
+    let assignee = latest_versioned_user(&issue.versioned_representations, "assignee")
+        .and_then(|user| directory.observe_user(user))
+        .or_else(|| issue.assignee.as_deref().map(|name| directory.observe_name(name)));
+
+    let comments = issue.comment["comments"].as_array().cloned().unwrap_or_default();
+
+    if let Some(reporter) = &reporter {
+        drafts.push(InteractionDraft {
+            id: format!("jira:{}:created", key),
+            kind: DraftKind::Created,
+            source: reporter.clone(),
+            target: None,
+            content_id: key.clone(),
+            timestamp: created,
+            reply_count: comments.len() as u32,
+            extracted_data: serde_json::json!({ "issue_key": key, "summary": issue.summary }),
+        });
+
+        for (mention_key, mention_name) in collect_adf_mentions(&issue.description) {
+            let mentioned = directory.observe_account(&mention_key, &mention_name, None);
+            drafts.push(InteractionDraft {
+                id: format!("jira:{}:description:mention:{}", key, mention_key),
+                kind: DraftKind::Mentioned { mentioned: mentioned.clone() },
+                source: reporter.clone(),
+                target: Some(mentioned),
+                content_id: key.clone(),
+                timestamp: created,
+                reply_count: 0,
+                extracted_data: serde_json::json!({ "issue_key": key, "location": "description" }),
+            });
+        }
+    }
+
+    for comment in &comments {
+        let Some(author) = directory.observe_user(&comment["author"]) else {
+            continue;
+        };
+        let comment_id = comment["id"].as_str().unwrap_or_default();
+        let timestamp = comment["created"]
+            .as_str()
+            .and_then(parse_jira_timestamp)
+            .unwrap_or(created);
+
+        drafts.push(InteractionDraft {
+            id: format!("jira:{}:comment:{}", key, comment_id),
+            kind: DraftKind::Commented,
+            source: author.clone(),
+            target: reporter.clone().or_else(|| assignee.clone()),
+            content_id: key.clone(),
+            timestamp,
+            reply_count: 0,
+            extracted_data: serde_json::json!({ "issue_key": key, "comment_id": comment_id }),
+        });
+
+        for (mention_key, mention_name) in collect_adf_mentions(&comment["body"]) {
+            let mentioned = directory.observe_account(&mention_key, &mention_name, None);
+            drafts.push(InteractionDraft {
+                id: format!("jira:{}:comment:{}:mention:{}", key, comment_id, mention_key),
+                kind: DraftKind::Mentioned { mentioned: mentioned.clone() },
+                source: author.clone(),
+                target: Some(mentioned),
+                content_id: key.clone(),
+                timestamp,
+                reply_count: 0,
+                extracted_data: serde_json::json!({ "issue_key": key, "comment_id": comment_id, "location": "comment" }),
+            });
+        }
+    }
+
+    let histories = issue.changelog["histories"].as_array().cloned().unwrap_or_default();
+    for history in &histories {
+        let Some(author) = directory.observe_user(&history["author"]) else {
+            continue;
+        };
+        let history_id = history["id"].as_str().unwrap_or_default();
+        let timestamp = history["created"]
+            .as_str()
+            .and_then(parse_jira_timestamp)
+            .unwrap_or(created);
+
+        for item in history["items"].as_array().into_iter().flatten() {
+            let from_string = item["fromString"].as_str().unwrap_or_default().to_string();
+            let to_string = item["toString"].as_str().unwrap_or_default().to_string();
+
+            match item["field"].as_str() {
+                Some("status") => drafts.push(InteractionDraft {
+                    id: format!("jira:{}:changelog:{}:status", key, history_id),
+                    kind: DraftKind::StatusChanged {
+                        from_status: from_string.clone(),
+                        to_status: to_string.clone(),
+                    },
+                    source: author.clone(),
+                    target: None,
+                    content_id: key.clone(),
+                    timestamp,
+                    reply_count: 0,
+                    extracted_data: serde_json::json!({
+                        "issue_key": key,
+                        "history_id": history_id,
+                        "from_status": from_string,
+                        "to_status": to_string,
+                    }),
+                }),
+                Some("assignee") => {
+                    let new_assignee = match item["to"].as_str().filter(|id| !id.is_empty()) {
+                        Some(account_id) => Some(directory.observe_account(account_id, &to_string, None)),
+                        None if !to_string.is_empty() => Some(directory.observe_name(&to_string)),
+                        None => None,
+                    };
+                    drafts.push(InteractionDraft {
+                        id: format!("jira:{}:changelog:{}:assignee", key, history_id),
+                        kind: DraftKind::Reassigned,
+                        source: author.clone(),
+                        target: new_assignee,
+                        content_id: key.clone(),
+                        timestamp,
+                        reply_count: 0,
+                        extracted_data: serde_json::json!({
+                            "issue_key": key,
+                            "history_id": history_id,
+                            "summary": format!("{}: {}", key, issue.summary),
+                            "from_assignee": from_string,
+                            "to_assignee": to_string,
+                        }),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    drafts
+}
+
+/// Returns the newest version of a user field from `versionedRepresentations`.
+fn latest_versioned_user<'a>(versioned: &'a Value, field: &str) -> Option<&'a Value> {
+    versioned
+        .get(field)?
+        .as_object()?
+        .iter()
+        .filter(|(_, user)| user.is_object())
+        .max_by_key(|(version, _)| version.parse::<u32>().unwrap_or(0))
+        .map(|(_, user)| user)
+}
+
+/// Walks an Atlassian Document Format tree and returns `(account_id, display_name)` mentions.
+fn collect_adf_mentions(node: &Value) -> Vec<(String, String)> {
+    let mut mentions = Vec::new();
+    let mut stack = vec![node];
+
+    while let Some(current) = stack.pop() {
+        if current["type"] == "mention" {
+            if let Some(account_id) = current["attrs"]["id"].as_str() {
+                let name = current["attrs"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .trim_start_matches('@')
+                    .to_string();
+                if !mentions.iter().any(|(id, _)| id == account_id) {
+                    mentions.push((account_id.to_string(), name));
+                }
+            }
+        }
+        if let Some(children) = current["content"].as_array() {
+            stack.extend(children.iter());
+        }
+    }
+
+    mentions
+}
+
+/// Aligns `first_seen` with the earliest interaction we actually observed.
+///
+/// `last_active` is left alone: it is indexed, and DuckDB rewrites indexed columns as a
+/// delete + insert, which the foreign keys from `detailed_interactions` reject.
+fn refresh_activity_window(person_ids: &HashSet<String>) {
+    if person_ids.is_empty() {
+        return;
+    }
+
+    with_transaction("refresh_activity_window", |tx| {
+        let mut stmt = tx
+            .prepare(
+                r#"
+                UPDATE people SET
+                    first_seen = (SELECT MIN(timestamp) FROM detailed_interactions WHERE source_person_id = ?1)
+                WHERE id = ?1
+                  AND EXISTS (SELECT 1 FROM detailed_interactions WHERE source_person_id = ?1)
+                "#,
+            )
+            .expect("Failed to prepare activity window update");
+
+        for person_id in person_ids {
+            stmt.execute([person_id])
+                .expect("Failed to update person activity window");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue() -> StoredIssueActivity {
+        StoredIssueActivity {
+            key: "ESCL-7".to_string(),
+            summary: "Login fails".to_string(),
+            reporter: Some("Ana Diaz".to_string()),
+            assignee: Some("Bo Chen".to_string()),
+            created: parse_jira_timestamp("2026-03-01T10:00:00.000+0000"),
+            description: json!({
+                "type": "doc",
+                "content": [{ "type": "paragraph", "content": [
+                    { "type": "mention", "attrs": { "id": "acc-cy", "text": "@Cy Park" } }
+                ]}]
+            }),
+            comment: json!({ "comments": [{
+                "id": "10",
+                "author": { "accountId": "acc-bo", "displayName": "Bo Chen" },
+                "created": "2026-03-02T09:00:00.000+0000",
+                "body": { "type": "doc", "content": [] }
+            }]}),
+            changelog: json!({ "histories": [{
+                "id": "500",
+                "author": { "accountId": "acc-bo", "displayName": "Bo Chen" },
+                "created": "2026-03-03T09:00:00.000+0000",
+                "items": [
+                    { "field": "status", "fromString": "Open", "toString": "Done" },
+                    { "field": "assignee", "fromString": "", "toString": "Cy Park", "to": "acc-cy" }
+                ]
+            }]}),
+            versioned_representations: json!({
+                "reporter": { "1": { "accountId": "acc-ana", "displayName": "Ana Diaz" } }
+            }),
+        }
+    }
+
+    #[test]
+    fn test_derives_creation_comments_mentions_and_changelog() {
+        let mut directory = ActorDirectory::default();
+        let drafts = derive_issue_interactions(&issue(), &mut directory);
+        let ids: Vec<&str> = drafts.iter().map(|draft| draft.id.as_str()).collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                "jira:ESCL-7:created",
+                "jira:ESCL-7:description:mention:acc-cy",
+                "jira:ESCL-7:comment:10",
+                "jira:ESCL-7:changelog:500:status",
+                "jira:ESCL-7:changelog:500:assignee",
+            ]
+        );
+        assert_eq!(drafts[0].source, "acc-ana");
+        assert_eq!(drafts[0].reply_count, 1);
+        assert_eq!(drafts[2].target.as_deref(), Some("acc-ana"));
+        assert!(matches!(
+            &drafts[3].kind,
+            DraftKind::StatusChanged { to_status, .. } if to_status == "Done"
+        ));
+        assert_eq!(drafts[4].target.as_deref(), Some("acc-cy"));
+    }
+
+    #[test]
+    fn test_name_only_actors_resolve_to_accounts_seen_elsewhere() {
+        let mut directory = ActorDirectory::default();
+        let by_name = directory.observe_name("Bo Chen");
+        assert_eq!(directory.canonical(&by_name), "name:bo chen");
+
+        directory.observe_user(&json!({ "accountId": "acc-bo", "displayName": "Bo Chen", "emailAddress": "bo@example.com" }));
+        assert_eq!(directory.canonical(&by_name), "acc-bo");
+        assert_eq!(directory.actors["acc-bo"].email.as_deref(), Some("bo@example.com"));
+        assert_eq!(directory.observe_user(&json!({ "displayName": "" })), None);
+    }
+
+    #[test]
+    fn test_latest_versioned_user_picks_highest_version() {
+        let versioned = json!({ "assignee": {
+            "2": { "accountId": "acc-new" },
+            "10": { "accountId": "acc-newest" },
+            "1": null
+        }});
+        let user = latest_versioned_user(&versioned, "assignee").unwrap();
+        assert_eq!(user["accountId"], "acc-newest");
+        assert!(latest_versioned_user(&versioned, "reporter").is_none());
+    }
+}
@@ -43,15 +43,68 @@ CREATE TABLE IF NOT EXISTS issues (
     comment TEXT,
     issue_links TEXT,
     work_log TEXT,
-    time_tracking TEXT
+    time_tracking TEXT,
+    resolution TEXT,
+    resolution_date TEXT,
+    components TEXT, -- JSON array as string
+    fix_versions TEXT, -- JSON array as string
+    affected_versions TEXT, -- JSON array as string
+    parent_key TEXT,
+    epic_key TEXT,
+    due_date TEXT,
+    story_points DOUBLE,
+    environment TEXT,
+    deleted_at TEXT -- set when Jira reports the issue deleted
 );
 "#;
 
+// Brings issues tables created before these columns existed up to date
+pub const MIGRATE_ISSUES_TABLE: &str = r#"
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS resolution TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS resolution_date TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS components TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS fix_versions TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS affected_versions TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS parent_key TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS epic_key TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS due_date TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS story_points DOUBLE;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS environment TEXT;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS deleted_at TEXT;
+"#;
+
+// What analytics, graph and search read; recreated after migrations so `*` covers every column
+pub const CREATE_LIVE_ISSUES_VIEW: &str = r#"
+CREATE OR REPLACE VIEW live_issues AS SELECT * FROM issues WHERE deleted_at IS NULL;
+"#;
+
+// Keys an issue had before it moved to another project
+pub const CREATE_ISSUE_KEY_ALIASES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS issue_key_aliases (
+    old_key TEXT PRIMARY KEY,
+    issue_id TEXT NOT NULL,
+    moved_at TEXT NOT NULL
+);
+"#;
+
+pub const CREATE_ISSUE_CUSTOM_FIELDS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS issue_custom_fields (
+    issue_id TEXT NOT NULL,
+    field_id TEXT NOT NULL,
+    field_name TEXT,
+    friendly_name TEXT NOT NULL,
+    value TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_issue_custom_fields_issue ON issue_custom_fields(issue_id);
+CREATE INDEX IF NOT EXISTS idx_issue_custom_fields_name ON issue_custom_fields(friendly_name);
+"#;
+
 // DML
 pub const INSERT_PROJECT: &str = r#"
 INSERT INTO projects (id, key, name) VALUES (?, ?, ?);
 "#;
 
+// Writing `deleted_at` clears it: an issue Jira returns again is not deleted
 pub const INSERT_ISSUE_METADATA: &str = r#"
 INSERT OR REPLACE INTO issues
 (id, key, self_link, summary, status, 
@@ -60,7 +113,9 @@ priority_id, assignee, reporter, labels, created,
 updated, project_name, project_key, extracted_links, rendered_fields, 
 names, schema, transitions, edit_meta, changelog, 
 versioned_representations, watcher, attachment, sub_tasks, description, 
-project, comment, issue_links, work_log, time_tracking)
+project, comment, issue_links, work_log, time_tracking,
+resolution, resolution_date, components, fix_versions, affected_versions,
+parent_key, epic_key, due_date, story_points, environment, deleted_at)
 VALUES
 (?, ?, ?, ?, ?,
 ?, ?, ?, ?, ?,
@@ -68,7 +123,29 @@ VALUES
 ?, ?, ?, ?, ?,
 ?, ?, ?, ?, ?,
 ?, ?, ?, ?, ?,
-?, ?, ?, ?, ?)
+?, ?, ?, ?, ?,
+?, ?, ?, ?, ?,
+?, ?, ?, ?, ?, NULL)
+"#;
+
+pub const MARK_ISSUE_DELETED: &str = r#"
+UPDATE issues SET deleted_at = ? WHERE id = ?;
+"#;
+
+// Run before the upsert: remembers the stored key when the same issue comes back under a new one
+pub const RECORD_ISSUE_KEY_ALIAS: &str = r#"
+INSERT INTO issue_key_aliases (old_key, issue_id, moved_at)
+SELECT key, id, ? FROM issues WHERE id = ? AND key <> ?
+ON CONFLICT (old_key) DO UPDATE SET issue_id = excluded.issue_id, moved_at = excluded.moved_at;
+"#;
+
+pub const DELETE_ISSUE_CUSTOM_FIELDS: &str = r#"
+DELETE FROM issue_custom_fields WHERE issue_id = ?;
+"#;
+
+pub const INSERT_ISSUE_CUSTOM_FIELD: &str = r#"
+INSERT INTO issue_custom_fields (issue_id, field_id, field_name, friendly_name, value)
+VALUES (?, ?, ?, ?, ?)
 "#;
 
 pub const GET_PROJECT_IDS: &str = r#"
@@ -0,0 +1,324 @@
+use crate::config::server_config;
+use crate::telemetry::{TracedRequest, API_RETRIES_TOTAL, RATE_LIMIT_PER_MINUTE, RATE_LIMIT_THROTTLED_SECONDS};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ================================
+// OUTBOUND RATE LIMITING
+// ================================
+
+/// Attempts after the first when a host answers 429 or 503.
+pub const MAX_RETRIES: u32 = 5;
+/// Seconds of traffic a bucket may send at once after idling.
+const BURST_SECONDS: f64 = 6.0;
+/// Slowdowns stop at this share of a host's configured rate.
+const MIN_RATE_FACTOR: f64 = 1.0 / 16.0;
+/// Share of the configured rate won back with each successful response.
+const RECOVERY_STEP: f64 = 0.05;
+/// Caps pauses asked for by response headers, so a bad header cannot stall a sync for hours.
+const MAX_PAUSE: Duration = Duration::from_secs(15 * 60);
+
+/// A token bucket whose rate halves on 429/503 and creeps back up on success.
+struct TokenBucket {
+    configured_per_sec: f64,
+    per_sec: f64,
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+/// What a response said about the host's limits.
+#[derive(Debug, Default, PartialEq)]
+struct RateLimitHints {
+    retry_after: Option<Duration>,
+    remaining: Option<u64>,
+    reset_after: Option<Duration>,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        let per_sec = per_minute as f64 / 60.0;
+        Self {
+            configured_per_sec: per_sec,
+            per_sec,
+            tokens: (per_sec * BURST_SECONDS).max(1.0),
+            refilled_at: now,
+            paused_until: None,
+        }
+    }
+
+    fn per_minute(&self) -> f64 {
+        self.per_sec * 60.0
+    }
+
+    /// Takes a token, or says how long to wait before asking again.
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let capacity = (self.per_sec * BURST_SECONDS).max(1.0);
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(capacity);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+
+    fn observe(&mut self, status: StatusCode, hints: &RateLimitHints, now: Instant) {
+        if is_throttled(status) {
+            self.per_sec = (self.per_sec / 2.0).max(self.configured_per_sec * MIN_RATE_FACTOR);
+            self.tokens = 0.0;
+            let pause = hints
+                .retry_after
+                .or(hints.reset_after)
+                .unwrap_or_else(|| Duration::from_secs_f64((1.0 / self.per_sec).max(1.0)));
+            self.pause(pause, now);
+        } else if let Some(pause) = hints.retry_after {
+            self.pause(pause, now);
+        } else if hints.remaining == Some(0) {
+            // The quota is spent; nothing more will succeed before it resets
+            if let Some(reset_after) = hints.reset_after {
+                self.pause(reset_after, now);
+            }
+        } else if status.is_success() {
+            self.per_sec = (self.per_sec + self.configured_per_sec * RECOVERY_STEP).min(self.configured_per_sec);
+        }
+    }
+
+    fn pause(&mut self, pause: Duration, now: Instant) {
+        let until = now + pause.min(MAX_PAUSE);
+        self.paused_until = Some(self.paused_until.map_or(until, |current| current.max(until)));
+    }
+}
+
+/// Per-host buckets, created on first use from the configured requests per minute.
+struct RateLimiter {
+    limits: BTreeMap<String, u32>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+static RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(server_config().rate_limits.clone()));
+
+impl RateLimiter {
+    fn new(limits: BTreeMap<String, u32>) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The exact host, then the longest `.suffix` pattern covering it, then `*`.
+    fn limit_for(&self, host: &str) -> Option<u32> {
+        let host = host.to_lowercase();
+        self.limits
+            .get(&host)
+            .or_else(|| {
+                self.limits
+                    .iter()
+                    .filter(|(pattern, _)| pattern.starts_with('.') && host.ends_with(pattern.as_str()))
+                    .max_by_key(|(pattern, _)| pattern.len())
+                    .map(|(_, limit)| limit)
+            })
+            .or_else(|| self.limits.get("*"))
+            .copied()
+    }
+
+    fn with_bucket<T>(&self, host: &str, f: impl FnOnce(&mut TokenBucket) -> T) -> Option<T> {
+        let per_minute = self.limit_for(host)?;
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(per_minute, Instant::now()));
+        Some(f(bucket))
+    }
+}
+
+/// Waits until `host` may be called again and returns how long that took.
+pub async fn acquire(host: &str) -> Duration {
+    let started = Instant::now();
+    while let Some(Err(wait)) = RATE_LIMITER.with_bucket(host, |bucket| bucket.try_acquire(Instant::now())) {
+        tokio::time::sleep(wait).await;
+    }
+    let throttled = started.elapsed();
+    if throttled >= Duration::from_millis(1) {
+        RATE_LIMIT_THROTTLED_SECONDS
+            .with_label_values(&[host])
+            .inc_by(throttled.as_secs_f64());
+        tracing::debug!("Throttled {} for {}ms", host, throttled.as_millis());
+    }
+    throttled
+}
+
+/// Feeds a response's status and rate limit headers back into the host's bucket.
+pub fn observe(host: &str, status: StatusCode, headers: &HeaderMap) {
+    let hints = parse_hints(headers, Utc::now());
+    let per_minute = RATE_LIMITER.with_bucket(host, |bucket| {
+        bucket.observe(status, &hints, Instant::now());
+        bucket.per_minute()
+    });
+    if let Some(per_minute) = per_minute {
+        RATE_LIMIT_PER_MINUTE.with_label_values(&[host]).set(per_minute);
+        if is_throttled(status) {
+            tracing::warn!("{} answered {}, slowing to {:.0} requests/minute", host, status, per_minute);
+        }
+    }
+}
+
+/// How long `host` is still paused for after a 429, 503 or spent quota.
+pub fn pause_remaining(host: &str) -> Duration {
+    RATE_LIMITER
+        .with_bucket(host, |bucket| {
+            bucket
+                .paused_until
+                .map_or(Duration::ZERO, |until| until.saturating_duration_since(Instant::now()))
+        })
+        .unwrap_or_default()
+}
+
+pub fn is_throttled(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// `send_traced`, repeated after a 429 or 503 once the host's pause is over. Requests whose
+/// body cannot be cloned are sent once.
+pub async fn send_with_retry(request: RequestBuilder, service: &'static str) -> reqwest::Result<Response> {
+    let mut attempts = 0;
+    loop {
+        let Some(attempt) = request.try_clone() else {
+            return request.send_traced(service).await;
+        };
+        let response = attempt.send_traced(service).await?;
+        if !is_throttled(response.status()) || attempts >= MAX_RETRIES {
+            return Ok(response);
+        }
+        attempts += 1;
+        API_RETRIES_TOTAL.with_label_values(&[service]).inc();
+        tracing::warn!(
+            "{} answered {}, retrying (attempt {}/{})",
+            service,
+            response.status(),
+            attempts,
+            MAX_RETRIES
+        );
+    }
+}
+
+/// Reads `Retry-After` (seconds or an HTTP date) and `X-RateLimit-Remaining` / `-Reset`, whose
+/// reset is epoch seconds on GitHub, seconds from now elsewhere, and an ISO timestamp on Jira.
+fn parse_hints(headers: &HeaderMap, now: DateTime<Utc>) -> RateLimitHints {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+    let until = |at: DateTime<Utc>| (at - now).to_std().unwrap_or(Duration::ZERO);
+
+    let retry_after = header("retry-after").and_then(|value| match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value).ok().map(|at| until(at.with_timezone(&Utc))),
+    });
+    let reset_after = header("x-ratelimit-reset").and_then(|value| match value.parse::<i64>() {
+        Ok(epoch) if epoch > 1_000_000_000 => DateTime::from_timestamp(epoch, 0).map(until),
+        Ok(seconds) => Some(Duration::from_secs(seconds.max(0) as u64)),
+        Err(_) => DateTime::parse_from_rfc3339(value).ok().map(|at| until(at.with_timezone(&Utc))),
+    });
+
+    RateLimitHints {
+        retry_after,
+        remaining: header("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+        reset_after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_paces() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(60, start);
+
+        for _ in 0..6 {
+            assert!(bucket.try_acquire(start).is_ok());
+        }
+        let wait = bucket.try_acquire(start).unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+        assert!(bucket.try_acquire(start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_throttling_halves_the_rate_and_success_recovers_it() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(600, start);
+
+        let hints = RateLimitHints {
+            retry_after: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        bucket.observe(StatusCode::TOO_MANY_REQUESTS, &hints, start);
+        assert_eq!(bucket.per_minute(), 300.0);
+        assert_eq!(bucket.try_acquire(start + Duration::from_secs(10)), Err(Duration::from_secs(20)));
+
+        for _ in 0..20 {
+            bucket.observe(StatusCode::OK, &RateLimitHints::default(), start);
+        }
+        assert_eq!(bucket.per_minute(), 600.0);
+
+        // Repeated 503s bottom out rather than stopping the host entirely
+        for _ in 0..10 {
+            bucket.observe(StatusCode::SERVICE_UNAVAILABLE, &RateLimitHints::default(), start);
+        }
+        assert_eq!(bucket.per_minute(), 600.0 * MIN_RATE_FACTOR);
+    }
+
+    #[test]
+    fn test_parses_retry_after_and_reset_formats() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 2, 10, 0, 0).unwrap();
+
+        let github = headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", &(now.timestamp() + 90).to_string())]);
+        let hints = parse_hints(&github, now);
+        assert_eq!(hints.remaining, Some(0));
+        assert_eq!(hints.reset_after, Some(Duration::from_secs(90)));
+
+        let jira = headers(&[("retry-after", "12"), ("x-ratelimit-reset", "2025-06-02T10:01:00Z")]);
+        let hints = parse_hints(&jira, now);
+        assert_eq!(hints.retry_after, Some(Duration::from_secs(12)));
+        assert_eq!(hints.reset_after, Some(Duration::from_secs(60)));
+
+        let dated = headers(&[("retry-after", "Mon, 02 Jun 2025 10:00:45 GMT")]);
+        assert_eq!(parse_hints(&dated, now).retry_after, Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn test_host_patterns() {
+        let limiter = RateLimiter::new(BTreeMap::from([
+            (".atlassian.net".to_string(), 600),
+            ("api.github.com".to_string(), 80),
+            ("*".to_string(), 300),
+        ]));
+        assert_eq!(limiter.limit_for("acme.atlassian.net"), Some(600));
+        assert_eq!(limiter.limit_for("API.GitHub.com"), Some(80));
+        assert_eq!(limiter.limit_for("example.com"), Some(300));
+    }
+}
@@ -1,32 +1,100 @@
 use crate::constants::PAGE_SIZE;
 
+/// Expansions requested with issue searches, so most issues need no follow-up `GET /issue/{id}`.
+pub const SEARCH_EXPAND: &str = "changelog,renderedFields,names,schema";
+
+/// `https://{domain}`, unless the configured domain already carries a scheme (a local mock or proxy).
+pub fn jira_base_url(domain: &str) -> String {
+    if domain.starts_with("http://") || domain.starts_with("https://") {
+        domain.trim_end_matches('/').to_string()
+    } else {
+        format!("https://{}", domain)
+    }
+}
+
 pub fn get_projects_api_route(domain: String) -> String {
-    return format!("https://{}/rest/api/3/project", domain);
+    return format!("{}/rest/api/3/project", jira_base_url(&domain));
 }
 
 pub fn search_issues_for_project(domain: &String, project_id: &str, start_at: usize) -> String {
     return format!(
-        "https://{}/rest/api/3/search?jql=project={}&startAt={}&maxResults={}",
-        domain, project_id, start_at, PAGE_SIZE
+        "{}/rest/api/3/search?jql=project={}&startAt={}&maxResults={}",
+        jira_base_url(domain), project_id, start_at, PAGE_SIZE
     );
 }
 
-pub fn search_issues_for_project_all_types(domain: &String, project_id: &str, start_at: usize) -> String {
+/// JQL for every issue type of a project, optionally only those updated since a JQL date.
+pub fn project_issues_jql(project_id: &str, updated_since: Option<&str>) -> String {
     // Enhanced JQL to explicitly capture all issue types including subtasks, epics, stories, etc.
-    let jql = format!(
+    let mut jql = format!(
         "project={} AND issuetype in (Epic, Story, Task, Sub-task, Bug, Feature, Improvement, \"New Feature\", Incident, \"Service Request\", Change, Problem, Subtask)",
         project_id
     );
-    return format!(
-        "https://{}/rest/api/3/search?jql={}&startAt={}&maxResults={}",
-        domain, 
-        urlencoding::encode(&jql),
-        start_at, 
+    // Incremental syncs pass a JQL date such as "-20m" to skip untouched issues
+    if let Some(since) = updated_since {
+        jql.push_str(&format!(" AND updated >= \"{}\"", since));
+    }
+    // Page tokens only stay meaningful across a resumed sync if the order is stable
+    jql.push_str(" ORDER BY created ASC, key ASC");
+    jql
+}
+
+/// One page of the token-paginated search, with all fields and `SEARCH_EXPAND` expansions.
+pub fn search_issues_jql_route(domain: &String, jql: &str, next_page_token: Option<&str>) -> String {
+    let mut url = format!(
+        "{}/rest/api/3/search/jql?jql={}&maxResults={}&fields=*all&expand={}",
+        jira_base_url(domain),
+        urlencoding::encode(jql),
+        PAGE_SIZE,
+        SEARCH_EXPAND
+    );
+    if let Some(token) = next_page_token {
+        url.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+    }
+    url
+}
+
+/// One page of the same search returning only ids and keys, for checking which issues still exist.
+pub fn search_issue_ids_route(domain: &String, jql: &str, next_page_token: Option<&str>) -> String {
+    let mut url = format!(
+        "{}/rest/api/3/search/jql?jql={}&maxResults={}&fields=key",
+        jira_base_url(domain),
+        urlencoding::encode(jql),
         PAGE_SIZE
     );
+    if let Some(token) = next_page_token {
+        url.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+    }
+    url
+}
+
+/// `POST` target taking `{"jql": ...}`; the token-paginated search no longer reports a total.
+pub fn approximate_count_route(domain: &String) -> String {
+    format!("{}/rest/api/3/search/approximate-count", jira_base_url(domain))
 }
 
 pub fn get_issue_object(domain: &String, issue_id: &String) -> String {
     // extra params -> ?fields=*all&expand=renderedFields,names,schema,editmeta,changelog,versionedRepresentations
-    return format!("https://{}/rest/api/3/issue/{}", domain, issue_id)
+    return format!("{}/rest/api/3/issue/{}", jira_base_url(domain), issue_id)
+}
+
+pub fn get_boards_route(domain: &String, project_key_or_id: &str, start_at: usize) -> String {
+    return format!(
+        "{}/rest/agile/1.0/board?projectKeyOrId={}&startAt={}&maxResults={}",
+        jira_base_url(domain), project_key_or_id, start_at, PAGE_SIZE
+    );
+}
+
+pub fn get_board_sprints_route(domain: &String, board_id: i64, start_at: usize) -> String {
+    return format!(
+        "{}/rest/agile/1.0/board/{}/sprint?startAt={}&maxResults={}",
+        jira_base_url(domain), board_id, start_at, PAGE_SIZE
+    );
+}
+
+pub fn get_sprint_issues_route(domain: &String, sprint_id: i64, start_at: usize) -> String {
+    return format!(
+        "{}/rest/agile/1.0/sprint/{}/issue?fields=key&startAt={}&maxResults={}",
+        jira_base_url(domain), sprint_id, start_at, PAGE_SIZE
+    );
 }
@@ -0,0 +1,702 @@
+use crate::attachments::fetch_issue_attachments;
+use crate::config::server_config;
+use crate::content_extractor::{queue_slack_refresh, run_queued_extractions, ExtractionSummary};
+use crate::db_utils::with_connection;
+use crate::dedupe::{run_dedupe, DEFAULT_DUPLICATE_THRESHOLD};
+use crate::events::{publish, ProgressEvent};
+use crate::expertise_engine::refresh_expertise;
+use crate::issue_graph::rebuild_issue_edges;
+use crate::jira::{reconcile_projects, sync_issues_updated_since};
+use crate::pagination::MAX_PAGE_LIMIT;
+use crate::people_sync::sync_people_from_issues;
+use crate::utils::{log_step, log_success};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use dirs::home_dir;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+// ================================
+// BACKGROUND JOB SCHEDULER
+// ================================
+
+/// Incremental syncs reach this far behind the last successful run to cover clock skew.
+const SYNC_OVERLAP_MINUTES: i64 = 5;
+const ATTACHMENT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+pub const CREATE_JOB_RUNS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS job_runs (
+    id TEXT PRIMARY KEY,
+    job TEXT NOT NULL,
+    trigger TEXT NOT NULL, -- schedule | manual
+    status TEXT NOT NULL, -- running | succeeded | failed
+    started_at TEXT NOT NULL,
+    finished_at TEXT,
+    log TEXT,
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_job_runs_job ON job_runs(job, started_at);
+"#;
+
+const INSERT_JOB_RUN: &str = r#"
+INSERT INTO job_runs (id, job, trigger, status, started_at) VALUES (?, ?, ?, 'running', ?)
+"#;
+
+const FINISH_JOB_RUN: &str = r#"
+UPDATE job_runs SET status = ?, finished_at = ?, log = ?, error = ? WHERE id = ?
+"#;
+
+/// Runs only execute inside this process, so any still marked running at startup were cut off.
+const FAIL_INTERRUPTED_JOB_RUNS: &str = r#"
+UPDATE job_runs
+SET status = 'failed', finished_at = ?, error = 'interrupted: the server stopped before the run finished'
+WHERE status = 'running'
+"#;
+
+/// Jobs the scheduler knows how to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Pulls issues updated since the last successful run (every issue when there is none); only a
+    /// sync in which every project finished counts as successful
+    JiraSync,
+    /// Lists every issue id to soft-delete issues removed from Jira and re-store moved ones,
+    /// which incremental syncs cannot see
+    JiraReconcile,
+    /// Downloads and indexes new Jira attachments
+    Attachments,
+    /// Queues stored Slack threads and messages again so new replies are picked up, then
+    /// runs the extraction queue
+    SlackSync,
+    /// Runs queued content extraction jobs, such as links found by syncs and webhooks
+    ContentExtraction,
+    /// Rebuilds the dependency graph, people graph, expertise scores and duplicate clusters
+    AnalyticsRefresh,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::JiraSync => "jira_sync",
+            JobKind::JiraReconcile => "jira_reconcile",
+            JobKind::Attachments => "attachments",
+            JobKind::SlackSync => "slack_sync",
+            JobKind::ContentExtraction => "content_extraction",
+            JobKind::AnalyticsRefresh => "analytics_refresh",
+        }
+    }
+
+    /// Jobs sharing a lock never run at the same time: both Jira jobs write the same
+    /// projects' issues, and both content jobs drain the same extraction queue.
+    fn lock(&self) -> &'static str {
+        match self {
+            JobKind::JiraSync | JobKind::JiraReconcile => "jira",
+            JobKind::SlackSync | JobKind::ContentExtraction => "content",
+            other => other.as_str(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            JobKind::JiraSync,
+            JobKind::JiraReconcile,
+            JobKind::Attachments,
+            JobKind::SlackSync,
+            JobKind::ContentExtraction,
+            JobKind::AnalyticsRefresh,
+        ]
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobConfig {
+    pub job: JobKind,
+    /// Five-field cron expression in UTC, or `@hourly` / `@daily` / `@weekly`
+    pub schedule: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Jira project keys; defaults to every project that has synced issues
+    #[serde(default)]
+    pub projects: Option<Vec<String>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    pub jobs: Vec<JobConfig>,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        let job = |job, schedule: &str| JobConfig {
+            job,
+            schedule: schedule.to_string(),
+            enabled: true,
+            projects: None,
+        };
+        Self {
+            jobs: vec![
+                job(JobKind::JiraSync, "*/15 * * * *"),
+                job(JobKind::JiraReconcile, "0 3 * * *"),
+                job(JobKind::Attachments, "0 * * * *"),
+                job(JobKind::SlackSync, "30 * * * *"),
+                job(JobKind::ContentExtraction, "*/5 * * * *"),
+                job(JobKind::AnalyticsRefresh, "0 2 * * *"),
+            ],
+        }
+    }
+}
+
+fn get_schedule_path() -> PathBuf {
+    let mut path = home_dir().expect("Could not find home directory");
+    path.push(".triage");
+    path.push("schedule.json");
+    path
+}
+
+/// `~/.triage/schedule.json` when present, otherwise the built-in schedule.
+pub fn load_schedule_config() -> Result<ScheduleConfig, String> {
+    let path = get_schedule_path();
+    if !path.exists() {
+        return Ok(ScheduleConfig::default());
+    }
+    let data = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let config: ScheduleConfig =
+        serde_json::from_str(&data).map_err(|e| format!("{}: {}", path.display(), e))?;
+    for job in &config.jobs {
+        CronSchedule::parse(&job.schedule).map_err(|e| format!("{}: {}", job.job.as_str(), e))?;
+    }
+    Ok(config)
+}
+
+// ================================
+// CRON EXPRESSIONS
+// ================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!("expected 5 cron fields, got {}", fields.len()));
+        };
+
+        let mut days_of_week = parse_cron_field(day_of_week, 0, 7)?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+
+        Ok(Self {
+            minutes: parse_cron_field(minute, 0, 59)?,
+            hours: parse_cron_field(hour, 0, 23)?,
+            days_of_month: parse_cron_field(day_of_month, 1, 31)?,
+            months: parse_cron_field(month, 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// Cron semantics: when both day fields are restricted, either one matching is enough.
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month[date.day() as usize];
+        let dow = self.days_of_week[date.weekday().num_days_from_sunday() as usize];
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// First matching minute strictly after `after`, searched up to four years ahead.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after + Duration::days(4 * 366);
+
+        while t <= limit {
+            if !self.months[t.month() as usize] {
+                let (year, month) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?.and_utc();
+            } else if !self.day_matches(t.date_naive()) {
+                t = (t.date_naive() + Duration::days(1)).and_hms_opt(0, 0, 0)?.and_utc();
+            } else if !self.hours[t.hour() as usize] {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if !self.minutes[t.minute() as usize] {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+/// Supports `*`, `*/n`, `a`, `a-b`, `a-b/n` and comma-separated lists of those.
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut allowed = vec![false; max as usize + 1];
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step in '{}'", part))?,
+            ),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_cron_value(a, part)?, parse_cron_value(b, part)?)
+        } else {
+            let value = parse_cron_value(range, part)?;
+            // "5/15" means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            allowed[value as usize] = true;
+        }
+    }
+
+    Ok(allowed)
+}
+
+fn parse_cron_value(value: &str, part: &str) -> Result<u32, String> {
+    value.parse::<u32>().map_err(|_| format!("invalid value in '{}'", part))
+}
+
+// ================================
+// JOB EXECUTION
+// ================================
+
+#[derive(Debug, Serialize)]
+pub struct JobRun {
+    pub id: String,
+    pub job: String,
+    pub trigger: String,
+    pub status: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobOverview {
+    pub job: JobKind,
+    pub schedule: String,
+    pub enabled: bool,
+    pub running: bool,
+    pub next_run: Option<String>,
+    pub last_run: Option<JobRun>,
+}
+
+#[derive(Debug)]
+pub enum TriggerError {
+    /// The given job holds the lock this one needs
+    AlreadyRunning(JobKind),
+}
+
+/// Jobs currently executing by lock; a job never starts while another run holding its lock
+/// is still going.
+static RUNNING_JOBS: Lazy<Mutex<HashMap<&'static str, JobKind>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct RunningGuard(JobKind);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING_JOBS.lock().unwrap().remove(self.0.lock());
+    }
+}
+
+pub async fn initialize_scheduler_tables() {
+    with_connection("initialize_scheduler_tables", |conn| {
+        conn.execute_batch(CREATE_JOB_RUNS_TABLE)
+            .expect("Failed to create job_runs table");
+        conn.execute(FAIL_INTERRUPTED_JOB_RUNS, [Utc::now().to_rfc3339()])
+            .expect("Failed to close interrupted job runs");
+    });
+}
+
+/// Starts `job` in the background and returns the id of its run.
+pub fn trigger_job(job: JobConfig, trigger: &str) -> Result<String, TriggerError> {
+    {
+        let mut running = RUNNING_JOBS.lock().unwrap();
+        if let Some(holder) = running.get(job.job.lock()) {
+            return Err(TriggerError::AlreadyRunning(*holder));
+        }
+        running.insert(job.job.lock(), job.job);
+    }
+    let guard = RunningGuard(job.job);
+
+    let run_id = Uuid::new_v4().to_string();
+    let started_at = Utc::now();
+    with_connection("trigger_job", |conn| {
+        conn.execute(
+            INSERT_JOB_RUN,
+            [run_id.as_str(), job.job.as_str(), trigger, started_at.to_rfc3339().as_str()],
+        )
+        .expect("Failed to record job run");
+    });
+
+    publish(
+        job.job.as_str(),
+        ProgressEvent::JobStarted {
+            run_id: run_id.clone(),
+            trigger: trigger.to_string(),
+        },
+    );
+
+    let id = run_id.clone();
+    let trigger = trigger.to_string();
+    tokio::spawn(async move {
+        let _guard = guard;
+        log_step("⏰", &format!("Running job {} ({})...", job.job.as_str(), trigger));
+
+        // A separate task so a panicking job is recorded as failed instead of taking the lock with it
+        let outcome = match tokio::spawn(execute_job(job.clone())).await {
+            Ok(outcome) => outcome,
+            Err(e) => Err((Vec::new(), format!("job panicked: {}", e))),
+        };
+        let (status, log, error) = match outcome {
+            Ok(log) => ("succeeded", log, None),
+            Err((log, error)) => ("failed", log, Some(error)),
+        };
+        let log = serde_json::to_string(&log).unwrap_or_default();
+
+        with_connection("finish_job_run", |conn| {
+            conn.execute(
+                FINISH_JOB_RUN,
+                duckdb::params![status, Utc::now().to_rfc3339(), log, error, id],
+            )
+            .expect("Failed to finish job run");
+        });
+        publish(
+            job.job.as_str(),
+            ProgressEvent::JobFinished {
+                run_id: id.clone(),
+                status: status.to_string(),
+                error: error.clone(),
+            },
+        );
+        match status {
+            "succeeded" => log_success(&format!("Job {} finished.", job.job.as_str())),
+            _ => tracing::error!("Job {} failed: {}", job.job.as_str(), error.unwrap_or_default()),
+        }
+    });
+
+    Ok(run_id)
+}
+
+type JobOutcome = Result<Vec<String>, (Vec<String>, String)>;
+
+async fn execute_job(job: JobConfig) -> JobOutcome {
+    let mut log = Vec::new();
+
+    match job.job {
+        JobKind::JiraSync => {
+            let projects = job.projects.clone().unwrap_or_else(synced_project_keys);
+            if projects.is_empty() {
+                return Err((log, "no synced projects; run `triage projects` first".to_string()));
+            }
+            let since = last_successful_start(JobKind::JiraSync).map(|started| {
+                let minutes = (Utc::now() - started).num_minutes() + SYNC_OVERLAP_MINUTES;
+                format!("-{}m", minutes.max(1))
+            });
+            log.push(format!(
+                "Syncing {} project(s): {} ({})",
+                projects.len(),
+                projects.join(", "),
+                since.as_ref().map(|s| format!("updated >= {}", s)).unwrap_or_else(|| "full sync".to_string())
+            ));
+            // A failed run leaves `last_successful_start` alone, so the next one covers this window again
+            if let Err(e) = sync_issues_updated_since(&projects, since).await {
+                return Err((log, e));
+            }
+        }
+        JobKind::JiraReconcile => {
+            let projects = job.projects.clone().unwrap_or_else(synced_project_keys);
+            if projects.is_empty() {
+                return Err((log, "no synced projects; run `triage projects` first".to_string()));
+            }
+            log.push(format!("Reconciling {} project(s): {}", projects.len(), projects.join(", ")));
+            if let Err(e) = reconcile_projects(&projects).await {
+                return Err((log, e));
+            }
+        }
+        JobKind::Attachments => {
+            let scopes: Vec<Option<&str>> = match &job.projects {
+                Some(keys) => keys.iter().map(|key| Some(key.as_str())).collect(),
+                None => vec![None],
+            };
+            for scope in scopes {
+                let summary = fetch_issue_attachments(scope, ATTACHMENT_MAX_BYTES).await;
+                log.push(format!(
+                    "{}: {} stored, {} already stored, {} skipped, {} failed",
+                    scope.unwrap_or("all projects"),
+                    summary.stored,
+                    summary.already_stored,
+                    summary.skipped,
+                    summary.failed
+                ));
+            }
+        }
+        JobKind::SlackSync => {
+            let queued = queue_slack_refresh();
+            log.push(format!("Queued {} stored Slack conversation(s) for refresh", queued));
+            log.push(extraction_log(run_queued_extractions(server_config().extraction_workers).await));
+        }
+        JobKind::ContentExtraction => {
+            log.push(extraction_log(run_queued_extractions(server_config().extraction_workers).await));
+        }
+        JobKind::AnalyticsRefresh => {
+            rebuild_issue_edges().await;
+            log.push("Rebuilt issue dependency graph".to_string());
+            sync_people_from_issues().await;
+            log.push("Synced people from issues".to_string());
+            refresh_expertise().await;
+            log.push("Refreshed expertise scores".to_string());
+            let report = run_dedupe(None, DEFAULT_DUPLICATE_THRESHOLD);
+            log.push(format!(
+                "Found {} duplicate cluster(s) across {} issue(s)",
+                report.clusters.len(),
+                report.issues_scanned
+            ));
+        }
+    }
+
+    Ok(log)
+}
+
+fn extraction_log(summary: ExtractionSummary) -> String {
+    format!(
+        "Extraction jobs: {} completed, {} to retry, {} failed",
+        summary.completed, summary.retrying, summary.failed
+    )
+}
+
+/// Project keys that have issues stored locally.
+pub fn synced_project_keys() -> Vec<String> {
+    let mut keys = Vec::new();
+    with_connection("synced_project_keys", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT project_key FROM live_issues WHERE project_key IS NOT NULL AND project_key <> '' ORDER BY project_key")
+            .expect("Failed to prepare project key query");
+        keys = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .expect("Failed to query project keys")
+            .flatten()
+            .collect();
+    });
+    keys
+}
+
+fn last_successful_start(job: JobKind) -> Option<DateTime<Utc>> {
+    let mut started_at = None;
+    with_connection("last_successful_start", |conn| {
+        started_at = conn
+            .query_row(
+                "SELECT MAX(started_at) FROM job_runs WHERE job = ? AND status = 'succeeded'",
+                [job.as_str()],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .unwrap_or(None);
+    });
+    started_at
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// ================================
+// SCHEDULER LOOP AND QUERIES
+// ================================
+
+/// Runs every enabled job on its schedule for as long as the server is up.
+pub async fn run_scheduler(config: ScheduleConfig) {
+    for job in config.jobs.into_iter().filter(|job| job.enabled) {
+        let Ok(schedule) = CronSchedule::parse(&job.schedule) else {
+            continue;
+        };
+        tracing::info!("Scheduled {} at '{}'", job.job.as_str(), job.schedule);
+
+        tokio::spawn(async move {
+            while let Some(next) = schedule.next_after(Utc::now()) {
+                let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+                if let Err(TriggerError::AlreadyRunning(holder)) = trigger_job(job.clone(), "schedule") {
+                    tracing::info!("Skipping {}: {} is still running", job.job.as_str(), holder.as_str());
+                }
+            }
+        });
+    }
+}
+
+pub fn is_running(job: JobKind) -> bool {
+    RUNNING_JOBS.lock().unwrap().get(job.lock()) == Some(&job)
+}
+
+pub fn job_overviews(config: &ScheduleConfig) -> Vec<JobOverview> {
+    config
+        .jobs
+        .iter()
+        .map(|job| JobOverview {
+            job: job.job,
+            schedule: job.schedule.clone(),
+            enabled: job.enabled,
+            running: is_running(job.job),
+            next_run: CronSchedule::parse(&job.schedule)
+                .ok()
+                .filter(|_| job.enabled)
+                .and_then(|schedule| schedule.next_after(Utc::now()))
+                .map(|t| t.to_rfc3339()),
+            last_run: list_job_runs(Some(job.job), 1).into_iter().next(),
+        })
+        .collect()
+}
+
+pub fn list_job_runs(job: Option<JobKind>, limit: usize) -> Vec<JobRun> {
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+    let mut runs = Vec::new();
+    with_connection("list_job_runs", |conn| {
+        let filter = if job.is_some() { "WHERE job = ?" } else { "" };
+        let query = format!(
+            "SELECT id, job, trigger, status, started_at, finished_at, error FROM job_runs {} ORDER BY started_at DESC LIMIT {}",
+            filter, limit
+        );
+        let params: Vec<&str> = job.iter().map(|j| j.as_str()).collect();
+        let mut stmt = conn.prepare(&query).expect("Failed to prepare job run query");
+        runs = stmt
+            .query_map(duckdb::params_from_iter(params), |row| {
+                Ok(JobRun {
+                    id: row.get(0)?,
+                    job: row.get(1)?,
+                    trigger: row.get(2)?,
+                    status: row.get(3)?,
+                    started_at: row.get(4)?,
+                    finished_at: row.get(5)?,
+                    log: None,
+                    error: row.get(6)?,
+                })
+            })
+            .expect("Failed to query job runs")
+            .flatten()
+            .collect();
+    });
+    runs
+}
+
+pub fn get_job_run(run_id: &str) -> Option<JobRun> {
+    let mut run = None;
+    with_connection("get_job_run", |conn| {
+        run = conn
+            .query_row(
+                "SELECT id, job, trigger, status, started_at, finished_at, log, error FROM job_runs WHERE id = ?",
+                [run_id],
+                |row| {
+                    let log: Option<String> = row.get(6)?;
+                    Ok(JobRun {
+                        id: row.get(0)?,
+                        job: row.get(1)?,
+                        trigger: row.get(2)?,
+                        status: row.get(3)?,
+                        started_at: row.get(4)?,
+                        finished_at: row.get(5)?,
+                        log: Some(log.and_then(|raw| serde_json::from_str(&raw).ok()).unwrap_or_default()),
+                        error: row.get(7)?,
+                    })
+                },
+            )
+            .ok();
+    });
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_next_run_for_common_schedules() {
+        let every_15 = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(every_15.next_after(at(2025, 3, 4, 10, 7)), Some(at(2025, 3, 4, 10, 15)));
+        assert_eq!(every_15.next_after(at(2025, 3, 4, 10, 45)), Some(at(2025, 3, 4, 11, 0)));
+
+        let nightly = CronSchedule::parse("30 2 * * *").unwrap();
+        assert_eq!(nightly.next_after(at(2025, 12, 31, 3, 0)), Some(at(2026, 1, 1, 2, 30)));
+
+        // 2025-03-04 is a Tuesday; the next Sunday is the 9th
+        let weekly = CronSchedule::parse("@weekly").unwrap();
+        assert_eq!(weekly.next_after(at(2025, 3, 4, 10, 0)), Some(at(2025, 3, 9, 0, 0)));
+
+        let weekdays = CronSchedule::parse("0 9 * * 1-5").unwrap();
+        assert_eq!(weekdays.next_after(at(2025, 3, 7, 10, 0)), Some(at(2025, 3, 10, 9, 0)));
+    }
+
+    #[test]
+    fn test_jobs_sharing_data_share_a_lock() {
+        assert_eq!(JobKind::JiraSync.lock(), JobKind::JiraReconcile.lock());
+        assert_eq!(JobKind::SlackSync.lock(), JobKind::ContentExtraction.lock());
+        assert_ne!(JobKind::JiraSync.lock(), JobKind::Attachments.lock());
+
+        RUNNING_JOBS.lock().unwrap().insert(JobKind::JiraSync.lock(), JobKind::JiraSync);
+        let _guard = RunningGuard(JobKind::JiraSync);
+        assert!(is_running(JobKind::JiraSync));
+        assert!(!is_running(JobKind::JiraReconcile));
+    }
+
+    #[test]
+    fn test_every_default_job_round_trips_by_name() {
+        for job in ScheduleConfig::default().jobs {
+            assert_eq!(JobKind::from_name(job.job.as_str()), Some(job.job));
+            assert!(CronSchedule::parse(&job.schedule).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 5-3 * * *").is_err());
+        assert!(CronSchedule::parse("0,30 9-17/2 1 */3 7").is_ok());
+    }
+}
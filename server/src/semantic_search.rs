@@ -1,5 +1,6 @@
 use crate::db_utils::with_connection;
 use crate::utils::{log_step, log_success};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use regex::Regex;
@@ -38,7 +39,7 @@ pub struct SearchContext {
     pub related_concepts: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct RelatedItem {
     pub id: String,
     pub title: String,
@@ -292,7 +293,7 @@ async fn perform_base_search(query: &SemanticSearchQuery, _analysis: &QueryAnaly
         SELECT id, key, summary, description, comment, status, created,
                JSON_EXTRACT(project, '$.name') as project_name,
                labels
-        FROM issues 
+        FROM live_issues 
         WHERE (summary LIKE ?1 OR description LIKE ?1 OR comment LIKE ?1)
            OR (? IN (summary, description, comment))
         ORDER BY created DESC
@@ -416,7 +417,7 @@ async fn find_related_items(item_id: &str, content: &str) -> Vec<RelatedItem> {
     with_connection("find_related", |conn| {
         let query = r#"
         SELECT id, key, summary, description
-        FROM issues 
+        FROM live_issues 
         WHERE id != ?1 
           AND (description LIKE ?2 OR summary LIKE ?2)
         LIMIT 5
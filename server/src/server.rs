@@ -1,83 +1,181 @@
 use crate::advanced_analytics::generate_advanced_metrics;
+use crate::agile::{calculate_sprint_metrics, list_sprints};
 use crate::analytics::{generate_analytics_dashboard, get_time_series_data};
+use crate::dedupe::{find_similar_issues, record_not_duplicate, DEFAULT_SIMILAR_THRESHOLD};
+use crate::dependency_risk::{analyze_dependency_risks, DEFAULT_MAX_CHAIN, DEFAULT_STALE_DAYS};
 use crate::graph::{analyze_graph_patterns, generate_knowledge_graph};
+use crate::issue_graph::DependencyGraph;
 use crate::smart_graph::generate_smart_graph;
+use crate::silo_report::build_silo_report;
 use crate::knowledge_engine::build_knowledge_base;
 use crate::semantic_search::{semantic_search, SemanticSearchQuery};
 use crate::unified_search::{unified_search, UnifiedSearchRequest};
 use crate::sync_status::get_sync_status;
+use crate::scheduler::{get_job_run, job_overviews, list_job_runs, load_schedule_config, trigger_job, JobKind, ScheduleConfig, TriggerError};
+use crate::triage_suggest::{suggest_triage, TicketText};
 use crate::user_notes::{create_note, get_user_notes, create_saved_view, get_saved_views, update_view_usage, toggle_view_favorite, CreateNoteRequest, CreateViewRequest};
 use crate::google_auth::{GoogleAuthManager, GoogleOAuthConfig, GoogleTokens};
 use crate::slack_auth::{SlackAuthManager, SlackOAuthConfig, SlackTokens};
+use crate::access_control::Viewer;
+use crate::accounts::{consume_oauth_state, issue_oauth_state, require_auth, user_count, AuthUser};
+use crate::config::{server_config, ServerConfig};
 use crate::content_extractor::JobPriority;
+use crate::events::subscribe as subscribe_events;
+use crate::pagination::{ListSpec, PageQuery, MAX_PAGE_LIMIT};
+use crate::openapi::{DOCS_PAGE, OPENAPI_DOCUMENT};
+use crate::telemetry::{render_metrics, track_http};
+use crate::webhook::{process_jira_event, verify_request, webhook_secret, JiraWebhookEvent, WebhookAction, SIGNATURE_HEADER, WEBHOOK_SECRET_ENV};
 use axum::{
+    body::Bytes,
     extract::{Query, Path},
-    http::StatusCode,
-    response::{Json},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, Json,
+    },
     routing::{get, post, put},
     Router,
 };
+use futures::stream::{Stream, StreamExt};
+use schemars::JsonSchema;
 use serde::Deserialize;
-use std::collections::HashMap;
-use tower_http::cors::CorsLayer;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
-#[derive(Deserialize)]
-struct GraphQuery {
-    limit: Option<usize>,
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct GraphQuery {
+    /// Comma-separated node types to keep, e.g. `Issue,Person`
     node_types: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct TimeSeriesQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct TimeSeriesQuery {
     metric: String,
     period: String,
 }
 
-#[derive(Deserialize)]
-struct SearchQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct SiloReportQuery {
+    days: Option<i64>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct SimilarIssuesQuery {
+    limit: Option<usize>,
+    threshold: Option<f64>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct TriageSuggestQuery {
+    key: Option<String>,
+    text: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct DependencyRiskQuery {
+    max_chain: Option<usize>,
+    stale_days: Option<i64>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct JobRunsQuery {
+    job: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct SearchQuery {
     q: String,
     types: Option<String>,
     threshold: Option<f64>,
-    limit: Option<usize>,
     include_related: Option<bool>,
 }
 
-#[derive(Deserialize)]
-struct NotesQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct NotesQuery {
     search: Option<String>,
-    user_id: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct GoogleAuthQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct GoogleAuthQuery {
     code: Option<String>,
     state: Option<String>,
     error: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct SlackAuthQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct SlackAuthQuery {
     code: Option<String>,
     state: Option<String>,
     error: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct ContentExtractionQuery {
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct EventsQuery {
+    /// Resume after this event; the `Last-Event-ID` header takes precedence
+    last_event_id: Option<u64>,
+    /// Only events of this job, e.g. `jira_sync` or `content_extraction`
+    job: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct ContentExtractionQuery {
     ticket_id: String,
-    user_id: String,
     priority: Option<String>,
 }
 
+const GRAPH_NODES: ListSpec = ListSpec {
+    id_field: "id",
+    sortable: &["size", "label", "node_type"],
+    default_sort: "-size",
+};
+
+const SEARCH_RESULTS: ListSpec = ListSpec {
+    id_field: "id",
+    sortable: &["similarity_score", "title", "created_date", "last_updated"],
+    default_sort: "-similarity_score",
+};
+
+const UNIFIED_SEARCH_RESULTS: ListSpec = ListSpec {
+    id_field: "id",
+    sortable: &["relevance_score", "similarity_score", "title", "created_date", "last_updated"],
+    default_sort: "-relevance_score",
+};
+
+const NOTES: ListSpec = ListSpec {
+    id_field: "id",
+    sortable: &["title", "created_at", "updated_at"],
+    default_sort: "-updated_at",
+};
+
+const SAVED_VIEWS: ListSpec = ListSpec {
+    id_field: "id",
+    sortable: &["name", "created_at", "updated_at", "last_accessed", "usage_count"],
+    default_sort: "-last_accessed",
+};
+
 pub async fn create_router() -> Router {
-    Router::new()
+    // Everything except health, metrics, API docs, login and incoming webhooks needs a signed-in user
+    let protected = Router::new()
         .route("/api/graph", get(get_knowledge_graph))
         .route("/api/graph/analysis", get(get_graph_analysis))
         .route("/api/graph/smart", get(get_smart_graph))
+        .route("/api/issues/:key/blockers", get(get_issue_blockers))
+        .route("/api/issues/:key/similar", get(get_similar_issues))
+        .route("/api/issues/:key/similar/:other/dismiss", post(dismiss_similar_issue))
+        .route("/api/epics/:key/tree", get(get_epic_tree))
+        .route("/api/epics/:key/critical-path", get(get_epic_critical_path))
         .route("/api/analytics", get(get_analytics_dashboard))
         .route("/api/analytics/advanced", get(get_advanced_analytics))
         .route("/api/analytics/timeseries", get(get_time_series))
+        .route("/api/analytics/dependency-risks", get(get_dependency_risks))
+        .route("/api/analytics/sprints", get(get_sprints))
+        .route("/api/analytics/sprints/:id", get(get_sprint_metrics))
+        .route("/api/reports/silos", get(get_silo_report))
         .route("/api/knowledge", get(get_knowledge_base))
+        .route("/api/triage/suggest", get(get_triage_suggestion))
+        .route("/api/triage/suggest", post(suggest_triage_for_ticket))
         .route("/api/search", get(semantic_search_endpoint))
         .route("/api/search/unified", post(unified_search_endpoint))
         .route("/api/notes", get(get_notes_endpoint))
@@ -87,6 +185,11 @@ pub async fn create_router() -> Router {
         .route("/api/views/:id/use", put(use_view_endpoint))
         .route("/api/views/:id/favorite", put(toggle_favorite_endpoint))
         .route("/api/sync/status", get(get_sync_status_endpoint))
+        .route("/api/events", get(events_endpoint))
+        .route("/api/jobs", get(get_jobs_endpoint))
+        .route("/api/jobs/runs", get(get_job_runs_endpoint))
+        .route("/api/jobs/runs/:id", get(get_job_run_endpoint))
+        .route("/api/jobs/:job/run", post(run_job_endpoint))
         .route("/api/auth/google", get(google_auth_initiate))
         .route("/api/auth/google/callback", get(google_auth_callback))
         .route("/api/auth/google/status", get(google_auth_status))
@@ -98,43 +201,92 @@ pub async fn create_router() -> Router {
         .route("/api/content/extract", post(trigger_content_extraction))
         .route("/api/content/status", get(get_extraction_status))
         .merge(crate::people_routes::create_people_routes())
+        .merge(crate::account_routes::create_account_routes())
+        .route_layer(axum::middleware::from_fn(require_auth));
+
+    Router::new()
+        .merge(protected)
+        .merge(crate::account_routes::create_public_account_routes())
+        .route("/api/webhooks/jira", post(jira_webhook_endpoint))
+        .route("/api/openapi.json", get(openapi_endpoint))
+        .route("/api/docs", get(api_docs_page))
         .route("/health", get(health_check))
-        .layer(CorsLayer::permissive())
+        .route("/metrics", get(metrics_endpoint))
+        .layer(axum::middleware::from_fn(track_http))
+        .layer(cors_layer(server_config()))
+}
+
+/// Browsers only send the session cookie cross-origin with credentials allowed, which in turn
+/// needs explicit origins, methods and headers rather than wildcards.
+fn cors_layer(config: &ServerConfig) -> CorsLayer {
+    // Origins were validated at startup, so every one parses as a header value
+    let origins: Vec<HeaderValue> = config
+        .cors_origins
+        .iter()
+        .filter_map(|origin| origin.parse().ok())
+        .collect();
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, HeaderName::from_static("x-api-key"), HeaderName::from_static("last-event-id")])
+        .allow_credentials(true)
 }
 
 async fn health_check() -> &'static str {
     "OK"
 }
 
+async fn openapi_endpoint() -> Json<&'static serde_json::Value> {
+    Json(&*OPENAPI_DOCUMENT)
+}
+
+async fn api_docs_page() -> Html<&'static str> {
+    Html(DOCS_PAGE)
+}
+
+/// Prometheus scrape target; public like `/health` so scrapers need no credentials
+async fn metrics_endpoint() -> ([(header::HeaderName, &'static str); 1], String) {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render_metrics(),
+    )
+}
+
 async fn get_knowledge_graph(
     Query(params): Query<GraphQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match generate_knowledge_graph().await {
-        graph => {
-            // Apply optional filtering
-            let mut filtered_graph = graph;
-            
-            if let Some(limit) = params.limit {
-                filtered_graph.nodes.truncate(limit);
-                // Filter edges to only include those with both nodes still present
-                let node_ids: std::collections::HashSet<String> = filtered_graph
-                    .nodes
-                    .iter()
-                    .map(|n| n.id.clone())
-                    .collect();
-                
-                filtered_graph.edges.retain(|e| {
-                    node_ids.contains(&e.source) && node_ids.contains(&e.target)
-                });
-                
-                // Update metadata
-                filtered_graph.metadata.total_nodes = filtered_graph.nodes.len();
-                filtered_graph.metadata.total_edges = filtered_graph.edges.len();
-            }
-            
-            Ok(Json(serde_json::to_value(filtered_graph).unwrap()))
+    let graph = generate_knowledge_graph().await;
+    let mut response = serde_json::to_value(graph).unwrap();
+
+    if let Some(types) = params.node_types {
+        let types: Vec<String> = types.split(',').map(|t| t.trim().to_lowercase()).collect();
+        if let Some(nodes) = response["nodes"].as_array_mut() {
+            nodes.retain(|node| {
+                node["node_type"]
+                    .as_str()
+                    .is_some_and(|node_type| types.contains(&node_type.to_lowercase()))
+            });
         }
     }
+    page.paginate_field(&mut response, "nodes", &GRAPH_NODES)?;
+
+    // Only keep edges with both nodes on this page
+    let node_ids: HashSet<String> = response["nodes"]
+        .as_array()
+        .map(|nodes| nodes.iter().filter_map(|n| n["id"].as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    if let Some(edges) = response["edges"].as_array_mut() {
+        edges.retain(|e| {
+            e["source"].as_str().is_some_and(|id| node_ids.contains(id))
+                && e["target"].as_str().is_some_and(|id| node_ids.contains(id))
+        });
+    }
+
+    let total_edges = response["edges"].as_array().map_or(0, Vec::len);
+    response["metadata"]["total_nodes"] = serde_json::json!(node_ids.len());
+    response["metadata"]["total_edges"] = serde_json::json!(total_edges);
+    Ok(Json(response))
 }
 
 async fn get_graph_analysis() -> Result<Json<serde_json::Value>, StatusCode> {
@@ -143,6 +295,55 @@ async fn get_graph_analysis() -> Result<Json<serde_json::Value>, StatusCode> {
     Ok(Json(analysis))
 }
 
+async fn get_issue_blockers(Path(key): Path<String>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let graph = DependencyGraph::load();
+    if !graph.nodes.contains_key(&key) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let blockers = graph.upstream_blockers(&key);
+    Ok(Json(serde_json::json!({
+        "key": key,
+        "unresolved": blockers.iter().filter(|b| !b.issue.resolved).count(),
+        "blockers": blockers,
+    })))
+}
+
+async fn get_similar_issues(
+    Path(key): Path<String>,
+    Query(params): Query<SimilarIssuesQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let threshold = params.threshold.unwrap_or(DEFAULT_SIMILAR_THRESHOLD);
+    match find_similar_issues(&key, params.limit.unwrap_or(10), threshold) {
+        Some(similar) => Ok(Json(serde_json::json!({
+            "key": key,
+            "threshold": threshold,
+            "similar": similar,
+        }))),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn dismiss_similar_issue(
+    Path((key, other)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    record_not_duplicate(&key, &other);
+    Ok(Json(serde_json::json!({ "success": true, "key": key, "other": other })))
+}
+
+async fn get_epic_tree(Path(key): Path<String>) -> Result<Json<serde_json::Value>, StatusCode> {
+    match DependencyGraph::load().epic_tree(&key) {
+        Some(tree) => Ok(Json(serde_json::to_value(tree).unwrap())),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn get_epic_critical_path(Path(key): Path<String>) -> Result<Json<serde_json::Value>, StatusCode> {
+    match DependencyGraph::load().critical_path(&key) {
+        Some(path) => Ok(Json(serde_json::to_value(path).unwrap())),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
 async fn get_analytics_dashboard() -> Result<Json<serde_json::Value>, StatusCode> {
     let dashboard = generate_analytics_dashboard().await;
     Ok(Json(serde_json::to_value(dashboard).unwrap()))
@@ -155,6 +356,60 @@ async fn get_time_series(
     Ok(Json(serde_json::to_value(data).unwrap()))
 }
 
+async fn get_dependency_risks(
+    Query(params): Query<DependencyRiskQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let report = analyze_dependency_risks(
+        params.max_chain.unwrap_or(DEFAULT_MAX_CHAIN),
+        params.stale_days.unwrap_or(DEFAULT_STALE_DAYS),
+    );
+    Ok(Json(serde_json::to_value(report).unwrap()))
+}
+
+async fn get_sprints() -> Result<Json<serde_json::Value>, StatusCode> {
+    Ok(Json(serde_json::to_value(list_sprints()).unwrap()))
+}
+
+async fn get_sprint_metrics(Path(id): Path<i64>) -> Result<Json<serde_json::Value>, StatusCode> {
+    match calculate_sprint_metrics(id) {
+        Some(metrics) => Ok(Json(serde_json::to_value(metrics).unwrap())),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn get_silo_report(
+    Query(params): Query<SiloReportQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let report =
+        build_silo_report(params.days.unwrap_or(90)).map_err(|_| StatusCode::BAD_REQUEST)?;
+    Ok(Json(serde_json::to_value(report).unwrap()))
+}
+
+async fn get_triage_suggestion(
+    Query(params): Query<TriageSuggestQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let ticket = params.text.map(|text| TicketText {
+        summary: text,
+        ..Default::default()
+    });
+    if params.key.is_none() && ticket.is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    match suggest_triage(params.key.as_deref(), ticket) {
+        Some(suggestion) => Ok(Json(serde_json::to_value(suggestion).unwrap())),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn suggest_triage_for_ticket(
+    Json(ticket): Json<TicketText>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    match suggest_triage(None, Some(ticket)) {
+        Some(suggestion) => Ok(Json(serde_json::to_value(suggestion).unwrap())),
+        None => Err(StatusCode::BAD_REQUEST),
+    }
+}
+
 async fn get_smart_graph() -> Result<Json<serde_json::Value>, StatusCode> {
     let smart_graph = generate_smart_graph().await;
     Ok(Json(serde_json::to_value(smart_graph).unwrap()))
@@ -172,6 +427,7 @@ async fn get_knowledge_base() -> Result<Json<serde_json::Value>, StatusCode> {
 
 async fn semantic_search_endpoint(
     Query(params): Query<SearchQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let _search_types = params.types
         .map(|t| t.split(',').map(|s| s.trim().to_string()).collect::<Vec<String>>())
@@ -181,103 +437,207 @@ async fn semantic_search_endpoint(
         query: params.q,
         search_types: vec![], // Convert string types to enum later
         similarity_threshold: params.threshold.unwrap_or(0.3),
-        max_results: params.limit.unwrap_or(20),
+        // Pages are cut from the best matches up to the server-wide cap
+        max_results: MAX_PAGE_LIMIT,
         context_filters: Vec::new(),
         include_related: params.include_related.unwrap_or(true),
     };
     
     let results = semantic_search(query).await;
-    Ok(Json(serde_json::to_value(results).unwrap()))
+    let mut response = serde_json::to_value(results).unwrap();
+    page.paginate_field(&mut response, "results", &SEARCH_RESULTS)?;
+    Ok(Json(response))
 }
 
 async fn unified_search_endpoint(
-    Json(request): Json<UnifiedSearchRequest>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+    Json(mut request): Json<UnifiedSearchRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match unified_search(request).await {
-        Ok(results) => Ok(Json(serde_json::to_value(results).unwrap())),
+    let viewer = Viewer::for_user(&user);
+    request.user_id = Some(user.id);
+    match unified_search(request, &viewer).await {
+        Ok(results) => {
+            let mut response = serde_json::to_value(results).unwrap();
+            page.paginate_field(&mut response, "results", &UNIFIED_SEARCH_RESULTS)?;
+            Ok(Json(response))
+        }
         Err(e) => {
-            eprintln!("❌ Unified search failed: {}", e);
+            tracing::error!("Unified search failed: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
 async fn get_notes_endpoint(
+    user: AuthUser,
     Query(params): Query<NotesQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let notes = get_user_notes(params.user_id, params.search).await;
-    Ok(Json(serde_json::to_value(notes).unwrap()))
+    let notes = get_user_notes(&user.id, params.search).await;
+    let mut response = serde_json::to_value(notes).unwrap();
+    page.paginate_field(&mut response, "notes", &NOTES)?;
+    Ok(Json(response))
 }
 
 async fn create_note_endpoint(
+    user: AuthUser,
     Json(request): Json<CreateNoteRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match create_note(request).await {
+    match create_note(&user.id, request).await {
         Ok(note) => Ok(Json(serde_json::to_value(note).unwrap())),
         Err(e) => {
-            eprintln!("Failed to create note: {}", e);
+            tracing::error!("Failed to create note: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
 async fn get_views_endpoint(
-    Query(params): Query<HashMap<String, String>>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let user_id = params.get("user_id").cloned();
-    let views = get_saved_views(user_id).await;
-    Ok(Json(serde_json::to_value(views).unwrap()))
+    let views = get_saved_views(&user.id).await;
+    let mut response = serde_json::to_value(views).unwrap();
+    page.paginate_field(&mut response, "views", &SAVED_VIEWS)?;
+    Ok(Json(response))
 }
 
 async fn create_view_endpoint(
+    user: AuthUser,
     Json(request): Json<CreateViewRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match create_saved_view(request).await {
+    match create_saved_view(&user.id, request).await {
         Ok(view) => Ok(Json(serde_json::to_value(view).unwrap())),
         Err(e) => {
-            eprintln!("Failed to create view: {}", e);
+            tracing::error!("Failed to create view: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
 async fn use_view_endpoint(
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match update_view_usage(&id).await {
+    // Views owned by someone else look the same as missing ones
+    match update_view_usage(&user.id, &id).await {
         Ok(_) => Ok(Json(serde_json::json!({"success": true}))),
-        Err(e) => {
-            eprintln!("Failed to update view usage: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(_) => Err(StatusCode::NOT_FOUND),
     }
 }
 
 async fn toggle_favorite_endpoint(
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    match toggle_view_favorite(&id).await {
+    match toggle_view_favorite(&user.id, &id).await {
         Ok(is_favorite) => Ok(Json(serde_json::json!({"is_favorite": is_favorite}))),
-        Err(e) => {
-            eprintln!("Failed to toggle favorite: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(_) => Err(StatusCode::NOT_FOUND),
     }
 }
 
-async fn get_sync_status_endpoint() -> Result<Json<serde_json::Value>, StatusCode> {
-    let status = get_sync_status().await;
+async fn get_sync_status_endpoint(
+    Query(page): Query<PageQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let status = get_sync_status(page.limit()).await;
     Ok(Json(serde_json::to_value(status).unwrap()))
 }
 
+/// Live progress from syncs, content extraction and scheduled jobs. Browsers reconnect with
+/// `Last-Event-ID` automatically and get the events they missed.
+async fn events_endpoint(
+    headers: HeaderMap,
+    Query(params): Query<EventsQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .or(params.last_event_id);
+
+    let stream = subscribe_events(last_event_id, params.job).map(|published| {
+        Ok(Event::default()
+            .id(published.id.to_string())
+            .event(published.event.name())
+            .json_data(&published)
+            .unwrap_or_default())
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn get_jobs_endpoint() -> Result<Json<serde_json::Value>, StatusCode> {
+    let config = load_schedule_config().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::json!({ "jobs": job_overviews(&config) })))
+}
+
+async fn get_job_runs_endpoint(
+    Query(params): Query<JobRunsQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let job = match params.job.as_deref() {
+        Some(name) => Some(JobKind::from_name(name).ok_or(StatusCode::NOT_FOUND)?),
+        None => None,
+    };
+    Ok(Json(serde_json::json!({ "runs": list_job_runs(job, params.limit.unwrap_or(50)) })))
+}
+
+async fn get_job_run_endpoint(Path(id): Path<String>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let run = get_job_run(&id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(serde_json::to_value(run).unwrap()))
+}
+
+async fn run_job_endpoint(
+    Path(job): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+    let kind = JobKind::from_name(&job).ok_or(StatusCode::NOT_FOUND)?;
+    let config = load_schedule_config().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Jobs missing from the schedule can still be run by hand with default settings
+    let job_config = config
+        .jobs
+        .into_iter()
+        .find(|j| j.job == kind)
+        .or_else(|| ScheduleConfig::default().jobs.into_iter().find(|j| j.job == kind))
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    match trigger_job(job_config, "manual") {
+        Ok(run_id) => Ok((StatusCode::ACCEPTED, Json(serde_json::json!({ "job": job, "run_id": run_id })))),
+        Err(TriggerError::AlreadyRunning(_)) => Err(StatusCode::CONFLICT),
+    }
+}
+
+async fn jira_webhook_endpoint(
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+    let Some(secret) = webhook_secret() else {
+        tracing::error!("Jira webhook received but {} is not set", WEBHOOK_SECRET_ENV);
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    let signature = headers.get(SIGNATURE_HEADER).and_then(|v| v.to_str().ok());
+    if !verify_request(&secret, &body, signature) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let event: JiraWebhookEvent = serde_json::from_slice(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let name = event.webhook_event.clone();
+    let key = event.issue_key();
+    if event.action() == WebhookAction::Ignore || key.is_none() {
+        return Ok((StatusCode::OK, Json(serde_json::json!({ "accepted": false, "event": name }))));
+    }
+
+    // Acknowledge straight away; Jira retries deliveries that take too long
+    tokio::spawn(process_jira_event(event));
+    Ok((StatusCode::ACCEPTED, Json(serde_json::json!({ "accepted": true, "event": name, "issue": key }))))
+}
+
 async fn google_auth_initiate(
-    Query(params): Query<HashMap<String, String>>,
+    user: AuthUser,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let config = GoogleOAuthConfig::default();
     let auth_manager = GoogleAuthManager::new(config);
     
-    let user_id = params.get("user_id").unwrap_or(&"default".to_string()).clone();
-    let state = format!("user_id={}", user_id);
+    let state = issue_oauth_state(&headers, &user, "google");
     
     let auth_url = auth_manager.get_authorization_url(&state);
     
@@ -288,6 +648,8 @@ async fn google_auth_initiate(
 }
 
 async fn google_auth_callback(
+    user: AuthUser,
+    headers: HeaderMap,
     Query(params): Query<GoogleAuthQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     if let Some(error) = params.error {
@@ -298,20 +660,18 @@ async fn google_auth_callback(
     }
 
     let code = params.code.ok_or(StatusCode::BAD_REQUEST)?;
-    let state = params.state.unwrap_or_default();
-    
-    // Extract user_id from state
-    let user_id = state.split('=')
-        .nth(1)
-        .unwrap_or("default")
-        .to_string();
+    // Tokens are stored for the signed-in user; the state proves this session started the flow
+    if !consume_oauth_state(&headers, &user, "google", params.state.as_deref()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let user_id = user.id;
 
     let config = GoogleOAuthConfig::default();
     let mut auth_manager = GoogleAuthManager::new(config);
     
     // Simplified authentication flow
     if let Err(e) = auth_manager.exchange_code_for_tokens(&code).await {
-        eprintln!("❌ Google authentication failed: {}", e);
+        tracing::error!("Google authentication failed: {}", e);
         return Ok(Json(serde_json::json!({
             "success": false,
             "error": "Authentication failed"
@@ -329,7 +689,7 @@ async fn google_auth_callback(
     if let Some(tokens) = auth_manager.get_tokens() {
         store_google_tokens(&user_id, tokens).await;
         
-        println!("✅ Successfully authenticated Google for user: {} with valid tokens", user_id);
+        tracing::info!("Successfully authenticated Google for user: {} with valid tokens", user_id);
         
         Ok(Json(serde_json::json!({
             "success": true,
@@ -350,9 +710,9 @@ async fn google_auth_callback(
 
 /// Check Google authentication status for a user
 async fn google_auth_status(
-    Query(params): Query<HashMap<String, String>>,
+    user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let user_id = params.get("user_id").unwrap_or(&"default".to_string()).clone();
+    let user_id = user.id;
     
     // Try to load stored tokens
     if let Some(tokens) = load_google_tokens(&user_id).await {
@@ -384,11 +744,11 @@ async fn google_auth_status(
 
 /// Refresh Google authentication tokens for a user
 async fn google_refresh_tokens(
-    Json(request): Json<HashMap<String, String>>,
+    user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let user_id = request.get("user_id").unwrap_or(&"default".to_string()).clone();
+    let user_id = user.id;
     
-    println!("🔄 Refreshing Google tokens for user: {}", user_id);
+    tracing::info!("Refreshing Google tokens for user: {}", user_id);
     
     // Try to load stored tokens
     let Some(tokens) = load_google_tokens(&user_id).await else {
@@ -404,7 +764,7 @@ async fn google_refresh_tokens(
     
     // Refresh tokens
     if let Err(e) = auth_manager.refresh_tokens().await {
-        println!("❌ Failed to refresh Google tokens for user {}: {}", user_id, e);
+        tracing::error!("Failed to refresh Google tokens for user {}: {}", user_id, e);
         return Ok(Json(serde_json::json!({
             "success": false,
             "error": format!("Token refresh failed: {}", e)
@@ -431,6 +791,7 @@ async fn google_refresh_tokens(
 }
 
 async fn trigger_content_extraction(
+    user: AuthUser,
     Json(request): Json<ContentExtractionQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     // TODO: Get extracted links for the ticket from database
@@ -443,23 +804,24 @@ async fn trigger_content_extraction(
         _ => JobPriority::Medium,
     };
     
-    println!("🔄 Content extraction requested for ticket {} by user {} with priority {:?}", 
-             request.ticket_id, request.user_id, priority);
+    tracing::info!("Content extraction requested for ticket {} by user {} with priority {:?}", 
+             request.ticket_id, user.id, priority);
     
     Ok(Json(serde_json::json!({
         "success": true,
         "ticket_id": request.ticket_id,
-        "user_id": request.user_id,
+        "user_id": user.id,
         "priority": priority,
         "message": "Content extraction jobs queued"
     })))
 }
 
 async fn get_extraction_status(
+    user: AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let ticket_id = params.get("ticket_id");
-    let user_id = params.get("user_id");
+    let user_id = user.id;
     
     // TODO: Get actual status from ContentExtractionService
     // This is a placeholder implementation
@@ -478,14 +840,15 @@ async fn get_extraction_status(
 }
 
 async fn slack_auth_initiate(
+    user: AuthUser,
+    headers: HeaderMap,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let config = SlackOAuthConfig::default();
     let auth_manager = SlackAuthManager::new(config);
     
-    let user_id = params.get("user_id").unwrap_or(&"default".to_string()).clone();
     let team_id = params.get("team_id");
-    let state = format!("user_id={}", user_id);
+    let state = issue_oauth_state(&headers, &user, "slack");
     
     let auth_url = auth_manager.get_authorization_url(&state, team_id.map(|s| s.as_str()));
     
@@ -496,6 +859,8 @@ async fn slack_auth_initiate(
 }
 
 async fn slack_auth_callback(
+    user: AuthUser,
+    headers: HeaderMap,
     Query(params): Query<SlackAuthQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     // Handle OAuth errors first
@@ -508,14 +873,10 @@ async fn slack_auth_callback(
 
     // Validate required parameters
     let code = params.code.ok_or(StatusCode::BAD_REQUEST)?;
-    let state = params.state.unwrap_or_default();
-    
-    // Extract user_id from state parameter
-    let user_id = state
-        .split('=')
-        .nth(1)
-        .unwrap_or("default")
-        .to_string();
+    if !consume_oauth_state(&headers, &user, "slack", params.state.as_deref()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let user_id = user.id;
 
     // Create auth manager and exchange code for tokens
     let config = SlackOAuthConfig::default();
@@ -523,7 +884,7 @@ async fn slack_auth_callback(
     
     // Exchange code for tokens
     if let Err(e) = auth_manager.exchange_code_for_tokens(&code).await {
-        eprintln!("❌ Slack authentication failed: {}", e);
+        tracing::error!("Slack authentication failed: {}", e);
         return Ok(Json(serde_json::json!({
             "success": false,
             "error": "Authentication failed"
@@ -549,7 +910,7 @@ async fn slack_auth_callback(
         store_slack_tokens(&user_id, tokens).await;
     }
     
-    println!("✅ Slack authentication successful for user: {} in team: {}", user_id, team_name);
+    tracing::info!("Slack authentication successful for user: {} in team: {}", user_id, team_name);
     
     Ok(Json(serde_json::json!({
         "success": true,
@@ -567,10 +928,13 @@ async fn slack_auth_callback(
 async fn store_google_tokens(user_id: &str, tokens: &GoogleTokens) {
     // In a real implementation, this would store tokens in a secure database
     // For now, we'll just log the successful storage
-    println!("📦 Storing Google tokens for user: {}", user_id);
-    println!("   - Access token present: {}", !tokens.access_token.is_empty());
-    println!("   - Refresh token present: {}", tokens.refresh_token.is_some());
-    println!("   - Expires at: {}", tokens.expires_at.to_rfc3339());
+    tracing::info!(
+        user_id,
+        has_access = !tokens.access_token.is_empty(),
+        has_refresh = tokens.refresh_token.is_some(),
+        expires_at = %tokens.expires_at.to_rfc3339(),
+        "Storing Google tokens"
+    );
     
     // TODO: Implement actual database storage using content_storage.rs
     // Example:
@@ -583,7 +947,7 @@ async fn store_google_tokens(user_id: &str, tokens: &GoogleTokens) {
 // Helper function to retrieve stored Google tokens
 async fn load_google_tokens(user_id: &str) -> Option<GoogleTokens> {
     // In a real implementation, this would load tokens from database
-    println!("📥 Loading Google tokens for user: {}", user_id);
+    tracing::info!("Loading Google tokens for user: {}", user_id);
     
     // TODO: Implement actual database retrieval
     // For now, return None (no stored tokens)
@@ -603,7 +967,7 @@ async fn get_valid_google_access_token(user_id: &str) -> Result<String, Box<dyn
         // Get a valid access token (this will refresh if needed)
         match auth_manager.get_valid_access_token().await {
             Ok(token) => {
-                println!("✅ Retrieved valid Google access token for user: {}", user_id);
+                tracing::info!("Retrieved valid Google access token for user: {}", user_id);
                 
                 // If tokens were refreshed, store the new ones
                 if let Some(updated_tokens) = auth_manager.get_tokens() {
@@ -613,7 +977,7 @@ async fn get_valid_google_access_token(user_id: &str) -> Result<String, Box<dyn
                 Ok(token)
             }
             Err(e) => {
-                println!("❌ Failed to get valid Google access token for user {}: {}", user_id, e);
+                tracing::error!("Failed to get valid Google access token for user {}: {}", user_id, e);
                 Err(format!("Failed to refresh Google access token: {}", e).into())
             }
         }
@@ -625,10 +989,14 @@ async fn get_valid_google_access_token(user_id: &str) -> Result<String, Box<dyn
 // Helper function to store Slack authentication tokens
 async fn store_slack_tokens(user_id: &str, tokens: &SlackTokens) {
     // In a real implementation, this would store tokens in a secure database
-    println!("📦 Storing Slack tokens for user: {}", user_id);
-    println!("   - Access token present: {}", !tokens.access_token.is_empty());
-    println!("   - User token present: {}", tokens.user_token.is_some());
-    println!("   - Team: {} ({})", tokens.team_name, tokens.team_id);
+    tracing::info!(
+        user_id,
+        has_access = !tokens.access_token.is_empty(),
+        has_user_token = tokens.user_token.is_some(),
+        team_id = %tokens.team_id,
+        team_name = %tokens.team_name,
+        "Storing Slack tokens"
+    );
     
     // TODO: Implement actual database storage
 }
@@ -636,7 +1004,7 @@ async fn store_slack_tokens(user_id: &str, tokens: &SlackTokens) {
 // Helper function to retrieve stored Slack tokens
 async fn load_slack_tokens(user_id: &str) -> Option<SlackTokens> {
     // In a real implementation, this would load tokens from database
-    println!("📥 Loading Slack tokens for user: {}", user_id);
+    tracing::info!("Loading Slack tokens for user: {}", user_id);
     
     // TODO: Implement actual database retrieval
     None
@@ -644,9 +1012,9 @@ async fn load_slack_tokens(user_id: &str) -> Option<SlackTokens> {
 
 /// Check Slack authentication status for a user
 async fn slack_auth_status(
-    Query(params): Query<HashMap<String, String>>,
+    user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let user_id = params.get("user_id").unwrap_or(&"default".to_string()).clone();
+    let user_id = user.id;
     
     // Try to load stored tokens
     if let Some(tokens) = load_slack_tokens(&user_id).await {
@@ -681,11 +1049,11 @@ async fn slack_auth_status(
 
 /// Test Slack authentication for a user
 async fn slack_test_auth(
-    Json(request): Json<HashMap<String, String>>,
+    user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    let user_id = request.get("user_id").unwrap_or(&"default".to_string()).clone();
+    let user_id = user.id;
     
-    println!("🧪 Testing Slack authentication for user: {}", user_id);
+    tracing::info!("Testing Slack authentication for user: {}", user_id);
     
     // Try to load stored tokens
     if let Some(tokens) = load_slack_tokens(&user_id).await {
@@ -695,7 +1063,7 @@ async fn slack_test_auth(
         
         match auth_manager.test_auth().await {
             Ok(auth_test) => {
-                println!("✅ Slack auth test successful for user: {}", user_id);
+                tracing::info!("Slack auth test successful for user: {}", user_id);
                 
                 Ok(Json(serde_json::json!({
                     "success": true,
@@ -713,7 +1081,7 @@ async fn slack_test_auth(
                 })))
             }
             Err(e) => {
-                println!("❌ Slack auth test failed for user {}: {}", user_id, e);
+                tracing::error!("Slack auth test failed for user {}: {}", user_id, e);
                 Ok(Json(serde_json::json!({
                     "success": false,
                     "error": format!("Slack auth test failed: {}", e)
@@ -728,27 +1096,38 @@ async fn slack_test_auth(
     }
 }
 
-pub async fn start_server() -> Result<(), Box<dyn std::error::Error>> {
+pub async fn start_server(config: &ServerConfig) -> Result<(), Box<dyn std::error::Error>> {
     let app = create_router().await;
     
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3001")
+    let address = std::net::SocketAddr::new(config.bind_address, config.port);
+    let listener = tokio::net::TcpListener::bind(address)
         .await
-        .unwrap();
+        .map_err(|e| format!("could not bind {}: {}", address, e))?;
+    let base = config.public_base_url();
         
-    println!("🚀 Server starting on http://127.0.0.1:3001");
-    println!("📊 Knowledge graph available at http://127.0.0.1:3001/api/graph");
-    println!("🧠 Knowledge base available at http://127.0.0.1:3001/api/knowledge");
-    println!("🔍 Semantic search available at http://127.0.0.1:3001/api/search");
-    println!("🌐 Unified cross-platform search at http://127.0.0.1:3001/api/search/unified");
-    println!("📝 User notes available at http://127.0.0.1:3001/api/notes");
-    println!("📊 Sync status available at http://127.0.0.1:3001/api/sync/status");
-    println!("🔐 Google OAuth available at http://127.0.0.1:3001/api/auth/google");
-    println!("🔍 Google auth status at http://127.0.0.1:3001/api/auth/google/status?user_id=USER_ID");
-    println!("🔄 Google token refresh at http://127.0.0.1:3001/api/auth/google/refresh");
-    println!("💬 Slack OAuth available at http://127.0.0.1:3001/api/auth/slack");
-    println!("🔍 Slack auth status at http://127.0.0.1:3001/api/auth/slack/status?user_id=USER_ID");
-    println!("🧪 Slack auth test at http://127.0.0.1:3001/api/auth/slack/test");
-    println!("📄 Content extraction available at http://127.0.0.1:3001/api/content/extract");
+    tracing::info!("Server starting on {} (listening on {})", base, address);
+    tracing::info!("Knowledge graph available at {}/api/graph", base);
+    tracing::info!("Knowledge base available at {}/api/knowledge", base);
+    tracing::info!("Semantic search available at {}/api/search", base);
+    tracing::info!("Unified cross-platform search at {}/api/search/unified", base);
+    tracing::info!("User notes available at {}/api/notes", base);
+    tracing::info!("Sync status available at {}/api/sync/status", base);
+    tracing::info!("Live progress events at {}/api/events", base);
+    tracing::info!("Google OAuth available at {}/api/auth/google", base);
+    tracing::info!("Google auth status at {}/api/auth/google/status", base);
+    tracing::info!("Google token refresh at {}/api/auth/google/refresh", base);
+    tracing::info!("Slack OAuth available at {}/api/auth/slack", base);
+    tracing::info!("Slack auth status at {}/api/auth/slack/status", base);
+    tracing::info!("Slack auth test at {}/api/auth/slack/test", base);
+    tracing::info!("Sign in at {}/api/auth/login", base);
+    tracing::info!("Content extraction available at {}/api/content/extract", base);
+    tracing::info!("Scheduled jobs at {}/api/jobs", base);
+    tracing::info!("Jira webhooks accepted at {}/api/webhooks/jira", base);
+    tracing::info!("Prometheus metrics at {}/metrics", base);
+    tracing::info!("API docs at {}/api/docs", base);
+    if user_count() == 0 {
+        tracing::warn!("No accounts yet; create the first admin with `triage users add <username> --admin`");
+    }
     
     axum::serve(listener, app).await?;
     
@@ -0,0 +1,562 @@
+use crate::advanced_analytics::calculate_gini_coefficient;
+use crate::db_utils::with_connection;
+use chrono::{Duration, Utc};
+use colored::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// ================================
+// KNOWLEDGE SILO REPORT
+// ================================
+
+/// Share of recent work one person must hold before a group is flagged as a silo.
+const SILO_TOP_SHARE: f64 = 0.6;
+/// Groups with less recent work than this are too quiet to judge.
+const MIN_GROUP_ACTIVITY: usize = 5;
+const COVERAGE_TARGET: f64 = 0.8;
+const CROSS_TRAIN_CANDIDATES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SiloScope {
+    Project,
+    Component,
+    Label,
+}
+
+impl SiloScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            SiloScope::Project => "project",
+            SiloScope::Component => "component",
+            SiloScope::Label => "label",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContributorShare {
+    pub person_id: String,
+    pub display_name: String,
+    pub interactions: usize,
+    pub share: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrossTrainCandidate {
+    pub person_id: String,
+    pub display_name: String,
+    pub adjacent_interactions: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SiloEntry {
+    pub scope: SiloScope,
+    pub name: String,
+    pub project: String,
+    pub total_interactions: usize,
+    pub contributor_count: usize,
+    pub gini: f64,
+    pub top_contributor_share: f64,
+    pub people_for_80_percent: usize,
+    pub is_silo: bool,
+    pub top_contributors: Vec<ContributorShare>,
+    pub cross_train_candidates: Vec<CrossTrainCandidate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SiloReport {
+    pub window_days: i64,
+    pub generated_at: String,
+    pub silo_count: usize,
+    pub entries: Vec<SiloEntry>,
+}
+
+struct IssueGroups {
+    project: String,
+    components: Vec<String>,
+    labels: Vec<String>,
+}
+
+fn load_issue_groups() -> HashMap<String, IssueGroups> {
+    let mut issues = HashMap::new();
+
+    with_connection("load_issue_groups", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT key, project_key, labels, components, versioned_representations FROM live_issues",
+            )
+            .expect("Failed to prepare issue group query");
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })
+            .expect("Failed to query issue groups");
+
+        for (key, project_key, labels, components, versioned) in rows.flatten() {
+            let project = project_key
+                .filter(|project| !project.is_empty())
+                .or_else(|| key.rsplit_once('-').map(|(project, _)| project.to_string()))
+                .unwrap_or_default();
+            let labels: Vec<String> = labels
+                .and_then(|raw| serde_json::from_str(&raw).ok())
+                .unwrap_or_default();
+            // Issues synced before the components column existed only have it in the history
+            let components = components
+                .and_then(|raw| serde_json::from_str::<Vec<String>>(&raw).ok())
+                .or_else(|| {
+                    versioned
+                        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+                        .map(|versioned| latest_component_names(&versioned))
+                })
+                .unwrap_or_default();
+
+            issues.insert(
+                key,
+                IssueGroups {
+                    project,
+                    components,
+                    labels,
+                },
+            );
+        }
+    });
+
+    issues
+}
+
+/// Component names from the newest `versionedRepresentations.components` entry.
+fn latest_component_names(versioned: &Value) -> Vec<String> {
+    versioned["components"]
+        .as_object()
+        .and_then(|versions| {
+            versions
+                .iter()
+                .max_by_key(|(version, _)| version.parse::<u32>().unwrap_or(0))
+                .map(|(_, components)| components)
+        })
+        .and_then(|components| components.as_array())
+        .map(|components| {
+            components
+                .iter()
+                .filter_map(|component| component["name"].as_str().map(|name| name.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn load_display_names() -> HashMap<String, String> {
+    let mut names = HashMap::new();
+
+    with_connection("load_silo_display_names", |conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, display_names FROM people")
+            .expect("Failed to prepare people query");
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("Failed to query people");
+        for (id, display_names) in rows.flatten() {
+            if let Some(name) = serde_json::from_str::<Vec<String>>(&display_names)
+                .ok()
+                .and_then(|names| names.into_iter().next())
+            {
+                names.insert(id, name);
+            }
+        }
+    });
+
+    names
+}
+
+/// Jira interactions inside the window as `(person_id, issue_key)` pairs.
+fn load_recent_work(window_days: i64) -> Vec<(String, String)> {
+    let since = (Utc::now() - Duration::days(window_days))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+    let mut work = Vec::new();
+
+    with_connection("load_recent_work", |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT source_person_id, content_id FROM detailed_interactions \
+                 WHERE platform = 'jira' AND timestamp >= CAST(? AS TIMESTAMP)",
+            )
+            .expect("Failed to prepare recent work query");
+        let rows = stmt
+            .query_map([&since], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("Failed to query recent work");
+
+        work.extend(rows.flatten());
+    });
+
+    work
+}
+
+/// Builds the silo report for work done in the last `window_days` days.
+pub fn build_silo_report(window_days: i64) -> Result<SiloReport, String> {
+    if window_days <= 0 {
+        return Err(format!("window must be at least one day, got {}", window_days));
+    }
+
+    let issues = load_issue_groups();
+    let names = load_display_names();
+    let work = load_recent_work(window_days);
+    let entries = score_groups(&issues, &work, &names);
+
+    Ok(SiloReport {
+        window_days,
+        generated_at: Utc::now().to_rfc3339(),
+        silo_count: entries.iter().filter(|entry| entry.is_silo).count(),
+        entries,
+    })
+}
+
+/// Groups recent work by project, component and label and scores how
+/// concentrated each group is, silos first.
+fn score_groups(
+    issues: &HashMap<String, IssueGroups>,
+    work: &[(String, String)],
+    names: &HashMap<String, String>,
+) -> Vec<SiloEntry> {
+    // (scope, project, name) -> person -> interactions
+    let mut groups: BTreeMap<(SiloScope, String, String), HashMap<&str, usize>> = BTreeMap::new();
+
+    for (person_id, issue_key) in work {
+        let Some(issue) = issues.get(issue_key) else {
+            continue;
+        };
+        let mut keys = vec![(SiloScope::Project, issue.project.clone(), issue.project.clone())];
+        keys.extend(
+            issue
+                .components
+                .iter()
+                .map(|component| (SiloScope::Component, issue.project.clone(), component.clone())),
+        );
+        keys.extend(
+            issue
+                .labels
+                .iter()
+                .map(|label| (SiloScope::Label, issue.project.clone(), label.clone())),
+        );
+
+        for key in keys {
+            *groups
+                .entry(key)
+                .or_default()
+                .entry(person_id.as_str())
+                .or_insert(0) += 1;
+        }
+    }
+
+    let display_name = |person_id: &str| {
+        names
+            .get(person_id)
+            .cloned()
+            .unwrap_or_else(|| person_id.to_string())
+    };
+
+    let mut entries = Vec::new();
+    for (key, contributors) in &groups {
+        let (scope, project, name) = key;
+        let total: usize = contributors.values().sum();
+        if total < MIN_GROUP_ACTIVITY {
+            continue;
+        }
+
+        let mut ranked: Vec<(&str, usize)> =
+            contributors.iter().map(|(person, count)| (*person, *count)).collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut covered = 0;
+        let mut people_for_80_percent = 0;
+        for (_, count) in &ranked {
+            if covered as f64 >= COVERAGE_TARGET * total as f64 {
+                break;
+            }
+            covered += count;
+            people_for_80_percent += 1;
+        }
+
+        let distribution: HashMap<String, f64> = contributors
+            .iter()
+            .map(|(person, count)| (person.to_string(), *count as f64))
+            .collect();
+        let top_contributor_share = ranked[0].1 as f64 / total as f64;
+        let is_silo = top_contributor_share >= SILO_TOP_SHARE || people_for_80_percent <= 1;
+
+        let cross_train_candidates = if is_silo {
+            let core: HashSet<&str> = ranked
+                .iter()
+                .take(people_for_80_percent)
+                .map(|(person, _)| *person)
+                .collect();
+            adjacent_candidates(*scope, project, name, &core, &groups)
+                .into_iter()
+                .take(CROSS_TRAIN_CANDIDATES)
+                .map(|(person_id, adjacent_interactions)| CrossTrainCandidate {
+                    display_name: display_name(&person_id),
+                    person_id,
+                    adjacent_interactions,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        entries.push(SiloEntry {
+            scope: *scope,
+            name: name.clone(),
+            project: project.clone(),
+            total_interactions: total,
+            contributor_count: contributors.len(),
+            gini: calculate_gini_coefficient(&distribution),
+            top_contributor_share,
+            people_for_80_percent,
+            is_silo,
+            top_contributors: ranked
+                .iter()
+                .take(5)
+                .map(|(person_id, interactions)| ContributorShare {
+                    person_id: person_id.to_string(),
+                    display_name: display_name(person_id),
+                    interactions: *interactions,
+                    share: *interactions as f64 / total as f64,
+                })
+                .collect(),
+            cross_train_candidates,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        b.is_silo
+            .cmp(&a.is_silo)
+            .then_with(|| {
+                b.top_contributor_share
+                    .partial_cmp(&a.top_contributor_share)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.scope.cmp(&b.scope))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    entries
+}
+
+/// People outside the core who did work next to the group: other components and
+/// labels of the same project, or the project itself for component/label groups.
+fn adjacent_candidates(
+    scope: SiloScope,
+    project: &str,
+    name: &str,
+    core: &HashSet<&str>,
+    groups: &BTreeMap<(SiloScope, String, String), HashMap<&str, usize>>,
+) -> Vec<(String, usize)> {
+    let mut adjacent: HashMap<&str, usize> = HashMap::new();
+
+    for ((other_scope, other_project, other_name), contributors) in groups {
+        if other_project != project || (*other_scope == scope && other_name == name) {
+            continue;
+        }
+        // A project silo only has its own components and labels as neighbours
+        if scope == SiloScope::Project && *other_scope == SiloScope::Project {
+            continue;
+        }
+        for (person, count) in contributors {
+            if !core.contains(person) {
+                *adjacent.entry(person).or_insert(0) += count;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = adjacent
+        .into_iter()
+        .map(|(person, count)| (person.to_string(), count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+// ================================
+// CLI OUTPUT
+// ================================
+
+pub fn print_silo_report(report: &SiloReport, show_all: bool) {
+    println!(
+        "{}",
+        format!(
+            "🏝️  Knowledge silos over the last {} days ({} flagged)",
+            report.window_days, report.silo_count
+        )
+        .bold()
+    );
+    println!(
+        "{:<10} {:<10} {:<28} {:>6} {:>6} {:>6} {:>5}  {:<24} CROSS-TRAIN",
+        "SCOPE", "PROJECT", "NAME", "WORK", "GINI", "TOP1", "80%", "TOP CONTRIBUTOR"
+    );
+
+    for entry in report
+        .entries
+        .iter()
+        .filter(|entry| show_all || entry.is_silo)
+    {
+        let top = entry
+            .top_contributors
+            .first()
+            .map(|contributor| contributor.display_name.clone())
+            .unwrap_or_default();
+        let candidates = entry
+            .cross_train_candidates
+            .iter()
+            .map(|candidate| candidate.display_name.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = format!(
+            "{:<10} {:<10} {:<28} {:>6} {:>6.2} {:>5.0}% {:>5}  {:<24} {}",
+            entry.scope.as_str(),
+            truncate(&entry.project, 10),
+            truncate(&entry.name, 28),
+            entry.total_interactions,
+            entry.gini,
+            entry.top_contributor_share * 100.0,
+            entry.people_for_80_percent,
+            truncate(&top, 24),
+            if candidates.is_empty() { "-".to_string() } else { candidates }
+        );
+
+        if entry.is_silo {
+            println!("{}", line.red());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        value.to_string()
+    } else {
+        let cut: String = value.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(project: &str, components: &[&str], labels: &[&str]) -> IssueGroups {
+        IssueGroups {
+            project: project.to_string(),
+            components: components.iter().map(|c| c.to_string()).collect(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    fn work(pairs: &[(&str, &str, usize)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .flat_map(|(person, key, times)| {
+                std::iter::repeat_n((person.to_string(), key.to_string()), *times)
+            })
+            .collect()
+    }
+
+    fn entry<'a>(entries: &'a [SiloEntry], scope: SiloScope, name: &str) -> &'a SiloEntry {
+        entries
+            .iter()
+            .find(|entry| entry.scope == scope && entry.name == name)
+            .expect("missing entry")
+    }
+
+    #[test]
+    fn test_rejects_non_positive_window() {
+        assert!(build_silo_report(0).is_err());
+        assert!(build_silo_report(-7).is_err());
+    }
+
+    #[test]
+    fn test_concentrated_component_is_flagged_with_cross_train_candidates() {
+        let issues = HashMap::from([
+            ("PAY-1".to_string(), issue("PAY", &["billing"], &[])),
+            ("PAY-2".to_string(), issue("PAY", &["checkout"], &["ui"])),
+        ]);
+        let work = work(&[
+            ("alice", "PAY-1", 9),
+            ("bob", "PAY-1", 1),
+            ("bob", "PAY-2", 3),
+            ("carol", "PAY-2", 4),
+        ]);
+        let names = HashMap::from([("alice".to_string(), "Alice".to_string())]);
+
+        let entries = score_groups(&issues, &work, &names);
+
+        let billing = entry(&entries, SiloScope::Component, "billing");
+        assert_eq!(billing.total_interactions, 10);
+        assert_eq!(billing.contributor_count, 2);
+        assert!((billing.top_contributor_share - 0.9).abs() < 1e-9);
+        assert_eq!(billing.people_for_80_percent, 1);
+        assert!(billing.is_silo);
+        assert_eq!(billing.top_contributors[0].display_name, "Alice");
+        assert_eq!(billing.top_contributors[1].display_name, "bob");
+        // Both work outside the core; across the PAY project, checkout and ui, Carol has
+        // 12 adjacent interactions to Bob's 10
+        let candidates: Vec<(&str, usize)> = billing
+            .cross_train_candidates
+            .iter()
+            .map(|candidate| (candidate.person_id.as_str(), candidate.adjacent_interactions))
+            .collect();
+        assert_eq!(candidates, vec![("carol", 12), ("bob", 10)]);
+
+        let checkout = entry(&entries, SiloScope::Component, "checkout");
+        assert!(!checkout.is_silo);
+        assert_eq!(checkout.people_for_80_percent, 2);
+        assert!(checkout.cross_train_candidates.is_empty());
+
+        // Silos sort first
+        assert!(entries[0].is_silo);
+    }
+
+    #[test]
+    fn test_quiet_groups_are_skipped() {
+        let issues = HashMap::from([("OPS-1".to_string(), issue("OPS", &[], &["oncall"]))]);
+        let work = work(&[("alice", "OPS-1", 4), ("ghost", "OPS-404", 10)]);
+
+        let entries = score_groups(&issues, &work, &HashMap::new());
+
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_even_spread_is_not_a_silo() {
+        let issues = HashMap::from([("WEB-1".to_string(), issue("WEB", &[], &[]))]);
+        let work = work(&[("alice", "WEB-1", 3), ("bob", "WEB-1", 3), ("carol", "WEB-1", 3)]);
+
+        let entries = score_groups(&issues, &work, &HashMap::new());
+
+        let project = entry(&entries, SiloScope::Project, "WEB");
+        assert!(!project.is_silo);
+        assert_eq!(project.people_for_80_percent, 3);
+        assert!(project.gini.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_latest_component_names_uses_newest_version() {
+        let versioned = json!({
+            "components": {
+                "2": [{"name": "api"}],
+                "10": [{"name": "api"}, {"name": "worker"}],
+            }
+        });
+
+        assert_eq!(latest_component_names(&versioned), vec!["api", "worker"]);
+        assert!(latest_component_names(&json!({})).is_empty());
+    }
+}
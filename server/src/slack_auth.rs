@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use reqwest::Client;
+use crate::telemetry::TracedRequest;
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -129,7 +130,7 @@ impl SlackAuthManager {
         let response = self.client
             .post(token_url)
             .form(&params)
-            .send()
+            .send_traced("slack")
             .await?;
 
         let token_response: SlackTokenResponse = response.json().await?;
@@ -174,7 +175,7 @@ impl SlackAuthManager {
             .post(test_url)
             .header("Authorization", format!("Bearer {}", tokens.access_token))
             .header("Content-Type", "application/x-www-form-urlencoded")
-            .send()
+            .send_traced("slack")
             .await?;
 
         let auth_test: SlackAuthTest = response.json().await?;
@@ -230,8 +231,7 @@ impl Default for SlackOAuthConfig {
         Self {
             client_id: std::env::var("SLACK_CLIENT_ID").unwrap_or_default(),
             client_secret: std::env::var("SLACK_CLIENT_SECRET").unwrap_or_default(),
-            redirect_uri: std::env::var("SLACK_REDIRECT_URI")
-                .unwrap_or_else(|_| "http://localhost:3001/auth/slack/callback".to_string()),
+            redirect_uri: crate::config::server_config().slack_redirect_uri.clone(),
             scopes: vec![
                 "channels:read".to_string(),      // Read public channel info
                 "groups:read".to_string(),        // Read private channel info (if bot is added)
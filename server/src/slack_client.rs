@@ -1,4 +1,6 @@
+use crate::access_control::ContentAcl;
 use crate::slack_auth::SlackAuthManager;
+use crate::telemetry::TracedRequest;
 use crate::types::PlatformType;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -14,9 +16,19 @@ pub struct SlackConversationContent {
     pub messages: Vec<SlackMessage>,
     pub channel_info: SlackChannelInfo,
     pub participants: Vec<SlackUser>,
+    /// Everyone who can read the conversation; only fetched for non-public channels
+    #[serde(default)]
+    pub member_ids: Vec<String>,
     pub extracted_at: String,
 }
 
+impl SlackConversationContent {
+    /// The access list to store alongside this conversation.
+    pub fn acl(&self) -> ContentAcl {
+        ContentAcl::from_slack_conversation(self).with_resolved_people()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SlackMessage {
     pub ts: String,
@@ -251,6 +263,12 @@ impl SlackApiClient {
         let user_ids_vec: Vec<String> = user_ids.into_iter().collect();
         let participants = self.get_users_info(&user_ids_vec).await?;
 
+        let member_ids = if channel_type == SlackChannelType::Public {
+            Vec::new()
+        } else {
+            self.get_channel_members(channel_id).await?
+        };
+
         Ok(SlackConversationContent {
             channel_id: channel_id.to_string(),
             channel_name: channel_info.name.clone(),
@@ -259,6 +277,7 @@ impl SlackApiClient {
             messages,
             channel_info,
             participants,
+            member_ids,
             extracted_at: chrono::Utc::now().to_rfc3339(),
         })
     }
@@ -275,7 +294,7 @@ impl SlackApiClient {
             .header("Authorization", format!("Bearer {}", bot_token))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .form(&params)
-            .send()
+            .send_traced("slack")
             .await?;
 
         let data: Value = response.json().await?;
@@ -331,7 +350,7 @@ impl SlackApiClient {
             .header("Authorization", format!("Bearer {}", bot_token))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .form(&params)
-            .send()
+            .send_traced("slack")
             .await?;
 
         let data: Value = response.json().await?;
@@ -364,7 +383,7 @@ impl SlackApiClient {
             .header("Authorization", format!("Bearer {}", bot_token))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .form(&params)
-            .send()
+            .send_traced("slack")
             .await?;
 
         let data: Value = response.json().await?;
@@ -384,6 +403,49 @@ impl SlackApiClient {
         Ok(messages)
     }
 
+    /// Member IDs of a conversation, following Slack's cursor pagination.
+    pub async fn get_channel_members(&self, channel_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let bot_token = self.auth_manager.get_bot_token().ok_or("No bot token available")?;
+        let mut members = Vec::new();
+        let mut cursor = String::new();
+
+        loop {
+            let url = "https://slack.com/api/conversations.members";
+            let mut params = HashMap::new();
+            params.insert("channel", channel_id);
+            params.insert("limit", "200");
+            if !cursor.is_empty() {
+                params.insert("cursor", cursor.as_str());
+            }
+
+            let response = self.client
+                .post(url)
+                .header("Authorization", format!("Bearer {}", bot_token))
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .form(&params)
+                .send_traced("slack")
+                .await?;
+
+            let data: Value = response.json().await?;
+
+            if !data["ok"].as_bool().unwrap_or(false) {
+                return Err(format!("Slack API error: {}",
+                    data["error"].as_str().unwrap_or("Unknown error")).into());
+            }
+
+            if let Some(ids) = data["members"].as_array() {
+                members.extend(ids.iter().filter_map(|id| id.as_str()).map(|id| id.to_string()));
+            }
+
+            cursor = data["response_metadata"]["next_cursor"].as_str().unwrap_or("").to_string();
+            if cursor.is_empty() {
+                break;
+            }
+        }
+
+        Ok(members)
+    }
+
     async fn get_users_info(&self, user_ids: &[String]) -> Result<Vec<SlackUser>, Box<dyn std::error::Error>> {
         let bot_token = self.auth_manager.get_bot_token().ok_or("No bot token available")?;
         let mut users = Vec::new();
@@ -399,7 +461,7 @@ impl SlackApiClient {
                 .header("Authorization", format!("Bearer {}", bot_token))
                 .header("Content-Type", "application/x-www-form-urlencoded")
                 .form(&params)
-                .send()
+                .send_traced("slack")
                 .await?;
 
             let data: Value = response.json().await?;
@@ -155,7 +155,7 @@ async fn generate_enhanced_base_graph() -> KnowledgeGraph {
                     WHEN i.status IN ('In Review', 'Testing') THEN 0.5
                     ELSE 0.3
                 END as completion_score
-            FROM issues i
+            FROM live_issues i
             WHERE i.id IS NOT NULL
         ),
         enhanced_issues AS (
@@ -279,8 +279,8 @@ async fn generate_enhanced_base_graph() -> KnowledgeGraph {
                     ELSE 1.0  
                 END as status_similarity,
                 ABS(julianday(i1.created) - julianday(i2.created)) as temporal_distance
-            FROM issues i1
-            JOIN issues i2 ON i1.id != i2.id
+            FROM live_issues i1
+            JOIN live_issues i2 ON i1.id != i2.id
             WHERE i1.issue_links IS NOT NULL 
                 AND i1.issue_links != ''
                 AND i2.key IS NOT NULL
@@ -348,6 +348,7 @@ async fn generate_enhanced_base_graph() -> KnowledgeGraph {
             node_types: node_type_counts,
             edge_types: edge_type_counts,
             generated_at: Utc::now().to_rfc3339(),
+            truncated_at: None,
         },
     }
 }
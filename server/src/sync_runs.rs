@@ -0,0 +1,532 @@
+use crate::db_utils::with_connection;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// ================================
+// SYNC RUNS, CHECKPOINTS AND FAILED ISSUES
+// ================================
+
+/// A running sync checkpoints after every page; one silent for this long has died.
+const STALE_RUN_MINUTES: i64 = 30;
+/// Runs listed in `/api/sync/status`.
+pub const SYNC_RUN_HISTORY: usize = 20;
+
+pub const CREATE_SYNC_RUN_TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS sync_runs (
+    id TEXT PRIMARY KEY,
+    mode TEXT NOT NULL, -- sync | retry_failed | reconcile
+    status TEXT NOT NULL, -- running | completed | failed
+    projects TEXT NOT NULL, -- JSON array of project keys or ids
+    updated_since TEXT,
+    started_at TEXT NOT NULL,
+    heartbeat_at TEXT NOT NULL,
+    finished_at TEXT,
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_sync_runs_started ON sync_runs(started_at);
+
+CREATE TABLE IF NOT EXISTS sync_checkpoints (
+    run_id TEXT NOT NULL,
+    project TEXT NOT NULL,
+    next_start_at INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    completed BOOLEAN NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (run_id, project)
+);
+ALTER TABLE sync_checkpoints ADD COLUMN IF NOT EXISTS next_page_token TEXT;
+
+CREATE TABLE IF NOT EXISTS sync_failed_issues (
+    issue_id TEXT PRIMARY KEY,
+    issue_key TEXT NOT NULL,
+    project TEXT NOT NULL,
+    run_id TEXT NOT NULL,
+    error TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    failed_at TEXT NOT NULL,
+    resolved_at TEXT
+);
+
+-- Issue ids a run's searches returned, kept until it completes so a resumed full sync still
+-- knows which stored issues Jira no longer has
+CREATE TABLE IF NOT EXISTS sync_seen_issues (
+    run_id TEXT NOT NULL,
+    issue_id TEXT NOT NULL,
+    PRIMARY KEY (run_id, issue_id)
+);
+"#;
+
+const INSERT_SYNC_RUN: &str = r#"
+INSERT INTO sync_runs (id, mode, status, projects, updated_since, started_at, heartbeat_at)
+VALUES (?, ?, 'running', ?, ?, ?, ?)
+"#;
+
+const FINISH_SYNC_RUN: &str = r#"
+UPDATE sync_runs SET status = ?, finished_at = ?, heartbeat_at = ?, error = ? WHERE id = ?
+"#;
+
+const RESUME_SYNC_RUN: &str = r#"
+UPDATE sync_runs SET status = 'running', heartbeat_at = ?, finished_at = NULL, error = NULL WHERE id = ?
+"#;
+
+const UPSERT_CHECKPOINT: &str = r#"
+INSERT INTO sync_checkpoints (run_id, project, next_start_at, next_page_token, total, completed, updated_at)
+VALUES (?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT (run_id, project) DO UPDATE SET
+    next_start_at = excluded.next_start_at,
+    next_page_token = excluded.next_page_token,
+    total = excluded.total,
+    completed = excluded.completed,
+    updated_at = excluded.updated_at
+"#;
+
+const UPSERT_FAILED_ISSUE: &str = r#"
+INSERT INTO sync_failed_issues (issue_id, issue_key, project, run_id, error, attempts, failed_at)
+VALUES (?, ?, ?, ?, ?, 1, ?)
+ON CONFLICT (issue_id) DO UPDATE SET
+    issue_key = excluded.issue_key,
+    project = excluded.project,
+    run_id = excluded.run_id,
+    error = excluded.error,
+    attempts = CASE WHEN sync_failed_issues.resolved_at IS NULL THEN sync_failed_issues.attempts + 1 ELSE 1 END,
+    failed_at = excluded.failed_at,
+    resolved_at = NULL
+"#;
+
+/// The newest plain sync that did not complete; retry-only runs have nothing to resume.
+/// A sync that completed after it already fetched everything it missed.
+const LATEST_UNFINISHED_RUN: &str = r#"
+SELECT r.id, r.status, r.projects, r.updated_since, r.started_at, r.heartbeat_at
+FROM sync_runs r
+WHERE r.mode = 'sync' AND r.status <> 'completed'
+  AND NOT EXISTS (
+      SELECT 1 FROM sync_runs newer
+      WHERE newer.mode = 'sync' AND newer.status = 'completed' AND newer.started_at > r.started_at
+  )
+ORDER BY r.started_at DESC
+LIMIT 1
+"#;
+
+const RUN_CHECKPOINTS: &str = r#"
+SELECT project, next_start_at, next_page_token, total, completed FROM sync_checkpoints WHERE run_id = ? ORDER BY project
+"#;
+
+const UNRESOLVED_FAILED_ISSUES: &str = r#"
+SELECT issue_id, issue_key, project, error, attempts
+FROM sync_failed_issues
+WHERE resolved_at IS NULL
+ORDER BY project, issue_key
+"#;
+
+/// Live issues of a project (by key or id) that the run never saw.
+const UNSEEN_PROJECT_ISSUES: &str = r#"
+SELECT i.id, i.key
+FROM live_issues i
+WHERE (i.project_key = ? OR json_extract_string(i.project, '$.id') = ?)
+  AND NOT EXISTS (SELECT 1 FROM sync_seen_issues s WHERE s.run_id = ? AND s.issue_id = i.id)
+ORDER BY i.key
+"#;
+
+const RECENT_SYNC_RUNS: &str = r#"
+SELECT r.id, r.mode, r.status, r.projects, r.updated_since, r.started_at, r.heartbeat_at, r.finished_at, r.error,
+       (SELECT COUNT(*) FROM sync_failed_issues f WHERE f.run_id = r.id AND f.resolved_at IS NULL) AS failed_issues
+FROM sync_runs r
+ORDER BY r.started_at DESC
+LIMIT ?
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Pages through every issue of the selected projects
+    Sync,
+    /// Re-fetches only issues recorded in `sync_failed_issues`
+    RetryFailed,
+    /// Lists issue ids only, to find stored issues Jira deleted or moved
+    Reconcile,
+}
+
+impl SyncMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncMode::Sync => "sync",
+            SyncMode::RetryFailed => "retry_failed",
+            SyncMode::Reconcile => "reconcile",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    Completed,
+    /// Some projects did not finish; the run stays resumable
+    Failed,
+}
+
+/// Where a project's paging got to; saved only after the page's issues are stored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Checkpoint {
+    pub project: String,
+    /// Issues fetched so far, i.e. the offset of the next page
+    pub next_start_at: usize,
+    /// Search token for the next page; absent before the first page and once completed
+    pub next_page_token: Option<String>,
+    pub total: u64,
+    pub completed: bool,
+}
+
+/// An issue whose metadata could not be fetched, even after retries.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedIssue {
+    pub issue_id: String,
+    pub issue_key: String,
+    pub project: String,
+    pub error: String,
+    pub attempts: u32,
+}
+
+/// An interrupted sync, ready to be continued.
+#[derive(Debug)]
+pub struct ResumableRun {
+    pub id: String,
+    pub projects: Vec<String>,
+    /// Widened to cover the time since the run started
+    pub updated_since: Option<String>,
+    pub checkpoints: HashMap<String, Checkpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRunSummary {
+    pub id: String,
+    pub mode: String,
+    /// running, completed, failed, or interrupted when a running sync stopped checkpointing
+    pub status: String,
+    pub projects: Vec<String>,
+    pub updated_since: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+    pub checkpoints: Vec<Checkpoint>,
+    pub failed_issues: usize,
+}
+
+pub async fn initialize_sync_run_tables() {
+    with_connection("initialize_sync_run_tables", |conn| {
+        conn.execute_batch(CREATE_SYNC_RUN_TABLES)
+            .expect("Failed to create sync run tables");
+    });
+}
+
+/// Records a new run and returns its id.
+pub fn start_sync_run(mode: SyncMode, projects: &[String], updated_since: Option<&str>) -> String {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let projects = serde_json::to_string(projects).unwrap_or_else(|_| "[]".to_string());
+    with_connection("start_sync_run", |conn| {
+        conn.execute(
+            INSERT_SYNC_RUN,
+            duckdb::params![id, mode.as_str(), projects, updated_since, now, now],
+        )
+        .expect("Failed to record sync run");
+    });
+    id
+}
+
+pub fn finish_sync_run(run_id: &str, outcome: RunOutcome, error: Option<String>) {
+    let status = match outcome {
+        RunOutcome::Completed => "completed",
+        RunOutcome::Failed => "failed",
+    };
+    let now = Utc::now().to_rfc3339();
+    with_connection("finish_sync_run", |conn| {
+        conn.execute(FINISH_SYNC_RUN, duckdb::params![status, now, now, error, run_id])
+            .expect("Failed to finish sync run");
+        // Only a resumed run needs what it has seen so far
+        if outcome == RunOutcome::Completed {
+            conn.execute("DELETE FROM sync_seen_issues WHERE run_id = ?", [run_id])
+                .expect("Failed to clear seen issues");
+        }
+    });
+}
+
+/// Saves a project checkpoint and doubles as the run's heartbeat.
+pub fn save_checkpoint(run_id: &str, checkpoint: &Checkpoint) {
+    let now = Utc::now().to_rfc3339();
+    with_connection("save_sync_checkpoint", |conn| {
+        conn.execute(
+            UPSERT_CHECKPOINT,
+            duckdb::params![
+                run_id,
+                checkpoint.project,
+                checkpoint.next_start_at as i64,
+                checkpoint.next_page_token,
+                checkpoint.total as i64,
+                checkpoint.completed,
+                now
+            ],
+        )
+        .expect("Failed to save sync checkpoint");
+        conn.execute("UPDATE sync_runs SET heartbeat_at = ? WHERE id = ?", duckdb::params![now, run_id])
+            .expect("Failed to update sync run heartbeat");
+    });
+}
+
+pub fn record_failed_issue(run_id: &str, issue: &FailedIssue) {
+    with_connection("record_failed_issue", |conn| {
+        conn.execute(
+            UPSERT_FAILED_ISSUE,
+            duckdb::params![
+                issue.issue_id,
+                issue.issue_key,
+                issue.project,
+                run_id,
+                issue.error,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .expect("Failed to record failed issue");
+    });
+}
+
+/// Marks previously failed issues as fixed once they have been stored.
+pub fn resolve_failed_issues(issue_ids: &[String]) {
+    if issue_ids.is_empty() {
+        return;
+    }
+    let placeholders = vec!["?"; issue_ids.len()].join(", ");
+    let query = format!(
+        "UPDATE sync_failed_issues SET resolved_at = ? WHERE resolved_at IS NULL AND issue_id IN ({})",
+        placeholders
+    );
+    let now = Utc::now().to_rfc3339();
+    let mut params: Vec<&dyn duckdb::ToSql> = vec![&now];
+    params.extend(issue_ids.iter().map(|id| id as &dyn duckdb::ToSql));
+    with_connection("resolve_failed_issues", |conn| {
+        conn.execute(&query, params.as_slice())
+            .expect("Failed to resolve failed issues");
+    });
+}
+
+/// Remembers which issues the run's searches returned, including those whose fetch failed.
+pub fn record_seen_issues(run_id: &str, issue_ids: &[String]) {
+    if issue_ids.is_empty() {
+        return;
+    }
+    let placeholders = vec!["(?, ?)"; issue_ids.len()].join(", ");
+    let query = format!(
+        "INSERT INTO sync_seen_issues (run_id, issue_id) VALUES {} ON CONFLICT DO NOTHING",
+        placeholders
+    );
+    let mut params: Vec<&dyn duckdb::ToSql> = Vec::with_capacity(issue_ids.len() * 2);
+    for id in issue_ids {
+        params.push(&run_id);
+        params.push(id);
+    }
+    with_connection("record_seen_issues", |conn| {
+        conn.execute(&query, params.as_slice())
+            .expect("Failed to record seen issues");
+    });
+}
+
+/// Stored, not yet deleted issues of `project` that the run's searches did not return, as
+/// `(id, key)` pairs.
+pub fn unseen_issues(run_id: &str, project: &str) -> Vec<(String, String)> {
+    let mut issues = Vec::new();
+    with_connection("unseen_issues", |conn| {
+        let mut stmt = conn
+            .prepare(UNSEEN_PROJECT_ISSUES)
+            .expect("Failed to prepare unseen issue query");
+        issues = stmt
+            .query_map([project, project, run_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .expect("Failed to query unseen issues")
+            .flatten()
+            .collect();
+    });
+    issues
+}
+
+pub fn unresolved_failed_issues() -> Vec<FailedIssue> {
+    let mut issues = Vec::new();
+    with_connection("unresolved_failed_issues", |conn| {
+        let mut stmt = conn
+            .prepare(UNRESOLVED_FAILED_ISSUES)
+            .expect("Failed to prepare failed issue query");
+        issues = stmt
+            .query_map([], |row| {
+                Ok(FailedIssue {
+                    issue_id: row.get(0)?,
+                    issue_key: row.get(1)?,
+                    project: row.get(2)?,
+                    error: row.get(3)?,
+                    attempts: row.get::<_, i64>(4)? as u32,
+                })
+            })
+            .expect("Failed to query failed issues")
+            .flatten()
+            .collect();
+    });
+    issues
+}
+
+/// The newest unfinished sync, unless a later sync completed or it is still
+/// checkpointing in another process.
+pub fn find_resumable_run() -> Result<ResumableRun, String> {
+    let mut latest = None;
+    with_connection("find_resumable_run", |conn| {
+        latest = conn
+            .query_row(LATEST_UNFINISHED_RUN, [], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })
+            .ok();
+    });
+    let (id, status, projects, updated_since, started_at, heartbeat_at) =
+        latest.ok_or_else(|| "no interrupted sync to resume".to_string())?;
+
+    let now = Utc::now();
+    if effective_status(&status, &heartbeat_at, now) == "running" {
+        return Err(format!("sync {} is still running (last checkpoint at {})", id, heartbeat_at));
+    }
+
+    let checkpoints = run_checkpoints(&id)
+        .into_iter()
+        .map(|checkpoint| (checkpoint.project.clone(), checkpoint))
+        .collect();
+    let started_at = parse_time(&started_at).unwrap_or(now);
+
+    Ok(ResumableRun {
+        projects: serde_json::from_str(&projects).unwrap_or_default(),
+        updated_since: updated_since.map(|since| widen_updated_since(&since, started_at, now)),
+        checkpoints,
+        id,
+    })
+}
+
+/// Flags the run as running again before its remaining projects are fetched.
+pub fn mark_run_resumed(run_id: &str) {
+    with_connection("mark_sync_run_resumed", |conn| {
+        conn.execute(RESUME_SYNC_RUN, duckdb::params![Utc::now().to_rfc3339(), run_id])
+            .expect("Failed to resume sync run");
+    });
+}
+
+/// Most recent runs first, each with its checkpoints and unresolved failures.
+pub fn recent_sync_runs(limit: usize) -> Vec<SyncRunSummary> {
+    let mut runs = Vec::new();
+    let now = Utc::now();
+    with_connection("recent_sync_runs", |conn| {
+        let mut stmt = conn
+            .prepare(RECENT_SYNC_RUNS)
+            .expect("Failed to prepare sync run query");
+        runs = stmt
+            .query_map([limit as i64], |row| {
+                let status: String = row.get(2)?;
+                let heartbeat_at: String = row.get(6)?;
+                Ok(SyncRunSummary {
+                    id: row.get(0)?,
+                    mode: row.get(1)?,
+                    status: effective_status(&status, &heartbeat_at, now).to_string(),
+                    projects: serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
+                    updated_since: row.get(4)?,
+                    started_at: row.get(5)?,
+                    finished_at: row.get(7)?,
+                    error: row.get(8)?,
+                    checkpoints: Vec::new(),
+                    failed_issues: row.get::<_, i64>(9)? as usize,
+                })
+            })
+            .expect("Failed to query sync runs")
+            .flatten()
+            .collect();
+    });
+    for run in &mut runs {
+        run.checkpoints = run_checkpoints(&run.id);
+    }
+    runs
+}
+
+fn run_checkpoints(run_id: &str) -> Vec<Checkpoint> {
+    let mut checkpoints = Vec::new();
+    with_connection("sync_checkpoints", |conn| {
+        let mut stmt = conn
+            .prepare(RUN_CHECKPOINTS)
+            .expect("Failed to prepare checkpoint query");
+        checkpoints = stmt
+            .query_map([run_id], |row| {
+                Ok(Checkpoint {
+                    project: row.get(0)?,
+                    next_start_at: row.get::<_, i64>(1)? as usize,
+                    next_page_token: row.get(2)?,
+                    total: row.get::<_, i64>(3)? as u64,
+                    completed: row.get(4)?,
+                })
+            })
+            .expect("Failed to query checkpoints")
+            .flatten()
+            .collect();
+    });
+    checkpoints
+}
+
+/// A relative JQL date such as `-20m` meant "since shortly before the run started"; keep that
+/// meaning when resuming later. Absolute dates are left alone.
+fn widen_updated_since(updated_since: &str, started_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = updated_since
+        .strip_prefix('-')
+        .and_then(|rest| rest.strip_suffix('m'))
+        .and_then(|n| n.parse::<i64>().ok());
+    match minutes {
+        Some(minutes) => format!("-{}m", minutes + (now - started_at).num_minutes().max(0)),
+        None => updated_since.to_string(),
+    }
+}
+
+/// A run left `running` by a process that died shows as `interrupted`.
+fn effective_status<'a>(status: &'a str, heartbeat_at: &str, now: DateTime<Utc>) -> &'a str {
+    let stale = parse_time(heartbeat_at)
+        .is_none_or(|heartbeat| now - heartbeat > Duration::minutes(STALE_RUN_MINUTES));
+    if status == "running" && stale {
+        "interrupted"
+    } else {
+        status
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 2, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_relative_updated_since_is_widened() {
+        assert_eq!(widen_updated_since("-20m", at(10, 0), at(11, 30)), "-110m");
+        assert_eq!(widen_updated_since("-20m", at(10, 0), at(10, 0)), "-20m");
+        assert_eq!(widen_updated_since("2025-06-01 08:00", at(10, 0), at(11, 30)), "2025-06-01 08:00");
+    }
+
+    #[test]
+    fn test_silent_running_runs_are_interrupted() {
+        let heartbeat = at(10, 0).to_rfc3339();
+        assert_eq!(effective_status("running", &heartbeat, at(10, 5)), "running");
+        assert_eq!(effective_status("running", &heartbeat, at(11, 0)), "interrupted");
+        assert_eq!(effective_status("failed", &heartbeat, at(11, 0)), "failed");
+        assert_eq!(effective_status("running", "not a time", at(10, 5)), "interrupted");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::db_utils::with_connection;
+use crate::sync_runs::{recent_sync_runs, SyncRunSummary, SYNC_RUN_HISTORY};
 use crate::utils::{log_step, log_success};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -12,12 +13,16 @@ pub struct SyncStatus {
     pub comment_rich_issues: Vec<CommentRichIssue>,
     pub escl_insights: ESCLInsights,
     pub knowledge_impact: KnowledgeImpact,
+    /// Recent sync runs with their checkpoints and unresolved failures, newest first
+    pub sync_runs: Vec<SyncRunSummary>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SyncStatistics {
     pub total_projects: usize,
     pub total_issues: usize,
+    /// Soft-deleted issues, kept for history but left out of every other count
+    pub deleted_issues: usize,
     pub new_issues_since_last_sync: usize,
     pub updated_issues_since_last_sync: usize,
     pub escl_count: usize,
@@ -109,13 +114,14 @@ pub struct KnowledgeImpact {
     pub recommended_actions: Vec<String>,
 }
 
-pub async fn get_sync_status() -> SyncStatus {
+/// `limit` caps each issue list; callers pass the page limit so it matches other list endpoints.
+pub async fn get_sync_status(limit: usize) -> SyncStatus {
     log_step("📊", "Generating sync status dashboard...");
     
     let sync_statistics = get_sync_statistics().await;
-    let recent_issues = get_recent_issues(20).await;
-    let updated_issues = get_recently_updated_issues(15).await;
-    let comment_rich_issues = get_comment_rich_issues(10).await;
+    let recent_issues = get_recent_issues(limit).await;
+    let updated_issues = get_recently_updated_issues(limit).await;
+    let comment_rich_issues = get_comment_rich_issues(limit).await;
     let escl_insights = analyze_escl_insights().await;
     let knowledge_impact = calculate_knowledge_impact().await;
     
@@ -127,6 +133,7 @@ pub async fn get_sync_status() -> SyncStatus {
         comment_rich_issues,
         escl_insights,
         knowledge_impact,
+        sync_runs: recent_sync_runs(SYNC_RUN_HISTORY),
     };
     
     log_success("Sync status dashboard generated");
@@ -137,6 +144,7 @@ async fn get_sync_statistics() -> SyncStatistics {
     let mut stats = SyncStatistics {
         total_projects: 0,
         total_issues: 0,
+        deleted_issues: 0,
         new_issues_since_last_sync: 0,
         updated_issues_since_last_sync: 0,
         escl_count: 0,
@@ -153,31 +161,36 @@ async fn get_sync_statistics() -> SyncStatistics {
         }).unwrap_or(0);
 
         // Total issues
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues").unwrap();
         stats.total_issues = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE deleted_at IS NOT NULL").unwrap();
+        stats.deleted_issues = stmt.query_row([], |row| {
+            Ok(row.get::<_, i64>(0)? as usize)
+        }).unwrap_or(0);
+
         // ESCL count (assuming ESCL keys start with 'ESCL-')
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE key LIKE 'ESCL-%'").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE key LIKE 'ESCL-%'").unwrap();
         stats.escl_count = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // Issues with comments
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE comment IS NOT NULL AND comment != '{}' AND comment != ''").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE comment IS NOT NULL AND comment != '{}' AND comment != ''").unwrap();
         stats.issues_with_comments = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // Issues created in last 7 days (approximation for "new since last sync")
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE created > datetime('now', '-7 days')").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE created > datetime('now', '-7 days')").unwrap();
         stats.new_issues_since_last_sync = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // Issues updated in last 7 days
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE updated > datetime('now', '-7 days')").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE updated > datetime('now', '-7 days')").unwrap();
         stats.updated_issues_since_last_sync = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
@@ -199,8 +212,8 @@ async fn get_recent_issues(limit: usize) -> Vec<RecentIssue> {
             id, key, summary, status, created,
             JSON_EXTRACT(project, '$.name') as project_name,
             description, comment
-        FROM issues 
-        ORDER BY created DESC 
+        FROM live_issues 
+        ORDER BY created DESC, key
         LIMIT ?1
         "#;
         
@@ -252,9 +265,9 @@ async fn get_recently_updated_issues(limit: usize) -> Vec<UpdatedIssue> {
             id, key, summary, status, updated,
             JSON_EXTRACT(project, '$.name') as project_name,
             comment
-        FROM issues 
+        FROM live_issues 
         WHERE updated > datetime('now', '-7 days')
-        ORDER BY updated DESC 
+        ORDER BY updated DESC, key
         LIMIT ?1
         "#;
         
@@ -304,12 +317,12 @@ async fn get_comment_rich_issues(limit: usize) -> Vec<CommentRichIssue> {
             id, key, summary, status,
             JSON_EXTRACT(project, '$.name') as project_name,
             comment, updated
-        FROM issues 
+        FROM live_issues 
         WHERE comment IS NOT NULL 
           AND comment != '{}' 
           AND comment != ''
           AND length(comment) > 200
-        ORDER BY length(comment) DESC 
+        ORDER BY length(comment) DESC, key
         LIMIT ?1
         "#;
         
@@ -364,25 +377,25 @@ async fn analyze_escl_insights() -> ESCLInsights {
     
     with_connection("escl_insights", |conn| {
         // Total ESCLs
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE key LIKE 'ESCL-%'").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE key LIKE 'ESCL-%'").unwrap();
         insights.total_escls = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // New ESCLs (last 7 days)
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE key LIKE 'ESCL-%' AND created > datetime('now', '-7 days')").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE key LIKE 'ESCL-%' AND created > datetime('now', '-7 days')").unwrap();
         insights.new_escls = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // Resolved ESCLs
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE key LIKE 'ESCL-%' AND (status LIKE '%resolved%' OR status LIKE '%closed%' OR status LIKE '%done%')").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE key LIKE 'ESCL-%' AND (status LIKE '%resolved%' OR status LIKE '%closed%' OR status LIKE '%done%')").unwrap();
         insights.resolved_escls = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
 
         // ESCLs with rich comments
-        let mut stmt = conn.prepare("SELECT COUNT(*) FROM issues WHERE key LIKE 'ESCL-%' AND comment IS NOT NULL AND length(comment) > 200").unwrap();
+        let mut stmt = conn.prepare("SELECT COUNT(*) FROM live_issues WHERE key LIKE 'ESCL-%' AND comment IS NOT NULL AND length(comment) > 200").unwrap();
         insights.escls_with_rich_comments = stmt.query_row([], |row| {
             Ok(row.get::<_, i64>(0)? as usize)
         }).unwrap_or(0);
@@ -0,0 +1,289 @@
+use crate::config::{LogFormat, ServerConfig};
+use crate::rate_limit;
+use axum::{extract::MatchedPath, extract::Request, middleware::Next, response::Response};
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_counter_vec_with_registry, register_gauge_vec_with_registry, register_histogram_vec_with_registry,
+    register_int_counter_vec_with_registry, CounterVec, Encoder, GaugeVec, HistogramVec, IntCounterVec, Registry,
+    TextEncoder,
+};
+use std::future::Future;
+use std::time::Instant;
+use tracing::Instrument;
+use tracing_subscriber::EnvFilter;
+
+// ================================
+// LOGGING
+// ================================
+
+/// Installs the global subscriber. The configured level applies everywhere, and any
+/// per-module directives in `RUST_LOG` (e.g. `triage::jira=debug`) are layered on top.
+pub fn init_tracing(config: &ServerConfig) {
+    let mut filter = EnvFilter::new(config.log_level.as_str());
+    if let Ok(rust_log) = std::env::var("RUST_LOG") {
+        for directive in rust_log.split(',').filter(|d| d.contains('=')) {
+            match directive.trim().parse() {
+                Ok(directive) => filter = filter.add_directive(directive),
+                Err(e) => eprintln!("Ignoring RUST_LOG directive '{}': {}", directive, e),
+            }
+        }
+    }
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_target(true);
+    let result = match config.log_format {
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+        LogFormat::Text => builder.try_init(),
+    };
+    if let Err(e) = result {
+        eprintln!("Tracing subscriber already installed: {}", e);
+    }
+}
+
+// ================================
+// METRICS
+// ================================
+
+static REGISTRY: Lazy<Registry> = Lazy::new(|| Registry::new_custom(Some("triage".to_string()), None).unwrap());
+
+const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+pub static HTTP_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "http_requests_total",
+        "Requests served, by route and response status",
+        &["method", "route", "status"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static HTTP_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec_with_registry!(
+        "http_request_duration_seconds",
+        "Time spent serving requests",
+        &["method", "route"],
+        LATENCY_BUCKETS.to_vec(),
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static UPSTREAM_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "upstream_requests_total",
+        "Calls to Jira, Google and Slack, by response status (`error` when no response arrived)",
+        &["service", "status"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static UPSTREAM_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec_with_registry!(
+        "upstream_request_duration_seconds",
+        "Latency of calls to Jira, Google and Slack",
+        &["service"],
+        LATENCY_BUCKETS.to_vec(),
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static UPSTREAM_RATE_LIMITED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "upstream_rate_limited_total",
+        "429 responses received from Jira, Google and Slack",
+        &["service"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static API_RETRIES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "api_retries_total",
+        "Upstream calls repeated after a failure or rate limit",
+        &["service"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static RATE_LIMIT_THROTTLED_SECONDS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec_with_registry!(
+        "rate_limit_throttled_seconds_total",
+        "Time outbound calls waited for a host's rate limit or a requested pause",
+        &["host"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static RATE_LIMIT_PER_MINUTE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec_with_registry!(
+        "rate_limit_requests_per_minute",
+        "Current outbound request rate per host, lowered after 429/503 responses",
+        &["host"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static SYNC_ISSUES_FETCHED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "sync_issues_fetched_total",
+        "Issues returned by Jira search during syncs",
+        &["project"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static EXTRACTION_JOBS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "extraction_jobs_total",
+        "Content extraction job attempts, by outcome: completed, retrying or failed",
+        &["outcome"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+pub static DB_QUERY_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec_with_registry!(
+        "db_query_duration_seconds",
+        "Time spent inside DuckDB connections and transactions, by call site",
+        &["context"],
+        LATENCY_BUCKETS.to_vec(),
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Everything registered above in the Prometheus text format.
+pub fn render_metrics() -> String {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("Failed to encode metrics");
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+// ================================
+// SPANS
+// ================================
+
+/// Wraps each request in an `http_request` span and records its latency and status.
+/// Routes are labelled by their pattern (`/api/issues/:key`) to keep label cardinality bounded.
+pub async fn track_http(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let span = tracing::info_span!(
+        "http_request",
+        method = %method,
+        route = %route,
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    );
+    let started = Instant::now();
+    let response = next.run(request).instrument(span.clone()).await;
+    let elapsed = started.elapsed();
+    let status = response.status().as_u16();
+
+    span.record("status", status);
+    span.record("latency_ms", elapsed.as_millis() as u64);
+    span.in_scope(|| tracing::debug!("{} {} -> {}", method, route, status));
+
+    HTTP_REQUESTS_TOTAL
+        .with_label_values(&[&method, &route, &status.to_string()])
+        .inc();
+    HTTP_REQUEST_DURATION
+        .with_label_values(&[&method, &route])
+        .observe(elapsed.as_secs_f64());
+    response
+}
+
+/// `send` for upstream calls: waits for the host's rate limit, runs the request in an
+/// `upstream_request` span and counts statuses, latency and 429s under `service` ("jira",
+/// "google", "slack" or "github"). Every response is fed back to the rate limiter.
+pub trait TracedRequest {
+    fn send_traced(
+        self,
+        service: &'static str,
+    ) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl TracedRequest for reqwest::RequestBuilder {
+    fn send_traced(
+        self,
+        service: &'static str,
+    ) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send {
+        let (client, request) = self.build_split();
+        async move {
+            let request = request?;
+            let host = request.url().host_str().unwrap_or_default().to_string();
+            let span = tracing::info_span!(
+                "upstream_request",
+                service,
+                method = %request.method(),
+                host = %host,
+                path = request.url().path(),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                throttled_ms = tracing::field::Empty,
+            );
+            let throttled = rate_limit::acquire(&host).instrument(span.clone()).await;
+            span.record("throttled_ms", throttled.as_millis() as u64);
+            let started = Instant::now();
+            let result = client.execute(request).instrument(span.clone()).await;
+            let elapsed = started.elapsed();
+
+            span.record("latency_ms", elapsed.as_millis() as u64);
+            UPSTREAM_REQUEST_DURATION
+                .with_label_values(&[service])
+                .observe(elapsed.as_secs_f64());
+
+            let status = match &result {
+                Ok(response) => {
+                    let status = response.status();
+                    span.record("status", status.as_u16());
+                    rate_limit::observe(&host, status, response.headers());
+                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        UPSTREAM_RATE_LIMITED_TOTAL.with_label_values(&[service]).inc();
+                        span.in_scope(|| tracing::warn!("{} rate limited the request", service));
+                    }
+                    status.as_u16().to_string()
+                }
+                Err(e) => {
+                    span.in_scope(|| tracing::warn!("{} request failed: {}", service, e));
+                    "error".to_string()
+                }
+            };
+            UPSTREAM_REQUESTS_TOTAL
+                .with_label_values(&[service, &status])
+                .inc();
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_render_with_namespace() {
+        SYNC_ISSUES_FETCHED_TOTAL.with_label_values(&["TEST"]).inc_by(3);
+        EXTRACTION_JOBS_TOTAL.with_label_values(&["completed"]).inc();
+
+        let rendered = render_metrics();
+
+        assert!(rendered.contains("triage_sync_issues_fetched_total{project=\"TEST\"} 3"));
+        assert!(rendered.contains("triage_extraction_jobs_total{outcome=\"completed\"}"));
+    }
+}
@@ -0,0 +1,599 @@
+use crate::constants::RESOLVED_STATUSES;
+use crate::db_utils::with_connection;
+use crate::link_detector::LinkDetector;
+use crate::utils::parse_jira_timestamp;
+use colored::*;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+// ================================
+// TICKET TRIAGE SUGGESTIONS
+// ================================
+
+/// Resolved issues most similar to the ticket that get a vote.
+const NEIGHBOURS: usize = 25;
+const TOP_ASSIGNEES: usize = 3;
+const EVIDENCE_PER_ASSIGNEE: usize = 3;
+
+/// Issue count and newest `updated` of `live_issues`. Every sync, webhook or soft delete
+/// moves one of them, including those made by another `triage` process.
+type HistoryStamp = (i64, Option<String>);
+
+type CachedModel = Option<(HistoryStamp, Arc<SuggestionModel>)>;
+
+/// The model built over every resolved issue, rebuilt once the stamp it was built for goes stale.
+static CACHED_MODEL: Lazy<RwLock<CachedModel>> = Lazy::new(|| RwLock::new(None));
+
+/// Text of the ticket to triage; either an existing issue or one that hasn't been filed yet.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct TicketText {
+    pub summary: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentSuggestion {
+    pub name: String,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimilarResolvedIssue {
+    pub key: String,
+    pub summary: String,
+    pub similarity: f64,
+    pub resolution_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssigneeSuggestion {
+    pub name: String,
+    pub confidence: f64,
+    /// Similar past issues this person resolved.
+    pub evidence: Vec<SimilarResolvedIssue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResolutionEstimate {
+    pub median_hours: f64,
+    pub p25_hours: f64,
+    pub p75_hours: f64,
+    pub based_on: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TriageSuggestion {
+    pub key: Option<String>,
+    pub component: Option<ComponentSuggestion>,
+    pub assignees: Vec<AssigneeSuggestion>,
+    pub expected_resolution: Option<ResolutionEstimate>,
+    pub similar_issues_considered: usize,
+}
+
+struct HistoricalIssue {
+    key: String,
+    summary: String,
+    assignee: Option<String>,
+    components: Vec<String>,
+    resolution_hours: Option<f64>,
+    terms: HashMap<String, f64>,
+}
+
+/// Lowercased words plus `label:` terms, counted.
+fn term_counts(summary: &str, description: &str, labels: &[String]) -> HashMap<String, f64> {
+    let mut counts = HashMap::new();
+    // The summary says what the ticket is about; weigh it above the description
+    for (text, weight) in [(summary, 2.0), (description, 1.0)] {
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.len() > 2 && !word.chars().all(|c| c.is_ascii_digit()))
+        {
+            *counts.entry(word.to_lowercase()).or_insert(0.0) += weight;
+        }
+    }
+    for label in labels {
+        *counts.entry(format!("label:{}", label.to_lowercase())).or_insert(0.0) += 2.0;
+    }
+    counts
+}
+
+fn resolution_hours(created: Option<&str>, resolved: Option<&str>) -> Option<f64> {
+    let created = parse_jira_timestamp(created?)?;
+    let resolved = parse_jira_timestamp(resolved?)?;
+    let minutes = (resolved - created).num_minutes();
+    (minutes >= 0).then(|| minutes as f64 / 60.0)
+}
+
+/// Weighted percentile of `(value, weight)` pairs, `fraction` in 0..=1.
+fn weighted_percentile(values: &[(f64, f64)], fraction: f64) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    let total: f64 = sorted.iter().map(|(_, weight)| weight).sum();
+    let mut running = 0.0;
+    for (value, weight) in &sorted {
+        running += weight;
+        if running >= total * fraction {
+            return *value;
+        }
+    }
+    sorted.last().map(|(value, _)| *value).unwrap_or(0.0)
+}
+
+/// Nearest-neighbour classifier over resolved issues, using TF-IDF cosine similarity.
+pub struct SuggestionModel {
+    history: Vec<HistoricalIssue>,
+    idf: HashMap<String, f64>,
+}
+
+impl SuggestionModel {
+    fn from_history(mut history: Vec<HistoricalIssue>) -> Self {
+        let mut document_frequency: HashMap<String, f64> = HashMap::new();
+        for issue in &history {
+            for term in issue.terms.keys() {
+                *document_frequency.entry(term.clone()).or_insert(0.0) += 1.0;
+            }
+        }
+        let documents = history.len() as f64;
+        let idf: HashMap<String, f64> = document_frequency
+            .into_iter()
+            .map(|(term, frequency)| (term, ((1.0 + documents) / (1.0 + frequency)).ln() + 1.0))
+            .collect();
+
+        for issue in &mut history {
+            issue.terms = Self::weigh(&idf, &issue.terms);
+        }
+
+        Self { history, idf }
+    }
+
+    /// Turns raw counts into an L2-normalised TF-IDF vector.
+    fn weigh(idf: &HashMap<String, f64>, counts: &HashMap<String, f64>) -> HashMap<String, f64> {
+        let mut vector: HashMap<String, f64> = counts
+            .iter()
+            .filter_map(|(term, count)| idf.get(term).map(|idf| (term.clone(), count * idf)))
+            .collect();
+        let norm = vector.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            vector.values_mut().for_each(|weight| *weight /= norm);
+        }
+        vector
+    }
+
+    pub fn suggest(&self, key: Option<&str>, ticket: &TicketText) -> TriageSuggestion {
+        let query = Self::weigh(
+            &self.idf,
+            &term_counts(&ticket.summary, &ticket.description, &ticket.labels),
+        );
+
+        let mut neighbours: Vec<(&HistoricalIssue, f64)> = self
+            .history
+            .iter()
+            .filter(|issue| Some(issue.key.as_str()) != key)
+            .map(|issue| {
+                let similarity = query
+                    .iter()
+                    .filter_map(|(term, weight)| issue.terms.get(term).map(|other| weight * other))
+                    .sum::<f64>();
+                (issue, similarity)
+            })
+            .filter(|(_, similarity)| *similarity > 0.0)
+            .collect();
+        neighbours.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.key.cmp(&b.0.key))
+        });
+        neighbours.truncate(NEIGHBOURS);
+
+        TriageSuggestion {
+            key: key.map(|key| key.to_string()),
+            component: Self::vote_component(&neighbours),
+            assignees: Self::vote_assignees(&neighbours),
+            expected_resolution: Self::estimate_resolution(&neighbours),
+            similar_issues_considered: neighbours.len(),
+        }
+    }
+
+    fn vote_component(neighbours: &[(&HistoricalIssue, f64)]) -> Option<ComponentSuggestion> {
+        let mut votes: HashMap<&str, f64> = HashMap::new();
+        let mut total = 0.0;
+        for (issue, similarity) in neighbours.iter().filter(|(issue, _)| !issue.components.is_empty()) {
+            total += similarity;
+            // An issue in several components splits its vote between them
+            let share = similarity / issue.components.len() as f64;
+            for component in &issue.components {
+                *votes.entry(component.as_str()).or_insert(0.0) += share;
+            }
+        }
+
+        votes
+            .into_iter()
+            .max_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.0.cmp(a.0))
+            })
+            .map(|(name, weight)| ComponentSuggestion {
+                name: name.to_string(),
+                confidence: weight / total,
+            })
+    }
+
+    fn vote_assignees(neighbours: &[(&HistoricalIssue, f64)]) -> Vec<AssigneeSuggestion> {
+        let mut votes: HashMap<&str, (f64, Vec<SimilarResolvedIssue>)> = HashMap::new();
+        let mut total = 0.0;
+        for (issue, similarity) in neighbours {
+            let Some(assignee) = issue.assignee.as_deref() else {
+                continue;
+            };
+            total += similarity;
+            let (weight, evidence) = votes.entry(assignee).or_insert((0.0, Vec::new()));
+            *weight += similarity;
+            // Neighbours are sorted, so the first few are the closest matches
+            if evidence.len() < EVIDENCE_PER_ASSIGNEE {
+                evidence.push(SimilarResolvedIssue {
+                    key: issue.key.clone(),
+                    summary: issue.summary.clone(),
+                    similarity: *similarity,
+                    resolution_hours: issue.resolution_hours,
+                });
+            }
+        }
+
+        let mut assignees: Vec<AssigneeSuggestion> = votes
+            .into_iter()
+            .map(|(name, (weight, evidence))| AssigneeSuggestion {
+                name: name.to_string(),
+                confidence: weight / total,
+                evidence,
+            })
+            .collect();
+        assignees.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        assignees.truncate(TOP_ASSIGNEES);
+        assignees
+    }
+
+    fn estimate_resolution(neighbours: &[(&HistoricalIssue, f64)]) -> Option<ResolutionEstimate> {
+        let durations: Vec<(f64, f64)> = neighbours
+            .iter()
+            .filter_map(|(issue, similarity)| issue.resolution_hours.map(|hours| (hours, *similarity)))
+            .collect();
+        if durations.is_empty() {
+            return None;
+        }
+
+        Some(ResolutionEstimate {
+            median_hours: weighted_percentile(&durations, 0.5),
+            p25_hours: weighted_percentile(&durations, 0.25),
+            p75_hours: weighted_percentile(&durations, 0.75),
+            based_on: durations.len(),
+        })
+    }
+}
+
+// ================================
+// LOADING
+// ================================
+
+struct IssueRow {
+    key: String,
+    ticket: TicketText,
+    assignee: Option<String>,
+    components: Vec<String>,
+    resolution_hours: Option<f64>,
+    resolved: bool,
+}
+
+/// Every live issue, or only `key` when given.
+fn load_issue_rows(key: Option<&str>) -> Vec<IssueRow> {
+    let mut issues = Vec::new();
+    let link_detector = LinkDetector::new();
+
+    with_connection("load_triage_history", |conn| {
+        let mut query = String::from(
+            "SELECT key, summary, description, labels, components, assignee, created, \
+             resolution_date, resolution, status FROM live_issues",
+        );
+        if key.is_some() {
+            query.push_str(" WHERE key = ?");
+        }
+        let mut stmt = conn.prepare(&query).expect("Failed to prepare triage history query");
+        let rows = stmt
+            .query_map(duckdb::params_from_iter(key), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, Option<String>>(9)?,
+                ))
+            })
+            .expect("Failed to query triage history");
+
+        for (key, summary, description, labels, components, assignee, created, resolution_date, resolution, status) in
+            rows.flatten()
+        {
+            let description = description
+                .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+                .map(|value| link_detector.extract_text_from_field(&value))
+                .unwrap_or_default();
+            let resolved = resolution.map(|r| !r.is_empty()).unwrap_or(false)
+                || RESOLVED_STATUSES.contains(&status.unwrap_or_default().as_str());
+
+            issues.push(IssueRow {
+                key,
+                ticket: TicketText {
+                    summary: summary.unwrap_or_default(),
+                    description,
+                    labels: labels.and_then(|raw| serde_json::from_str(&raw).ok()).unwrap_or_default(),
+                },
+                assignee: assignee.filter(|assignee| !assignee.is_empty()),
+                components: components.and_then(|raw| serde_json::from_str(&raw).ok()).unwrap_or_default(),
+                resolution_hours: resolution_hours(created.as_deref(), resolution_date.as_deref()),
+                resolved,
+            });
+        }
+    });
+
+    issues
+}
+
+fn history_stamp() -> HistoryStamp {
+    let mut stamp: HistoryStamp = (0, None);
+    with_connection("triage_history_stamp", |conn| {
+        stamp = conn
+            .query_row("SELECT COUNT(*), MAX(updated) FROM live_issues", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .expect("Failed to read triage history stamp");
+    });
+    stamp
+}
+
+fn build_model() -> SuggestionModel {
+    let history = load_issue_rows(None)
+        .into_iter()
+        .filter(|row| row.resolved)
+        .map(|row| HistoricalIssue {
+            terms: term_counts(&row.ticket.summary, &row.ticket.description, &row.ticket.labels),
+            key: row.key,
+            summary: row.ticket.summary,
+            assignee: row.assignee,
+            components: row.components,
+            resolution_hours: row.resolution_hours,
+        })
+        .collect();
+
+    SuggestionModel::from_history(history)
+}
+
+/// The cached model, rebuilt when the issues changed since it was built.
+fn suggestion_model() -> Arc<SuggestionModel> {
+    cached_model(&CACHED_MODEL, history_stamp(), build_model)
+}
+
+/// The model in `cache` if it was built for `stamp`, otherwise a fresh one from `build`.
+fn cached_model(
+    cache: &RwLock<CachedModel>,
+    stamp: HistoryStamp,
+    build: impl FnOnce() -> SuggestionModel,
+) -> Arc<SuggestionModel> {
+    if let Some((built_for, model)) = cache.read().unwrap().as_ref() {
+        if *built_for == stamp {
+            return model.clone();
+        }
+    }
+
+    let mut cached = cache.write().unwrap();
+    match cached.as_ref() {
+        Some((built_for, model)) if *built_for == stamp => model.clone(),
+        _ => {
+            let model = Arc::new(build());
+            *cached = Some((stamp, model.clone()));
+            model
+        }
+    }
+}
+
+/// Suggests a component, assignees and resolution time for an existing issue or new ticket text.
+/// Returns `None` when `key` is given but not synced.
+pub fn suggest_triage(key: Option<&str>, ticket: Option<TicketText>) -> Option<TriageSuggestion> {
+    let ticket = match (key, ticket) {
+        (_, Some(ticket)) => ticket,
+        (Some(key), None) => load_issue_rows(Some(key)).pop()?.ticket,
+        (None, None) => return None,
+    };
+
+    Some(suggestion_model().suggest(key, &ticket))
+}
+
+fn format_hours(hours: f64) -> String {
+    if hours < 48.0 {
+        format!("{:.0}h", hours)
+    } else {
+        format!("{:.1}d", hours / 24.0)
+    }
+}
+
+pub fn print_triage_suggestion(suggestion: &TriageSuggestion) {
+    println!(
+        "{}",
+        format!(
+            "🧭 Triage suggestion for {} (from {} similar resolved issue(s))",
+            suggestion.key.as_deref().unwrap_or("new ticket"),
+            suggestion.similar_issues_considered
+        )
+        .bold()
+    );
+
+    match &suggestion.component {
+        Some(component) => println!(
+            "Component: {} {}",
+            component.name.bright_cyan(),
+            format!("({:.0}%)", component.confidence * 100.0).bright_black()
+        ),
+        None => println!("Component: {}", "no prediction".bright_black()),
+    }
+
+    match &suggestion.expected_resolution {
+        Some(estimate) => println!(
+            "Expected resolution: {} {}",
+            format_hours(estimate.median_hours).bright_cyan(),
+            format!(
+                "(typically {}–{}, from {} issue(s))",
+                format_hours(estimate.p25_hours),
+                format_hours(estimate.p75_hours),
+                estimate.based_on
+            )
+            .bright_black()
+        ),
+        None => println!("Expected resolution: {}", "no prediction".bright_black()),
+    }
+
+    println!("Assignees:");
+    for assignee in &suggestion.assignees {
+        println!(
+            "  {} {}",
+            assignee.name.yellow(),
+            format!("({:.0}%)", assignee.confidence * 100.0).bright_black()
+        );
+        for evidence in &assignee.evidence {
+            println!(
+                "    ↳ {} {:.2} {}",
+                evidence.key,
+                evidence.similarity,
+                evidence.summary.bright_black()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(key: &str, summary: &str, assignee: &str, component: &str, hours: f64) -> HistoricalIssue {
+        HistoricalIssue {
+            key: key.to_string(),
+            summary: summary.to_string(),
+            assignee: Some(assignee.to_string()),
+            components: vec![component.to_string()],
+            resolution_hours: Some(hours),
+            terms: term_counts(summary, "", &[]),
+        }
+    }
+
+    #[test]
+    fn test_suggests_owner_of_similar_resolved_issues() {
+        let model = SuggestionModel::from_history(vec![
+            resolved("OPS-1", "Kafka consumer lag alert on payments cluster", "Dana", "Streaming", 4.0),
+            resolved("OPS-2", "Kafka consumer lag after broker restart", "Dana", "Streaming", 6.0),
+            resolved("OPS-3", "Kafka topic retention misconfigured", "Sam", "Streaming", 30.0),
+            resolved("OPS-4", "Login page returns 500 for SSO users", "Lee", "Auth", 2.0),
+        ]);
+
+        let suggestion = model.suggest(
+            None,
+            &TicketText {
+                summary: "Consumer lag growing on kafka payments topic".to_string(),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(suggestion.component.as_ref().unwrap().name, "Streaming");
+        assert_eq!(suggestion.assignees[0].name, "Dana");
+        assert_eq!(suggestion.assignees[0].evidence[0].key, "OPS-1");
+        assert!(suggestion.assignees.iter().all(|assignee| assignee.name != "Lee"));
+        assert!(suggestion.expected_resolution.unwrap().median_hours <= 6.0);
+    }
+
+    #[test]
+    fn test_no_history_suggests_nothing() {
+        let ticket = TicketText {
+            summary: "Kafka consumer lag on payments".to_string(),
+            ..Default::default()
+        };
+
+        let empty = SuggestionModel::from_history(Vec::new()).suggest(None, &ticket);
+        assert!(empty.component.is_none());
+        assert!(empty.assignees.is_empty());
+        assert!(empty.expected_resolution.is_none());
+        assert_eq!(empty.similar_issues_considered, 0);
+
+        // History that shares no terms with the ticket is no better
+        let unrelated = SuggestionModel::from_history(vec![resolved(
+            "OPS-4",
+            "Login page returns 500 for SSO users",
+            "Lee",
+            "Auth",
+            2.0,
+        )])
+        .suggest(None, &ticket);
+        assert!(unrelated.component.is_none());
+        assert!(unrelated.assignees.is_empty());
+        assert_eq!(unrelated.similar_issues_considered, 0);
+    }
+
+    #[test]
+    fn test_tied_candidates_are_ordered_by_name() {
+        let model = SuggestionModel::from_history(vec![
+            resolved("OPS-2", "Kafka consumer lag alert", "Sam", "Streaming", 4.0),
+            resolved("OPS-1", "Kafka consumer lag alert", "Dana", "Platform", 4.0),
+        ]);
+
+        let suggestion = model.suggest(
+            None,
+            &TicketText {
+                summary: "Kafka consumer lag alert".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let names: Vec<&str> = suggestion.assignees.iter().map(|assignee| assignee.name.as_str()).collect();
+        assert_eq!(names, vec!["Dana", "Sam"]);
+        assert!((suggestion.assignees[0].confidence - suggestion.assignees[1].confidence).abs() < 1e-9);
+        let component = suggestion.component.unwrap();
+        assert_eq!(component.name, "Platform");
+        assert!((component.confidence - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cached_model_is_rebuilt_when_the_stamp_changes() {
+        let cache: RwLock<CachedModel> = RwLock::new(None);
+        let builds = std::cell::Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            SuggestionModel::from_history(Vec::new())
+        };
+        let stamp = |count: i64, updated: &str| (count, Some(updated.to_string()));
+
+        let first = cached_model(&cache, stamp(10, "2025-01-01"), build);
+        let again = cached_model(&cache, stamp(10, "2025-01-01"), build);
+        assert_eq!(builds.get(), 1);
+        assert!(Arc::ptr_eq(&first, &again));
+
+        // A new issue or a newer update each invalidate the model
+        let added = cached_model(&cache, stamp(11, "2025-01-01"), build);
+        let updated = cached_model(&cache, stamp(11, "2025-01-02"), build);
+        assert_eq!(builds.get(), 3);
+        assert!(!Arc::ptr_eq(&first, &added));
+        assert!(!Arc::ptr_eq(&added, &updated));
+    }
+}
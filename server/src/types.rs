@@ -64,6 +64,9 @@ pub struct GraphMetadata {
     pub node_types: HashMap<NodeType, usize>,
     pub edge_types: HashMap<EdgeType, usize>,
     pub generated_at: String,
+    /// Set to the issue cap when more live issues exist than the graph draws
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_at: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -81,7 +84,7 @@ pub struct Issue {
     pub fields: IssueFields,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct IssueFields {
     pub summary: Option<String>,
     pub description: Option<serde_json::Value>,
@@ -154,6 +157,26 @@ pub struct IssueFieldMetadata {
     pub issue_links: Option<serde_json::Value>,
     pub work_log: Option<serde_json::Value>,
     pub time_tracking: Option<serde_json::Value>,
+    pub resolution: Option<String>,
+    pub resolution_date: Option<String>,
+    pub components: Option<Vec<String>>,
+    pub fix_versions: Option<Vec<String>>,
+    pub affected_versions: Option<Vec<String>>,
+    pub parent_key: Option<String>,
+    pub epic_key: Option<String>,
+    pub due_date: Option<String>,
+    pub story_points: Option<f64>,
+    pub environment: Option<String>,
+    pub custom_fields: Vec<IssueCustomField>,
+}
+
+/// A Jira custom field value captured under the friendly name from `custom_fields.json`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueCustomField {
+    pub field_id: String,
+    pub field_name: String,
+    pub friendly_name: String,
+    pub value: String,
 }
 
 // New types for link extraction
@@ -1,10 +1,13 @@
+use crate::access_control::{ContentAcl, Viewer, REDACTED_TITLE};
 use crate::content_storage::{SEARCH_CONTENT_FULL_TEXT, GET_RELATED_CONTENT};
 use crate::db_utils::with_connection;
+use crate::pagination::MAX_PAGE_LIMIT;
 use crate::semantic_search::RelatedItem;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct UnifiedSearchRequest {
     pub query: String,
     pub content_types: Vec<UnifiedContentType>,
@@ -12,14 +15,25 @@ pub struct UnifiedSearchRequest {
     pub date_range: Option<DateRange>,
     pub authors: Vec<String>,
     pub projects: Vec<String>,
+    /// Matches gathered before `limit`/`cursor` paging; capped at 200
     pub max_results: Option<usize>,
     pub similarity_threshold: Option<f64>,
     pub include_relationships: bool,
     pub search_mode: SearchMode,
     pub user_id: Option<String>, // For permission filtering
+    /// Also match Jira issues soft-deleted by a sync or webhook
+    #[serde(default)]
+    pub include_deleted: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+impl UnifiedSearchRequest {
+    /// `max_results`, or `default` when unset, never more than one full page.
+    fn result_limit(&self, default: usize) -> usize {
+        self.max_results.unwrap_or(default).clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 pub enum UnifiedContentType {
     JiraIssue,
     GoogleDoc,
@@ -30,6 +44,7 @@ pub enum UnifiedContentType {
     ConfluencePage,
     GitHubPR,
     GitHubIssue,
+    JiraAttachment,
     UserNote,
     SavedView,
     KnowledgeConcept,
@@ -37,7 +52,7 @@ pub enum UnifiedContentType {
     All,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub enum SearchMode {
     Relevance,        // Sort by relevance score
     Recent,          // Sort by most recent
@@ -46,13 +61,13 @@ pub enum SearchMode {
     Semantic,        // AI-powered semantic matching
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct DateRange {
     pub from: String, // ISO 8601 date
     pub to: String,   // ISO 8601 date
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct UnifiedSearchResult {
     pub query: String,
     pub total_results: usize,
@@ -63,7 +78,7 @@ pub struct UnifiedSearchResult {
     pub related_queries: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct EnhancedSearchResult {
     pub id: String,
     pub title: String,
@@ -85,7 +100,7 @@ pub struct EnhancedSearchResult {
     pub access_info: AccessInfo,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct EnhancedSearchContext {
     pub project: String,
     pub platform: String,
@@ -98,7 +113,7 @@ pub struct EnhancedSearchContext {
     pub related_concepts: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct EngagementSummary {
     pub view_count: u32,
     pub comment_count: u32,
@@ -108,7 +123,7 @@ pub struct EngagementSummary {
     pub knowledge_score: f64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct AccessInfo {
     pub is_accessible: bool,
     pub requires_auth: bool,
@@ -116,7 +131,7 @@ pub struct AccessInfo {
     pub sharing_level: String, // "public", "team", "private"
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct SearchFacets {
     pub platforms: HashMap<String, usize>,
     pub content_types: HashMap<String, usize>,
@@ -138,14 +153,15 @@ impl UnifiedSearchEngine {
         }
     }
 
-    pub async fn search(&self, request: UnifiedSearchRequest) -> Result<UnifiedSearchResult, Box<dyn std::error::Error>> {
+    /// Content the viewer cannot open is left out; related items pointing at it are redacted.
+    pub async fn search(&self, request: UnifiedSearchRequest, viewer: &Viewer) -> Result<UnifiedSearchResult, Box<dyn std::error::Error>> {
         let start_time = std::time::Instant::now();
         let mut all_results = Vec::new();
         
         // Search across different content types
         if request.content_types.contains(&UnifiedContentType::All) || 
-           request.content_types.iter().any(|t| matches!(t, UnifiedContentType::GoogleDoc | UnifiedContentType::GoogleSheet | UnifiedContentType::GoogleSlide | UnifiedContentType::SlackThread | UnifiedContentType::SlackMessage)) {
-            let content_results = self.search_extracted_content(&request).await?;
+           request.content_types.iter().any(|t| matches!(t, UnifiedContentType::GoogleDoc | UnifiedContentType::GoogleSheet | UnifiedContentType::GoogleSlide | UnifiedContentType::SlackThread | UnifiedContentType::SlackMessage | UnifiedContentType::JiraAttachment)) {
+            let content_results = self.search_extracted_content(&request, viewer).await?;
             all_results.extend(content_results);
         }
 
@@ -178,13 +194,13 @@ impl UnifiedSearchEngine {
             }
         });
 
-        let max_results = request.max_results.unwrap_or(self.max_results);
+        let max_results = request.result_limit(self.max_results);
         all_results.truncate(max_results);
 
         // Add related content if requested
         if request.include_relationships {
             for result in &mut all_results {
-                result.related_items = self.find_related_content(&result.id).await?;
+                result.related_items = self.find_related_content(&result.id, viewer).await?;
             }
         }
 
@@ -208,71 +224,89 @@ impl UnifiedSearchEngine {
         })
     }
 
-    async fn search_extracted_content(&self, request: &UnifiedSearchRequest) -> Result<Vec<EnhancedSearchResult>, Box<dyn std::error::Error>> {
+    async fn search_extracted_content(&self, request: &UnifiedSearchRequest, viewer: &Viewer) -> Result<Vec<EnhancedSearchResult>, Box<dyn std::error::Error>> {
         let mut results = Vec::new();
         
         with_connection("search_extracted_content", |conn| {
             let search_pattern = format!("%{}%", request.query);
-            let limit = request.max_results.unwrap_or(50);
+            let limit = request.result_limit(50);
             
             let mut stmt = conn.prepare(SEARCH_CONTENT_FULL_TEXT).expect("Failed to prepare search statement");
-            let rows = stmt.query_map([&search_pattern, &limit.to_string()], |row| {
-                Ok((
-                    row.get::<_, String>(0)?, // id
-                    row.get::<_, String>(1)?, // content_type
-                    row.get::<_, String>(2)?, // source_url
-                    row.get::<_, String>(3)?, // source_platform
-                    row.get::<_, String>(4)?, // title
-                    row.get::<_, String>(5)?, // body_text
-                    row.get::<_, Option<String>>(6)?, // author
-                    row.get::<_, Option<String>>(7)?, // created_at
-                    row.get::<_, String>(8)?, // last_updated_at
-                    row.get::<_, String>(9)?, // metadata (JSON)
-                ))
-            }).expect("Failed to execute search query");
-
-            for row_result in rows {
-                if let Ok((id, content_type_str, source_url, platform, title, body_text, author, created_at, last_updated_at, metadata)) = row_result {
-                    let content_type = self.parse_content_type(&content_type_str);
-                    let metadata_json: serde_json::Value = serde_json::from_str(&metadata).unwrap_or_default();
+            // Rows the viewer cannot see are dropped, so keep reading batches until the page is full
+            let mut offset = 0;
+            while results.len() < limit {
+                let rows: Vec<_> = stmt.query_map(duckdb::params![search_pattern, limit as i64, offset as i64], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?, // id
+                        row.get::<_, String>(1)?, // content_type
+                        row.get::<_, String>(2)?, // source_url
+                        row.get::<_, String>(3)?, // source_platform
+                        row.get::<_, String>(4)?, // title
+                        row.get::<_, String>(5)?, // body_text
+                        row.get::<_, Option<String>>(6)?, // author
+                        row.get::<_, Option<String>>(7)?, // created_at
+                        row.get::<_, String>(8)?, // last_updated_at
+                        row.get::<_, String>(9)?, // metadata (JSON)
+                        row.get::<_, Option<String>>(10)?, // parent_ticket
+                        row.get::<_, Option<String>>(11)?, // access_permissions (JSON)
+                    ))
+                }).expect("Failed to execute search query").collect();
+                let fetched = rows.len();
+                offset += fetched;
+
+                for row_result in rows {
+                    if let Ok((id, content_type_str, source_url, platform, title, body_text, author, created_at, last_updated_at, metadata, parent_ticket, access_permissions)) = row_result {
+                        let acl = ContentAcl::from_stored(access_permissions.as_deref(), &platform);
+                        if !acl.allows(viewer) {
+                            continue;
+                        }
+                        let content_type = self.parse_content_type(&content_type_str);
+                        let metadata_json: serde_json::Value = serde_json::from_str(&metadata).unwrap_or_default();
                     
-                    let result = EnhancedSearchResult {
-                        id: id.clone(),
-                        title: title.clone(),
-                        content: body_text.clone(),
-                        content_preview: self.create_preview(&body_text, &request.query),
-                        result_type: content_type.clone(),
-                        platform: platform.clone(),
-                        similarity_score: self.calculate_similarity(&title, &body_text, &request.query),
-                        relevance_score: self.calculate_relevance(&title, &body_text, &request.query, &metadata_json),
-                        context: EnhancedSearchContext {
-                            project: self.extract_project_from_metadata(&metadata_json),
-                            platform: source_url.split('/').nth(2).unwrap_or("unknown").to_string(),
-                            content_type: content_type.to_string(),
-                            category: self.extract_category_from_metadata(&metadata_json),
-                            source_url: source_url.clone(),
-                            parent_ticket: self.find_parent_ticket(&id).unwrap_or_default(),
-                            knowledge_impact_score: metadata_json["quality_score"].as_f64().unwrap_or(5.0),
-                            usage_frequency: metadata_json["engagement_metrics"]["view_count"].as_i64().unwrap_or(0) as i32,
-                            related_concepts: self.extract_concepts_from_metadata(&metadata_json),
-                        },
-                        related_items: Vec::new(), // Filled later if requested
-                        tags: self.extract_tags_from_metadata(&metadata_json),
-                        concepts: self.extract_concepts_from_metadata(&metadata_json),
-                        technologies: self.extract_technologies_from_metadata(&metadata_json),
-                        created_date: created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
-                        last_updated: last_updated_at,
-                        author,
-                        engagement_metrics: self.extract_engagement_metrics(&metadata_json),
-                        access_info: AccessInfo {
-                            is_accessible: true, // TODO: Check user permissions
-                            requires_auth: !platform.contains("jira"),
-                            platform_auth_required: if platform.contains("google") { vec!["google".to_string()] } else if platform.contains("slack") { vec!["slack".to_string()] } else { vec![] },
-                            sharing_level: "team".to_string(), // TODO: Extract from permissions
-                        },
-                    };
+                        let result = EnhancedSearchResult {
+                            id: id.clone(),
+                            title: title.clone(),
+                            content: body_text.clone(),
+                            content_preview: self.create_preview(&body_text, &request.query),
+                            result_type: content_type.clone(),
+                            platform: platform.clone(),
+                            similarity_score: self.calculate_similarity(&title, &body_text, &request.query),
+                            relevance_score: self.calculate_relevance(&title, &body_text, &request.query, &metadata_json),
+                            context: EnhancedSearchContext {
+                                project: self.extract_project_from_metadata(&metadata_json),
+                                platform: source_url.split('/').nth(2).unwrap_or("unknown").to_string(),
+                                content_type: content_type.to_string(),
+                                category: self.extract_category_from_metadata(&metadata_json),
+                                source_url: source_url.clone(),
+                                parent_ticket,
+                                knowledge_impact_score: metadata_json["quality_score"].as_f64().unwrap_or(5.0),
+                                usage_frequency: metadata_json["engagement_metrics"]["view_count"].as_i64().unwrap_or(0) as i32,
+                                related_concepts: self.extract_concepts_from_metadata(&metadata_json),
+                            },
+                            related_items: Vec::new(), // Filled later if requested
+                            tags: self.extract_tags_from_metadata(&metadata_json),
+                            concepts: self.extract_concepts_from_metadata(&metadata_json),
+                            technologies: self.extract_technologies_from_metadata(&metadata_json),
+                            created_date: created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                            last_updated: last_updated_at,
+                            author,
+                            engagement_metrics: self.extract_engagement_metrics(&metadata_json),
+                            access_info: AccessInfo {
+                                is_accessible: true,
+                                requires_auth: !platform.contains("jira"),
+                                platform_auth_required: if platform.contains("google") { vec!["google".to_string()] } else if platform.contains("slack") { vec!["slack".to_string()] } else { vec![] },
+                                sharing_level: acl.sharing_level().to_string(),
+                            },
+                        };
                     
-                    results.push(result);
+                        results.push(result);
+                        if results.len() == limit {
+                            break;
+                        }
+                    }
+                }
+                if fetched < limit {
+                    break;
                 }
             }
             
@@ -287,17 +321,18 @@ impl UnifiedSearchEngine {
         with_connection("search_jira_issues", |conn| {
             let search_pattern = format!("%{}%", request.query);
             
-            let query = r#"
+            let table = if request.include_deleted { "issues" } else { "live_issues" };
+            let query = format!(r#"
                 SELECT id, key, summary, status, issue_type, priority, assignee, reporter, 
                        created, updated, project_name, project_key, description, extracted_links
-                FROM issues 
+                FROM {} 
                 WHERE (summary LIKE ? OR description LIKE ?)
                 ORDER BY updated DESC
                 LIMIT ?
-            "#;
+            "#, table);
             
-            let mut stmt = conn.prepare(query).expect("Failed to prepare search query");
-            let limit = request.max_results.unwrap_or(25);
+            let mut stmt = conn.prepare(&query).expect("Failed to prepare search query");
+            let limit = request.result_limit(25);
             let rows = stmt.query_map([&search_pattern, &search_pattern, &limit.to_string()], |row| {
                 Ok((
                     row.get::<_, String>(0)?, // id
@@ -386,7 +421,7 @@ impl UnifiedSearchEngine {
         Ok(Vec::new())
     }
 
-    async fn find_related_content(&self, content_id: &str) -> Result<Vec<RelatedItem>, Box<dyn std::error::Error>> {
+    async fn find_related_content(&self, content_id: &str, viewer: &Viewer) -> Result<Vec<RelatedItem>, Box<dyn std::error::Error>> {
         let mut related_items = Vec::new();
         
         with_connection("find_related_content", |conn| {
@@ -397,14 +432,18 @@ impl UnifiedSearchEngine {
                     row.get::<_, String>(1)?, // title  
                     row.get::<_, String>(2)?, // relationship_type
                     row.get::<_, f64>(3)?,    // strength
+                    row.get::<_, String>(4)?, // source_platform
+                    row.get::<_, Option<String>>(5)?, // access_permissions
                 ))
             }).expect("Failed to execute search query");
 
             for row_result in rows {
-                if let Ok((id, title, relationship_type, strength)) = row_result {
+                if let Ok((id, title, relationship_type, strength, platform, access_permissions)) = row_result {
+                    // Keep the link so the relationship is visible, but not what it points at
+                    let acl = ContentAcl::from_stored(access_permissions.as_deref(), &platform);
                     related_items.push(RelatedItem {
                         id,
-                        title,
+                        title: if acl.allows(viewer) { title } else { REDACTED_TITLE.to_string() },
                         relationship_type,
                         similarity_score: strength,
                     });
@@ -472,6 +511,7 @@ impl UnifiedSearchEngine {
             "confluence_page" => UnifiedContentType::ConfluencePage,
             "github_pr" => UnifiedContentType::GitHubPR,
             "github_issue" => UnifiedContentType::GitHubIssue,
+            "jira_attachment" => UnifiedContentType::JiraAttachment,
             _ => UnifiedContentType::JiraIssue,
         }
     }
@@ -479,9 +519,16 @@ impl UnifiedSearchEngine {
     fn create_preview(&self, content: &str, query: &str) -> String {
         let query_lower = query.to_lowercase();
         if let Some(pos) = content.to_lowercase().find(&query_lower) {
-            let start = pos.saturating_sub(50);
-            let end = std::cmp::min(content.len(), pos + query.len() + 150);
-            let preview = &content[start..end];
+            // Lowercasing can shift byte offsets, so snap to char boundaries before slicing
+            let mut start = pos.saturating_sub(50).min(content.len());
+            while !content.is_char_boundary(start) {
+                start -= 1;
+            }
+            let mut end = std::cmp::min(content.len(), pos + query.len() + 150);
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            let preview = &content[start..end.max(start)];
             format!("...{preview}...")
         } else {
             content.chars().take(200).collect::<String>() + if content.len() > 200 { "..." } else { "" }
@@ -570,10 +617,6 @@ impl UnifiedSearchEngine {
         }
     }
 
-    fn find_parent_ticket(&self, _content_id: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        // TODO: Query content_extraction_jobs to find source ticket
-        Ok(None)
-    }
 }
 
 impl UnifiedContentType {
@@ -588,6 +631,7 @@ impl UnifiedContentType {
             UnifiedContentType::ConfluencePage => "confluence_page".to_string(),
             UnifiedContentType::GitHubPR => "github_pr".to_string(),
             UnifiedContentType::GitHubIssue => "github_issue".to_string(),
+            UnifiedContentType::JiraAttachment => "jira_attachment".to_string(),
             UnifiedContentType::UserNote => "user_note".to_string(),
             UnifiedContentType::SavedView => "saved_view".to_string(),
             UnifiedContentType::KnowledgeConcept => "knowledge_concept".to_string(),
@@ -598,7 +642,7 @@ impl UnifiedContentType {
 }
 
 // Main search function to be called from the API
-pub async fn unified_search(request: UnifiedSearchRequest) -> Result<UnifiedSearchResult, Box<dyn std::error::Error>> {
+pub async fn unified_search(request: UnifiedSearchRequest, viewer: &Viewer) -> Result<UnifiedSearchResult, Box<dyn std::error::Error>> {
     let search_engine = UnifiedSearchEngine::new();
-    search_engine.search(request).await
+    search_engine.search(request, viewer).await
 }
\ No newline at end of file
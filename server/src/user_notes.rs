@@ -1,10 +1,11 @@
 use crate::db_utils::with_connection;
 use crate::utils::{log_step, log_success};
 use chrono::Utc;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct UserNote {
     pub id: String,
     pub title: String,
@@ -19,7 +20,7 @@ pub struct UserNote {
     pub metadata: NoteMetadata,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub enum NoteType {
     PersonalNote,
     LearningNote,
@@ -29,7 +30,7 @@ pub enum NoteType {
     ProjectNote,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct LinkedItem {
     pub item_id: String,
     pub item_type: String,
@@ -37,7 +38,7 @@ pub struct LinkedItem {
     pub relationship: String, // "relates_to", "solution_for", "inspired_by", etc.
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct NoteMetadata {
     pub color: Option<String>,
     pub priority: i32, // 1-5 scale
@@ -47,7 +48,7 @@ pub struct NoteMetadata {
     pub source_context: Option<String>, // where the note was created from
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub enum CompletionStatus {
     NotStarted,
     InProgress,
@@ -56,7 +57,7 @@ pub enum CompletionStatus {
     Archived,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct SavedView {
     pub id: String,
     pub name: String,
@@ -71,7 +72,7 @@ pub struct SavedView {
     pub last_accessed: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub enum ViewType {
     Search,
     Filter,
@@ -80,7 +81,7 @@ pub enum ViewType {
     Learning,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct ViewConfiguration {
     pub search_query: Option<String>,
     pub filters: HashMap<String, serde_json::Value>,
@@ -89,7 +90,7 @@ pub struct ViewConfiguration {
     pub custom_fields: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct DisplayOptions {
     pub layout: String, // "grid", "list", "cards", "timeline"
     pub items_per_page: i32,
@@ -98,7 +99,7 @@ pub struct DisplayOptions {
     pub color_coding: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct SortPreferences {
     pub primary_sort: String,
     pub secondary_sort: Option<String>,
@@ -106,7 +107,7 @@ pub struct SortPreferences {
     pub custom_weights: HashMap<String, f64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct CreateNoteRequest {
     pub title: String,
     pub content: String,
@@ -117,7 +118,7 @@ pub struct CreateNoteRequest {
     pub metadata: Option<NoteMetadata>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct CreateViewRequest {
     pub name: String,
     pub description: String,
@@ -125,7 +126,7 @@ pub struct CreateViewRequest {
     pub configuration: ViewConfiguration,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct NotesResponse {
     pub notes: Vec<UserNote>,
     pub total_count: usize,
@@ -133,7 +134,7 @@ pub struct NotesResponse {
     pub note_types: HashMap<String, usize>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct ViewsResponse {
     pub views: Vec<SavedView>,
     pub favorites: Vec<SavedView>,
@@ -217,12 +218,11 @@ pub async fn initialize_notes_tables() {
     log_success("User notes and views tables initialized");
 }
 
-pub async fn create_note(request: CreateNoteRequest) -> Result<UserNote, String> {
+pub async fn create_note(user_id: &str, request: CreateNoteRequest) -> Result<UserNote, String> {
     log_step("📝", &format!("Creating note: {}", request.title));
 
     let note_id = format!("note_{}", uuid::Uuid::new_v4());
     let now = Utc::now().to_rfc3339();
-    let user_id = "default_user"; // In a real app, this would come from authentication
 
     let metadata = request.metadata.unwrap_or(NoteMetadata {
         color: None,
@@ -282,10 +282,9 @@ pub async fn create_note(request: CreateNoteRequest) -> Result<UserNote, String>
     Ok(note)
 }
 
-pub async fn get_user_notes(user_id: Option<String>, search: Option<String>) -> NotesResponse {
+pub async fn get_user_notes(user_id: &str, search: Option<String>) -> NotesResponse {
     log_step("📋", "Retrieving user notes...");
 
-    let user_id = user_id.unwrap_or_else(|| "default_user".to_string());
     let mut notes = Vec::new();
     let mut all_tags = Vec::new();
     let mut note_type_counts = HashMap::new();
@@ -311,12 +310,12 @@ pub async fn get_user_notes(user_id: Option<String>, search: Option<String>) ->
 
         let rows = if let Some(search_term) = &search {
             let search_pattern = format!("%{}%", search_term);
-            stmt.query_map([&user_id, &search_pattern], |row| build_note_from_row(row))
+            stmt.query_map([user_id, search_pattern.as_str()], build_note_from_row)
             .expect("Failed to execute search query")
             .collect::<Result<Vec<_>, _>>()
             .expect("Failed to collect search notes")
         } else {
-            stmt.query_map([&user_id], |row| build_note_from_row(row))
+            stmt.query_map([user_id], build_note_from_row)
             .expect("Failed to execute notes query")
             .collect::<Result<Vec<_>, _>>()
             .expect("Failed to collect notes")
@@ -348,12 +347,11 @@ pub async fn get_user_notes(user_id: Option<String>, search: Option<String>) ->
     }
 }
 
-pub async fn create_saved_view(request: CreateViewRequest) -> Result<SavedView, String> {
+pub async fn create_saved_view(user_id: &str, request: CreateViewRequest) -> Result<SavedView, String> {
     log_step("👁️", &format!("Creating saved view: {}", request.name));
 
     let view_id = format!("view_{}", uuid::Uuid::new_v4());
     let now = Utc::now().to_rfc3339();
-    let user_id = "default_user";
 
     let view = SavedView {
         id: view_id.clone(),
@@ -396,10 +394,9 @@ pub async fn create_saved_view(request: CreateViewRequest) -> Result<SavedView,
     Ok(view)
 }
 
-pub async fn get_saved_views(user_id: Option<String>) -> ViewsResponse {
+pub async fn get_saved_views(user_id: &str) -> ViewsResponse {
     log_step("👁️", "Retrieving saved views...");
 
-    let user_id = user_id.unwrap_or_else(|| "default_user".to_string());
     let mut views = Vec::new();
     let mut favorites = Vec::new();
     let mut recent = Vec::new();
@@ -414,7 +411,7 @@ pub async fn get_saved_views(user_id: Option<String>) -> ViewsResponse {
 
         let mut stmt = conn.prepare(query).expect("Failed to prepare views query");
         let rows = stmt
-            .query_map([&user_id], |row| {
+            .query_map([user_id], |row| {
                 let view = SavedView {
                     id: row.get::<_, String>(0)?,
                     name: row.get::<_, String>(1)?,
@@ -457,47 +454,53 @@ pub async fn get_saved_views(user_id: Option<String>) -> ViewsResponse {
     }
 }
 
-pub async fn update_view_usage(view_id: &str) -> Result<(), String> {
+/// Only the owner's views can be touched; anyone else gets "View not found".
+pub async fn update_view_usage(user_id: &str, view_id: &str) -> Result<(), String> {
     let now = Utc::now().to_rfc3339();
+    let mut updated = 0;
 
     with_connection("update_view_usage", |conn| {
-        conn.execute(
-            r#"
-            UPDATE saved_views 
-            SET usage_count = usage_count + 1, last_accessed = ?1
-            WHERE id = ?2
-            "#,
-            duckdb::params![now, view_id],
-        )
-        .map_err(|e| format!("Failed to update view usage: {}", e));
+        updated = conn
+            .execute(
+                r#"
+                UPDATE saved_views 
+                SET usage_count = usage_count + 1, last_accessed = ?1
+                WHERE id = ?2 AND user_id = ?3
+                "#,
+                duckdb::params![now, view_id, user_id],
+            )
+            .unwrap_or(0);
     });
 
+    if updated == 0 {
+        return Err("View not found".to_string());
+    }
     Ok(())
 }
 
-pub async fn toggle_view_favorite(view_id: &str) -> Result<bool, String> {
-    let mut is_favorite = false;
+pub async fn toggle_view_favorite(user_id: &str, view_id: &str) -> Result<bool, String> {
+    let mut is_favorite = None;
 
     with_connection("toggle_favorite", |conn| {
         // First get current state
         let mut stmt = conn
-            .prepare("SELECT is_favorite FROM saved_views WHERE id = ?1")
+            .prepare("SELECT is_favorite FROM saved_views WHERE id = ?1 AND user_id = ?2")
             .expect("Failed to prepare query");
-        let current_state = stmt
-            .query_row([view_id], |row| Ok(row.get::<_, i32>(0)? != 0))
-            .unwrap_or(false);
+        let Ok(current_state) = stmt.query_row([view_id, user_id], |row| Ok(row.get::<_, i32>(0)? != 0)) else {
+            return;
+        };
 
-        is_favorite = !current_state;
+        is_favorite = Some(!current_state);
 
         // Update the state
         conn.execute(
             "UPDATE saved_views SET is_favorite = ?1 WHERE id = ?2",
-            duckdb::params![is_favorite as i32, view_id],
+            duckdb::params![!current_state as i32, view_id],
         )
         .map_err(|e| format!("Failed to update favorite status: {}", e));
     });
 
-    Ok(is_favorite)
+    is_favorite.ok_or_else(|| "View not found".to_string())
 }
 
 fn build_note_from_row(row: &duckdb::Row) -> Result<UserNote, duckdb::Error> {
@@ -1,4 +1,4 @@
-use colored::*;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 
 pub fn get_optional_field<T: for<'de> serde::Deserialize<'de>>(
@@ -20,12 +20,28 @@ pub fn json_opt_to_string(value: &Option<serde_json::Value>) -> String {
     value.as_ref().map(|v| v.to_string()).unwrap_or_default()
 }
 
+/// Parses Jira timestamps, which use `+0000` style offsets rather than RFC 3339.
+pub fn parse_jira_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+/// FNV-1a, so hashes kept in the database or compared across runs don't change between
+/// builds the way `DefaultHasher` may.
+pub fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
 pub fn log_step(icon: &str, msg: &str) {
-    println!("{} {}", icon.bright_yellow(), msg.yellow());
+    tracing::info!(icon, "{}", msg);
 }
 
 pub fn log_success(msg: &str) {
-    println!("{} {}", "✅".green(), msg.green());
+    tracing::info!(outcome = "success", "{}", msg);
 }
 
 pub fn log_error(context: &str) -> String {
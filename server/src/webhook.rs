@@ -0,0 +1,206 @@
+use crate::content_extractor::{create_extraction_jobs_from_links, ContentExtractionJob, JobPriority};
+use crate::content_storage::{ContentType, INSERT_EXTRACTION_JOB};
+use crate::db::{mark_issue_deleted, save_issues_batch_to_duckdb};
+use crate::db_utils::{with_connection, with_transaction};
+use crate::issue_graph::update_issue_edges;
+use crate::jira::refresh_issue;
+use crate::types::{ExtractedLink, Issue};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::HashSet;
+
+// ================================
+// JIRA WEBHOOK RECEIVER
+// ================================
+
+/// Shared secret configured on the Jira webhook; requests are rejected while it is unset.
+pub const WEBHOOK_SECRET_ENV: &str = "JIRA_WEBHOOK_SECRET";
+
+/// Header Jira signs the request body into when the webhook has a secret.
+pub const SIGNATURE_HEADER: &str = "x-hub-signature";
+
+#[derive(Debug, Deserialize)]
+pub struct JiraWebhookEvent {
+    #[serde(rename = "webhookEvent")]
+    pub webhook_event: String,
+    pub issue: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebhookAction {
+    Upsert,
+    MarkDeleted,
+    Ignore,
+}
+
+impl JiraWebhookEvent {
+    /// Comment events carry the parent issue, so they refresh it like an update.
+    pub fn action(&self) -> WebhookAction {
+        match self.webhook_event.as_str() {
+            "jira:issue_created" | "jira:issue_updated" => WebhookAction::Upsert,
+            "comment_created" | "comment_updated" | "comment_deleted" => WebhookAction::Upsert,
+            "jira:issue_deleted" => WebhookAction::MarkDeleted,
+            _ => WebhookAction::Ignore,
+        }
+    }
+
+    pub fn issue_key(&self) -> Option<String> {
+        self.issue.as_ref()?["key"].as_str().map(|s| s.to_string())
+    }
+
+    fn parsed_issue(&self) -> Option<Issue> {
+        serde_json::from_value(self.issue.clone()?).ok()
+    }
+}
+
+pub fn webhook_secret() -> Option<String> {
+    std::env::var(WEBHOOK_SECRET_ENV).ok().filter(|s| !s.is_empty())
+}
+
+/// Requires an HMAC-SHA256 signature of the body (`sha256=<hex>`); the secret itself is never
+/// accepted, since anything in the URL ends up in access logs.
+pub fn verify_request(secret: &str, body: &[u8], signature: Option<&str>) -> bool {
+    let Some(hex) = signature.and_then(|signature| signature.trim().strip_prefix("sha256=")) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let expected: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    constant_time_eq(expected.as_bytes(), hex.to_lowercase().as_bytes())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Applies one webhook event to the local store; runs after the request has been acknowledged.
+pub async fn process_jira_event(event: JiraWebhookEvent) {
+    let Some(key) = event.issue_key() else {
+        tracing::warn!("Webhook event {} has no issue, ignoring", event.webhook_event);
+        return;
+    };
+
+    match event.action() {
+        WebhookAction::Upsert => {
+            let Some(issue) = event.parsed_issue() else {
+                tracing::error!("Webhook event {} carried an unreadable issue [{}]", event.webhook_event, key);
+                return;
+            };
+            let known_links = stored_link_urls(&issue.id);
+
+            let metadata = match refresh_issue(issue).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    tracing::error!("Webhook refresh failed for [{}]: {}", key, e);
+                    return;
+                }
+            };
+            save_issues_batch_to_duckdb(std::slice::from_ref(&metadata)).await;
+
+            let new_links: Vec<ExtractedLink> = metadata
+                .extracted_links
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|link| !known_links.contains(&link.url))
+                .collect();
+            let jobs = create_extraction_jobs_from_links(&metadata.key, &new_links, "system", JobPriority::High);
+            if !jobs.is_empty() {
+                enqueue_extraction_jobs(&jobs);
+                tracing::info!("Queued {} content extraction job(s) for [{}]", jobs.len(), metadata.key);
+            }
+        }
+        WebhookAction::MarkDeleted => {
+            let Some(id) = event.issue.as_ref().and_then(|issue| issue["id"].as_str()) else {
+                return;
+            };
+            mark_issue_deleted(id).await;
+        }
+        WebhookAction::Ignore => return,
+    }
+
+    update_issue_edges(&key).await;
+    tracing::info!("Applied {} for [{}]", event.webhook_event, key);
+}
+
+/// URLs already detected on the stored copy of an issue, so only new links get queued.
+fn stored_link_urls(issue_id: &str) -> HashSet<String> {
+    let mut urls = HashSet::new();
+    with_connection("stored_link_urls", |conn| {
+        let raw: Option<String> = conn
+            .query_row("SELECT extracted_links FROM issues WHERE id = ?", [issue_id], |row| row.get(0))
+            .unwrap_or(None);
+        if let Some(links) = raw.and_then(|raw| serde_json::from_str::<Vec<ExtractedLink>>(&raw).ok()) {
+            urls.extend(links.into_iter().map(|link| link.url));
+        }
+    });
+    urls
+}
+
+fn enqueue_extraction_jobs(jobs: &[ContentExtractionJob]) {
+    with_transaction("enqueue_extraction_jobs", |tx| {
+        for job in jobs {
+            tx.execute(
+                INSERT_EXTRACTION_JOB,
+                duckdb::params![
+                    job.id.to_string(),
+                    job.source_ticket_id,
+                    job.extracted_link.url,
+                    ContentType::from_platform_type(&job.extracted_link.platform_type).to_string(),
+                    job.user_id,
+                    Option::<String>::None,
+                    format!("{:?}", job.priority),
+                    "Pending",
+                    job.retry_count,
+                    job.created_at.to_rfc3339(),
+                    Option::<String>::None,
+                    Option::<String>::None,
+                    Option::<String>::None,
+                    Option::<String>::None,
+                ],
+            )
+            .expect("Failed to enqueue extraction job");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let hex: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        format!("sha256={}", hex)
+    }
+
+    #[test]
+    fn test_verifies_signature_only() {
+        let body = br#"{"webhookEvent":"jira:issue_updated"}"#;
+
+        assert!(verify_request("s3cret", body, Some(&sign("s3cret", body))));
+        assert!(!verify_request("s3cret", body, Some(&sign("other", body))));
+        assert!(!verify_request("s3cret", b"tampered", Some(&sign("s3cret", body))));
+        assert!(!verify_request("s3cret", body, Some("s3cret")));
+        assert!(!verify_request("s3cret", body, Some("sha256=")));
+        assert!(!verify_request("s3cret", body, None));
+    }
+
+    #[test]
+    fn test_maps_events_to_actions() {
+        let event = |name: &str| JiraWebhookEvent { webhook_event: name.to_string(), issue: None };
+
+        assert_eq!(event("jira:issue_created").action(), WebhookAction::Upsert);
+        assert_eq!(event("comment_deleted").action(), WebhookAction::Upsert);
+        assert_eq!(event("jira:issue_deleted").action(), WebhookAction::MarkDeleted);
+        assert_eq!(event("sprint_started").action(), WebhookAction::Ignore);
+    }
+}